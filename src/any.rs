@@ -3,6 +3,7 @@
 use crate::fn_native::{FnPtr, SendSync};
 use crate::parser::{ImmutableString, INT};
 use crate::r#unsafe::{unsafe_cast_box, unsafe_try_cast};
+use crate::resume::Generator;
 
 #[cfg(not(feature = "no_closure"))]
 use crate::fn_native::{shared_try_take, Shared};
@@ -10,11 +11,17 @@ use crate::fn_native::{shared_try_take, Shared};
 #[cfg(not(feature = "no_float"))]
 use crate::parser::FLOAT;
 
+#[cfg(feature = "decimal")]
+use rust_decimal::Decimal;
+
+#[cfg(feature = "bigint")]
+use num_bigint::BigInt;
+
 #[cfg(not(feature = "no_index"))]
 use crate::engine::Array;
 
 #[cfg(not(feature = "no_object"))]
-use crate::engine::Map;
+use crate::{engine::Map, record::Record};
 
 use crate::stdlib::{
     any::{type_name, Any, TypeId},
@@ -38,7 +45,7 @@ use crate::stdlib::sync::{Arc, RwLock, RwLockReadGuard, RwLockWriteGuard};
 #[cfg(not(feature = "no_object"))]
 use crate::stdlib::collections::HashMap;
 
-#[cfg(not(feature = "no_index"))]
+#[cfg(any(not(feature = "no_index"), not(feature = "no_object")))]
 use crate::stdlib::vec::Vec;
 
 #[cfg(not(feature = "no_std"))]
@@ -154,6 +161,10 @@ pub enum Union {
     Int(INT),
     #[cfg(not(feature = "no_float"))]
     Float(FLOAT),
+    #[cfg(feature = "decimal")]
+    Decimal(Box<Decimal>),
+    #[cfg(feature = "bigint")]
+    BigInt(Box<BigInt>),
     #[cfg(not(feature = "no_index"))]
     Array(Box<Array>),
     #[cfg(not(feature = "no_object"))]
@@ -308,6 +319,10 @@ impl Dynamic {
             Union::Int(_) => TypeId::of::<INT>(),
             #[cfg(not(feature = "no_float"))]
             Union::Float(_) => TypeId::of::<FLOAT>(),
+            #[cfg(feature = "decimal")]
+            Union::Decimal(_) => TypeId::of::<Decimal>(),
+            #[cfg(feature = "bigint")]
+            Union::BigInt(_) => TypeId::of::<BigInt>(),
             #[cfg(not(feature = "no_index"))]
             Union::Array(_) => TypeId::of::<Array>(),
             #[cfg(not(feature = "no_object"))]
@@ -340,6 +355,10 @@ impl Dynamic {
             Union::Int(_) => type_name::<INT>(),
             #[cfg(not(feature = "no_float"))]
             Union::Float(_) => type_name::<FLOAT>(),
+            #[cfg(feature = "decimal")]
+            Union::Decimal(_) => "decimal",
+            #[cfg(feature = "bigint")]
+            Union::BigInt(_) => "bigint",
             #[cfg(not(feature = "no_index"))]
             Union::Array(_) => "array",
             #[cfg(not(feature = "no_object"))]
@@ -374,6 +393,8 @@ pub(crate) fn map_std_type_name(name: &str) -> &str {
         "string"
     } else if name == type_name::<FnPtr>() {
         "Fn"
+    } else if name == type_name::<Generator>() {
+        "generator"
     } else {
         #[cfg(not(feature = "no_std"))]
         if name == type_name::<Instant>() {
@@ -387,11 +408,61 @@ pub(crate) fn map_std_type_name(name: &str) -> &str {
         if name == type_name::<Map>() {
             return "map";
         }
+        #[cfg(not(feature = "no_object"))]
+        if name == type_name::<Record>() {
+            return "record";
+        }
+        #[cfg(feature = "decimal")]
+        if name == type_name::<Decimal>() {
+            return "decimal";
+        }
+        #[cfg(feature = "bigint")]
+        if name == type_name::<BigInt>() {
+            return "bigint";
+        }
 
         name
     }
 }
 
+/// Wrapper formatting a value with the properties of object maps, including those nested in
+/// arrays and object maps, in a stable order: in insertion order under the `ordered_map` feature,
+/// otherwise sorted by name.
+pub(crate) struct StableFormat<'a>(pub &'a Dynamic);
+
+impl fmt::Debug for StableFormat<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &(self.0).0 {
+            #[cfg(not(feature = "no_index"))]
+            Union::Array(value) => f
+                .debug_list()
+                .entries(value.iter().map(StableFormat))
+                .finish(),
+            #[cfg(not(feature = "no_object"))]
+            Union::Map(value) => fmt::Debug::fmt(&MapFormat(value), f),
+            _ => fmt::Debug::fmt(self.0, f),
+        }
+    }
+}
+
+/// Wrapper formatting an object map in a stable order, as in `StableFormat`.
+#[cfg(not(feature = "no_object"))]
+pub(crate) struct MapFormat<'a>(pub &'a Map);
+
+#[cfg(not(feature = "no_object"))]
+impl fmt::Debug for MapFormat<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        #[allow(unused_mut)]
+        let mut entries: Vec<_> = self.0.iter().map(|(k, v)| (k, StableFormat(v))).collect();
+
+        #[cfg(not(feature = "ordered_map"))]
+        entries.sort_by_key(|(k, _)| *k);
+
+        f.write_str("#")?;
+        f.debug_map().entries(entries).finish()
+    }
+}
+
 impl fmt::Display for Dynamic {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match &self.0 {
@@ -402,6 +473,10 @@ impl fmt::Display for Dynamic {
             Union::Int(value) => fmt::Display::fmt(value, f),
             #[cfg(not(feature = "no_float"))]
             Union::Float(value) => fmt::Display::fmt(value, f),
+            #[cfg(feature = "decimal")]
+            Union::Decimal(value) => fmt::Display::fmt(value, f),
+            #[cfg(feature = "bigint")]
+            Union::BigInt(value) => fmt::Display::fmt(value, f),
             #[cfg(not(feature = "no_index"))]
             Union::Array(value) => fmt::Debug::fmt(value, f),
             #[cfg(not(feature = "no_object"))]
@@ -413,6 +488,10 @@ impl fmt::Display for Dynamic {
 
             #[cfg(not(feature = "no_std"))]
             Union::Variant(value) if value.is::<Instant>() => f.write_str("<timestamp>"),
+            #[cfg(not(feature = "no_object"))]
+            Union::Variant(value) if value.is::<Record>() => {
+                fmt::Display::fmt((***value).as_any().downcast_ref::<Record>().unwrap(), f)
+            }
             Union::Variant(value) => f.write_str((*value).type_name()),
 
             #[cfg(not(feature = "no_closure"))]
@@ -441,6 +520,10 @@ impl fmt::Debug for Dynamic {
             Union::Int(value) => fmt::Debug::fmt(value, f),
             #[cfg(not(feature = "no_float"))]
             Union::Float(value) => fmt::Debug::fmt(value, f),
+            #[cfg(feature = "decimal")]
+            Union::Decimal(value) => fmt::Debug::fmt(value, f),
+            #[cfg(feature = "bigint")]
+            Union::BigInt(value) => fmt::Debug::fmt(value, f),
             #[cfg(not(feature = "no_index"))]
             Union::Array(value) => fmt::Debug::fmt(value, f),
             #[cfg(not(feature = "no_object"))]
@@ -452,6 +535,10 @@ impl fmt::Debug for Dynamic {
 
             #[cfg(not(feature = "no_std"))]
             Union::Variant(value) if value.is::<Instant>() => write!(f, "<timestamp>"),
+            #[cfg(not(feature = "no_object"))]
+            Union::Variant(value) if value.is::<Record>() => {
+                fmt::Display::fmt((***value).as_any().downcast_ref::<Record>().unwrap(), f)
+            }
             Union::Variant(value) => write!(f, "{}", (*value).type_name()),
 
             #[cfg(not(feature = "no_closure"))]
@@ -480,6 +567,10 @@ impl Clone for Dynamic {
             Union::Int(value) => Self(Union::Int(value)),
             #[cfg(not(feature = "no_float"))]
             Union::Float(value) => Self(Union::Float(value)),
+            #[cfg(feature = "decimal")]
+            Union::Decimal(ref value) => Self(Union::Decimal(value.clone())),
+            #[cfg(feature = "bigint")]
+            Union::BigInt(ref value) => Self(Union::BigInt(value.clone())),
             #[cfg(not(feature = "no_index"))]
             Union::Array(ref value) => Self(Union::Array(value.clone())),
             #[cfg(not(feature = "no_object"))]
@@ -589,7 +680,23 @@ impl Dynamic {
         #[cfg(not(feature = "no_object"))]
         {
             boxed = match unsafe_cast_box::<_, Map>(boxed) {
-                Ok(map) => return (*map).into(),
+                Ok(map) => return Self(Union::Map(map)),
+                Err(val) => val,
+            }
+        }
+
+        #[cfg(feature = "decimal")]
+        {
+            boxed = match unsafe_cast_box::<_, Decimal>(boxed) {
+                Ok(d) => return Self(Union::Decimal(d)),
+                Err(val) => val,
+            }
+        }
+
+        #[cfg(feature = "bigint")]
+        {
+            boxed = match unsafe_cast_box::<_, BigInt>(boxed) {
+                Ok(n) => return Self(Union::BigInt(n)),
                 Err(val) => val,
             }
         }
@@ -736,6 +843,22 @@ impl Dynamic {
             };
         }
 
+        #[cfg(feature = "decimal")]
+        if type_id == TypeId::of::<Decimal>() {
+            return match self.0 {
+                Union::Decimal(value) => unsafe_cast_box::<_, T>(value).ok().map(|v| *v),
+                _ => None,
+            };
+        }
+
+        #[cfg(feature = "bigint")]
+        if type_id == TypeId::of::<BigInt>() {
+            return match self.0 {
+                Union::BigInt(value) => unsafe_cast_box::<_, T>(value).ok().map(|v| *v),
+                _ => None,
+            };
+        }
+
         if type_id == TypeId::of::<FnPtr>() {
             return match self.0 {
                 Union::FnPtr(value) => unsafe_cast_box::<_, T>(value).ok().map(|v| *v),
@@ -981,6 +1104,20 @@ impl Dynamic {
                 _ => None,
             };
         }
+        #[cfg(feature = "decimal")]
+        if type_id == TypeId::of::<Decimal>() {
+            return match &self.0 {
+                Union::Decimal(value) => <dyn Any>::downcast_ref::<T>(value.as_ref()),
+                _ => None,
+            };
+        }
+        #[cfg(feature = "bigint")]
+        if type_id == TypeId::of::<BigInt>() {
+            return match &self.0 {
+                Union::BigInt(value) => <dyn Any>::downcast_ref::<T>(value.as_ref()),
+                _ => None,
+            };
+        }
         if type_id == TypeId::of::<FnPtr>() {
             return match &self.0 {
                 Union::FnPtr(value) => <dyn Any>::downcast_ref::<T>(value.as_ref()),
@@ -1058,6 +1195,20 @@ impl Dynamic {
                 _ => None,
             };
         }
+        #[cfg(feature = "decimal")]
+        if type_id == TypeId::of::<Decimal>() {
+            return match &mut self.0 {
+                Union::Decimal(value) => <dyn Any>::downcast_mut::<T>(value.as_mut()),
+                _ => None,
+            };
+        }
+        #[cfg(feature = "bigint")]
+        if type_id == TypeId::of::<BigInt>() {
+            return match &mut self.0 {
+                Union::BigInt(value) => <dyn Any>::downcast_mut::<T>(value.as_mut()),
+                _ => None,
+            };
+        }
         if type_id == TypeId::of::<FnPtr>() {
             return match &mut self.0 {
                 Union::FnPtr(value) => <dyn Any>::downcast_mut::<T>(value.as_mut()),
@@ -1107,6 +1258,35 @@ impl Dynamic {
         }
     }
 
+    /// Cast the `Dynamic` as a `Decimal` and return it.
+    /// Returns the name of the actual type if the cast fails.
+    #[cfg(feature = "decimal")]
+    #[inline(always)]
+    pub fn as_decimal(&self) -> Result<Decimal, &'static str> {
+        match &self.0 {
+            Union::Decimal(n) => Ok(**n),
+            #[cfg(not(feature = "no_closure"))]
+            Union::Shared(_) => self.read_lock().map(|v| *v).ok_or_else(|| self.type_name()),
+            _ => Err(self.type_name()),
+        }
+    }
+
+    /// Cast the `Dynamic` as a `BigInt` and return it.
+    /// Returns the name of the actual type if the cast fails.
+    #[cfg(feature = "bigint")]
+    #[inline(always)]
+    pub fn as_bigint(&self) -> Result<BigInt, &'static str> {
+        match &self.0 {
+            Union::BigInt(n) => Ok(n.as_ref().clone()),
+            #[cfg(not(feature = "no_closure"))]
+            Union::Shared(_) => self
+                .read_lock::<BigInt>()
+                .map(|v| v.clone())
+                .ok_or_else(|| self.type_name()),
+            _ => Err(self.type_name()),
+        }
+    }
+
     /// Cast the `Dynamic` as a `bool` and return it.
     /// Returns the name of the actual type if the cast fails.
     #[inline(always)]
@@ -1202,6 +1382,20 @@ impl From<INT> for Dynamic {
         Self(Union::Int(value))
     }
 }
+#[cfg(feature = "decimal")]
+impl From<Decimal> for Dynamic {
+    #[inline(always)]
+    fn from(value: Decimal) -> Self {
+        Self(Union::Decimal(Box::new(value)))
+    }
+}
+#[cfg(feature = "bigint")]
+impl From<BigInt> for Dynamic {
+    #[inline(always)]
+    fn from(value: BigInt) -> Self {
+        Self(Union::BigInt(Box::new(value)))
+    }
+}
 #[cfg(not(feature = "no_float"))]
 impl From<FLOAT> for Dynamic {
     #[inline(always)]
@@ -1251,6 +1445,19 @@ impl<K: Into<ImmutableString>, T: Variant + Clone> From<HashMap<K, T>> for Dynam
         )))
     }
 }
+#[cfg(not(feature = "no_object"))]
+#[cfg(feature = "ordered_map")]
+impl<K: Into<ImmutableString>, T: Variant + Clone> From<indexmap::IndexMap<K, T>> for Dynamic {
+    #[inline(always)]
+    fn from(value: indexmap::IndexMap<K, T>) -> Self {
+        Self(Union::Map(Box::new(
+            value
+                .into_iter()
+                .map(|(k, v)| (k.into(), Dynamic::from(v)))
+                .collect(),
+        )))
+    }
+}
 impl From<FnPtr> for Dynamic {
     #[inline(always)]
     fn from(value: FnPtr) -> Self {
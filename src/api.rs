@@ -1,14 +1,16 @@
 //! Module that defines the extern API of `Engine`.
 
 use crate::any::{Dynamic, Variant};
+use crate::cancel::CancellationToken;
 use crate::engine::{Engine, Imports, State};
 use crate::error::ParseError;
-use crate::fn_native::{IteratorFn, SendSync};
+use crate::fn_native::{IteratorFn, NativeCallContext, SendSync};
 use crate::module::{FuncReturn, Module};
 use crate::optimize::OptimizationLevel;
 use crate::parser::AST;
 use crate::result::EvalAltResult;
 use crate::scope::Scope;
+use crate::syntax::EvalContext;
 use crate::token::{lex, Position};
 
 #[cfg(not(feature = "no_index"))]
@@ -24,8 +26,8 @@ use crate::{
 
 #[cfg(not(feature = "no_function"))]
 use crate::{
-    engine::get_script_function_by_signature, fn_args::FuncArgs, fn_call::ensure_no_data_race,
-    utils::StaticVec,
+    calc_fn_hash, engine::get_script_function_by_signature, fn_args::FuncArgs,
+    fn_call::ensure_no_data_race, stdlib::iter::empty, utils::StaticVec,
 };
 
 #[cfg(not(feature = "no_optimize"))]
@@ -65,12 +67,97 @@ impl Engine {
         &mut self,
         name: &str,
         arg_types: &[TypeId],
-        func: impl Fn(&Engine, &Module, &mut [&mut Dynamic]) -> FuncReturn<T> + SendSync + 'static,
+        func: impl Fn(NativeCallContext, &mut [&mut Dynamic]) -> FuncReturn<T> + SendSync + 'static,
     ) -> &mut Self {
+        self.record_deterministic(name);
         self.global_module.set_raw_fn(name, arg_types, func);
         self
     }
 
+    /// Register a variadic function with the `Engine`, which can be called with any number of
+    /// arguments after the fixed parameters of the types in `arg_types`.
+    ///
+    /// A variadic function is only called when no function of the same name matches the
+    /// number and types of the arguments exactly.  There can only be one variadic function of
+    /// each name and number of fixed parameters, and the one with the most fixed parameters
+    /// matching the arguments is called.
+    ///
+    /// ## WARNING - Low Level API
+    ///
+    /// This function is very low level.
+    ///
+    /// Arguments are simply passed in as a mutable array of `&mut Dynamic`.
+    /// The arguments for the fixed parameters are guaranteed to be of the correct types
+    /// matching the `TypeId`'s, followed by all the trailing arguments.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<rhai::EvalAltResult>> {
+    /// use rhai::Engine;
+    ///
+    /// let mut engine = Engine::new();
+    ///
+    /// engine.register_variadic_fn("count", &[], |_, args| Ok(args.len() as i64));
+    ///
+    /// assert_eq!(engine.eval::<i64>("count()")?, 0);
+    /// assert_eq!(engine.eval::<i64>("count(1, true, 'x')")?, 3);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn register_variadic_fn<T: Variant + Clone>(
+        &mut self,
+        name: &str,
+        arg_types: &[TypeId],
+        func: impl Fn(NativeCallContext, &mut [&mut Dynamic]) -> FuncReturn<T> + SendSync + 'static,
+    ) -> &mut Self {
+        self.record_deterministic(name);
+        self.global_module.set_variadic_fn(name, arg_types, func);
+        self
+    }
+
+    /// Register functions marked deterministic, i.e. which always return the same results for the
+    /// same arguments, via a closure.
+    ///
+    /// In deterministic mode, scripts can only call functions registered, and packages loaded,
+    /// within this closure.  Calling other functions fails with
+    /// `EvalAltResult::ErrorNotDeterministic`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<rhai::EvalAltResult>> {
+    /// use rhai::{Engine, RegisterFn};
+    ///
+    /// let mut engine = Engine::new();
+    /// engine.set_deterministic(true);
+    ///
+    /// engine.register_deterministic(|engine| {
+    ///     engine.register_fn("add", |x: i64, y: i64| x + y);
+    /// });
+    ///
+    /// assert_eq!(engine.eval::<i64>("add(40, 2)")?, 42);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn register_deterministic(&mut self, register: impl FnOnce(&mut Self)) -> &mut Self {
+        let marking = self.marking_deterministic;
+        self.marking_deterministic = true;
+        register(self);
+        self.marking_deterministic = marking;
+        self
+    }
+
+    /// Record a function being registered as not deterministic, unless it is registered within
+    /// `register_deterministic`.
+    pub(crate) fn record_deterministic(&mut self, name: &str) {
+        if !self.marking_deterministic {
+            self.nondeterministic_fns
+                .get_or_insert_with(Default::default)
+                .insert(name.into());
+        }
+    }
+
     /// Register a custom type for use with the `Engine`.
     /// The type must implement `Clone`.
     ///
@@ -175,6 +262,32 @@ impl Engine {
         self
     }
 
+    /// Register the parameter names of the functions with a particular name and number of
+    /// parameters, so that they can be called with named arguments.
+    ///
+    /// The names apply to all functions of that name and number of parameters,
+    /// whether they are registered before or after this call.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<rhai::EvalAltResult>> {
+    /// use rhai::{Engine, RegisterFn};
+    ///
+    /// let mut engine = Engine::new();
+    ///
+    /// engine.register_fn("sub", |x: i64, y: i64| x - y);
+    /// engine.register_fn_param_names("sub", &["x", "y"]);
+    ///
+    /// assert_eq!(engine.eval::<i64>("sub(y: 1, x: 43)")?, 42);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn register_fn_param_names(&mut self, name: &str, param_names: &[&str]) -> &mut Self {
+        self.global_module.set_fn_param_names(name, param_names);
+        self
+    }
+
     /// Register a getter function for a member of a registered type with the `Engine`.
     ///
     /// The function signature must start with `&mut self` and not `&self`.
@@ -812,7 +925,7 @@ impl Engine {
     /// Read the contents of a file into a string.
     #[cfg(not(feature = "no_std"))]
     #[cfg(not(target_arch = "wasm32"))]
-    fn read_file(path: PathBuf) -> Result<String, Box<EvalAltResult>> {
+    pub(crate) fn read_file(path: PathBuf) -> Result<String, Box<EvalAltResult>> {
         let mut f = File::open(path.clone()).map_err(|err| {
             EvalAltResult::ErrorReadingScriptFile(path.clone(), Position::none(), err)
         })?;
@@ -1233,9 +1346,85 @@ impl Engine {
         &self,
         scope: &mut Scope,
         ast: &AST,
+    ) -> Result<T, Box<EvalAltResult>> {
+        self.eval_ast_with_scope_and_state(scope, &mut State::new(), ast)
+    }
+
+    /// Evaluate a string, terminating when a `CancellationToken` is cancelled.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<rhai::EvalAltResult>> {
+    /// use rhai::{CancellationToken, Engine, EvalAltResult, INT};
+    ///
+    /// let engine = Engine::new();
+    /// let token = CancellationToken::new();
+    ///
+    /// assert_eq!(engine.eval_with_cancel::<INT>("40 + 2", &token)?, 42);
+    ///
+    /// token.cancel();
+    ///
+    /// assert!(matches!(
+    ///     *engine.eval_with_cancel::<INT>("40 + 2", &token).expect_err("should error"),
+    ///     EvalAltResult::ErrorTerminated(_)
+    /// ));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn eval_with_cancel<T: Variant + Clone>(
+        &self,
+        script: &str,
+        token: &CancellationToken,
+    ) -> Result<T, Box<EvalAltResult>> {
+        self.eval_with_scope_with_cancel(&mut Scope::new(), script, token)
+    }
+
+    /// Evaluate a string with own scope, terminating when a `CancellationToken` is cancelled.
+    pub fn eval_with_scope_with_cancel<T: Variant + Clone>(
+        &self,
+        scope: &mut Scope,
+        script: &str,
+        token: &CancellationToken,
+    ) -> Result<T, Box<EvalAltResult>> {
+        let ast = self.compile_with_scope_and_optimization_level(
+            scope,
+            &[script],
+            self.optimization_level,
+        )?;
+        self.eval_ast_with_scope_with_cancel(scope, &ast, token)
+    }
+
+    /// Evaluate an `AST`, terminating when a `CancellationToken` is cancelled.
+    pub fn eval_ast_with_cancel<T: Variant + Clone>(
+        &self,
+        ast: &AST,
+        token: &CancellationToken,
+    ) -> Result<T, Box<EvalAltResult>> {
+        self.eval_ast_with_scope_with_cancel(&mut Scope::new(), ast, token)
+    }
+
+    /// Evaluate an `AST` with own scope, terminating when a `CancellationToken` is cancelled.
+    pub fn eval_ast_with_scope_with_cancel<T: Variant + Clone>(
+        &self,
+        scope: &mut Scope,
+        ast: &AST,
+        token: &CancellationToken,
+    ) -> Result<T, Box<EvalAltResult>> {
+        let mut state = State::new();
+        state.cancel = Some(token.clone());
+        self.eval_ast_with_scope_and_state(scope, &mut state, ast)
+    }
+
+    /// Evaluate an `AST` with own scope and `State`.
+    fn eval_ast_with_scope_and_state<T: Variant + Clone>(
+        &self,
+        scope: &mut Scope,
+        state: &mut State,
+        ast: &AST,
     ) -> Result<T, Box<EvalAltResult>> {
         let mut mods = Imports::new();
-        let (result, _) = self.eval_ast_with_scope_raw(scope, &mut mods, ast)?;
+        let result = self.eval_ast_with_scope_raw(scope, &mut mods, state, ast)?;
 
         let typ = self.map_type_name(result.type_name());
 
@@ -1254,20 +1443,18 @@ impl Engine {
         &self,
         scope: &mut Scope,
         mods: &mut Imports,
+        state: &mut State,
         ast: &'a AST,
-    ) -> Result<(Dynamic, u64), Box<EvalAltResult>> {
-        let mut state = State::new();
-
+    ) -> Result<Dynamic, Box<EvalAltResult>> {
         ast.statements()
             .iter()
             .try_fold(().into(), |_, stmt| {
-                self.eval_stmt(scope, mods, &mut state, ast.lib(), &mut None, stmt, 0)
+                self.eval_stmt(scope, mods, state, ast.lib(), &mut None, stmt, 0)
             })
             .or_else(|err| match *err {
                 EvalAltResult::Return(out, _) => Ok(out),
                 _ => Err(err),
             })
-            .map(|v| (v, state.operations))
     }
 
     /// Evaluate a file, but throw away the result and only return error (if any).
@@ -1321,14 +1508,70 @@ impl Engine {
         &self,
         scope: &mut Scope,
         ast: &AST,
+    ) -> Result<(), Box<EvalAltResult>> {
+        self.consume_ast_with_scope_and_state(scope, &mut State::new(), ast)
+    }
+
+    /// Evaluate a string, terminating when a `CancellationToken` is cancelled,
+    /// but throw away the result and only return error (if any).
+    pub fn consume_with_cancel(
+        &self,
+        script: &str,
+        token: &CancellationToken,
+    ) -> Result<(), Box<EvalAltResult>> {
+        self.consume_with_scope_with_cancel(&mut Scope::new(), script, token)
+    }
+
+    /// Evaluate a string with own scope, terminating when a `CancellationToken` is cancelled,
+    /// but throw away the result and only return error (if any).
+    pub fn consume_with_scope_with_cancel(
+        &self,
+        scope: &mut Scope,
+        script: &str,
+        token: &CancellationToken,
+    ) -> Result<(), Box<EvalAltResult>> {
+        let scripts = [script];
+        let stream = lex(&scripts, None, self);
+        let ast = self.parse(&mut stream.peekable(), scope, self.optimization_level)?;
+        self.consume_ast_with_scope_with_cancel(scope, &ast, token)
+    }
+
+    /// Evaluate an `AST`, terminating when a `CancellationToken` is cancelled,
+    /// but throw away the result and only return error (if any).
+    pub fn consume_ast_with_cancel(
+        &self,
+        ast: &AST,
+        token: &CancellationToken,
+    ) -> Result<(), Box<EvalAltResult>> {
+        self.consume_ast_with_scope_with_cancel(&mut Scope::new(), ast, token)
+    }
+
+    /// Evaluate an `AST` with own scope, terminating when a `CancellationToken` is cancelled,
+    /// but throw away the result and only return error (if any).
+    pub fn consume_ast_with_scope_with_cancel(
+        &self,
+        scope: &mut Scope,
+        ast: &AST,
+        token: &CancellationToken,
     ) -> Result<(), Box<EvalAltResult>> {
         let mut state = State::new();
+        state.cancel = Some(token.clone());
+        self.consume_ast_with_scope_and_state(scope, &mut state, ast)
+    }
+
+    /// Evaluate an `AST` with own scope and `State`, but throw away the result.
+    fn consume_ast_with_scope_and_state(
+        &self,
+        scope: &mut Scope,
+        state: &mut State,
+        ast: &AST,
+    ) -> Result<(), Box<EvalAltResult>> {
         let mut mods = Default::default();
 
         ast.statements()
             .iter()
             .try_fold(().into(), |_, stmt| {
-                self.eval_stmt(scope, &mut mods, &mut state, ast.lib(), &mut None, stmt, 0)
+                self.eval_stmt(scope, &mut mods, state, ast.lib(), &mut None, stmt, 0)
             })
             .map_or_else(
                 |err| match *err {
@@ -1382,9 +1625,40 @@ impl Engine {
         ast: &AST,
         name: &str,
         args: A,
+    ) -> Result<T, Box<EvalAltResult>> {
+        self.call_fn_with_state(scope, &mut State::new(), ast, name, args)
+    }
+
+    /// Call a script function defined in an `AST` with multiple arguments,
+    /// terminating when a `CancellationToken` is cancelled.
+    /// Arguments are passed as a tuple.
+    #[cfg(not(feature = "no_function"))]
+    pub fn call_fn_with_cancel<A: FuncArgs, T: Variant + Clone>(
+        &self,
+        scope: &mut Scope,
+        ast: &AST,
+        name: &str,
+        args: A,
+        token: &CancellationToken,
+    ) -> Result<T, Box<EvalAltResult>> {
+        let mut state = State::new();
+        state.cancel = Some(token.clone());
+        self.call_fn_with_state(scope, &mut state, ast, name, args)
+    }
+
+    /// Call a script function defined in an `AST` with multiple arguments and own `State`.
+    #[cfg(not(feature = "no_function"))]
+    fn call_fn_with_state<A: FuncArgs, T: Variant + Clone>(
+        &self,
+        scope: &mut Scope,
+        state: &mut State,
+        ast: &AST,
+        name: &str,
+        args: A,
     ) -> Result<T, Box<EvalAltResult>> {
         let mut arg_values = args.into_vec();
-        let result = self.call_fn_dynamic_raw(scope, ast, name, &mut None, arg_values.as_mut())?;
+        let result =
+            self.call_fn_dynamic_raw(scope, state, ast, name, &mut None, arg_values.as_mut())?;
 
         let typ = self.map_type_name(result.type_name());
 
@@ -1456,7 +1730,47 @@ impl Engine {
         mut this_ptr: Option<&mut Dynamic>,
         mut arg_values: impl AsMut<[Dynamic]>,
     ) -> FuncReturn<Dynamic> {
-        self.call_fn_dynamic_raw(scope, lib, name, &mut this_ptr, arg_values.as_mut())
+        self.call_fn_dynamic_raw(
+            scope,
+            &mut State::new(),
+            lib,
+            name,
+            &mut this_ptr,
+            arg_values.as_mut(),
+        )
+    }
+
+    /// Call a script function defined in an `AST` with multiple `Dynamic` arguments
+    /// and optionally a value for binding to the 'this' pointer,
+    /// terminating when a `CancellationToken` is cancelled.
+    ///
+    /// ## WARNING
+    ///
+    /// All the arguments are _consumed_, meaning that they're replaced by `()`.
+    /// This is to avoid unnecessarily cloning the arguments.
+    /// Do not use the arguments after this call. If they are needed afterwards,
+    /// clone them _before_ calling this function.
+    #[cfg(not(feature = "no_function"))]
+    pub fn call_fn_dynamic_with_cancel(
+        &self,
+        scope: &mut Scope,
+        lib: impl AsRef<Module>,
+        name: &str,
+        mut this_ptr: Option<&mut Dynamic>,
+        mut arg_values: impl AsMut<[Dynamic]>,
+        token: &CancellationToken,
+    ) -> FuncReturn<Dynamic> {
+        let mut state = State::new();
+        state.cancel = Some(token.clone());
+
+        self.call_fn_dynamic_raw(
+            scope,
+            &mut state,
+            lib,
+            name,
+            &mut this_ptr,
+            arg_values.as_mut(),
+        )
     }
 
     /// Call a script function defined in an `AST` with multiple `Dynamic` arguments.
@@ -1471,6 +1785,7 @@ impl Engine {
     pub(crate) fn call_fn_dynamic_raw(
         &self,
         scope: &mut Scope,
+        state: &mut State,
         lib: impl AsRef<Module>,
         name: &str,
         this_ptr: &mut Option<&mut Dynamic>,
@@ -1478,10 +1793,17 @@ impl Engine {
     ) -> FuncReturn<Dynamic> {
         let lib = lib.as_ref();
         let mut args: StaticVec<_> = arg_values.iter_mut().collect();
-        let fn_def = get_script_function_by_signature(lib, name, args.len(), true)
-            .ok_or_else(|| EvalAltResult::ErrorFunctionNotFound(name.into(), Position::none()))?;
 
-        let mut state = State::new();
+        // Qualifiers (none) + function name + number of arguments.
+        let hash_script = calc_fn_hash(empty(), name, args.len(), empty());
+        let overloads = lib.iter_fn_overloads(hash_script, true);
+
+        let fn_def = match self.resolve_fn_overload(overloads, name, &args)? {
+            Some(func) => Some(func.get_fn_def()),
+            None => get_script_function_by_signature(lib, name, args.len(), true),
+        }
+        .ok_or_else(|| EvalAltResult::ErrorFunctionNotFound(name.into(), Position::none()))?;
+
         let mut mods = Imports::new();
         let args = args.as_mut();
 
@@ -1491,7 +1813,7 @@ impl Engine {
         }
 
         self.call_script_fn(
-            scope, &mut mods, &mut state, lib, this_ptr, name, fn_def, args, 0,
+            scope, &mut mods, state, lib, this_ptr, name, fn_def, args, 0,
         )
     }
 
@@ -1527,6 +1849,51 @@ impl Engine {
         optimize_into_ast(self, scope, stmt, lib, optimization_level)
     }
 
+    /// Register a callback that resolves variables not found in the `Scope`.
+    ///
+    /// The callback receives the name and position of the variable together with the evaluation
+    /// context.  It returns `Some((value, is_constant))` to provide the variable, or `None` if the
+    /// variable is unknown, in which case `ErrorVariableNotFound` is raised as usual.
+    ///
+    /// Resolved variables are not added to the `Scope`, so the callback is consulted every time
+    /// such a variable is accessed.  Assigning to a variable marked constant is an error.
+    /// A variable not marked constant is added to the `Scope` when it is changed, shadowing the
+    /// callback until the end of the current block.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<rhai::EvalAltResult>> {
+    /// use rhai::{Engine, EvalAltResult, INT};
+    ///
+    /// let mut engine = Engine::new();
+    ///
+    /// // Look up context fields lazily instead of pushing all of them into the scope
+    /// engine.on_var(|name, _, _| match name {
+    ///     "user_id" => Ok(Some(((42 as INT).into(), true))),
+    ///     "beta" => Ok(Some((true.into(), true))),
+    ///     _ => Ok(None),
+    /// });
+    ///
+    /// assert_eq!(engine.eval::<INT>("if beta { user_id + 1 } else { 0 }")?, 43);
+    ///
+    /// assert!(matches!(
+    ///     *engine.consume("user_id = 1").expect_err("should error"),
+    ///     EvalAltResult::ErrorAssignmentToConstant(_, _)
+    /// ));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn on_var(
+        &mut self,
+        callback: impl Fn(&str, Position, &EvalContext) -> Result<Option<(Dynamic, bool)>, Box<EvalAltResult>>
+            + SendSync
+            + 'static,
+    ) -> &mut Self {
+        self.var_resolver = Some(Box::new(callback));
+        self
+    }
+
     /// Register a callback for script evaluation progress.
     ///
     /// # Example
@@ -0,0 +1,60 @@
+//! Module defining a token for cooperative cancellation of running scripts.
+
+use crate::result::EvalAltResult;
+use crate::token::Position;
+
+use crate::stdlib::{boxed::Box, sync::Arc};
+
+use core::sync::atomic::{AtomicBool, Ordering};
+
+/// A token which terminates running scripts when cancelled.
+///
+/// All clones of a `CancellationToken` share the same state, so a clone can be sent to another
+/// thread and cancelled from there.  Scripts check the token before each operation, and terminate
+/// with `EvalAltResult::ErrorTerminated` once it is cancelled.
+///
+/// Modules imported by the script run under the same token.
+///
+/// Native Rust functions registered as raw functions can get the token of the running script via
+/// `NativeCallContext::cancel_token`.  Those that run for a long time can call `check` to stop early.
+///
+/// # Example
+///
+/// ```
+/// use rhai::{CancellationToken, Engine, EvalAltResult};
+///
+/// let engine = Engine::new();
+/// let token = CancellationToken::new();
+///
+/// let handle = token.clone();
+/// std::thread::spawn(move || handle.cancel());
+///
+/// let result = engine.eval_with_cancel::<()>("loop {}", &token);
+///
+/// assert!(matches!(*result.expect_err("should error"), EvalAltResult::ErrorTerminated(_)));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    /// Create a new `CancellationToken`.
+    pub fn new() -> Self {
+        Default::default()
+    }
+    /// Cancel all scripts running with this token.
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+    /// Has this token been cancelled?
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::SeqCst)
+    }
+    /// Return `EvalAltResult::ErrorTerminated` if this token has been cancelled.
+    pub fn check(&self) -> Result<(), Box<EvalAltResult>> {
+        if self.is_cancelled() {
+            EvalAltResult::ErrorTerminated(Position::none()).into()
+        } else {
+            Ok(())
+        }
+    }
+}
@@ -2,14 +2,20 @@
 
 use crate::any::{map_std_type_name, Dynamic, Union};
 use crate::calc_fn_hash;
+use crate::cancel::CancellationToken;
 use crate::fn_call::run_builtin_op_assignment;
-use crate::fn_native::{CallableFunction, Callback, FnPtr};
+use crate::fn_native::{
+    CallableFunction, Callback, FnPtr, NativeCallContext, OnVarCallback, Shared,
+};
 use crate::module::{Module, ModuleRef};
 use crate::optimize::OptimizationLevel;
 use crate::packages::{Package, PackagesCollection, StandardPackage};
-use crate::parser::{Expr, ReturnType, Stmt};
+use crate::parser::{Expr, Pattern, ReturnType, Stmt};
+use crate::profiler::{ProfileFrame, Profiler};
 use crate::r#unsafe::unsafe_cast_var_name_to_lifetime;
 use crate::result::EvalAltResult;
+use crate::resume::{is_same_yield, Generator};
+use crate::sandbox::SandboxProfile;
 use crate::scope::{EntryType as ScopeEntryType, Scope};
 use crate::syntax::{CustomSyntax, EvalContext};
 use crate::token::Position;
@@ -22,36 +28,67 @@ use crate::any::Variant;
 use crate::parser::ScriptFnDef;
 
 #[cfg(not(feature = "no_module"))]
-use crate::module::ModuleResolver;
+use crate::module::{ImportContext, ModuleResolver};
+
+#[cfg(not(feature = "no_module"))]
+use crate::parser::ExportItem;
+
+#[cfg(not(feature = "no_module"))]
+use crate::token::Token;
+
+#[cfg(feature = "serde")]
+use crate::serde::snapshot::SerializableType;
 
 #[cfg(not(feature = "no_std"))]
 #[cfg(not(feature = "no_module"))]
 use crate::module::resolvers;
 
-#[cfg(any(not(feature = "no_object"), not(feature = "no_module")))]
 use crate::utils::ImmutableString;
 
 #[cfg(not(feature = "no_closure"))]
 #[cfg(not(feature = "no_object"))]
 use crate::any::DynamicWriteLock;
 
+#[cfg(not(feature = "no_object"))]
+use crate::record::{record_type_name, Record};
+
 use crate::stdlib::{
     borrow::Cow,
     boxed::Box,
     collections::{HashMap, HashSet},
     fmt, format,
+    hash::{Hash, Hasher},
     iter::{empty, once},
     ops::DerefMut,
     string::{String, ToString},
     vec::Vec,
 };
 
-#[cfg(not(feature = "no_index"))]
+#[cfg(any(not(feature = "no_index"), feature = "serde"))]
 use crate::stdlib::any::TypeId;
 
-#[cfg(not(feature = "no_closure"))]
+#[cfg(any(not(feature = "no_closure"), not(feature = "unchecked")))]
 use crate::stdlib::mem;
 
+#[cfg(not(feature = "no_module"))]
+#[cfg(not(feature = "sync"))]
+use crate::stdlib::cell::RefCell;
+
+#[cfg(not(feature = "no_module"))]
+#[cfg(feature = "sync")]
+use crate::stdlib::sync::RwLock;
+
+#[cfg(not(feature = "no_std"))]
+#[cfg(not(target_arch = "wasm32"))]
+use crate::stdlib::time::Instant;
+
+#[cfg(not(feature = "no_std"))]
+#[cfg(target_arch = "wasm32")]
+use instant::Instant;
+
+#[cfg(not(feature = "no_std"))]
+use crate::stdlib::time::Duration;
+
 /// Variable-sized array of `Dynamic` values.
 ///
 /// Not available under the `no_index` feature.
@@ -62,15 +99,32 @@ pub type Array = Vec<Dynamic>;
 ///
 /// Not available under the `no_object` feature.
 #[cfg(not(feature = "no_object"))]
+#[cfg(not(feature = "ordered_map"))]
 pub type Map = HashMap<ImmutableString, Dynamic>;
 
+/// Hash map of `Dynamic` values with `ImmutableString` keys, preserving the insertion order of keys.
+///
+/// Not available under the `no_object` feature.
+#[cfg(not(feature = "no_object"))]
+#[cfg(feature = "ordered_map")]
+pub type Map = indexmap::IndexMap<ImmutableString, Dynamic>;
+
 /// [INTERNALS] A stack of imported modules.
 /// Exported under the `internals` feature only.
 ///
 /// ## WARNING
 ///
 /// This type is volatile and may change.
-pub type Imports<'a> = Vec<(Cow<'a, str>, Module)>;
+pub type Imports<'a> = Vec<(Cow<'a, str>, Shared<Module>)>;
+
+/// Imported modules shared by all scripts run on an `Engine`, keyed by `ModuleResolver::module_key`.
+#[cfg(not(feature = "no_module"))]
+#[cfg(not(feature = "sync"))]
+pub(crate) type SharedModules = RefCell<HashMap<String, Shared<Module>>>;
+/// Imported modules shared by all scripts run on an `Engine`, keyed by `ModuleResolver::module_key`.
+#[cfg(not(feature = "no_module"))]
+#[cfg(feature = "sync")]
+pub(crate) type SharedModules = RwLock<HashMap<String, Shared<Module>>>;
 
 #[cfg(not(feature = "unchecked"))]
 #[cfg(debug_assertions)]
@@ -126,7 +180,6 @@ pub enum ChainType {
 }
 
 /// A type that encapsulates a mutation target for an expression with side effects.
-#[cfg(any(not(feature = "no_index"), not(feature = "no_object")))]
 #[derive(Debug)]
 pub enum Target<'a> {
     /// The target is a mutable reference to a `Dynamic` value somewhere.
@@ -144,32 +197,13 @@ pub enum Target<'a> {
     StringChar(&'a mut Dynamic, usize, Dynamic),
 }
 
-#[cfg(any(not(feature = "no_index"), not(feature = "no_object")))]
 impl Target<'_> {
-    /// Is the `Target` a reference pointing to other data?
-    #[inline(always)]
-    pub fn is_ref(&self) -> bool {
-        match self {
-            Self::Ref(_) => true,
-            #[cfg(not(feature = "no_closure"))]
-            #[cfg(not(feature = "no_object"))]
-            Self::LockGuard(_) => true,
-            Self::Value(_) => false,
-            #[cfg(not(feature = "no_index"))]
-            Self::StringChar(_, _, _) => false,
-        }
-    }
-    /// Is the `Target` an owned value?
+    /// Get the temporary value of the `Target`, if it is not a reference.
     #[inline(always)]
-    pub fn is_value(&self) -> bool {
+    pub fn take_value(self) -> Option<Dynamic> {
         match self {
-            Self::Ref(_) => false,
-            #[cfg(not(feature = "no_closure"))]
-            #[cfg(not(feature = "no_object"))]
-            Self::LockGuard(_) => false,
-            Self::Value(_) => true,
-            #[cfg(not(feature = "no_index"))]
-            Self::StringChar(_, _, _) => false,
+            Self::Value(value) => Some(value),
+            _ => None,
         }
     }
     /// Is the `Target` a shared value?
@@ -185,31 +219,30 @@ impl Target<'_> {
             Self::StringChar(_, _, _) => false,
         }
     }
-    /// Is the `Target` a specific type?
-    #[allow(dead_code)]
+    /// Get the value of the `Target` as a `Dynamic`, cloning a referenced value if necessary.
     #[inline(always)]
-    pub fn is<T: Variant + Clone>(&self) -> bool {
+    pub fn clone_into_dynamic(self) -> Dynamic {
         match self {
-            Target::Ref(r) => r.is::<T>(),
+            Self::Ref(r) => r.clone(), // Referenced value is cloned
             #[cfg(not(feature = "no_closure"))]
             #[cfg(not(feature = "no_object"))]
-            Target::LockGuard((r, _)) => r.is::<T>(),
-            Target::Value(r) => r.is::<T>(),
+            Self::LockGuard((_, orig)) => orig, // Original value is simply taken
+            Self::Value(v) => v,       // Owned value is simply taken
             #[cfg(not(feature = "no_index"))]
-            Target::StringChar(_, _, _) => TypeId::of::<T>() == TypeId::of::<char>(),
+            Self::StringChar(_, _, ch) => ch, // Character is taken
         }
     }
-    /// Get the value of the `Target` as a `Dynamic`, cloning a referenced value if necessary.
+    /// Get a reference from the `Target`.
     #[inline(always)]
-    pub fn clone_into_dynamic(self) -> Dynamic {
+    pub fn as_ref(&self) -> &Dynamic {
         match self {
-            Self::Ref(r) => r.clone(), // Referenced value is cloned
+            Self::Ref(r) => r,
             #[cfg(not(feature = "no_closure"))]
             #[cfg(not(feature = "no_object"))]
-            Self::LockGuard((_, orig)) => orig, // Original value is simply taken
-            Self::Value(v) => v,       // Owned value is simply taken
+            Self::LockGuard((r, _)) => r,
+            Self::Value(ref r) => r,
             #[cfg(not(feature = "no_index"))]
-            Self::StringChar(_, _, ch) => ch, // Character is taken
+            Self::StringChar(_, _, ref r) => r,
         }
     }
     /// Get a mutable reference from the `Target`.
@@ -225,6 +258,50 @@ impl Target<'_> {
             Self::StringChar(_, _, ref mut r) => r,
         }
     }
+}
+
+#[cfg(any(not(feature = "no_index"), not(feature = "no_object")))]
+impl Target<'_> {
+    /// Is the `Target` a reference pointing to other data?
+    #[inline(always)]
+    pub fn is_ref(&self) -> bool {
+        match self {
+            Self::Ref(_) => true,
+            #[cfg(not(feature = "no_closure"))]
+            #[cfg(not(feature = "no_object"))]
+            Self::LockGuard(_) => true,
+            Self::Value(_) => false,
+            #[cfg(not(feature = "no_index"))]
+            Self::StringChar(_, _, _) => false,
+        }
+    }
+    /// Is the `Target` an owned value?
+    #[inline(always)]
+    pub fn is_value(&self) -> bool {
+        match self {
+            Self::Ref(_) => false,
+            #[cfg(not(feature = "no_closure"))]
+            #[cfg(not(feature = "no_object"))]
+            Self::LockGuard(_) => false,
+            Self::Value(_) => true,
+            #[cfg(not(feature = "no_index"))]
+            Self::StringChar(_, _, _) => false,
+        }
+    }
+    /// Is the `Target` a specific type?
+    #[allow(dead_code)]
+    #[inline(always)]
+    pub fn is<T: Variant + Clone>(&self) -> bool {
+        match self {
+            Target::Ref(r) => r.is::<T>(),
+            #[cfg(not(feature = "no_closure"))]
+            #[cfg(not(feature = "no_object"))]
+            Target::LockGuard((r, _)) => r.is::<T>(),
+            Target::Value(r) => r.is::<T>(),
+            #[cfg(not(feature = "no_index"))]
+            Target::StringChar(_, _, _) => TypeId::of::<T>() == TypeId::of::<char>(),
+        }
+    }
     /// Update the value of the `Target`.
     /// Position in `EvalAltResult` is `None` and must be set afterwards.
     pub fn set_value(&mut self, new_val: Dynamic) -> Result<(), Box<EvalAltResult>> {
@@ -262,7 +339,6 @@ impl Target<'_> {
     }
 }
 
-#[cfg(any(not(feature = "no_index"), not(feature = "no_object")))]
 impl<'a> From<&'a mut Dynamic> for Target<'a> {
     #[inline(always)]
     fn from(value: &'a mut Dynamic) -> Self {
@@ -278,7 +354,6 @@ impl<'a> From<&'a mut Dynamic> for Target<'a> {
     }
 }
 
-#[cfg(any(not(feature = "no_index"), not(feature = "no_object")))]
 impl<T: Into<Dynamic>> From<T> for Target<'_> {
     #[inline(always)]
     fn from(value: T) -> Self {
@@ -292,7 +367,7 @@ impl<T: Into<Dynamic>> From<T> for Target<'_> {
 /// ## WARNING
 ///
 /// This type is volatile and may change.
-#[derive(Debug, Clone, Eq, PartialEq, Hash, Default)]
+#[derive(Debug, Clone, Default)]
 pub struct State {
     /// Normally, access to variables are parsed with a relative offset into the scope to avoid a lookup.
     /// In some situation, e.g. after running an `eval` statement, subsequent offsets become mis-aligned.
@@ -305,6 +380,32 @@ pub struct State {
     pub operations: u64,
     /// Number of modules loaded.
     pub modules: usize,
+    /// Number of `yield` expressions evaluated outside of generator functions.
+    pub yields: usize,
+    /// Values to return from `yield` expressions when replaying a resumed script, in order.
+    pub resume_values: Vec<Dynamic>,
+    /// Values passed to, and positions of, the `yield` expressions the script was resumed from,
+    /// in order.  Used to detect a replay that does not follow the original run.
+    pub yielded: Vec<(Dynamic, Position)>,
+    /// Number of `yield` expressions still to skip, and the value of the `yield` stopped at,
+    /// of the generator function currently running, if any.
+    pub generator: Option<(usize, Option<Dynamic>)>,
+    /// Approximate number of bytes held in the scope of each call frame, indexed by call level.
+    /// Only tracked when there is a memory limit.
+    pub frame_memory: Vec<FrameMemory>,
+    /// Time when the script started running.  Only tracked when there is a time limit.
+    #[cfg(not(feature = "no_std"))]
+    pub started: Option<Instant>,
+    /// Token to cancel the script, if any.
+    pub cancel: Option<CancellationToken>,
+    /// Function calls being profiled.  Only tracked when there is a profiler.
+    pub profile_stack: Vec<ProfileFrame>,
+    /// Members of other modules re-exported by `export` statements.
+    #[cfg(not(feature = "no_module"))]
+    pub exports: Module,
+    /// Keys of the modules being imported, outermost first, ending with the module being evaluated.
+    #[cfg(not(feature = "no_module"))]
+    pub import_chain: Vec<String>,
 }
 
 impl State {
@@ -313,6 +414,143 @@ impl State {
     pub fn new() -> Self {
         Default::default()
     }
+    /// Is the script being replayed up to the last `yield` it was suspended at, or the generator
+    /// function currently running replayed up to the last value it yielded?
+    #[inline(always)]
+    pub fn is_replaying(&self) -> bool {
+        match self.generator {
+            Some((skip, _)) if skip > 0 => true,
+            _ => self.yields < self.resume_values.len(),
+        }
+    }
+}
+
+/// [INTERNALS] Approximate number of bytes held by the variables in the scope of a call frame.
+/// Exported under the `internals` feature only.
+///
+/// Only variables that may have changed are re-calculated after each statement.
+///
+/// ## WARNING
+///
+/// This type is volatile and may change.
+#[derive(Debug, Clone, Default, Eq, PartialEq, Hash)]
+pub struct FrameMemory {
+    /// Number of bytes held by each variable, in scope order.
+    vars: Vec<usize>,
+    /// Total number of bytes held by all the variables.
+    total: usize,
+    /// Indices of variables that may have been modified since last calculated.
+    modified: Vec<usize>,
+    /// Indices of variables holding shared values, which can be modified anywhere.
+    shared: Vec<usize>,
+    /// Must all the variables be re-calculated?
+    all_modified: bool,
+}
+
+#[cfg(not(feature = "unchecked"))]
+impl FrameMemory {
+    /// Bring the number of bytes held up to date with the scope, and return it.
+    fn update(&mut self, scope: &Scope) -> usize {
+        let len = scope.len();
+
+        if self.all_modified {
+            self.all_modified = false;
+            self.vars.clear();
+            self.total = 0;
+            self.shared.clear();
+        }
+
+        // Variables removed from the scope
+        if self.vars.len() > len {
+            self.total -= self.vars[len..].iter().sum::<usize>();
+            self.vars.truncate(len);
+            self.shared.retain(|&index| index < len);
+        }
+
+        // Variables modified, including all shared values
+        let mut modified = mem::take(&mut self.modified);
+        modified.extend(self.shared.iter().copied());
+
+        let num_vars = self.vars.len();
+
+        for index in modified.drain(..).filter(|&index| index < num_vars) {
+            let (name, value) = scope.get_by_index(index);
+            let size = name.len() + calc_memory(value);
+            self.total = self.total - self.vars[index] + size;
+            self.vars[index] = size;
+
+            if value.is_shared() && !self.shared.contains(&index) {
+                self.shared.push(index);
+            }
+        }
+
+        self.modified = modified;
+
+        // Variables added to the scope
+        for index in self.vars.len()..len {
+            let (name, value) = scope.get_by_index(index);
+            let size = name.len() + calc_memory(value);
+            self.total += size;
+            self.vars.push(size);
+
+            if value.is_shared() {
+                self.shared.push(index);
+            }
+        }
+
+        self.total
+    }
+}
+
+/// Calculate the approximate number of bytes held by a value, recursively.
+#[cfg(not(feature = "unchecked"))]
+fn calc_memory(value: &Dynamic) -> usize {
+    mem::size_of::<Dynamic>()
+        + match &value.0 {
+            Union::Str(s) => mem::size_of::<String>() + s.len(),
+            #[cfg(not(feature = "no_index"))]
+            Union::Array(arr) => arr.iter().map(calc_memory).sum(),
+            #[cfg(not(feature = "no_object"))]
+            Union::Map(map) => map
+                .iter()
+                .map(|(k, v)| mem::size_of::<String>() + k.len() + calc_memory(v))
+                .sum(),
+            Union::FnPtr(f) => f.fn_name().len() + f.curry().iter().map(calc_memory).sum::<usize>(),
+            #[cfg(not(feature = "no_closure"))]
+            Union::Shared(_) if value.is_locked() => 0,
+            #[cfg(not(feature = "no_closure"))]
+            Union::Shared(_) => value
+                .read_lock::<Dynamic>()
+                .map(|v| calc_memory(&v))
+                .unwrap_or(0),
+            Union::Variant(v) => mem::size_of_val(&***v),
+            _ => 0,
+        }
+}
+
+// Only the counters are compared and hashed - values, tokens, profiling data and modules are not.
+impl PartialEq for State {
+    fn eq(&self, other: &Self) -> bool {
+        self.always_search == other.always_search
+            && self.scope_level == other.scope_level
+            && self.operations == other.operations
+            && self.modules == other.modules
+            && self.yields == other.yields
+            && self.frame_memory == other.frame_memory
+    }
+}
+
+impl Eq for State {}
+
+impl Hash for State {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.always_search.hash(state);
+        self.scope_level.hash(state);
+        self.operations.hash(state);
+        self.modules.hash(state);
+        self.yields.hash(state);
+        self.frame_memory.hash(state);
+    }
 }
 
 /// Get a script-defined function definition from a module.
@@ -340,6 +578,7 @@ pub fn get_script_function_by_signature<'a>(
 ///
 /// This type is volatile and may change.
 #[cfg(not(feature = "unchecked"))]
+#[derive(Debug, Clone)]
 pub struct Limits {
     /// Maximum levels of call-stack to prevent infinite recursion.
     ///
@@ -359,6 +598,11 @@ pub struct Limits {
     pub max_array_size: usize,
     /// Maximum number of properties in a map.
     pub max_map_size: usize,
+    /// Maximum approximate number of bytes of data held by a script.
+    pub max_memory: usize,
+    /// Maximum amount of time a script is allowed to run.
+    #[cfg(not(feature = "no_std"))]
+    pub max_duration: Option<Duration>,
 }
 
 /// Rhai main scripting engine.
@@ -389,6 +633,9 @@ pub struct Engine {
     /// A module resolution service.
     #[cfg(not(feature = "no_module"))]
     pub(crate) module_resolver: Option<Box<dyn ModuleResolver>>,
+    /// Imported modules evaluated once and shared, if enabled.
+    #[cfg(not(feature = "no_module"))]
+    pub(crate) shared_modules: Option<SharedModules>,
 
     /// A hashmap mapping type names to pretty-print names.
     pub(crate) type_names: Option<HashMap<String, String>>,
@@ -397,15 +644,43 @@ pub struct Engine {
     pub(crate) disabled_symbols: Option<HashSet<String>>,
     /// A hashset containing custom keywords and precedence to recognize.
     pub(crate) custom_keywords: Option<HashMap<String, u8>>,
+    /// A hashset containing custom binary operators that bind to the right.
+    pub(crate) custom_bind_right: Option<HashSet<String>>,
+    /// A hashset containing custom prefix (unary) operators.
+    pub(crate) custom_prefix_operators: Option<HashSet<String>>,
     /// Custom syntax.
     pub(crate) custom_syntax: Option<HashMap<String, CustomSyntax>>,
 
+    /// Custom types that can be stored in a `Snapshot`.
+    #[cfg(feature = "serde")]
+    pub(crate) serializable_types: Option<HashMap<TypeId, SerializableType>>,
+
     /// Callback closure for implementing the `print` command.
     pub(crate) print: Callback<str, ()>,
     /// Callback closure for implementing the `debug` command.
     pub(crate) debug: Callback<str, ()>,
     /// Callback closure for progress reporting.
     pub(crate) progress: Option<Callback<u64, bool>>,
+    /// Callback closure for resolving variables not found in the scope.
+    pub(crate) var_resolver: Option<Box<OnVarCallback>>,
+    /// Profiler for function calls.
+    pub(crate) profiler: Option<Profiler>,
+    /// Sandbox profile restricting what scripts are allowed to do.
+    pub(crate) sandbox: Option<SandboxProfile>,
+
+    /// Deterministic execution mode.
+    pub(crate) deterministic: bool,
+    /// Are functions being registered marked deterministic?
+    pub(crate) marking_deterministic: bool,
+    /// Names of registered functions not marked deterministic.
+    pub(crate) nondeterministic_fns: Option<HashSet<String>>,
+    /// Clock for timestamps, with the instant it started from.
+    #[cfg(not(feature = "no_std"))]
+    pub(crate) clock: Option<(Instant, Callback<(), Duration>)>,
+
+    /// Promote overflowing integer arithmetic to `BigInt`?
+    #[cfg(feature = "bigint")]
+    pub(crate) bigint_promotion: bool,
 
     /// Optimize the AST after compilation.
     pub(crate) optimization_level: OptimizationLevel,
@@ -441,18 +716,37 @@ impl Default for Engine {
             #[cfg(not(feature = "no_module"))]
             #[cfg(any(feature = "no_std", target_arch = "wasm32",))]
             module_resolver: None,
+            #[cfg(not(feature = "no_module"))]
+            shared_modules: None,
 
             type_names: None,
             disabled_symbols: None,
             custom_keywords: None,
+            custom_bind_right: None,
+            custom_prefix_operators: None,
             custom_syntax: None,
 
+            #[cfg(feature = "serde")]
+            serializable_types: None,
+
             // default print/debug implementations
             print: Box::new(default_print),
             debug: Box::new(default_print),
 
             // progress callback
             progress: None,
+            var_resolver: None,
+            profiler: None,
+            sandbox: None,
+
+            deterministic: false,
+            marking_deterministic: false,
+            nondeterministic_fns: None,
+            #[cfg(not(feature = "no_std"))]
+            clock: None,
+
+            #[cfg(feature = "bigint")]
+            bigint_promotion: false,
 
             // optimization level
             optimization_level: if cfg!(feature = "no_optimize") {
@@ -471,6 +765,9 @@ impl Default for Engine {
                 max_string_size: 0,
                 max_array_size: 0,
                 max_map_size: 0,
+                max_memory: 0,
+                #[cfg(not(feature = "no_std"))]
+                max_duration: None,
             },
         };
 
@@ -494,6 +791,16 @@ pub fn make_setter(id: &str) -> String {
     format!("{}{}", FN_SET, id)
 }
 
+/// Is the target an instance of a record type with a particular field?
+#[cfg(not(feature = "no_object"))]
+fn is_record_field(target: &Target, field: &str) -> bool {
+    target
+        .as_ref()
+        .read_lock::<Record>()
+        .map(|record| record.contains(field))
+        .unwrap_or(false)
+}
+
 /// Print/debug to stdout
 fn default_print(_s: &str) {
     #[cfg(not(feature = "no_std"))]
@@ -519,126 +826,16 @@ pub fn search_imports<'s>(
 
     Ok(if let Some(index) = index {
         let offset = mods.len() - index.get();
-        &mods.get(offset).unwrap().1
+        mods.get(offset).unwrap().1.as_ref()
     } else {
         mods.iter()
             .rev()
             .find(|(n, _)| n == root)
-            .map(|(_, m)| m)
-            .ok_or_else(|| EvalAltResult::ErrorModuleNotFound(root.to_string(), *root_pos))?
-    })
-}
-
-/// Search for a module within an imports stack.
-/// Position in `EvalAltResult` is `None` and must be set afterwards.
-pub fn search_imports_mut<'s>(
-    mods: &'s mut Imports,
-    state: &mut State,
-    modules: &Box<ModuleRef>,
-) -> Result<&'s mut Module, Box<EvalAltResult>> {
-    let (root, root_pos) = &modules[0];
-
-    // Qualified - check if the root module is directly indexed
-    let index = if state.always_search {
-        None
-    } else {
-        modules.index()
-    };
-
-    Ok(if let Some(index) = index {
-        let offset = mods.len() - index.get();
-        &mut mods.get_mut(offset).unwrap().1
-    } else {
-        mods.iter_mut()
-            .rev()
-            .find(|(n, _)| n == root)
-            .map(|(_, m)| m)
+            .map(|(_, m)| m.as_ref())
             .ok_or_else(|| EvalAltResult::ErrorModuleNotFound(root.to_string(), *root_pos))?
     })
 }
 
-/// Search for a variable within the scope or within imports,
-/// depending on whether the variable name is qualified.
-pub fn search_namespace<'s, 'a>(
-    scope: &'s mut Scope,
-    mods: &'s mut Imports,
-    state: &mut State,
-    this_ptr: &'s mut Option<&mut Dynamic>,
-    expr: &'a Expr,
-) -> Result<(&'s mut Dynamic, &'a str, ScopeEntryType, Position), Box<EvalAltResult>> {
-    match expr {
-        Expr::Variable(v) => match v.as_ref() {
-            // Qualified variable
-            ((name, pos), Some(modules), hash_var, _) => {
-                let module = search_imports_mut(mods, state, modules)?;
-                let target = module
-                    .get_qualified_var_mut(*hash_var)
-                    .map_err(|err| match *err {
-                        EvalAltResult::ErrorVariableNotFound(_, _) => {
-                            EvalAltResult::ErrorVariableNotFound(
-                                format!("{}{}", modules, name),
-                                *pos,
-                            )
-                            .into()
-                        }
-                        _ => err.new_position(*pos),
-                    })?;
-
-                // Module variables are constant
-                Ok((target, name, ScopeEntryType::Constant, *pos))
-            }
-            // Normal variable access
-            _ => search_scope_only(scope, state, this_ptr, expr),
-        },
-        _ => unreachable!(),
-    }
-}
-
-/// Search for a variable within the scope
-pub fn search_scope_only<'s, 'a>(
-    scope: &'s mut Scope,
-    state: &mut State,
-    this_ptr: &'s mut Option<&mut Dynamic>,
-    expr: &'a Expr,
-) -> Result<(&'s mut Dynamic, &'a str, ScopeEntryType, Position), Box<EvalAltResult>> {
-    let ((name, pos), _, _, index) = match expr {
-        Expr::Variable(v) => v.as_ref(),
-        _ => unreachable!(),
-    };
-
-    // Check if the variable is `this`
-    if name == KEYWORD_THIS {
-        if let Some(val) = this_ptr {
-            return Ok(((*val).into(), KEYWORD_THIS, ScopeEntryType::Normal, *pos));
-        } else {
-            return EvalAltResult::ErrorUnboundThis(*pos).into();
-        }
-    }
-
-    // Check if it is directly indexed
-    let index = if state.always_search { None } else { *index };
-
-    let index = if let Some(index) = index {
-        scope.len() - index.get()
-    } else {
-        // Find the variable in the scope
-        scope
-            .get_index(name)
-            .ok_or_else(|| EvalAltResult::ErrorVariableNotFound(name.into(), *pos))?
-            .0
-    };
-
-    let (val, typ) = scope.get_mut(index);
-
-    // Check for data race - probably not necessary because the only place it should conflict is in a method call
-    //                       when the object variable is also used as a parameter.
-    // if cfg!(not(feature = "no_closure")) && val.is_locked() {
-    //     return EvalAltResult::ErrorDataRace(name.into(), *pos).into();
-    // }
-
-    Ok((val, name, typ, *pos))
-}
-
 impl Engine {
     /// Create a new `Engine`
     pub fn new() -> Self {
@@ -656,15 +853,34 @@ impl Engine {
 
             #[cfg(not(feature = "no_module"))]
             module_resolver: None,
+            #[cfg(not(feature = "no_module"))]
+            shared_modules: None,
 
             type_names: None,
             disabled_symbols: None,
             custom_keywords: None,
+            custom_bind_right: None,
+            custom_prefix_operators: None,
             custom_syntax: None,
 
+            #[cfg(feature = "serde")]
+            serializable_types: None,
+
             print: Box::new(|_| {}),
             debug: Box::new(|_| {}),
             progress: None,
+            var_resolver: None,
+            profiler: None,
+            sandbox: None,
+
+            deterministic: false,
+            marking_deterministic: false,
+            nondeterministic_fns: None,
+            #[cfg(not(feature = "no_std"))]
+            clock: None,
+
+            #[cfg(feature = "bigint")]
+            bigint_promotion: false,
 
             optimization_level: if cfg!(feature = "no_optimize") {
                 OptimizationLevel::None
@@ -682,12 +898,135 @@ impl Engine {
                 max_string_size: 0,
                 max_array_size: 0,
                 max_map_size: 0,
+                max_memory: 0,
+                #[cfg(not(feature = "no_std"))]
+                max_duration: None,
+            },
+        }
+    }
+
+    /// Get the current time from the clock of the `Engine`, if any, otherwise from the system.
+    #[cfg(not(feature = "no_std"))]
+    pub(crate) fn now(&self) -> Instant {
+        match &self.clock {
+            Some((start, clock)) => *start + clock(&()),
+            None => Instant::now(),
+        }
+    }
+
+    /// Search for a variable within the scope or within imports,
+    /// depending on whether the variable name is qualified.
+    pub(crate) fn search_namespace<'s, 'a>(
+        &self,
+        scope: &'s mut Scope,
+        context: EvalContext<'s, '_, '_, '_, 's, '_>,
+        expr: &'a Expr,
+    ) -> Result<(Target<'s>, &'a str, ScopeEntryType, Position), Box<EvalAltResult>> {
+        match expr {
+            Expr::Variable(v) => match v.as_ref() {
+                // Qualified variable
+                ((name, pos), Some(modules), hash_var, _) => {
+                    // Imported modules may be shared, so never modify them
+                    let module = search_imports(context.mods, context.state, modules)?;
+                    let target = module
+                        .get_qualified_var(*hash_var)
+                        .map_err(|err| match *err {
+                            EvalAltResult::ErrorVariableNotFound(_, _) => {
+                                EvalAltResult::ErrorVariableNotFound(
+                                    format!("{}{}", modules, name),
+                                    *pos,
+                                )
+                                .into()
+                            }
+                            _ => err.new_position(*pos),
+                        })?;
+
+                    // Module variables are constant
+                    Ok((
+                        Target::Value(target.clone()),
+                        name,
+                        ScopeEntryType::Constant,
+                        *pos,
+                    ))
+                }
+                // Normal variable access
+                _ => self.search_scope_only(scope, context, expr),
             },
+            _ => unreachable!(),
         }
     }
 
+    /// Search for a variable within the scope.
+    ///
+    /// Variables not found are resolved via the `on_var` callback, if any, into temporary values.
+    pub(crate) fn search_scope_only<'s, 'a>(
+        &self,
+        scope: &'s mut Scope,
+        context: EvalContext<'_, '_, '_, '_, 's, '_>,
+        expr: &'a Expr,
+    ) -> Result<(Target<'s>, &'a str, ScopeEntryType, Position), Box<EvalAltResult>> {
+        let ((name, pos), _, _, index) = match expr {
+            Expr::Variable(v) => v.as_ref(),
+            _ => unreachable!(),
+        };
+
+        // Check if the variable is `this`
+        if name == KEYWORD_THIS {
+            if let Some(val) = context.this_ptr {
+                return Ok((Target::Ref(val), KEYWORD_THIS, ScopeEntryType::Normal, *pos));
+            } else {
+                return EvalAltResult::ErrorUnboundThis(*pos).into();
+            }
+        }
+
+        // Check if it is directly indexed
+        let index = if context.state.always_search {
+            None
+        } else {
+            *index
+        };
+
+        let index = if let Some(index) = index {
+            scope.len() - index.get()
+        } else if let Some((index, _)) = scope.get_index(name) {
+            index
+        } else if let Some(resolver) = &self.var_resolver {
+            // Not in the scope - ask the variable resolver, leaving the scope untouched
+            // so that offsets of other variables remain valid
+            return match resolver(name, *pos, &context).map_err(|err| err.new_position(*pos))? {
+                Some((value, true)) => Ok((value.into(), name, ScopeEntryType::Constant, *pos)),
+                Some((value, false)) => Ok((value.into(), name, ScopeEntryType::Normal, *pos)),
+                None => EvalAltResult::ErrorVariableNotFound(name.into(), *pos).into(),
+            };
+        } else {
+            return EvalAltResult::ErrorVariableNotFound(name.into(), *pos).into();
+        };
+
+        let (val, typ) = scope.get_mut_by_index(index);
+
+        // Check for data race - probably not necessary because the only place it should conflict is in a method call
+        //                       when the object variable is also used as a parameter.
+        // if cfg!(not(feature = "no_closure")) && val.is_locked() {
+        //     return EvalAltResult::ErrorDataRace(name.into(), *pos).into();
+        // }
+
+        Ok((Target::Ref(val), name, typ, *pos))
+    }
+
+    /// Add a variable resolved via the `on_var` callback, and then modified, to the scope.
+    /// It shadows the callback until the end of the current block.
+    fn keep_resolved_var(&self, scope: &mut Scope, state: &mut State, name: &str, value: Dynamic) {
+        scope.push_dynamic(name.to_string(), value);
+
+        // The new variable is unknown to the parser, so offsets of variables are mis-aligned
+        state.always_search = true;
+    }
+
     /// Chain-evaluate a dot/index chain.
     /// Position in `EvalAltResult` is `None` and must be set afterwards.
+    ///
+    /// If `optional` is set (i.e. `?.` or `?[`) and `target` is `()`, the rest of the chain is skipped
+    /// and `()` is returned.
     #[cfg(any(not(feature = "no_index"), not(feature = "no_object")))]
     fn eval_dot_index_chain_helper(
         &self,
@@ -698,6 +1037,7 @@ impl Engine {
         rhs: &Expr,
         idx_values: &mut StaticVec<Dynamic>,
         chain_type: ChainType,
+        optional: bool,
         level: usize,
         new_val: Option<Dynamic>,
     ) -> Result<(Dynamic, bool), Box<EvalAltResult>> {
@@ -705,6 +1045,11 @@ impl Engine {
             panic!();
         }
 
+        // Short-circuit optional chaining on ()
+        if optional && target.is::<()>() {
+            return Ok((Default::default(), false));
+        }
+
         let is_ref = target.is_ref();
 
         let next_chain = match rhs {
@@ -724,15 +1069,15 @@ impl Engine {
                 match rhs {
                     // xxx[idx].expr... | xxx[idx][expr]...
                     Expr::Dot(x) | Expr::Index(x) => {
-                        let (idx, expr, pos) = x.as_ref();
+                        let (idx, expr, pos, optional) = x.as_ref();
                         let idx_pos = idx.position();
                         let obj_ptr = &mut self.get_indexed_mut(
                             state, lib, target, idx_val, idx_pos, false, true, level,
                         )?;
 
                         self.eval_dot_index_chain_helper(
-                            state, lib, this_ptr, obj_ptr, expr, idx_values, next_chain, level,
-                            new_val,
+                            state, lib, this_ptr, obj_ptr, expr, idx_values, next_chain, *optional,
+                            level, new_val,
                         )
                         .map_err(|err| err.new_position(*pos))
                     }
@@ -799,7 +1144,7 @@ impl Engine {
                 match rhs {
                     // xxx.fn_name(arg_expr_list)
                     Expr::FnCall(x) if x.1.is_none() => {
-                        let ((name, native, _, pos), _, hash, _, def_val) = x.as_ref();
+                        let ((name, native, _, _, pos), _, hash, _, def_val) = x.as_ref();
                         self.make_method_call(
                             state, lib, name, *hash, target, idx_val, *def_val, *native, false,
                             level,
@@ -808,8 +1153,11 @@ impl Engine {
                     }
                     // xxx.module::fn_name(...) - syntax error
                     Expr::FnCall(_) => unreachable!(),
-                    // {xxx:map}.id = ???
-                    Expr::Property(x) if target.is::<Map>() && new_val.is_some() => {
+                    // {xxx:map}.id = ??? | {xxx:record}.field = ???
+                    Expr::Property(x)
+                        if new_val.is_some()
+                            && (target.is::<Map>() || is_record_field(target, &(x.0).0)) =>
+                    {
                         let ((prop, _, _), pos) = x.as_ref();
                         let index = prop.clone().into();
                         let mut val = self
@@ -819,8 +1167,10 @@ impl Engine {
                             .map_err(|err| err.new_position(rhs.position()))?;
                         Ok((Default::default(), true))
                     }
-                    // {xxx:map}.id
-                    Expr::Property(x) if target.is::<Map>() => {
+                    // {xxx:map}.id | {xxx:record}.field
+                    Expr::Property(x)
+                        if target.is::<Map>() || is_record_field(target, &(x.0).0) =>
+                    {
                         let ((prop, _, _), pos) = x.as_ref();
                         let index = prop.clone().into();
                         let val = self.get_indexed_mut(
@@ -832,11 +1182,18 @@ impl Engine {
                     // xxx.id = ???
                     Expr::Property(x) if new_val.is_some() => {
                         let ((_, _, setter), pos) = x.as_ref();
+                        let redirected =
+                            self.redirect_record_fn(lib, target.as_ref(), setter, 1, false);
+                        let (setter, hash, setter_lib) = redirected
+                            .as_ref()
+                            .map_or((setter.as_str(), 0, lib), |(f, h, m)| {
+                                (f.as_str(), *h, m.as_deref().unwrap_or(lib))
+                            });
                         let mut new_val = new_val;
                         let mut args = [target.as_mut(), new_val.as_mut().unwrap()];
                         self.exec_fn_call(
-                            state, lib, setter, 0, &mut args, is_ref, true, false, None, None,
-                            level,
+                            state, setter_lib, setter, hash, &mut args, is_ref, true, false, None,
+                            None, level,
                         )
                         .map(|(v, _)| (v, true))
                         .map_err(|err| err.new_position(*pos))
@@ -844,17 +1201,28 @@ impl Engine {
                     // xxx.id
                     Expr::Property(x) => {
                         let ((_, getter, _), pos) = x.as_ref();
+                        let redirected =
+                            self.redirect_record_fn(lib, target.as_ref(), getter, 0, false);
+                        let (getter, hash, getter_lib) = redirected
+                            .as_ref()
+                            .map_or((getter.as_str(), 0, lib), |(f, h, m)| {
+                                (f.as_str(), *h, m.as_deref().unwrap_or(lib))
+                            });
                         let mut args = [target.as_mut()];
                         self.exec_fn_call(
-                            state, lib, getter, 0, &mut args, is_ref, true, false, None, None,
-                            level,
+                            state, getter_lib, getter, hash, &mut args, is_ref, true, false, None,
+                            None, level,
                         )
                         .map(|(v, _)| (v, false))
                         .map_err(|err| err.new_position(*pos))
                     }
                     // {xxx:map}.sub_lhs[expr] | {xxx:map}.sub_lhs.expr
-                    Expr::Index(x) | Expr::Dot(x) if target.is::<Map>() => {
-                        let (sub_lhs, expr, pos) = x.as_ref();
+                    // | {xxx:record}.field[expr] | {xxx:record}.field.expr
+                    Expr::Index(x) | Expr::Dot(x)
+                        if target.is::<Map>()
+                            || matches!(&x.0, Expr::Property(p) if is_record_field(target, &(p.0).0)) =>
+                    {
+                        let (sub_lhs, expr, pos, optional) = x.as_ref();
 
                         let mut val = match sub_lhs {
                             Expr::Property(p) => {
@@ -866,7 +1234,7 @@ impl Engine {
                             }
                             // {xxx:map}.fn_name(arg_expr_list)[expr] | {xxx:map}.fn_name(arg_expr_list).expr
                             Expr::FnCall(x) if x.1.is_none() => {
-                                let ((name, native, _, pos), _, hash, _, def_val) = x.as_ref();
+                                let ((name, native, _, _, pos), _, hash, _, def_val) = x.as_ref();
                                 let (val, _) = self
                                     .make_method_call(
                                         state, lib, name, *hash, target, idx_val, *def_val,
@@ -882,25 +1250,48 @@ impl Engine {
                         };
 
                         self.eval_dot_index_chain_helper(
-                            state, lib, this_ptr, &mut val, expr, idx_values, next_chain, level,
-                            new_val,
+                            state, lib, this_ptr, &mut val, expr, idx_values, next_chain,
+                            *optional, level, new_val,
                         )
                         .map_err(|err| err.new_position(*pos))
                     }
                     // xxx.sub_lhs[expr] | xxx.sub_lhs.expr
                     Expr::Index(x) | Expr::Dot(x) => {
-                        let (sub_lhs, expr, _) = x.as_ref();
+                        let (sub_lhs, expr, _, optional) = x.as_ref();
 
                         match sub_lhs {
                             // xxx.prop[expr] | xxx.prop.expr
                             Expr::Property(p) => {
                                 let ((_, getter, setter), pos) = p.as_ref();
+                                let getter_redirected =
+                                    self.redirect_record_fn(lib, target.as_ref(), getter, 0, false);
+                                let (getter, getter_hash, getter_lib) = getter_redirected
+                                    .as_ref()
+                                    .map_or((getter.as_str(), 0, lib), |(f, h, m)| {
+                                        (f.as_str(), *h, m.as_deref().unwrap_or(lib))
+                                    });
+                                let setter_redirected =
+                                    self.redirect_record_fn(lib, target.as_ref(), setter, 1, false);
+                                let (setter, setter_hash, setter_lib) = setter_redirected
+                                    .as_ref()
+                                    .map_or((setter.as_str(), 0, lib), |(f, h, m)| {
+                                        (f.as_str(), *h, m.as_deref().unwrap_or(lib))
+                                    });
                                 let arg_values = &mut [target.as_mut(), &mut Default::default()];
                                 let args = &mut arg_values[..1];
                                 let (mut val, updated) = self
                                     .exec_fn_call(
-                                        state, lib, getter, 0, args, is_ref, true, false, None,
-                                        None, level,
+                                        state,
+                                        getter_lib,
+                                        getter,
+                                        getter_hash,
+                                        args,
+                                        is_ref,
+                                        true,
+                                        false,
+                                        None,
+                                        None,
+                                        level,
                                     )
                                     .map_err(|err| err.new_position(*pos))?;
 
@@ -915,6 +1306,7 @@ impl Engine {
                                         expr,
                                         idx_values,
                                         next_chain,
+                                        *optional,
                                         level,
                                         new_val,
                                     )
@@ -925,8 +1317,17 @@ impl Engine {
                                     // Re-use args because the first &mut parameter will not be consumed
                                     arg_values[1] = val;
                                     self.exec_fn_call(
-                                        state, lib, setter, 0, arg_values, is_ref, true, false,
-                                        None, None, level,
+                                        state,
+                                        setter_lib,
+                                        setter,
+                                        setter_hash,
+                                        arg_values,
+                                        is_ref,
+                                        true,
+                                        false,
+                                        None,
+                                        None,
+                                        level,
                                     )
                                     .or_else(
                                         |err| match *err {
@@ -943,7 +1344,7 @@ impl Engine {
                             }
                             // xxx.fn_name(arg_expr_list)[expr] | xxx.fn_name(arg_expr_list).expr
                             Expr::FnCall(x) if x.1.is_none() => {
-                                let ((name, native, _, pos), _, hash, _, def_val) = x.as_ref();
+                                let ((name, native, _, _, pos), _, hash, _, def_val) = x.as_ref();
                                 let (mut val, _) = self
                                     .make_method_call(
                                         state, lib, name, *hash, target, idx_val, *def_val,
@@ -955,7 +1356,7 @@ impl Engine {
 
                                 self.eval_dot_index_chain_helper(
                                     state, lib, this_ptr, target, expr, idx_values, next_chain,
-                                    level, new_val,
+                                    *optional, level, new_val,
                                 )
                                 .map_err(|err| err.new_position(*pos))
                             }
@@ -987,7 +1388,7 @@ impl Engine {
         level: usize,
         new_val: Option<Dynamic>,
     ) -> Result<Dynamic, Box<EvalAltResult>> {
-        let ((dot_lhs, dot_rhs, op_pos), chain_type) = match expr {
+        let ((dot_lhs, dot_rhs, op_pos, optional), chain_type) = match expr {
             Expr::Index(x) => (x.as_ref(), ChainType::Index),
             Expr::Dot(x) => (x.as_ref(), ChainType::Dot),
             _ => unreachable!(),
@@ -1007,24 +1408,57 @@ impl Engine {
                 self.inc_operations(state)
                     .map_err(|err| err.new_position(*var_pos))?;
 
-                let (target, _, typ, pos) =
-                    search_namespace(scope, mods, state, this_ptr, dot_lhs)?;
+                self.touch_var(scope, state, dot_lhs, level);
 
-                // Constants cannot be modified
-                match typ {
-                    ScopeEntryType::Constant if new_val.is_some() => {
-                        return EvalAltResult::ErrorAssignmentToConstant(var_name.to_string(), pos)
+                let (value, resolved) = {
+                    let (target, _, typ, pos) = self.search_namespace(
+                        scope,
+                        EvalContext {
+                            mods,
+                            state,
+                            lib,
+                            this_ptr,
+                            level,
+                        },
+                        dot_lhs,
+                    )?;
+
+                    // Constants cannot be modified
+                    match typ {
+                        ScopeEntryType::Constant if new_val.is_some() => {
+                            return EvalAltResult::ErrorAssignmentToConstant(
+                                var_name.to_string(),
+                                pos,
+                            )
                             .into();
+                        }
+                        ScopeEntryType::Constant | ScopeEntryType::Normal => (),
+                    }
+
+                    // Lock shared variables for the whole chain
+                    let mut obj = match target {
+                        Target::Ref(r) => r.into(),
+                        target => target,
+                    };
+                    let (value, changed) = self
+                        .eval_dot_index_chain_helper(
+                            state, lib, &mut None, &mut obj, dot_rhs, idx_values, chain_type,
+                            *optional, level, new_val,
+                        )
+                        .map_err(|err| err.new_position(*op_pos))?;
+
+                    match typ {
+                        ScopeEntryType::Normal if changed => (value, obj.take_value()),
+                        _ => (value, None),
                     }
-                    ScopeEntryType::Constant | ScopeEntryType::Normal => (),
+                };
+
+                // A variable resolved via `on_var` is only a temporary value - keep the changes
+                if let Some(resolved) = resolved {
+                    self.keep_resolved_var(scope, state, var_name, resolved);
                 }
 
-                let obj_ptr = &mut target.into();
-                self.eval_dot_index_chain_helper(
-                    state, lib, &mut None, obj_ptr, dot_rhs, idx_values, chain_type, level, new_val,
-                )
-                .map(|(v, _)| v)
-                .map_err(|err| err.new_position(*op_pos))
+                Ok(value)
             }
             // {expr}.??? = ??? or {expr}[???] = ???
             expr if new_val.is_some() => {
@@ -1035,7 +1469,8 @@ impl Engine {
                 let val = self.eval_expr(scope, mods, state, lib, this_ptr, expr, level)?;
                 let obj_ptr = &mut val.into();
                 self.eval_dot_index_chain_helper(
-                    state, lib, this_ptr, obj_ptr, dot_rhs, idx_values, chain_type, level, new_val,
+                    state, lib, this_ptr, obj_ptr, dot_rhs, idx_values, chain_type, *optional,
+                    level, new_val,
                 )
                 .map(|(v, _)| v)
                 .map_err(|err| err.new_position(*op_pos))
@@ -1079,7 +1514,7 @@ impl Engine {
             Expr::FnCall(_) => unreachable!(),
             Expr::Property(_) => idx_values.push(().into()), // Store a placeholder - no need to copy the property name
             Expr::Index(x) | Expr::Dot(x) => {
-                let (lhs, rhs, _) = x.as_ref();
+                let (lhs, rhs, _, _) = x.as_ref();
 
                 // Evaluate in left-to-right order
                 let lhs_val = match lhs {
@@ -1180,6 +1615,30 @@ impl Engine {
                 })
             }
 
+            #[cfg(not(feature = "no_object"))]
+            Dynamic(Union::Variant(_)) if val.is::<Record>() => {
+                // val_record[field]
+                let index = idx
+                    .read_lock::<ImmutableString>()
+                    .ok_or_else(|| EvalAltResult::ErrorStringIndexExpr(idx_pos))?;
+                let record = val.downcast_mut::<Record>().unwrap();
+
+                // Fields cannot be added to a record
+                if record.contains(&index) {
+                    Ok(record.get_mut(&index).unwrap().into())
+                } else {
+                    EvalAltResult::ErrorDotExpr(
+                        format!(
+                            "Unknown property '{}' for {}",
+                            index.as_str(),
+                            record.type_name()
+                        ),
+                        Position::none(),
+                    )
+                    .into()
+                }
+            }
+
             #[cfg(not(feature = "no_index"))]
             Dynamic(Union::Str(s)) => {
                 // val_string[idx]
@@ -1317,9 +1776,38 @@ impl Engine {
                     EvalAltResult::ErrorUnboundThis((x.0).1).into()
                 }
             }
+            // Qualified variable - read it without cloning a shared module
+            Expr::Variable(x) if x.1.is_some() => {
+                let ((name, pos), modules, hash_var, _) = x.as_ref();
+                let modules = modules.as_ref().unwrap();
+                let module = search_imports(mods, state, modules)?;
+                let val = module
+                    .get_qualified_var(*hash_var)
+                    .map_err(|err| match *err {
+                        EvalAltResult::ErrorVariableNotFound(_, _) => {
+                            EvalAltResult::ErrorVariableNotFound(
+                                format!("{}{}", modules, name),
+                                *pos,
+                            )
+                            .into()
+                        }
+                        _ => err.new_position(*pos),
+                    })?;
+                Ok(val.clone())
+            }
             Expr::Variable(_) => {
-                let (val, _, _, _) = search_namespace(scope, mods, state, this_ptr, expr)?;
-                Ok(val.clone())
+                let (val, _, _, _) = self.search_namespace(
+                    scope,
+                    EvalContext {
+                        mods,
+                        state,
+                        lib,
+                        this_ptr,
+                        level,
+                    },
+                    expr,
+                )?;
+                Ok(val.clone_into_dynamic())
             }
             Expr::Property(_) => unreachable!(),
 
@@ -1331,16 +1819,28 @@ impl Engine {
                 let (lhs_expr, op, rhs_expr, op_pos) = x.as_ref();
                 let mut rhs_val =
                     self.eval_expr(scope, mods, state, lib, this_ptr, rhs_expr, level)?;
-                let (lhs_ptr, name, typ, pos) =
-                    search_namespace(scope, mods, state, this_ptr, lhs_expr)?;
+                self.touch_var(scope, state, lhs_expr, level);
+                let (mut lhs_target, name, typ, pos) = self.search_namespace(
+                    scope,
+                    EvalContext {
+                        mods,
+                        state,
+                        lib,
+                        this_ptr,
+                        level,
+                    },
+                    lhs_expr,
+                )?;
+                let lhs_ptr = lhs_target.as_mut();
                 self.inc_operations(state)
                     .map_err(|err| err.new_position(pos))?;
 
                 match typ {
                     // Assignment to constant variable
-                    ScopeEntryType::Constant => Err(Box::new(
-                        EvalAltResult::ErrorAssignmentToConstant(name.to_string(), pos),
-                    )),
+                    ScopeEntryType::Constant => {
+                        return EvalAltResult::ErrorAssignmentToConstant(name.to_string(), pos)
+                            .into()
+                    }
                     // Normal assignment
                     ScopeEntryType::Normal if op.is_empty() => {
                         let value = rhs_val.flatten();
@@ -1349,7 +1849,6 @@ impl Engine {
                         } else {
                             *lhs_ptr = value;
                         }
-                        Ok(Default::default())
                     }
                     // Op-assignment - in order of precedence:
                     ScopeEntryType::Normal => {
@@ -1366,17 +1865,26 @@ impl Engine {
                             .get_fn(hash_fn, false)
                             .or_else(|| self.packages.get_fn(hash_fn, false))
                         {
+                            self.check_deterministic_call(op, hash_fn, &[], false)
+                                .map_err(|err| err.new_position(*op_pos))?;
+
                             if cfg!(not(feature = "no_closure")) && lhs_ptr.is_shared() {
                                 let mut lock_guard = lhs_ptr.write_lock::<Dynamic>().unwrap();
                                 let lhs_ptr_inner = lock_guard.deref_mut();
 
                                 // Overriding exact implementation
-                                func(self, lib, &mut [lhs_ptr_inner, &mut rhs_val])?;
+                                let context =
+                                    NativeCallContext::new(self, lib, state.cancel.as_ref());
+                                func(context, &mut [lhs_ptr_inner, &mut rhs_val])?;
                             } else {
                                 // Overriding exact implementation
-                                func(self, lib, &mut [lhs_ptr, &mut rhs_val])?;
+                                let context =
+                                    NativeCallContext::new(self, lib, state.cancel.as_ref());
+                                func(context, &mut [lhs_ptr, &mut rhs_val])?;
                             }
-                        } else if run_builtin_op_assignment(op, lhs_ptr, &rhs_val)?.is_none() {
+                        } else if self.promotes_to_bigint(lhs_ptr, &rhs_val)
+                            || run_builtin_op_assignment(op, lhs_ptr, &rhs_val)?.is_none()
+                        {
                             // Not built in, map to `var = var op rhs`
                             let op = &op[..op.len() - 1]; // extract operator without =
 
@@ -1397,9 +1905,15 @@ impl Engine {
                                 *lhs_ptr = value;
                             }
                         }
-                        Ok(Default::default())
                     }
                 }
+
+                // A variable resolved via `on_var` is only a temporary value - keep the new value
+                if let Some(resolved) = lhs_target.take_value() {
+                    self.keep_resolved_var(scope, state, name, resolved);
+                }
+
+                Ok(Default::default())
             }
 
             // lhs op= rhs
@@ -1481,22 +1995,47 @@ impl Engine {
                         self.eval_expr(scope, mods, state, lib, this_ptr, expr, level)
                             .map(|val| (key.clone(), val))
                     })
-                    .collect::<Result<HashMap<_, _>, _>>()?,
+                    .collect::<Result<Map, _>>()?,
             )))),
 
+            #[cfg(not(feature = "no_object"))]
+            Expr::Record(x) => {
+                let (type_name, fields, methods) = x.as_ref();
+                let fields = self
+                    .eval_expr(scope, mods, state, lib, this_ptr, fields, level)?
+                    .cast::<Map>();
+                let record = Record::new(type_name.clone(), fields).with_methods(methods);
+
+                Ok(Dynamic::from(record))
+            }
+
             // Normal function call
             Expr::FnCall(x) if x.1.is_none() => {
-                let ((name, native, capture, pos), _, hash, args_expr, def_val) = x.as_ref();
+                let ((name, native, capture, pipe, pos), _, hash, args_expr, def_val) = x.as_ref();
                 self.make_function_call(
                     scope, mods, state, lib, this_ptr, name, args_expr, *def_val, *hash, *native,
-                    false, *capture, level,
+                    false, *capture, *pipe, level,
                 )
                 .map_err(|err| err.new_position(*pos))
             }
 
+            // Function call with named arguments
+            Expr::FnCallNamed(x) => {
+                let ((name, capture, pos), args_expr, named_args) = x.as_ref();
+                let context = EvalContext {
+                    mods,
+                    state,
+                    lib,
+                    this_ptr,
+                    level,
+                };
+                self.make_named_function_call(scope, context, name, args_expr, named_args, *capture)
+                    .map_err(|err| err.new_position(*pos))
+            }
+
             // Module-qualified function call
             Expr::FnCall(x) if x.1.is_some() => {
-                let ((name, _, capture, pos), modules, hash, args_expr, def_val) = x.as_ref();
+                let ((name, _, capture, _, pos), modules, hash, args_expr, def_val) = x.as_ref();
                 self.make_qualified_function_call(
                     scope, mods, state, lib, this_ptr, modules, name, args_expr, *def_val, *hash,
                     *capture, level,
@@ -1524,6 +2063,17 @@ impl Engine {
                 .into())
             }
 
+            Expr::Coalesce(x) => {
+                let (lhs, rhs, _) = x.as_ref();
+                match self.eval_expr(scope, mods, state, lib, this_ptr, lhs, level)? {
+                    // Short-circuit unless lhs is ()
+                    value if value.is::<()>() => {
+                        self.eval_expr(scope, mods, state, lib, this_ptr, rhs, level)
+                    }
+                    value => Ok(value),
+                }
+            }
+
             Expr::Or(x) => {
                 let (lhs, rhs, _) = x.as_ref();
                 Ok((self
@@ -1546,7 +2096,41 @@ impl Engine {
             Expr::False(_) => Ok(false.into()),
             Expr::Unit(_) => Ok(().into()),
 
+            Expr::Yield(x) => {
+                let (expr, pos) = x.as_ref();
+                let value = self.eval_expr(scope, mods, state, lib, this_ptr, expr, level)?;
+
+                // Inside a generator function - skip the values already yielded,
+                // then stop the function at the next one
+                if let Some((skip, yielded)) = state.generator.as_mut() {
+                    if *skip > 0 {
+                        *skip -= 1;
+                        return Ok(().into());
+                    }
+                    *yielded = Some(value);
+                    return EvalAltResult::Return(().into(), *pos).into();
+                }
+
+                // Replaying a resumed script - return the value previously resumed with
+                let index = state.yields;
+                state.yields += 1;
+
+                match (state.resume_values.get(index), state.yielded.get(index)) {
+                    // The script must reach the same `yield` with the same value as before
+                    (Some(resumed), Some((prev_value, prev_pos)))
+                        if prev_pos == pos && is_same_yield(prev_value, &value) =>
+                    {
+                        Ok(resumed.clone())
+                    }
+                    (Some(_), _) => EvalAltResult::ErrorResumeDiverged(*pos).into(),
+                    (None, _) => EvalAltResult::Yield(value, *pos).into(),
+                }
+            }
+
             Expr::Custom(x) => {
+                // Custom syntax can do anything to the scope
+                self.touch_var_index(state, level, None);
+
                 let func = (x.0).1.as_ref();
                 let ep = (x.0).0.iter().map(|e| e.into()).collect::<StaticVec<_>>();
                 let mut context = EvalContext {
@@ -1562,10 +2146,230 @@ impl Engine {
             _ => unreachable!(),
         };
 
-        self.check_data_size(result)
+        self.check_data_size(None, state, level, result)
             .map_err(|err| err.new_position(expr.position()))
     }
 
+    /// Resolve a module for an `import` statement.
+    #[cfg(not(feature = "no_module"))]
+    fn import_module(
+        &self,
+        scope: &mut Scope,
+        context: EvalContext,
+        expr: &Expr,
+        _pos: Position,
+    ) -> Result<Shared<Module>, Box<EvalAltResult>> {
+        let EvalContext {
+            mods,
+            state,
+            lib,
+            this_ptr,
+            level,
+        } = context;
+
+        // Guard against too many modules
+        #[cfg(not(feature = "unchecked"))]
+        if state.modules >= self.limits.max_modules {
+            return EvalAltResult::ErrorTooManyModules(_pos).into();
+        }
+
+        if let Some(path) = self
+            .eval_expr(scope, mods, state, lib, this_ptr, expr, level)?
+            .try_cast::<ImmutableString>()
+        {
+            if let Some(sandbox) = &self.sandbox {
+                if let Some(err) = sandbox
+                    .check_keyword(Token::Import.syntax().as_ref())
+                    .or_else(|| sandbox.check_module(&path))
+                {
+                    return EvalAltResult::ErrorSandboxViolation(err, expr.position()).into();
+                }
+            }
+
+            if let Some(resolver) = &self.module_resolver {
+                // Every import counts towards the limit, even if the module is already shared
+                state.modules += 1;
+
+                // The module runs under the same time limit and cancellation token
+                let mut context = ImportContext::new();
+                context.chain = state.import_chain.clone();
+                #[cfg(not(feature = "no_std"))]
+                {
+                    context.started = state.started;
+                }
+                context.cancel = state.cancel.clone();
+
+                let key = match &self.shared_modules {
+                    Some(shared) => {
+                        let key = resolver.module_key(&context, &path);
+
+                        #[cfg(not(feature = "sync"))]
+                        let shared = shared.borrow();
+                        #[cfg(feature = "sync")]
+                        let shared = shared.read().unwrap();
+
+                        if let Some(module) = shared.get(&key) {
+                            return Ok(module.clone());
+                        }
+                        Some(key)
+                    }
+                    None => None,
+                };
+
+                // The module is evaluated without holding the lock, because it may import
+                // other shared modules.
+                let mut module = resolver.resolve_import(self, &context, &path, expr.position())?;
+                module.set_id(Some(path));
+                module.index_all_sub_modules();
+                let module: Shared<Module> = module.into();
+
+                match (key, &self.shared_modules) {
+                    (Some(key), Some(shared)) => {
+                        #[cfg(not(feature = "sync"))]
+                        let mut shared = shared.borrow_mut();
+                        #[cfg(feature = "sync")]
+                        let mut shared = shared.write().unwrap();
+
+                        Ok(shared.entry(key).or_insert(module).clone())
+                    }
+                    _ => Ok(module),
+                }
+            } else {
+                EvalAltResult::ErrorModuleNotFound(path.to_string(), expr.position()).into()
+            }
+        } else {
+            EvalAltResult::ErrorImportExpr(expr.position()).into()
+        }
+    }
+
+    /// Destructure a value according to a pattern.
+    /// The values bound to the variables in the pattern are pushed into `values`,
+    /// in the same order as `Pattern::names`.
+    fn destructure(
+        &self,
+        scope: &mut Scope,
+        context: EvalContext,
+        pattern: &Pattern,
+        value: Dynamic,
+        values: &mut StaticVec<Dynamic>,
+    ) -> Result<(), Box<EvalAltResult>> {
+        let EvalContext {
+            mods,
+            state,
+            lib,
+            this_ptr,
+            level,
+        } = context;
+
+        match pattern {
+            Pattern::Variable(_) => values.push(value.flatten()),
+
+            #[cfg(not(feature = "no_index"))]
+            Pattern::Array(x) => {
+                let (items, rest, pos) = x.as_ref();
+
+                let arr = match value.flatten() {
+                    Dynamic(Union::Array(arr)) => *arr,
+                    value => {
+                        return EvalAltResult::ErrorPatternMismatch(
+                            format!(
+                                "expecting an array, not '{}'",
+                                self.map_type_name(value.type_name())
+                            ),
+                            *pos,
+                        )
+                        .into()
+                    }
+                };
+                let len = arr.len();
+                let mut arr = arr.into_iter();
+
+                for (index, (item, default)) in items.iter().enumerate() {
+                    let value = match (arr.next(), default) {
+                        (Some(value), _) => value,
+                        (None, Some(expr)) => {
+                            self.eval_expr(scope, mods, state, lib, this_ptr, expr, level)?
+                        }
+                        (None, None) => {
+                            return EvalAltResult::ErrorPatternMismatch(
+                                format!("array of length {} has no element [{}]", len, index),
+                                item.position(),
+                            )
+                            .into()
+                        }
+                    };
+                    let context = EvalContext {
+                        mods,
+                        state,
+                        lib,
+                        this_ptr,
+                        level,
+                    };
+                    self.destructure(scope, context, item, value, values)?;
+                }
+
+                // ..rest
+                if rest.is_some() {
+                    values.push(Dynamic(Union::Array(Box::new(arr.collect()))));
+                }
+            }
+
+            #[cfg(not(feature = "no_object"))]
+            Pattern::Map(x) => {
+                let (items, rest, pos) = x.as_ref();
+
+                let map = match value.flatten() {
+                    Dynamic(Union::Map(map)) => *map,
+                    value => {
+                        return EvalAltResult::ErrorPatternMismatch(
+                            format!(
+                                "expecting an object map, not '{}'",
+                                self.map_type_name(value.type_name())
+                            ),
+                            *pos,
+                        )
+                        .into()
+                    }
+                };
+
+                for ((key, key_pos), item, default) in items.iter() {
+                    let value = match (map.get(key), default) {
+                        (Some(value), _) => value.clone(),
+                        (None, Some(expr)) => {
+                            self.eval_expr(scope, mods, state, lib, this_ptr, expr, level)?
+                        }
+                        (None, None) => {
+                            return EvalAltResult::ErrorPatternMismatch(
+                                format!("object map has no property '{}'", key),
+                                *key_pos,
+                            )
+                            .into()
+                        }
+                    };
+                    let context = EvalContext {
+                        mods,
+                        state,
+                        lib,
+                        this_ptr,
+                        level,
+                    };
+                    self.destructure(scope, context, item, value, values)?;
+                }
+
+                // ..rest
+                if rest.is_some() {
+                    let rest: Map = map
+                        .into_iter()
+                        .filter(|(k, _)| items.iter().all(|((key, _), _, _)| key != k))
+                        .collect();
+                    values.push(Dynamic(Union::Map(Box::new(rest))));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
     /// Evaluate a statement
     ///
     ///
@@ -1671,50 +2475,94 @@ impl Engine {
 
             // For loop
             Stmt::For(x) => {
-                let (name, expr, stmt, _) = x.as_ref();
+                let (pattern, expr, stmt, _) = x.as_ref();
                 let iter_type = self.eval_expr(scope, mods, state, lib, this_ptr, expr, level)?;
                 let tid = iter_type.type_id();
 
-                if let Some(func) = self
+                // A generator is run again for each value, so it can yield without end
+                let (mut iter, generator) = if let Some(func) = self
                     .global_module
                     .get_iter(tid)
                     .or_else(|| self.packages.get_iter(tid))
                 {
-                    // Add the loop variable
+                    (func(iter_type), None)
+                } else if iter_type.is::<Generator>() {
+                    let iter: Box<dyn Iterator<Item = Dynamic>> = Box::new(empty());
+                    (iter, Some(iter_type.cast::<Generator>()))
+                } else {
+                    return EvalAltResult::ErrorFor(x.1.position()).into();
+                };
+
+                // Add the loop variables
+                let start = scope.len();
+                for name in pattern.names() {
                     let var_name = unsafe_cast_var_name_to_lifetime(name, &state);
                     scope.push(var_name, ());
-                    let index = scope.len() - 1;
-                    state.scope_level += 1;
+                }
+                state.scope_level += 1;
+
+                let mut values = StaticVec::new();
+
+                for index in 0.. {
+                    let iter_value = match generator {
+                        Some(ref generator) => self
+                            .step_generator(mods, state, lib, generator, index, level)
+                            .map_err(|err| err.new_position(expr.position()))?,
+                        None => iter.next(),
+                    };
+
+                    let iter_value = match iter_value {
+                        Some(value) => value,
+                        None => break,
+                    };
+
+                    if let Pattern::Variable(_) = pattern {
+                        values.push(iter_value.flatten());
+                    } else {
+                        // Default values in the pattern are parsed before the loop variables
+                        // are added, so their offsets are mis-aligned - search by name instead.
+                        let always_search = state.always_search;
+                        state.always_search = true;
+                        let context = EvalContext {
+                            mods,
+                            state,
+                            lib,
+                            this_ptr,
+                            level,
+                        };
+                        let result =
+                            self.destructure(scope, context, pattern, iter_value, &mut values);
+                        state.always_search = always_search;
+                        result?;
+                    }
 
-                    for iter_value in func(iter_type) {
-                        let (loop_var, _) = scope.get_mut(index);
+                    for (index, value) in values.drain(..).enumerate() {
+                        self.touch_var_index(state, level, Some(start + index));
+                        let (loop_var, _) = scope.get_mut_by_index(start + index);
 
-                        let value = iter_value.flatten();
                         if cfg!(not(feature = "no_closure")) && loop_var.is_shared() {
                             *loop_var.write_lock().unwrap() = value;
                         } else {
                             *loop_var = value;
                         }
+                    }
 
-                        self.inc_operations(state)
-                            .map_err(|err| err.new_position(stmt.position()))?;
+                    self.inc_operations(state)
+                        .map_err(|err| err.new_position(stmt.position()))?;
 
-                        match self.eval_stmt(scope, mods, state, lib, this_ptr, stmt, level) {
-                            Ok(_) => (),
-                            Err(err) => match *err {
-                                EvalAltResult::ErrorLoopBreak(false, _) => (),
-                                EvalAltResult::ErrorLoopBreak(true, _) => break,
-                                _ => return Err(err),
-                            },
-                        }
+                    match self.eval_stmt(scope, mods, state, lib, this_ptr, stmt, level) {
+                        Ok(_) => (),
+                        Err(err) => match *err {
+                            EvalAltResult::ErrorLoopBreak(false, _) => (),
+                            EvalAltResult::ErrorLoopBreak(true, _) => break,
+                            _ => return Err(err),
+                        },
                     }
-
-                    scope.rewind(scope.len() - 1);
-                    state.scope_level -= 1;
-                    Ok(Default::default())
-                } else {
-                    EvalAltResult::ErrorFor(x.1.position()).into()
                 }
+
+                scope.rewind(start);
+                state.scope_level -= 1;
+                Ok(Default::default())
             }
 
             // Continue statement
@@ -1775,6 +2623,27 @@ impl Engine {
                 Ok(Default::default())
             }
 
+            // Let statement with a destructuring pattern
+            Stmt::LetPattern(x) => {
+                let (pattern, expr, _) = x.as_ref();
+                let val = self.eval_expr(scope, mods, state, lib, this_ptr, expr, level)?;
+                let mut values = StaticVec::new();
+                let context = EvalContext {
+                    mods,
+                    state,
+                    lib,
+                    this_ptr,
+                    level,
+                };
+                self.destructure(scope, context, pattern, val, &mut values)?;
+
+                for (var_name, val) in pattern.names().into_iter().zip(values) {
+                    let var_name = unsafe_cast_var_name_to_lifetime(var_name, &state);
+                    scope.push_dynamic_value(var_name, ScopeEntryType::Normal, val, false);
+                }
+                Ok(Default::default())
+            }
+
             // Const statement
             Stmt::Const(x) if x.1.is_constant() => {
                 let ((var_name, _), expr, _) = x.as_ref();
@@ -1792,50 +2661,112 @@ impl Engine {
             // Import statement
             #[cfg(not(feature = "no_module"))]
             Stmt::Import(x) => {
-                let (expr, alias, _pos) = x.as_ref();
-
-                // Guard against too many modules
-                #[cfg(not(feature = "unchecked"))]
-                if state.modules >= self.limits.max_modules {
-                    return EvalAltResult::ErrorTooManyModules(*_pos).into();
-                }
-
-                if let Some(path) = self
-                    .eval_expr(scope, mods, state, lib, this_ptr, &expr, level)?
-                    .try_cast::<ImmutableString>()
-                {
-                    if let Some(resolver) = &self.module_resolver {
-                        let mut module = resolver.resolve(self, &path, expr.position())?;
-
-                        if let Some((name, _)) = alias {
-                            module.index_all_sub_modules();
-                            mods.push((name.clone().into(), module));
-                        }
+                let (expr, alias, pos) = x.as_ref();
 
-                        state.modules += 1;
+                let context = EvalContext {
+                    mods,
+                    state,
+                    lib,
+                    this_ptr,
+                    level,
+                };
+                let module = self.import_module(scope, context, expr, *pos)?;
 
-                        Ok(Default::default())
-                    } else {
-                        Err(
-                            EvalAltResult::ErrorModuleNotFound(path.to_string(), expr.position())
-                                .into(),
-                        )
-                    }
-                } else {
-                    EvalAltResult::ErrorImportExpr(expr.position()).into()
+                if let Some((name, _)) = alias {
+                    mods.push((name.clone().into(), module));
                 }
+
+                Ok(Default::default())
             }
 
             // Export statement
             #[cfg(not(feature = "no_module"))]
             Stmt::Export(x) => {
-                for ((id, id_pos), rename) in x.0.iter() {
-                    // Mark scope variables as public
-                    if let Some(index) = scope.get_index(id).map(|(i, _)| i) {
-                        let alias = rename.as_ref().map(|(n, _)| n).unwrap_or_else(|| id);
-                        scope.set_entry_alias(index, alias.clone());
-                    } else {
-                        return EvalAltResult::ErrorVariableNotFound(id.into(), *id_pos).into();
+                for item in x.0.iter() {
+                    match item {
+                        // Mark scope variables as public
+                        ExportItem::Variable((id, id_pos), rename) => {
+                            if let Some(index) = scope.get_index(id).map(|(i, _)| i) {
+                                let alias = rename.as_ref().map(|(n, _)| n).unwrap_or_else(|| id);
+                                scope.set_entry_alias(index, alias.clone());
+                            } else {
+                                return EvalAltResult::ErrorVariableNotFound(id.into(), *id_pos)
+                                    .into();
+                            }
+                        }
+                        // Declare a variable and mark it as public
+                        ExportItem::Declaration(stmt) => {
+                            self.eval_stmt(scope, mods, state, lib, this_ptr, stmt, level)?;
+
+                            match stmt.as_ref() {
+                                Stmt::Let(x) => {
+                                    scope.set_entry_alias(scope.len() - 1, (x.0).0.clone());
+                                }
+                                Stmt::Const(x) => {
+                                    scope.set_entry_alias(scope.len() - 1, (x.0).0.clone());
+                                }
+                                Stmt::LetPattern(x) => {
+                                    let names = x.0.names();
+                                    let start = scope.len() - names.len();
+                                    names.into_iter().enumerate().for_each(|(i, name)| {
+                                        scope.set_entry_alias(start + i, name.to_string());
+                                    });
+                                }
+                                _ => unreachable!(),
+                            }
+                        }
+                        // Mark all scope variables as public
+                        ExportItem::AllVariables(_) => {
+                            scope.set_all_entry_aliases();
+                        }
+                        // Re-export members of an imported module
+                        ExportItem::Member((name, pos), member, rename) => {
+                            let module = mods
+                                .iter()
+                                .rev()
+                                .find(|(n, _)| n == name)
+                                .map(|(_, m)| m)
+                                .ok_or_else(|| {
+                                    EvalAltResult::ErrorModuleNotFound(name.to_string(), *pos)
+                                })?;
+
+                            match member {
+                                None => {
+                                    state.exports.re_export_all(module);
+                                }
+                                Some((id, id_pos)) => {
+                                    let alias = rename.as_ref().map(|(n, _)| n).unwrap_or(id);
+
+                                    if !state.exports.re_export(module, id, alias) {
+                                        return EvalAltResult::ErrorVariableNotFound(
+                                            format!("{}::{}", name, id),
+                                            *id_pos,
+                                        )
+                                        .into();
+                                    }
+                                }
+                            }
+                        }
+                        // Import a module and re-export it
+                        ExportItem::Module(expr, alias) => {
+                            let context = EvalContext {
+                                mods,
+                                state,
+                                lib,
+                                this_ptr,
+                                level,
+                            };
+                            let module = self.import_module(scope, context, expr, x.1)?;
+
+                            match alias {
+                                Some((name, _)) => {
+                                    mods.push((name.clone().into(), module));
+                                }
+                                None => {
+                                    state.exports.re_export_all(&module);
+                                }
+                            }
+                        }
                     }
                 }
                 Ok(Default::default())
@@ -1848,7 +2779,8 @@ impl Engine {
 
                 match scope.get_index(var_name) {
                     Some((index, ScopeEntryType::Normal)) => {
-                        let (val, _) = scope.get_mut(index);
+                        self.touch_var_index(state, level, Some(index));
+                        let (val, _) = scope.get_mut_by_index(index);
 
                         if !val.is_shared() {
                             // Replace the variable with a shared value.
@@ -1861,7 +2793,7 @@ impl Engine {
             }
         };
 
-        self.check_data_size(result)
+        self.check_data_size(Some(scope), state, level, result)
             .map_err(|err| err.new_position(stmt.position()))
     }
 
@@ -1871,6 +2803,9 @@ impl Engine {
     #[inline(always)]
     fn check_data_size(
         &self,
+        _scope: Option<&Scope>,
+        _state: &mut State,
+        _level: usize,
         result: Result<Dynamic, Box<EvalAltResult>>,
     ) -> Result<Dynamic, Box<EvalAltResult>> {
         result
@@ -1878,11 +2813,22 @@ impl Engine {
 
     /// Check a result to ensure that the data size is within allowable limit.
     /// Position in `EvalAltResult` may be None and should be set afterwards.
+    ///
+    /// When there is a memory limit, the memory held by variables in the scope of the current
+    /// call frame that may have changed is re-calculated if `scope` is passed.
+    /// Otherwise, the last calculated amount is used.
     #[cfg(not(feature = "unchecked"))]
     fn check_data_size(
         &self,
+        scope: Option<&Scope>,
+        state: &mut State,
+        level: usize,
         result: Result<Dynamic, Box<EvalAltResult>>,
     ) -> Result<Dynamic, Box<EvalAltResult>> {
+        if self.limits.max_memory > 0 {
+            self.check_memory(scope, state, level, &result)?;
+        }
+
         // If no data size limits, just return
         if self.limits.max_string_size + self.limits.max_array_size + self.limits.max_map_size == 0
         {
@@ -1987,6 +2933,103 @@ impl Engine {
         }
     }
 
+    /// Check that the approximate memory held by all call frames, plus a result, is within limit.
+    /// Position in `EvalAltResult` is `None` and must be set afterwards.
+    #[cfg(not(feature = "unchecked"))]
+    fn check_memory(
+        &self,
+        scope: Option<&Scope>,
+        state: &mut State,
+        level: usize,
+        result: &Result<Dynamic, Box<EvalAltResult>>,
+    ) -> Result<(), Box<EvalAltResult>> {
+        // Re-calculate the memory held by variables in the current scope that may have changed
+        if let Some(scope) = scope {
+            if state.frame_memory.len() <= level {
+                state.frame_memory.resize_with(level + 1, Default::default);
+            }
+            state.frame_memory[level].update(scope);
+        }
+
+        let memory = state
+            .frame_memory
+            .iter()
+            .take(level + 1)
+            .map(|frame| frame.total)
+            .sum::<usize>()
+            + result.as_ref().map(calc_memory).unwrap_or(0);
+
+        if memory > self.limits.max_memory {
+            EvalAltResult::ErrorDataTooLarge(
+                "Memory usage".to_string(),
+                self.limits.max_memory,
+                memory,
+                Position::none(),
+            )
+            .into()
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Record that a variable may be modified, so that the memory it holds is re-calculated
+    /// after the current statement.  Only tracked when there is a memory limit.
+    #[cfg(not(feature = "unchecked"))]
+    pub(crate) fn touch_var(&self, scope: &Scope, state: &mut State, expr: &Expr, level: usize) {
+        if self.limits.max_memory == 0 {
+            return;
+        }
+
+        let index = match expr {
+            Expr::Variable(x) => match x.as_ref() {
+                // Module variables are constant
+                (_, Some(_), _, _) => return,
+                (_, None, _, Some(index)) if !state.always_search => scope.len() - index.get(),
+                ((name, _), None, _, _) => match scope.get_index(name) {
+                    Some((index, _)) => index,
+                    None => return,
+                },
+            },
+            _ => return,
+        };
+
+        self.touch_var_index(state, level, Some(index));
+    }
+
+    /// Record that a variable may be modified, so that the memory it holds is re-calculated
+    /// after the current statement.  Only tracked when there is a memory limit.
+    #[cfg(feature = "unchecked")]
+    #[inline(always)]
+    pub(crate) fn touch_var(&self, _: &Scope, _: &mut State, _: &Expr, _: usize) {}
+
+    /// Record that the variable at an index in the scope may be modified, or all variables if
+    /// `None`, so that the memory held is re-calculated after the current statement.
+    /// Only tracked when there is a memory limit.
+    #[cfg(not(feature = "unchecked"))]
+    pub(crate) fn touch_var_index(&self, state: &mut State, level: usize, index: Option<usize>) {
+        if self.limits.max_memory == 0 {
+            return;
+        }
+
+        if state.frame_memory.len() <= level {
+            state.frame_memory.resize_with(level + 1, Default::default);
+        }
+
+        let frame = &mut state.frame_memory[level];
+
+        match index {
+            Some(index) => frame.modified.push(index),
+            None => frame.all_modified = true,
+        }
+    }
+
+    /// Record that the variable at an index in the scope may be modified, or all variables if
+    /// `None`, so that the memory held is re-calculated after the current statement.
+    /// Only tracked when there is a memory limit.
+    #[cfg(feature = "unchecked")]
+    #[inline(always)]
+    pub(crate) fn touch_var_index(&self, _: &mut State, _: usize, _: Option<usize>) {}
+
     /// Check if the number of operations stay within limit.
     /// Position in `EvalAltResult` is `None` and must be set afterwards.
     pub(crate) fn inc_operations(&self, state: &mut State) -> Result<(), Box<EvalAltResult>> {
@@ -1998,6 +3041,20 @@ impl Engine {
             return EvalAltResult::ErrorTooManyOperations(Position::none()).into();
         }
 
+        // Guard against running for too long
+        #[cfg(not(feature = "unchecked"))]
+        #[cfg(not(feature = "no_std"))]
+        if let Some(max_duration) = self.limits.max_duration {
+            if state.started.get_or_insert_with(Instant::now).elapsed() > max_duration {
+                return EvalAltResult::ErrorTerminated(Position::none()).into();
+            }
+        }
+
+        // Terminate script if cancelled
+        if let Some(token) = &state.cancel {
+            token.check()?;
+        }
+
         // Report progress - only in steps
         if let Some(progress) = &self.progress {
             if !progress(&state.operations) {
@@ -2017,4 +3074,16 @@ impl Engine {
             .and_then(|t| t.get(name).map(String::as_str))
             .unwrap_or(map_std_type_name(name))
     }
+
+    /// Get the pretty-print name of the type of a value, as returned by `type_of`.
+    ///
+    /// Instances of record types declared in script report the name of their record type.
+    pub(crate) fn map_value_type_name(&self, value: &Dynamic) -> ImmutableString {
+        #[cfg(not(feature = "no_object"))]
+        if let Some(type_name) = record_type_name(value) {
+            return type_name;
+        }
+
+        self.map_type_name(value.type_name()).into()
+    }
 }
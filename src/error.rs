@@ -35,6 +35,8 @@ pub enum LexError {
     MalformedIdentifier(String),
     /// Bad symbol encountered when tokenizing the script text.
     ImproperSymbol(String),
+    /// A keyword is not allowed by the sandbox profile. Wrapped value describes the keyword.
+    SandboxViolation(String),
 }
 
 impl Error for LexError {}
@@ -54,6 +56,7 @@ impl fmt::Display for LexError {
                 max
             ),
             Self::ImproperSymbol(s) => f.write_str(s),
+            Self::SandboxViolation(s) => write!(f, "{} is not allowed by the sandbox profile", s),
         }
     }
 }
@@ -131,6 +134,19 @@ pub enum ParseErrorType {
     ///
     /// Never appears under the `no_function` feature.
     FnMissingBody(String),
+    /// Defining a record type `struct` in an appropriate place (e.g. inside a function).
+    ///
+    /// Never appears under the `no_object` and `no_function` features.
+    WrongStructDefinition,
+    /// A record type definition has duplicated fields, methods or property accessors.
+    /// Wrapped values are the record type name and the field, method or property name.
+    ///
+    /// Never appears under the `no_object` and `no_function` features.
+    StructDuplicatedField(String, String),
+    /// A record type is declared more than once.  Wrapped value is the record type name.
+    ///
+    /// Never appears under the `no_object` and `no_function` features.
+    DuplicatedStruct(String),
     /// An export statement has duplicated names.
     ///
     /// Never appears under the `no_module` feature.
@@ -153,6 +169,9 @@ pub enum ParseErrorType {
     LiteralTooLarge(String, usize),
     /// Break statement not inside a loop.
     LoopBreak,
+    /// A function, keyword or module is not allowed by the sandbox profile.
+    /// Wrapped value describes the item not allowed.
+    SandboxViolation(String),
 }
 
 impl ParseErrorType {
@@ -182,13 +201,17 @@ impl ParseErrorType {
             Self::FnDuplicatedParam(_,_) => "Duplicated parameters in function declaration",
             Self::FnMissingBody(_) => "Expecting body statement block for function declaration",
             Self::WrongFnDefinition => "Function definitions must be at global level and cannot be inside a block or another function",
+            Self::WrongStructDefinition => "Record type definitions must be at global level and cannot be inside a block or a function",
+            Self::StructDuplicatedField(_,_) => "Duplicated field in record type declaration",
+            Self::DuplicatedStruct(_) => "Duplicated record type declaration",
             Self::DuplicatedExport(_) => "Duplicated variable/function in export statement",
             Self::WrongExport => "Export statement can only appear at global level",
             Self::AssignmentToCopy => "Only a copy of the value is change with this assignment",
             Self::AssignmentToConstant(_) => "Cannot assign to a constant value",
             Self::ExprTooDeep => "Expression exceeds maximum complexity",
             Self::LiteralTooLarge(_, _) => "Literal exceeds maximum limit",
-            Self::LoopBreak => "Break statement should only be used inside a loop"
+            Self::LoopBreak => "Break statement should only be used inside a loop",
+            Self::SandboxViolation(_) => "Not allowed by the sandbox profile"
         }
     }
 }
@@ -227,6 +250,12 @@ impl fmt::Display for ParseErrorType {
                 write!(f, "Duplicated parameter '{}' for function '{}'", arg, s)
             }
 
+            Self::StructDuplicatedField(s, field) => {
+                write!(f, "Duplicated field '{}' for record type '{}'", field, s)
+            }
+
+            Self::DuplicatedStruct(s) => write!(f, "Record type '{}' is already declared", s),
+
             Self::DuplicatedExport(s) => write!(
                 f,
                 "Duplicated variable/function '{}' in export statement",
@@ -241,6 +270,7 @@ impl fmt::Display for ParseErrorType {
                 write!(f, "{} exceeds the maximum limit ({})", typ, max)
             }
             Self::Reserved(s) => write!(f, "'{}' is a reserved keyword", s),
+            Self::SandboxViolation(s) => write!(f, "{} is not allowed by the sandbox profile", s),
             _ => f.write_str(self.desc()),
         }
     }
@@ -252,6 +282,7 @@ impl From<&LexError> for ParseErrorType {
             LexError::StringTooLong(max) => {
                 Self::LiteralTooLarge("Length of string literal".to_string(), *max)
             }
+            LexError::SandboxViolation(s) => Self::SandboxViolation(s.clone()),
             _ => Self::BadInput(err.to_string()),
         }
     }
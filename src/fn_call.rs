@@ -3,35 +3,59 @@
 use crate::any::Dynamic;
 use crate::calc_fn_hash;
 use crate::engine::{
-    search_imports, search_namespace, search_scope_only, Engine, Imports, State, KEYWORD_DEBUG,
-    KEYWORD_EVAL, KEYWORD_FN_PTR, KEYWORD_FN_PTR_CALL, KEYWORD_FN_PTR_CURRY, KEYWORD_IS_SHARED,
-    KEYWORD_PRINT, KEYWORD_TYPE_OF,
+    search_imports, Engine, Imports, State, Target, KEYWORD_DEBUG, KEYWORD_EVAL, KEYWORD_FN_PTR,
+    KEYWORD_FN_PTR_CALL, KEYWORD_FN_PTR_CURRY, KEYWORD_IS_SHARED, KEYWORD_PRINT, KEYWORD_TYPE_OF,
 };
 use crate::error::ParseErrorType;
-use crate::fn_native::{FnCallArgs, FnPtr};
+use crate::fn_native::{FnCallArgs, FnPtr, NativeCallContext};
 use crate::module::{Module, ModuleRef};
 use crate::optimize::OptimizationLevel;
 use crate::parser::{Expr, ImmutableString, AST, INT};
+use crate::profiler::Profiler;
 use crate::result::EvalAltResult;
 use crate::scope::Scope;
 use crate::stdlib::ops::Deref;
-use crate::token::Position;
+use crate::syntax::EvalContext;
+use crate::token::{is_keyword_function, Position, Token};
 use crate::utils::StaticVec;
 
 #[cfg(not(feature = "no_function"))]
 use crate::{
-    parser::ScriptFnDef, r#unsafe::unsafe_cast_var_name_to_lifetime,
+    fn_native::CallableFunction, parser::ScriptFnDef, r#unsafe::unsafe_cast_var_name_to_lifetime,
     scope::EntryType as ScopeEntryType,
 };
 
 #[cfg(not(feature = "no_float"))]
 use crate::parser::FLOAT;
 
+#[cfg(feature = "decimal")]
+use rust_decimal::Decimal;
+
+#[cfg(feature = "bigint")]
+use num_bigint::BigInt;
+
 #[cfg(not(feature = "no_index"))]
 use crate::engine::{FN_IDX_GET, FN_IDX_SET};
 
+#[cfg(not(feature = "no_index"))]
+#[cfg(not(feature = "no_function"))]
+use crate::engine::Array;
+
+#[cfg(not(feature = "no_object"))]
+use crate::engine::{Map, FN_GET, FN_SET};
+
+#[cfg(any(not(feature = "no_object"), not(feature = "no_function")))]
+use crate::fn_native::Shared;
+
+#[cfg(not(feature = "no_object"))]
+use crate::record::{make_record_fn_name, record_methods, record_type_name};
+
 #[cfg(not(feature = "no_object"))]
-use crate::engine::{Map, Target, FN_GET, FN_SET};
+#[cfg(not(feature = "no_function"))]
+use crate::record::Record;
+
+#[cfg(not(feature = "no_function"))]
+use crate::resume::Generator;
 
 #[cfg(not(feature = "no_closure"))]
 #[cfg(not(feature = "no_function"))]
@@ -190,6 +214,20 @@ pub fn ensure_no_data_race(
 }
 
 impl Engine {
+    /// Does `INT` arithmetic between these arguments promote to `BigInt` instead of overflowing?
+    #[cfg(feature = "bigint")]
+    #[inline(always)]
+    pub(crate) fn promotes_to_bigint(&self, x: &Dynamic, y: &Dynamic) -> bool {
+        self.bigint_promotion && x.as_int().is_ok() && y.as_int().is_ok()
+    }
+
+    /// Does `INT` arithmetic between these arguments promote to `BigInt` instead of overflowing?
+    #[cfg(not(feature = "bigint"))]
+    #[inline(always)]
+    pub(crate) fn promotes_to_bigint(&self, _: &Dynamic, _: &Dynamic) -> bool {
+        false
+    }
+
     /// Call a native Rust function registered with the `Engine`.
     /// Position in `EvalAltResult` is `None` and must be set afterwards.
     ///
@@ -214,20 +252,46 @@ impl Engine {
         // Search for the native function
         // First search registered functions (can override packages)
         // Then search packages
-        let func = self
-            .global_module
-            .get_fn(hash_fn, pub_only)
-            .or_else(|| self.packages.get_fn(hash_fn, pub_only));
+        let func = self.global_module.get_fn(hash_fn, pub_only);
+
+        // Integer arithmetic promoted to BigInt overrides packages
+        #[cfg(feature = "bigint")]
+        if func.is_none() && args.len() == 2 && self.promotes_to_bigint(args[0], args[1]) {
+            let (x, y) = (args[0].as_int().unwrap(), args[1].as_int().unwrap());
+
+            if let Some(v) = run_promoted_int_op(fn_name, x, y)? {
+                return Ok((v, false));
+            }
+        }
+
+        let func = func
+            .or_else(|| self.packages.get_fn(hash_fn, pub_only))
+            // Then search variadic functions
+            .or_else(|| self.global_module.get_variadic_fn(fn_name, args, pub_only))
+            .or_else(|| self.packages.get_variadic_fn(fn_name, args, pub_only));
 
         if let Some(func) = func {
             assert!(func.is_native());
 
+            self.check_sandbox_call(None, fn_name)?;
+            self.check_deterministic_call(fn_name, hash_fn, args, pub_only)?;
+
             // Calling pure function but the first argument is a reference?
             let mut backup: ArgBackup = Default::default();
             backup.change_first_arg_to_copy(is_ref && func.is_pure(), args);
 
+            if self.profiler.is_some() {
+                let name = fn_name.to_string();
+                Profiler::enter(&mut state.profile_stack, name, true, state.operations);
+            }
+
             // Run external function
-            let result = func.get_native_fn()(self, lib, args);
+            let context = NativeCallContext::new(self, lib, state.cancel.as_ref());
+            let result = func.get_native_fn()(context, args);
+
+            if let Some(profiler) = &self.profiler {
+                profiler.exit(&mut state.profile_stack, state.operations);
+            }
 
             // Restore the original reference
             backup.restore_first_arg(args);
@@ -236,6 +300,8 @@ impl Engine {
 
             // See if the function match print/debug (which requires special processing)
             return Ok(match fn_name {
+                // Output was already produced before the script suspended, so skip it when replaying
+                KEYWORD_PRINT | KEYWORD_DEBUG if state.is_replaying() => (().into(), false),
                 KEYWORD_PRINT => (
                     (self.print)(result.as_str().map_err(|typ| {
                         EvalAltResult::ErrorMismatchOutputType(
@@ -281,7 +347,7 @@ impl Engine {
                 format!(
                     "Unknown property '{}' for {}, or it is write-only",
                     prop,
-                    self.map_type_name(args[0].type_name())
+                    self.map_value_type_name(args[0])
                 ),
                 Position::none(),
             )
@@ -294,7 +360,7 @@ impl Engine {
                 format!(
                     "Unknown property '{}' for {}, or it is read-only",
                     prop,
-                    self.map_type_name(args[0].type_name())
+                    self.map_value_type_name(args[0])
                 ),
                 Position::none(),
             )
@@ -330,7 +396,12 @@ impl Engine {
         }
 
         // Raise error
-        EvalAltResult::ErrorFunctionNotFound(
+        Err(self.make_fn_not_found_error(fn_name, args))
+    }
+
+    /// Create an error for a function not found, listing the types of the arguments.
+    fn make_fn_not_found_error(&self, fn_name: &str, args: &FnCallArgs) -> Box<EvalAltResult> {
+        Box::new(EvalAltResult::ErrorFunctionNotFound(
             format!(
                 "{} ({})",
                 fn_name,
@@ -344,8 +415,52 @@ impl Engine {
                     .join(", ")
             ),
             Position::none(),
-        )
-        .into()
+        ))
+    }
+
+    /// Evaluate the default values of the parameters of a script-defined function left out of a call,
+    /// each one with the parameters before it in scope, and return the values of all the parameters.
+    /// Position in `EvalAltResult` is `None` and must be set afterwards.
+    #[cfg(not(feature = "no_function"))]
+    fn fill_default_params(
+        &self,
+        mods: &mut Imports,
+        state: &mut State,
+        lib: &Module,
+        fn_def: &ScriptFnDef,
+        args: impl Iterator<Item = Option<Dynamic>>,
+        level: usize,
+    ) -> Result<StaticVec<Dynamic>, Box<EvalAltResult>> {
+        let mut scope = Scope::new();
+
+        fn_def
+            .params
+            .iter()
+            .zip(args)
+            .enumerate()
+            .map(|(index, (name, arg))| {
+                let value = match arg {
+                    Some(value) => value,
+                    None => {
+                        let expr = fn_def.default_value(index).ok_or_else(|| {
+                            Box::new(EvalAltResult::ErrorFunctionNotFound(
+                                fn_def.to_string(),
+                                Position::none(),
+                            ))
+                        })?;
+                        self.eval_expr(&mut scope, mods, state, lib, &mut None, expr, level)?
+                    }
+                };
+
+                scope.push_dynamic_value(
+                    name.clone(),
+                    ScopeEntryType::Normal,
+                    value.clone(),
+                    false,
+                );
+                Ok(value)
+            })
+            .collect()
     }
 
     /// Call a script-defined function.
@@ -380,31 +495,101 @@ impl Engine {
             ));
         }
 
+        // The function may no longer take this number of arguments, e.g. when it was resolved
+        // via a default parameter of a definition since replaced by merging another `AST`
+        if args.len() < fn_def.num_required_params() {
+            return Err(self.make_fn_not_found_error(fn_name, args));
+        }
+
+        if self.profiler.is_some() {
+            let name = match fn_def.rest_param {
+                Some(ref rest) if fn_def.params.is_empty() => {
+                    format!("{}(...{})", fn_def.name, rest)
+                }
+                Some(ref rest) => {
+                    format!("{}({},...{})", fn_def.name, fn_def.params.join(","), rest)
+                }
+                None => format!("{}({})", fn_def.name, fn_def.params.join(",")),
+            };
+            Profiler::enter(&mut state.profile_stack, name, false, state.operations);
+        }
+
         let orig_scope_level = state.scope_level;
         state.scope_level += 1;
 
         let prev_scope_len = scope.len();
         let prev_mods_len = mods.len();
 
+        // Actually consume the arguments instead of cloning them,
+        // filling in the default values of the trailing parameters left out
+        let num_args = args.len();
+        let values = if num_args < fn_def.params.len() {
+            let args = args.iter_mut().map(|v| Some(mem::take(*v)));
+            let missing = (num_args..fn_def.params.len()).map(|_| None);
+            self.fill_default_params(mods, state, lib, fn_def, args.chain(missing), level + 1)
+        } else {
+            Ok(args
+                .iter_mut()
+                .take(fn_def.params.len())
+                .map(|v| mem::take(*v))
+                .collect())
+        };
+
         // Put arguments into scope as variables
-        // Actually consume the arguments instead of cloning them
-        scope.extend(
-            fn_def
-                .params
-                .iter()
-                .zip(args.iter_mut().map(|v| mem::take(*v)))
-                .map(|(name, value)| {
-                    let var_name = unsafe_cast_var_name_to_lifetime(name.as_str(), state);
-                    (var_name, ScopeEntryType::Normal, value)
-                }),
-        );
+        let result = values.map(|values| {
+            scope.extend(fn_def.params.iter().zip(values).map(|(name, value)| {
+                let var_name = unsafe_cast_var_name_to_lifetime(name.as_str(), state);
+                (var_name, ScopeEntryType::Normal, value)
+            }));
+        });
+
+        // Collect all the arguments after the parameters into an array for the rest parameter
+        #[cfg(not(feature = "no_index"))]
+        if let Some(ref name) = fn_def.rest_param {
+            let rest: Array = args
+                .iter_mut()
+                .skip(fn_def.params.len())
+                .map(|v| mem::take(*v))
+                .collect();
+            let var_name = unsafe_cast_var_name_to_lifetime(name.as_str(), state);
+            scope.push(var_name, rest);
+        }
+
+        // Calling a generator function only keeps its arguments - it is run by iterating the result
+        if fn_def.is_generator {
+            let result = result.map(|_| {
+                let args = (prev_scope_len..scope.len())
+                    .map(|index| mem::take(scope.get_mut_by_index(index).0))
+                    .collect();
+
+                Dynamic::from(Generator {
+                    fn_name: fn_name.into(),
+                    fn_def: Shared::new(fn_def.clone()),
+                    args,
+                    this: this_ptr.as_deref().cloned(),
+                })
+            });
+
+            scope.rewind(prev_scope_len);
+            state.scope_level = orig_scope_level;
+
+            if let Some(profiler) = &self.profiler {
+                profiler.exit(&mut state.profile_stack, state.operations);
+            }
+
+            return result;
+        }
 
         // Evaluate the function at one higher level of call depth
-        let result = self
-            .eval_stmt(scope, mods, state, lib, this_ptr, &fn_def.body, level + 1)
+        let result = result
+            .and_then(|_| {
+                self.eval_stmt(scope, mods, state, lib, this_ptr, &fn_def.body, level + 1)
+            })
             .or_else(|err| match *err {
                 // Convert return statement to return value
                 EvalAltResult::Return(x, _) => Ok(x),
+                // Script suspension passes straight through
+                EvalAltResult::Yield(_, _) => Err(err),
                 EvalAltResult::ErrorInFunctionCall(name, err, _) => {
                     EvalAltResult::ErrorInFunctionCall(
                         format!("{} > {}", fn_name, name),
@@ -421,6 +606,11 @@ impl Engine {
         scope.rewind(prev_scope_len);
         mods.truncate(prev_mods_len);
         state.scope_level = orig_scope_level;
+        state.frame_memory.truncate(level + 1);
+
+        if let Some(profiler) = &self.profiler {
+            profiler.exit(&mut state.profile_stack, state.operations);
+        }
 
         result
     }
@@ -467,19 +657,50 @@ impl Engine {
             ensure_no_data_race(fn_name, args, is_ref)?;
         }
 
+        // Function names redirected from function pointers may be keywords
+        if is_keyword_function(fn_name) {
+            self.check_sandbox_call(None, fn_name)?;
+        }
+
         // Qualifiers (none) + function name + number of arguments + argument `TypeId`'s.
         let arg_types = args.iter().map(|a| a.type_id());
         let hash_fn = calc_fn_hash(empty(), fn_name, args.len(), arg_types);
 
+        // Search for a script function with typed parameters matching the arguments,
+        // then a script function with the right number of parameters,
+        // then a variadic script function (typed first) if no native function matches exactly
+        #[cfg(not(feature = "no_function"))]
+        let script_fn = if hash_script == 0 {
+            None
+        } else {
+            let script_args = if _is_method { &args[1..] } else { &args[..] };
+            let overloads = lib.iter_fn_overloads(hash_script, pub_only);
+
+            match self
+                .resolve_fn_overload(overloads, fn_name, script_args)?
+                .or_else(|| lib.get_fn(hash_script, pub_only))
+            {
+                None => {
+                    let overloads =
+                        lib.iter_variadic_fn_overloads(fn_name, script_args.len(), pub_only);
+
+                    self.resolve_fn_overload(overloads, fn_name, script_args)?
+                        .or_else(|| lib.get_variadic_fn(fn_name, script_args, pub_only))
+                        .filter(|_| {
+                            !self.global_module.contains_fn(hash_fn, pub_only)
+                                && !self.packages.contains_fn(hash_fn, pub_only)
+                        })
+                }
+                r => r,
+            }
+        };
+
         match fn_name {
             // type_of
             KEYWORD_TYPE_OF
                 if args.len() == 1 && !self.has_override(lib, hash_fn, hash_script, pub_only) =>
             {
-                Ok((
-                    self.map_type_name(args[0].type_name()).to_string().into(),
-                    false,
-                ))
+                Ok((self.map_value_type_name(args[0]).into(), false))
             }
 
             // Fn
@@ -506,9 +727,9 @@ impl Engine {
 
             // Normal script function call
             #[cfg(not(feature = "no_function"))]
-            _ if hash_script > 0 && lib.contains_fn(hash_script, pub_only) => {
+            _ if script_fn.is_some() => {
                 // Get scripted function
-                let func = lib.get_fn(hash_script, pub_only).unwrap().get_fn_def();
+                let func = script_fn.unwrap().get_fn_def();
 
                 let scope = &mut Scope::new();
                 let mods = &mut Imports::new();
@@ -552,9 +773,180 @@ impl Engine {
                 Ok((result, false))
             }
             // Normal native function call
-            _ => self.call_native_fn(
-                state, lib, fn_name, hash_fn, args, is_ref, pub_only, def_val,
-            ),
+            _ => {
+                let result = self.call_native_fn(
+                    state, lib, fn_name, hash_fn, args, is_ref, pub_only, def_val,
+                );
+
+                // List the overloads with typed parameters, none of which matches
+                #[cfg(not(feature = "no_function"))]
+                let result = result.map_err(|err| match *err {
+                    EvalAltResult::ErrorFunctionNotFound(sig, pos)
+                        if hash_script > 0
+                            && lib
+                                .iter_fn_overloads(hash_script, pub_only)
+                                .next()
+                                .is_some() =>
+                    {
+                        let overloads = lib
+                            .iter_fn_overloads(hash_script, pub_only)
+                            .map(|f| f.get_fn_def().to_string())
+                            .collect::<Vec<_>>()
+                            .join(", ");
+
+                        EvalAltResult::ErrorFunctionNotFound(
+                            format!("{} matches none of {}", sig, overloads),
+                            pos,
+                        )
+                        .into()
+                    }
+                    _ => err,
+                });
+
+                result
+            }
+        }
+    }
+
+    /// Does the type of an argument match the declared type of a parameter of a script-defined function?
+    ///
+    /// Arguments always match parameters without a declared type.
+    #[cfg(not(feature = "no_function"))]
+    fn matches_param_type(&self, fn_def: &ScriptFnDef, index: usize, value: &Dynamic) -> bool {
+        match fn_def.param_type(index) {
+            #[cfg(not(feature = "no_object"))]
+            Some(typ) if value.is::<Record>() => {
+                matches!(record_type_name(value), Some(name) if name == typ)
+            }
+            Some(typ) => {
+                let name = value.type_name();
+                typ == self.map_type_name(name) || typ == name
+            }
+            None => true,
+        }
+    }
+
+    /// Do the types of a list of arguments match the declared types of the parameters of a
+    /// script-defined function?
+    #[cfg(not(feature = "no_function"))]
+    pub(crate) fn matches_param_types(&self, fn_def: &ScriptFnDef, args: &FnCallArgs) -> bool {
+        args.iter()
+            .enumerate()
+            .all(|(index, value)| self.matches_param_type(fn_def, index, value))
+    }
+
+    /// Resolve the overload of a script-defined function with typed parameters that matches
+    /// the types of a list of arguments, if any.
+    /// Position in `EvalAltResult` is `None` and must be set afterwards.
+    ///
+    /// The overload with the most arguments matching declared parameter types wins.
+    /// It is an error if more than one overload matches equally well.
+    #[cfg(not(feature = "no_function"))]
+    pub(crate) fn resolve_fn_overload<'a>(
+        &self,
+        overloads: impl Iterator<Item = &'a CallableFunction>,
+        fn_name: &str,
+        args: &FnCallArgs,
+    ) -> Result<Option<&'a CallableFunction>, Box<EvalAltResult>> {
+        let mut best: StaticVec<&CallableFunction> = Default::default();
+        let mut best_score = 0;
+
+        for func in overloads {
+            let fn_def = func.get_fn_def();
+
+            if !self.matches_param_types(fn_def, args) {
+                continue;
+            }
+
+            let score = (0..args.len())
+                .filter(|&index| fn_def.param_type(index).is_some())
+                .count();
+
+            if best.is_empty() || score > best_score {
+                best.clear();
+                best_score = score;
+            } else if score < best_score {
+                continue;
+            }
+
+            best.push(func);
+        }
+
+        match best.len() {
+            0 => Ok(None),
+            1 => Ok(best.pop()),
+            _ => EvalAltResult::ErrorFunctionNotFound(
+                format!(
+                    "{} ({}) is ambiguous between {}",
+                    fn_name,
+                    args.iter()
+                        .map(|a| self.map_type_name(a.type_name()))
+                        .collect::<Vec<_>>()
+                        .join(", "),
+                    best.iter()
+                        .map(|f| f.get_fn_def().to_string())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ),
+                Position::none(),
+            )
+            .into(),
+        }
+    }
+
+    /// Check a function call against the sandbox profile, if any.
+    /// Position in `EvalAltResult` is `None` and must be set afterwards.
+    fn check_sandbox_call(
+        &self,
+        namespace: Option<&str>,
+        fn_name: &str,
+    ) -> Result<(), Box<EvalAltResult>> {
+        match self
+            .sandbox
+            .as_ref()
+            .and_then(|sandbox| sandbox.check_function(namespace, fn_name))
+        {
+            Some(err) => EvalAltResult::ErrorSandboxViolation(err, Position::none()).into(),
+            None => Ok(()),
+        }
+    }
+
+    /// In deterministic mode, make sure that the native function to call is deterministic.
+    /// Functions are searched in the same order as `call_native_fn`.
+    pub(crate) fn check_deterministic_call(
+        &self,
+        fn_name: &str,
+        hash_fn: u64,
+        args: &FnCallArgs,
+        pub_only: bool,
+    ) -> Result<(), Box<EvalAltResult>> {
+        if !self.deterministic {
+            return Ok(());
+        }
+
+        let registered =
+            || !matches!(&self.nondeterministic_fns, Some(names) if names.contains(fn_name));
+
+        let deterministic = if self.global_module.contains_fn(hash_fn, pub_only) {
+            registered()
+        } else if let Some(deterministic) = self.packages.is_deterministic_fn(hash_fn, pub_only) {
+            deterministic
+        } else if self
+            .global_module
+            .get_variadic_fn(fn_name, args, pub_only)
+            .is_some()
+        {
+            registered()
+        } else {
+            self.packages
+                .is_deterministic_variadic_fn(fn_name, args, pub_only)
+                .unwrap_or(true)
+        };
+
+        if deterministic {
+            Ok(())
+        } else {
+            EvalAltResult::ErrorNotDeterministic(fn_name.into(), Position::none()).into()
         }
     }
 
@@ -569,6 +961,7 @@ impl Engine {
         script_expr: &Dynamic,
         _level: usize,
     ) -> Result<Dynamic, Box<EvalAltResult>> {
+        self.check_sandbox_call(None, KEYWORD_EVAL)?;
         self.inc_operations(state)?;
 
         // Check for stack overflow
@@ -604,15 +997,67 @@ impl Engine {
         let statements = mem::take(ast.statements_mut());
         let ast = AST::new(statements, lib.clone());
 
-        // Evaluate the AST
-        let (result, operations) = self.eval_ast_with_scope_raw(scope, mods, &ast)?;
+        // Evaluate the AST, under the same time limit and cancellation token
+        let mut eval_state = State::new();
+        #[cfg(not(feature = "no_std"))]
+        {
+            eval_state.started = state.started;
+        }
+        eval_state.cancel = state.cancel.clone();
+        #[cfg(not(feature = "no_module"))]
+        {
+            eval_state.import_chain = state.import_chain.clone();
+        }
+
+        let result = self.eval_ast_with_scope_raw(scope, mods, &mut eval_state, &ast)?;
+
+        // The script can modify any variable in the scope
+        self.touch_var_index(state, _level, None);
 
-        state.operations += operations;
+        state.operations += eval_state.operations;
         self.inc_operations(state)?;
 
         return Ok(result);
     }
 
+    /// Redirect a method or a property accessor called on an instance of a record type to the
+    /// function declared with the record type, if there is one.
+    ///
+    /// Returns the name of the function and the hash of the script-defined function to call.
+    /// The function is searched for in `lib`, then in the methods carried by the record itself
+    /// (e.g. when it is created by another script), which are also returned in that case.
+    #[cfg(not(feature = "no_object"))]
+    pub(crate) fn redirect_record_fn(
+        &self,
+        lib: &Module,
+        obj: &Dynamic,
+        fn_name: &str,
+        num_args: usize,
+        pub_only: bool,
+    ) -> Option<(ImmutableString, u64, Option<Shared<Module>>)> {
+        let type_name = record_type_name(obj)?;
+        let fn_name = make_record_fn_name(&type_name, fn_name);
+        let hash = calc_fn_hash(empty(), &fn_name, num_args, empty());
+
+        let found = |lib: &Module| {
+            #[cfg(not(feature = "no_function"))]
+            return lib.contains_fn(hash, pub_only)
+                || lib.iter_fn_overloads(hash, pub_only).next().is_some()
+                || lib.contains_variadic_fn(&fn_name);
+            #[cfg(feature = "no_function")]
+            return lib.contains_fn(hash, pub_only);
+        };
+
+        if found(lib) {
+            return Some((fn_name.into(), hash, None));
+        }
+
+        match record_methods(obj) {
+            Some(methods) if found(&methods) => Some((fn_name.into(), hash, Some(methods))),
+            _ => None,
+        }
+    }
+
     /// Call a dot method.
     /// Position in `EvalAltResult` is `None` and must be set afterwards.
     #[cfg(not(feature = "no_object"))]
@@ -708,11 +1153,21 @@ impl Engine {
         } else {
             #[cfg(not(feature = "no_object"))]
             let redirected;
+            #[cfg(not(feature = "no_object"))]
+            let mut methods = None;
             let mut hash = hash_script;
 
-            // Check if it is a map method call in OOP style
+            // Check if it is a method of a record type, or a map method call in OOP style
             #[cfg(not(feature = "no_object"))]
-            if let Some(map) = obj.read_lock::<Map>() {
+            if let Some((fn_name, hash_fn, record_methods)) =
+                self.redirect_record_fn(lib, obj, _fn_name, idx.len(), pub_only)
+            {
+                // Remap the function name to the method declared with the record type
+                redirected = fn_name;
+                _fn_name = &redirected;
+                hash = hash_fn;
+                methods = record_methods;
+            } else if let Some(map) = obj.read_lock::<Map>() {
                 if let Some(val) = map.get(_fn_name) {
                     if let Some(fn_ptr) = val.read_lock::<FnPtr>() {
                         // Remap the function name
@@ -741,6 +1196,11 @@ impl Engine {
                 hash = 0;
             }
 
+            // A method of a record type not declared in this script is called within the methods
+            // declared with the record type
+            #[cfg(not(feature = "no_object"))]
+            let lib = methods.as_deref().unwrap_or(lib);
+
             // Attached object pointer in front of the arguments
             let mut arg_values = once(obj).chain(idx.iter_mut()).collect::<StaticVec<_>>();
             let args = arg_values.as_mut();
@@ -775,8 +1235,14 @@ impl Engine {
         native: bool,
         pub_only: bool,
         capture: bool,
+        pipe: bool,
         level: usize,
     ) -> Result<Dynamic, Box<EvalAltResult>> {
+        // Keywords are handled here, before reaching native functions
+        if is_keyword_function(name) {
+            self.check_sandbox_call(None, name)?;
+        }
+
         // Handle Fn()
         if name == KEYWORD_FN_PTR && args_expr.len() == 1 {
             let hash_fn = calc_fn_hash(empty(), name, 1, once(TypeId::of::<ImmutableString>()));
@@ -862,6 +1328,10 @@ impl Engine {
                 args_expr = &args_expr.as_ref()[1..];
                 // Recalculate hash
                 hash_script = calc_fn_hash(empty(), name, curry.len() + args_expr.len(), empty());
+                // Check the redirected function name against the sandbox
+                if is_keyword_function(name) {
+                    self.check_sandbox_call(None, name)?;
+                }
             } else {
                 return EvalAltResult::ErrorMismatchOutputType(
                     self.map_type_name(type_name::<FnPtr>()).into(),
@@ -896,63 +1366,310 @@ impl Engine {
         }
 
         // Normal function call - except for Fn, curry, call and eval (handled above)
-        let mut arg_values: StaticVec<_>;
-        let mut args: StaticVec<_>;
-        let mut is_ref = false;
-        let capture = if cfg!(not(feature = "no_closure")) && capture && !scope.is_empty() {
-            Some(scope.flatten_clone())
-        } else {
-            None
+
+        // x |> func - func may also be a variable holding a function pointer
+        let (result, fn_args) = {
+            let mut arg_values: StaticVec<_>;
+            let mut target: Target;
+            let mut args: StaticVec<_>;
+            let mut is_ref = false;
+            let capture = if cfg!(not(feature = "no_closure")) && capture && !scope.is_empty() {
+                Some(scope.flatten_clone())
+            } else {
+                None
+            };
+
+            if args_expr.is_empty() && curry.is_empty() {
+                // No arguments
+                args = Default::default();
+            } else {
+                // If the first argument is a variable, and there is no curried arguments, convert to method-call style
+                // in order to leverage potential &mut first argument and avoid cloning the value
+                match args_expr.get(0).unwrap() {
+                    // func(x, ...) -> x.func(...)
+                    lhs @ Expr::Variable(_) if curry.is_empty() => {
+                        arg_values = args_expr
+                            .iter()
+                            .skip(1)
+                            .map(|expr| {
+                                self.eval_expr(scope, mods, state, lib, this_ptr, expr, level)
+                            })
+                            .collect::<Result<_, _>>()?;
+
+                        self.touch_var(scope, state, lhs, level);
+
+                        let (var, _, _, pos) = self.search_namespace(
+                            scope,
+                            EvalContext {
+                                mods,
+                                state,
+                                lib,
+                                this_ptr,
+                                level,
+                            },
+                            lhs,
+                        )?;
+                        target = var;
+
+                        self.inc_operations(state)
+                            .map_err(|err| err.new_position(pos))?;
+
+                        args = if target.is_shared() {
+                            arg_values.insert(0, target.as_mut().flatten_clone());
+                            arg_values.iter_mut().collect()
+                        } else {
+                            // Turn it into a method call only if the object is not shared
+                            is_ref = true;
+                            once(target.as_mut()).chain(arg_values.iter_mut()).collect()
+                        };
+                    }
+                    // func(..., ...)
+                    _ => {
+                        arg_values = args_expr
+                            .iter()
+                            .map(|expr| {
+                                self.eval_expr(scope, mods, state, lib, this_ptr, expr, level)
+                            })
+                            .collect::<Result<_, _>>()?;
+
+                        args = curry.iter_mut().chain(arg_values.iter_mut()).collect();
+                    }
+                }
+            }
+
+            let hash = if native { 0 } else { hash_script };
+
+            match self.exec_fn_call(
+                state,
+                lib,
+                name,
+                hash,
+                args.as_mut(),
+                is_ref,
+                false,
+                pub_only,
+                capture,
+                def_val,
+                level,
+            ) {
+                // Function not found - keep the arguments to try a function pointer instead
+                Err(err) if pipe && matches!(*err, EvalAltResult::ErrorFunctionNotFound(_, _)) => {
+                    let fn_args: StaticVec<_> = args.iter().map(|v| Dynamic::clone(v)).collect();
+                    (Err(err), fn_args)
+                }
+                result => (result, Default::default()),
+            }
         };
 
-        if args_expr.is_empty() && curry.is_empty() {
-            // No arguments
-            args = Default::default();
-        } else {
-            // If the first argument is a variable, and there is no curried arguments, convert to method-call style
-            // in order to leverage potential &mut first argument and avoid cloning the value
-            match args_expr.get(0).unwrap() {
-                // func(x, ...) -> x.func(...)
-                lhs @ Expr::Variable(_) if curry.is_empty() => {
-                    arg_values = args_expr
-                        .iter()
-                        .skip(1)
-                        .map(|expr| self.eval_expr(scope, mods, state, lib, this_ptr, expr, level))
-                        .collect::<Result<_, _>>()?;
+        match result {
+            // Call the function pointer held in a variable of the same name, if any
+            Err(err) if pipe && matches!(*err, EvalAltResult::ErrorFunctionNotFound(_, _)) => {
+                let fn_ptr = match scope.get_value::<FnPtr>(name) {
+                    Some(fn_ptr) => fn_ptr,
+                    None => return Err(err),
+                };
 
-                    let (target, _, _, pos) = search_namespace(scope, mods, state, this_ptr, lhs)?;
+                let (fn_name, mut fn_curry) = fn_ptr.take_data();
+                let mut fn_args = fn_args;
+                let num_args = fn_curry.len() + fn_args.len();
+                let hash = calc_fn_hash(empty(), &fn_name, num_args, empty());
+                let mut args: StaticVec<_> =
+                    fn_curry.iter_mut().chain(fn_args.iter_mut()).collect();
+
+                self.exec_fn_call(
+                    state,
+                    lib,
+                    &fn_name,
+                    hash,
+                    args.as_mut(),
+                    false,
+                    false,
+                    pub_only,
+                    None,
+                    def_val,
+                    level,
+                )
+                .map(|(v, _)| v)
+            }
+            result => result.map(|(v, _)| v),
+        }
+    }
 
-                    self.inc_operations(state)
-                        .map_err(|err| err.new_position(pos))?;
+    /// Call a function with named arguments in normal function-call style.
+    /// Position in `EvalAltResult` is `None` and must be set afterwards.
+    ///
+    /// Named arguments are matched against the parameter names of script-defined functions,
+    /// filling in default values for parameters left out, or of native Rust functions
+    /// registered via `Engine::register_fn_param_names`.
+    pub(crate) fn make_named_function_call(
+        &self,
+        scope: &mut Scope,
+        context: EvalContext,
+        name: &str,
+        args_expr: &[Expr],
+        named_args: &[((String, Position), Expr)],
+        _capture: bool,
+    ) -> Result<Dynamic, Box<EvalAltResult>> {
+        let EvalContext {
+            mods,
+            state,
+            lib,
+            this_ptr,
+            level,
+        } = context;
+
+        let mut arg_values: StaticVec<_> = args_expr
+            .iter()
+            .chain(named_args.iter().map(|(_, expr)| expr))
+            .map(|expr| self.eval_expr(scope, mods, state, lib, this_ptr, expr, level))
+            .collect::<Result<_, _>>()?;
 
-                    args = if target.is_shared() {
-                        arg_values.insert(0, target.flatten_clone());
-                        arg_values.iter_mut().collect()
+        let num_args = arg_values.len();
+        let num_positional = args_expr.len();
+
+        // Map each parameter to the index of the argument passed to it, if any
+        let arrange_args = |params: &[String]| -> Option<StaticVec<Option<usize>>> {
+            if num_positional > params.len() {
+                return None;
+            }
+
+            let mut slots: StaticVec<_> = (0..params.len())
+                .map(|index| {
+                    if index < num_positional {
+                        Some(index)
                     } else {
-                        // Turn it into a method call only if the object is not shared
-                        is_ref = true;
-                        once(target).chain(arg_values.iter_mut()).collect()
-                    };
-                }
-                // func(..., ...)
-                _ => {
-                    arg_values = args_expr
-                        .iter()
-                        .map(|expr| self.eval_expr(scope, mods, state, lib, this_ptr, expr, level))
-                        .collect::<Result<_, _>>()?;
+                        None
+                    }
+                })
+                .collect();
+
+            for (offset, ((arg_name, _), _)) in named_args.iter().enumerate() {
+                let index = params.iter().position(|param| param == arg_name)?;
 
-                    args = curry.iter_mut().chain(arg_values.iter_mut()).collect();
+                if slots[index].is_some() {
+                    return None;
                 }
+                slots[index] = Some(num_positional + offset);
+            }
+
+            Some(slots)
+        };
+
+        // First search for a script-defined function - parameters left out must have default values
+        #[cfg(not(feature = "no_function"))]
+        {
+            let target = lib
+                .iter_script_fn()
+                .filter(|fn_def| fn_def.rest_param.is_none())
+                .filter(|fn_def| fn_def.name == name && fn_def.params.len() >= num_args)
+                .filter_map(|fn_def| {
+                    let slots = arrange_args(&fn_def.params)?;
+
+                    if slots.iter().enumerate().all(|(index, slot)| match slot {
+                        Some(slot) => self.matches_param_type(&fn_def, index, &arg_values[*slot]),
+                        None => fn_def.default_value(index).is_some(),
+                    }) {
+                        Some((fn_def, slots))
+                    } else {
+                        None
+                    }
+                })
+                .min_by_key(|(fn_def, _)| fn_def.params.len());
+
+            if let Some((fn_def, slots)) = target {
+                let args = slots
+                    .into_iter()
+                    .map(|slot| slot.map(|slot| mem::take(&mut arg_values[slot])));
+                let mut values = self
+                    .fill_default_params(mods, state, lib, &fn_def, args, level + 1)
+                    .map_err(|err| {
+                        Box::new(EvalAltResult::ErrorInFunctionCall(
+                            name.to_string(),
+                            err,
+                            Position::none(),
+                        ))
+                    })?;
+
+                let hash = calc_fn_hash(empty(), name, values.len(), empty());
+                let mut args: StaticVec<_> = values.iter_mut().collect();
+                let capture = if cfg!(not(feature = "no_closure")) && _capture && !scope.is_empty()
+                {
+                    Some(scope.flatten_clone())
+                } else {
+                    None
+                };
+
+                return self
+                    .exec_fn_call(
+                        state,
+                        lib,
+                        name,
+                        hash,
+                        args.as_mut(),
+                        false,
+                        false,
+                        false,
+                        capture,
+                        None,
+                        level,
+                    )
+                    .map(|(v, _)| v);
             }
         }
 
-        let hash = if native { 0 } else { hash_script };
-        let args = args.as_mut();
+        // Then search for a native Rust function with registered parameter names
+        let slots = self
+            .global_module
+            .get_fn_param_names(name, num_args)
+            .or_else(|| self.packages.get_fn_param_names(name, num_args))
+            .and_then(arrange_args);
+
+        if let Some(slots) = slots {
+            // All parameters are filled as there are as many arguments as parameters
+            let mut values: StaticVec<_> = slots
+                .into_iter()
+                .map(|slot| mem::take(&mut arg_values[slot.unwrap()]))
+                .collect();
+            let mut args: StaticVec<_> = values.iter_mut().collect();
+
+            return self
+                .exec_fn_call(
+                    state,
+                    lib,
+                    name,
+                    0,
+                    args.as_mut(),
+                    false,
+                    false,
+                    false,
+                    None,
+                    None,
+                    level,
+                )
+                .map(|(v, _)| v);
+        }
 
-        self.exec_fn_call(
-            state, lib, name, hash, args, is_ref, false, pub_only, capture, def_val, level,
+        EvalAltResult::ErrorFunctionNotFound(
+            format!(
+                "{} ({})",
+                name,
+                arg_values
+                    .iter()
+                    .enumerate()
+                    .map(|(index, value)| {
+                        let typ = self.map_type_name(value.type_name());
+
+                        match index.checked_sub(num_positional) {
+                            Some(offset) => format!("{}: {}", (named_args[offset].0).0, typ),
+                            None => typ.to_string(),
+                        }
+                    })
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            Position::none(),
         )
-        .map(|(v, _)| v)
+        .into()
     }
 
     /// Call a module-qualified function in normal function-call style.
@@ -974,6 +1691,7 @@ impl Engine {
     ) -> Result<Dynamic, Box<EvalAltResult>> {
         let modules = modules.as_ref().unwrap();
         let mut arg_values: StaticVec<_>;
+        let mut target: Target;
         let mut args: StaticVec<_>;
 
         if args_expr.is_empty() {
@@ -992,13 +1710,23 @@ impl Engine {
                         .map(|expr| self.eval_expr(scope, mods, state, lib, this_ptr, expr, level))
                         .collect::<Result<_, _>>()?;
 
-                    let (target, _, _, pos) =
-                        search_scope_only(scope, state, this_ptr, args_expr.get(0).unwrap())?;
+                    let (var, _, _, pos) = self.search_scope_only(
+                        scope,
+                        EvalContext {
+                            mods,
+                            state,
+                            lib,
+                            this_ptr,
+                            level,
+                        },
+                        args_expr.get(0).unwrap(),
+                    )?;
+                    target = var;
 
                     self.inc_operations(state)
                         .map_err(|err| err.new_position(pos))?;
 
-                    args = once(target).chain(arg_values.iter_mut()).collect();
+                    args = once(target.as_mut()).chain(arg_values.iter_mut()).collect();
                 }
                 // func(..., ...) or func(mod::x, ...)
                 _ => {
@@ -1014,8 +1742,26 @@ impl Engine {
 
         let module = search_imports(mods, state, modules)?;
 
-        // First search in script-defined functions (can override built-in)
-        let func = match module.get_qualified_fn(hash_script) {
+        if self.sandbox.is_some() {
+            let namespace = once(module.id().unwrap_or(&modules[0].0))
+                .chain(modules.iter().skip(1).map(|(m, _)| m.as_str()))
+                .collect::<Vec<_>>()
+                .join(Token::DoubleColon.syntax().as_ref());
+
+            self.check_sandbox_call(Some(&namespace), name)?;
+        }
+
+        // First search in script-defined functions with typed parameters
+        #[cfg(not(feature = "no_function"))]
+        let func = {
+            let overloads = module.iter_qualified_fn_overloads(hash_script);
+            self.resolve_fn_overload(overloads, name, &args)?
+        };
+        #[cfg(feature = "no_function")]
+        let func = None;
+
+        // Then search in script-defined functions (can override built-in)
+        let func = match func.or_else(|| module.get_qualified_fn(hash_script)) {
             // Then search in Rust functions
             None => {
                 self.inc_operations(state)?;
@@ -1034,6 +1780,24 @@ impl Engine {
             r => r,
         };
 
+        // Then search in variadic functions with typed parameters
+        #[cfg(not(feature = "no_function"))]
+        let func = match func {
+            None => {
+                let qualifiers = modules.iter().map(|(m, _)| m.as_str());
+                let overloads =
+                    module.iter_qualified_variadic_fn_overloads(qualifiers, name, args.len());
+                self.resolve_fn_overload(overloads, name, &args)?
+            }
+            r => r,
+        };
+
+        // Then search in variadic functions
+        let func = func.or_else(|| {
+            let qualifiers = modules.iter().map(|(m, _)| m.as_str());
+            module.get_qualified_variadic_fn(qualifiers, name, &args)
+        });
+
         match func {
             #[cfg(not(feature = "no_function"))]
             Some(f) if f.is_script() => {
@@ -1055,7 +1819,29 @@ impl Engine {
 
                 self.call_script_fn(scope, mods, state, lib, &mut None, name, func, args, level)
             }
-            Some(f) => f.get_native_fn()(self, lib, args.as_mut()),
+            Some(f) => {
+                if self.deterministic && !module.is_deterministic() {
+                    return EvalAltResult::ErrorNotDeterministic(
+                        format!("{}{}", modules, name),
+                        Position::none(),
+                    )
+                    .into();
+                }
+
+                if self.profiler.is_some() {
+                    let name = format!("{}{}", modules, name);
+                    Profiler::enter(&mut state.profile_stack, name, true, state.operations);
+                }
+
+                let context = NativeCallContext::new(self, lib, state.cancel.as_ref());
+                let result = f.get_native_fn()(context, args.as_mut());
+
+                if let Some(profiler) = &self.profiler {
+                    profiler.exit(&mut state.profile_stack, state.operations);
+                }
+
+                result
+            }
             None if def_val.is_some() => Ok(def_val.unwrap().into()),
             None => EvalAltResult::ErrorFunctionNotFound(
                 format!(
@@ -1078,6 +1864,60 @@ impl Engine {
     }
 }
 
+/// Integer arithmetic which promotes the result to a `BigInt` instead of overflowing.
+#[cfg(feature = "bigint")]
+fn run_promoted_int_op(op: &str, x: INT, y: INT) -> Result<Option<Dynamic>, Box<EvalAltResult>> {
+    use crate::packages::arithmetic::{bigint_op, bigint_to_int};
+    use crate::stdlib::convert::TryFrom;
+
+    let result = match op {
+        "+" => x.checked_add(y),
+        "-" => x.checked_sub(y),
+        "*" => x.checked_mul(y),
+        "/" if y != 0 => x.checked_div(y),
+        "%" if y != 0 => x.checked_rem(y),
+        "~" => match u32::try_from(y) {
+            Ok(y) => x.checked_pow(y),
+            Err(_) => return Ok(None),
+        },
+        // Left-shifts silently drop bits, so always go through BigInt
+        "<<" if y >= 0 => None,
+        // Everything else cannot overflow, or is an error anyway
+        _ => return Ok(None),
+    };
+
+    if let Some(v) = result {
+        return Ok(Some(v.into()));
+    }
+
+    Ok(
+        bigint_op(op, &x.into(), &y.into())?.map(|v| match bigint_to_int(&v) {
+            Some(n) => n.into(),
+            None => v.into(),
+        }),
+    )
+}
+
+/// Build in operators between two `BigInt` values.
+#[cfg(feature = "bigint")]
+fn run_bigint_binary_op(
+    op: &str,
+    x: &BigInt,
+    y: &BigInt,
+) -> Result<Option<Dynamic>, Box<EvalAltResult>> {
+    use crate::packages::arithmetic::bigint_op;
+
+    match op {
+        "==" => Ok(Some((x == y).into())),
+        "!=" => Ok(Some((x != y).into())),
+        ">" => Ok(Some((x > y).into())),
+        ">=" => Ok(Some((x >= y).into())),
+        "<" => Ok(Some((x < y).into())),
+        "<=" => Ok(Some((x <= y).into())),
+        _ => Ok(bigint_op(op, x, y)?.map(Into::into)),
+    }
+}
+
 /// Build in common binary operator implementations to avoid the cost of calling a registered function.
 pub fn run_builtin_binary_op(
     op: &str,
@@ -1088,6 +1928,21 @@ pub fn run_builtin_binary_op(
 
     let args_type = x.type_id();
 
+    // Big integers mix freely with integers
+    #[cfg(feature = "bigint")]
+    if args_type == TypeId::of::<BigInt>() || y.type_id() == TypeId::of::<BigInt>() {
+        let to_bigint = |v: &Dynamic| {
+            v.as_bigint()
+                .ok()
+                .or_else(|| v.as_int().ok().map(Into::into))
+        };
+
+        return match (to_bigint(x), to_bigint(y)) {
+            (Some(x), Some(y)) => run_bigint_binary_op(op, &x, &y),
+            _ => Ok(None),
+        };
+    }
+
     if y.type_id() != args_type {
         return Ok(None);
     }
@@ -1203,6 +2058,42 @@ pub fn run_builtin_binary_op(
         }
     }
 
+    #[cfg(feature = "decimal")]
+    if args_type == TypeId::of::<Decimal>() {
+        let x = x.clone().cast::<Decimal>();
+        let y = y.clone().cast::<Decimal>();
+
+        if cfg!(not(feature = "unchecked")) {
+            match op {
+                "+" => return add(x, y).map(Into::into).map(Some),
+                "-" => return sub(x, y).map(Into::into).map(Some),
+                "*" => return mul(x, y).map(Into::into).map(Some),
+                "/" => return div(x, y).map(Into::into).map(Some),
+                "%" => return modulo(x, y).map(Into::into).map(Some),
+                _ => (),
+            }
+        } else {
+            match op {
+                "+" => return Ok(Some((x + y).into())),
+                "-" => return Ok(Some((x - y).into())),
+                "*" => return Ok(Some((x * y).into())),
+                "/" => return Ok(Some((x / y).into())),
+                "%" => return Ok(Some((x % y).into())),
+                _ => (),
+            }
+        }
+
+        match op {
+            "==" => return Ok(Some((x == y).into())),
+            "!=" => return Ok(Some((x != y).into())),
+            ">" => return Ok(Some((x > y).into())),
+            ">=" => return Ok(Some((x >= y).into())),
+            "<" => return Ok(Some((x < y).into())),
+            "<=" => return Ok(Some((x <= y).into())),
+            _ => (),
+        }
+    }
+
     Ok(None)
 }
 
@@ -1291,5 +2182,44 @@ pub fn run_builtin_op_assignment(
         }
     }
 
+    #[cfg(feature = "decimal")]
+    if args_type == TypeId::of::<Decimal>() {
+        let y = y.clone().cast::<Decimal>();
+        let mut x = x.write_lock::<Decimal>().unwrap();
+
+        if cfg!(not(feature = "unchecked")) {
+            match op {
+                "+=" => return Ok(Some(*x = add(*x, y)?)),
+                "-=" => return Ok(Some(*x = sub(*x, y)?)),
+                "*=" => return Ok(Some(*x = mul(*x, y)?)),
+                "/=" => return Ok(Some(*x = div(*x, y)?)),
+                "%=" => return Ok(Some(*x = modulo(*x, y)?)),
+                _ => (),
+            }
+        } else {
+            match op {
+                "+=" => return Ok(Some(*x += y)),
+                "-=" => return Ok(Some(*x -= y)),
+                "*=" => return Ok(Some(*x *= y)),
+                "/=" => return Ok(Some(*x /= y)),
+                "%=" => return Ok(Some(*x %= y)),
+                _ => (),
+            }
+        }
+    }
+
+    #[cfg(feature = "bigint")]
+    if args_type == TypeId::of::<BigInt>() {
+        use crate::packages::arithmetic::bigint_op;
+
+        let y = y.as_bigint().unwrap();
+        let mut x = x.write_lock::<BigInt>().unwrap();
+
+        if let Some(v) = bigint_op(&op[..op.len() - 1], &x, &y)? {
+            *x = v;
+            return Ok(Some(()));
+        }
+    }
+
     Ok(None)
 }
@@ -1,15 +1,17 @@
 //! Module defining interfaces to native-Rust functions.
 
 use crate::any::Dynamic;
+use crate::cancel::CancellationToken;
 use crate::engine::Engine;
 use crate::module::Module;
 use crate::parser::{FnAccess, ScriptFnDef};
 use crate::result::EvalAltResult;
+use crate::syntax::EvalContext;
 use crate::token::{is_valid_identifier, Position};
 use crate::utils::ImmutableString;
 
 #[cfg(not(feature = "no_function"))]
-use crate::{calc_fn_hash, module::FuncReturn, utils::StaticVec};
+use crate::{calc_fn_hash, engine::State, module::FuncReturn, utils::StaticVec};
 
 use crate::stdlib::{boxed::Box, convert::TryFrom, fmt, string::String, vec::Vec};
 
@@ -70,6 +72,49 @@ pub fn shared_take<T>(value: Shared<T>) -> T {
 
 pub type FnCallArgs<'a> = [&'a mut Dynamic];
 
+/// Context of a call to a native Rust function.
+#[derive(Clone, Copy)]
+pub struct NativeCallContext<'e, 'a> {
+    engine: &'e Engine,
+    lib: &'a Module,
+    cancel: Option<&'a CancellationToken>,
+}
+
+impl<'e, 'a> From<(&'e Engine, &'a Module)> for NativeCallContext<'e, 'a> {
+    fn from(value: (&'e Engine, &'a Module)) -> Self {
+        Self::new(value.0, value.1, None)
+    }
+}
+
+impl<'e, 'a> NativeCallContext<'e, 'a> {
+    /// Create a new `NativeCallContext`.
+    pub(crate) fn new(
+        engine: &'e Engine,
+        lib: &'a Module,
+        cancel: Option<&'a CancellationToken>,
+    ) -> Self {
+        Self {
+            engine,
+            lib,
+            cancel,
+        }
+    }
+    /// The current `Engine`.
+    pub fn engine(&self) -> &'e Engine {
+        self.engine
+    }
+    /// The global namespace containing the script-defined functions of the calling script.
+    pub fn lib(&self) -> &'a Module {
+        self.lib
+    }
+    /// Get the token to cancel the calling script, if any.
+    ///
+    /// A long-running function can check it to stop early when the script is cancelled.
+    pub fn cancel_token(&self) -> Option<&'a CancellationToken> {
+        self.cancel
+    }
+}
+
 /// A general function pointer, which may carry additional (i.e. curried) argument values
 /// to be passed onto a function during a call.
 #[derive(Debug, Clone, Default)]
@@ -97,10 +142,13 @@ impl FnPtr {
         &self.1
     }
 
-    /// Call the function pointer with curried arguments (if any).
+    /// Call the function pointer with curried arguments (if any), from within a native Rust
+    /// function with the given `NativeCallContext`.
     ///
     /// If this function is a script-defined function, it must not be marked private.
     ///
+    /// The call is terminated when the calling script is cancelled.
+    ///
     /// ## WARNING
     ///
     /// All the arguments are _consumed_, meaning that they're replaced by `()`.
@@ -110,8 +158,7 @@ impl FnPtr {
     #[cfg(not(feature = "no_function"))]
     pub fn call_dynamic(
         &self,
-        engine: &Engine,
-        lib: impl AsRef<Module>,
+        context: NativeCallContext,
         this_ptr: Option<&mut Dynamic>,
         mut arg_values: impl AsMut<[Dynamic]>,
     ) -> FuncReturn<Dynamic> {
@@ -133,10 +180,14 @@ impl FnPtr {
         let fn_name = self.0.as_str();
         let hash_script = calc_fn_hash(empty(), fn_name, args_len, empty());
 
-        engine
+        let mut state = State::new();
+        state.cancel = context.cancel_token().cloned();
+
+        context
+            .engine()
             .exec_fn_call(
-                &mut Default::default(),
-                lib.as_ref(),
+                &mut state,
+                context.lib(),
                 fn_name,
                 hash_script,
                 args.as_mut(),
@@ -189,11 +240,21 @@ impl TryFrom<&str> for FnPtr {
 
 /// A general function trail object.
 #[cfg(not(feature = "sync"))]
-pub type FnAny = dyn Fn(&Engine, &Module, &mut FnCallArgs) -> Result<Dynamic, Box<EvalAltResult>>;
+pub type FnAny = dyn Fn(NativeCallContext, &mut FnCallArgs) -> Result<Dynamic, Box<EvalAltResult>>;
 /// A general function trail object.
 #[cfg(feature = "sync")]
 pub type FnAny =
-    dyn Fn(&Engine, &Module, &mut FnCallArgs) -> Result<Dynamic, Box<EvalAltResult>> + Send + Sync;
+    dyn Fn(NativeCallContext, &mut FnCallArgs) -> Result<Dynamic, Box<EvalAltResult>> + Send + Sync;
+
+/// A callback that resolves a variable not found in the scope into a value and whether it is constant.
+#[cfg(not(feature = "sync"))]
+pub type OnVarCallback =
+    dyn Fn(&str, Position, &EvalContext) -> Result<Option<(Dynamic, bool)>, Box<EvalAltResult>>;
+/// A callback that resolves a variable not found in the scope into a value and whether it is constant.
+#[cfg(feature = "sync")]
+pub type OnVarCallback = dyn Fn(&str, Position, &EvalContext) -> Result<Option<(Dynamic, bool)>, Box<EvalAltResult>>
+    + Send
+    + Sync;
 
 /// A standard function that gets an iterator from a type.
 pub type IteratorFn = fn(Dynamic) -> Box<dyn Iterator<Item = Dynamic>>;
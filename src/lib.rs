@@ -59,6 +59,7 @@ extern crate alloc;
 
 mod any;
 mod api;
+mod cancel;
 mod engine;
 mod error;
 mod fn_args;
@@ -70,7 +71,12 @@ mod module;
 mod optimize;
 pub mod packages;
 mod parser;
+mod profiler;
+#[cfg(not(feature = "no_object"))]
+mod record;
 mod result;
+mod resume;
+mod sandbox;
 mod scope;
 #[cfg(feature = "serde")]
 mod serde;
@@ -82,14 +88,19 @@ mod r#unsafe;
 mod utils;
 
 pub use any::Dynamic;
+pub use cancel::CancellationToken;
 pub use engine::Engine;
 pub use error::{ParseError, ParseErrorType};
-pub use fn_native::{FnPtr, IteratorFn};
+pub use fn_native::{FnPtr, IteratorFn, NativeCallContext};
 pub use fn_register::{RegisterFn, RegisterResultFn};
 pub use module::Module;
 pub use parser::{ImmutableString, AST, INT};
+pub use profiler::{ProfileEntry, Profiler};
 pub use result::EvalAltResult;
+pub use resume::{Generator, Resumable, Suspended};
+pub use sandbox::SandboxProfile;
 pub use scope::Scope;
+pub use settings::Associativity;
 pub use syntax::{EvalContext, Expression};
 pub use token::Position;
 pub use utils::calc_fn_spec as calc_fn_hash;
@@ -106,11 +117,26 @@ pub use engine::Array;
 #[cfg(not(feature = "no_object"))]
 pub use engine::Map;
 
+#[cfg(not(feature = "no_object"))]
+pub use record::Record;
+
 #[cfg(not(feature = "no_float"))]
 pub use parser::FLOAT;
 
+/// The fixed-point decimal type, from the [`rust_decimal`](https://crates.io/crates/rust_decimal) crate.
+///
+/// Requires the `decimal` feature.
+#[cfg(feature = "decimal")]
+pub use rust_decimal::Decimal;
+
+/// The arbitrary-precision integer type, from the [`num-bigint`](https://crates.io/crates/num-bigint) crate.
+///
+/// Requires the `bigint` feature.
+#[cfg(feature = "bigint")]
+pub use num_bigint::BigInt;
+
 #[cfg(not(feature = "no_module"))]
-pub use module::ModuleResolver;
+pub use module::{ImportContext, ModuleResolver};
 
 /// Module containing all built-in _module resolvers_ available to Rhai.
 ///
@@ -135,6 +161,9 @@ pub mod de {
     pub use crate::serde::de::from_dynamic;
 }
 
+#[cfg(feature = "serde")]
+pub use crate::serde::snapshot::Snapshot;
+
 #[cfg(not(feature = "no_optimize"))]
 pub use optimize::OptimizationLevel;
 
@@ -150,16 +179,25 @@ pub use token::{get_next_token, parse_string_literal, InputStream, Token, Tokeni
 
 #[cfg(feature = "internals")]
 #[deprecated(note = "this type is volatile and may change")]
-pub use parser::{CustomExpr, Expr, FloatWrapper, ReturnType, ScriptFnDef, Stmt};
+pub use parser::{CustomExpr, Expr, FloatWrapper, Pattern, ReturnType, ScriptFnDef, Stmt};
+
+#[cfg(feature = "internals")]
+#[cfg(not(feature = "no_module"))]
+#[deprecated(note = "this type is volatile and may change")]
+pub use parser::ExportItem;
 
 #[cfg(feature = "internals")]
 #[deprecated(note = "this type is volatile and may change")]
-pub use engine::{Imports, Limits, State as EvalState};
+pub use engine::{FrameMemory, Imports, Limits, State as EvalState};
 
 #[cfg(feature = "internals")]
 #[deprecated(note = "this type is volatile and may change")]
 pub use module::ModuleRef;
 
+#[cfg(feature = "internals")]
+#[deprecated(note = "this type is volatile and may change")]
+pub use profiler::ProfileFrame;
+
 #[cfg(feature = "internals")]
 #[deprecated(note = "this type is volatile and may change")]
 pub use utils::StaticVec;
@@ -3,18 +3,24 @@
 use crate::any::{Dynamic, Variant};
 use crate::calc_fn_hash;
 use crate::engine::Engine;
-use crate::fn_native::{CallableFunction as Func, FnCallArgs, IteratorFn, SendSync};
+use crate::fn_native::{
+    CallableFunction as Func, FnCallArgs, IteratorFn, NativeCallContext, SendSync,
+};
 use crate::parser::{FnAccess, FnAccess::Public, ScriptFnDef};
 use crate::result::EvalAltResult;
 use crate::token::{Position, Token};
-use crate::utils::{StaticVec, StraightHasherBuilder};
+use crate::utils::{
+    calc_typed_fn_hash, calc_variadic_fn_hash, ImmutableString, StaticVec, StraightHasherBuilder,
+};
 
 #[cfg(not(feature = "no_function"))]
 use crate::fn_native::Shared;
 
 #[cfg(not(feature = "no_module"))]
 use crate::{
-    engine::Imports,
+    cancel::CancellationToken,
+    engine::{Imports, State},
+    fn_native::shared_try_take,
     parser::AST,
     scope::{Entry as ScopeEntry, Scope},
 };
@@ -49,6 +55,16 @@ use crate::stdlib::cell::RefCell;
 #[cfg(feature = "sync")]
 use crate::stdlib::sync::RwLock;
 
+#[cfg(not(feature = "no_std"))]
+#[cfg(not(feature = "no_module"))]
+#[cfg(not(target_arch = "wasm32"))]
+use crate::stdlib::time::Instant;
+
+#[cfg(not(feature = "no_std"))]
+#[cfg(not(feature = "no_module"))]
+#[cfg(target_arch = "wasm32")]
+use instant::Instant;
+
 /// Return type of module-level Rust function.
 pub type FuncReturn<T> = Result<T, Box<EvalAltResult>>;
 
@@ -58,6 +74,9 @@ pub type FuncReturn<T> = Result<T, Box<EvalAltResult>>;
 /// Not available under the `no_module` feature.
 #[derive(Default)]
 pub struct Module {
+    /// Identifier of the module, e.g. the path it is imported from.
+    id: Option<ImmutableString>,
+
     /// Sub-modules.
     modules: HashMap<String, Module>,
 
@@ -70,6 +89,26 @@ pub struct Module {
     /// External Rust functions.
     functions: HashMap<u64, (String, FnAccess, StaticVec<TypeId>, Func), StraightHasherBuilder>,
 
+    /// Variadic functions, native or scripted, keyed by function name.
+    /// The parameter types are those of the fixed parameters before the variable arguments.
+    variadic_functions:
+        HashMap<u64, (String, FnAccess, StaticVec<TypeId>, Func), StraightHasherBuilder>,
+
+    /// Script-defined functions with default parameter values, mapping the hash of each
+    /// smaller number of arguments they can be called with to the hash of the function itself.
+    fn_arities: HashMap<u64, u64, StraightHasherBuilder>,
+
+    /// Script-defined functions with typed parameters, mapping the hash of each number of arguments
+    /// they can be called with to the hashes of all the overloads.
+    fn_overloads: HashMap<u64, StaticVec<u64>, StraightHasherBuilder>,
+
+    /// Script-defined variadic functions with typed parameters, mapping the hash of the function
+    /// name and number of required parameters to the hashes of all the overloads.
+    variadic_overloads: HashMap<u64, StaticVec<u64>, StraightHasherBuilder>,
+
+    /// Parameter names of external Rust functions, keyed by function name and number of parameters.
+    fn_param_names: HashMap<u64, StaticVec<String>, StraightHasherBuilder>,
+
     /// Iterator functions, keyed by the type producing the iterator.
     type_iterators: HashMap<TypeId, IteratorFn>,
 
@@ -77,6 +116,21 @@ pub struct Module {
     /// including those in sub-modules.
     all_functions: HashMap<u64, Func, StraightHasherBuilder>,
 
+    /// Flattened collection of all variadic functions, native or scripted, including those in
+    /// sub-modules, together with the types of their fixed parameters.
+    all_variadic_functions: HashMap<u64, (StaticVec<TypeId>, Func), StraightHasherBuilder>,
+
+    /// Flattened collection of all script-defined functions with typed parameters,
+    /// including those in sub-modules.
+    all_fn_overloads: HashMap<u64, StaticVec<Func>, StraightHasherBuilder>,
+
+    /// Flattened collection of all script-defined variadic functions with typed parameters,
+    /// including those in sub-modules.
+    all_variadic_overloads: HashMap<u64, StaticVec<Func>, StraightHasherBuilder>,
+
+    /// Are the external Rust functions in the module deterministic?
+    deterministic: bool,
+
     /// Is the module indexed?
     indexed: bool,
 }
@@ -111,6 +165,9 @@ impl Clone for Module {
         Self {
             all_variables: self.all_variables.clone(),
             all_functions: self.all_functions.clone(),
+            all_variadic_functions: self.all_variadic_functions.clone(),
+            all_fn_overloads: self.all_fn_overloads.clone(),
+            all_variadic_overloads: self.all_variadic_overloads.clone(),
             indexed: self.indexed,
             ..self.do_clone(false)
         }
@@ -170,11 +227,70 @@ impl Module {
             },
             variables: self.variables.clone(),
             functions: self.functions.clone(),
+            variadic_functions: self.variadic_functions.clone(),
+            fn_arities: self.fn_arities.clone(),
+            fn_overloads: self.fn_overloads.clone(),
+            variadic_overloads: self.variadic_overloads.clone(),
+            fn_param_names: self.fn_param_names.clone(),
             type_iterators: self.type_iterators.clone(),
+            id: self.id.clone(),
+            deterministic: self.deterministic,
             ..Default::default()
         }
     }
 
+    /// Get the identifier of the module, if any.
+    ///
+    /// Modules imported by `import` statements are identified by their paths.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rhai::Module;
+    ///
+    /// let mut module = Module::new();
+    /// module.set_id(Some("hello"));
+    /// assert_eq!(module.id(), Some("hello"));
+    /// ```
+    pub fn id(&self) -> Option<&str> {
+        self.id.as_ref().map(|s| s.as_str())
+    }
+
+    /// Set the identifier of the module.
+    pub fn set_id<S: Into<ImmutableString>>(&mut self, id: Option<S>) -> &mut Self {
+        self.id = id.map(Into::into);
+        self
+    }
+
+    /// Are the external Rust functions in the module marked deterministic?
+    ///
+    /// In deterministic mode (see `Engine::set_deterministic`), scripts can only call external
+    /// Rust functions in packages and imported modules that are marked deterministic.
+    ///
+    /// A module evaluated from a script is deterministic if all the modules it imports and
+    /// re-exports are.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rhai::Module;
+    ///
+    /// let mut module = Module::new();
+    /// assert!(!module.is_deterministic());
+    /// module.set_deterministic(true);
+    /// assert!(module.is_deterministic());
+    /// ```
+    pub fn is_deterministic(&self) -> bool {
+        self.deterministic
+    }
+
+    /// Mark the external Rust functions in the module as deterministic, i.e. always returning the
+    /// same results for the same arguments, or not.
+    pub fn set_deterministic(&mut self, deterministic: bool) -> &mut Self {
+        self.deterministic = deterministic;
+        self
+    }
+
     /// Does a variable exist in the module?
     ///
     /// # Examples
@@ -239,15 +355,12 @@ impl Module {
         self
     }
 
-    /// Get a mutable reference to a modules-qualified variable.
+    /// Get a reference to a modules-qualified variable.
     /// Name and Position in `EvalAltResult` are None and must be set afterwards.
     ///
     /// The `u64` hash is calculated by the function `crate::calc_fn_hash`.
-    pub(crate) fn get_qualified_var_mut(
-        &mut self,
-        hash_var: u64,
-    ) -> Result<&mut Dynamic, Box<EvalAltResult>> {
-        self.all_variables.get_mut(&hash_var).ok_or_else(|| {
+    pub(crate) fn get_qualified_var(&self, hash_var: u64) -> Result<&Dynamic, Box<EvalAltResult>> {
+        self.all_variables.get(&hash_var).ok_or_else(|| {
             EvalAltResult::ErrorVariableNotFound(String::new(), Position::none()).into()
         })
     }
@@ -256,8 +369,88 @@ impl Module {
     ///
     /// If there is an existing function of the same name and number of arguments, it is replaced.
     pub(crate) fn set_script_fn(&mut self, fn_def: ScriptFnDef) -> &mut Self {
+        // A variadic function can be called with any number of arguments
+        if fn_def.rest_param.is_some() {
+            let hash_fn =
+                calc_variadic_fn_hash(empty(), &fn_def.name, fn_def.num_required_params());
+
+            // Overloads with typed parameters are told apart by the parameter types
+            let hash_script = if fn_def.is_typed() {
+                let hash_script = hash_fn
+                    ^ calc_typed_fn_hash(
+                        &fn_def.name,
+                        fn_def
+                            .param_types
+                            .iter()
+                            .map(|t| t.as_ref().map(String::as_str)),
+                    );
+                let overloads = self.variadic_overloads.entry(hash_fn).or_default();
+
+                if !overloads.contains(&hash_script) {
+                    overloads.push(hash_script);
+                }
+                hash_script
+            } else {
+                hash_fn
+            };
+
+            self.variadic_functions.insert(
+                hash_script,
+                (
+                    fn_def.name.to_string(),
+                    fn_def.access,
+                    Default::default(),
+                    fn_def.into(),
+                ),
+            );
+            self.indexed = false;
+            return self;
+        }
+
+        // Overloads with typed parameters are told apart by the parameter types
+        if fn_def.is_typed() {
+            let hash_script = calc_typed_fn_hash(
+                &fn_def.name,
+                fn_def
+                    .param_types
+                    .iter()
+                    .map(|t| t.as_ref().map(String::as_str)),
+            );
+
+            for num_args in fn_def.num_required_params()..=fn_def.params.len() {
+                let hash = calc_fn_hash(empty(), &fn_def.name, num_args, empty());
+                let overloads = self.fn_overloads.entry(hash).or_default();
+
+                if !overloads.contains(&hash_script) {
+                    overloads.push(hash_script);
+                }
+            }
+
+            self.functions.insert(
+                hash_script,
+                (
+                    fn_def.name.to_string(),
+                    fn_def.access,
+                    Default::default(),
+                    fn_def.into(),
+                ),
+            );
+            self.indexed = false;
+            return self;
+        }
+
         // None + function name + number of arguments.
         let hash_script = calc_fn_hash(empty(), &fn_def.name, fn_def.params.len(), empty());
+
+        // The function can also be called with any of its default parameter values left out,
+        // but no longer with those of a function it replaces
+        self.fn_arities.retain(|_, hash| *hash != hash_script);
+
+        for num_args in fn_def.num_required_params()..fn_def.params.len() {
+            let hash = calc_fn_hash(empty(), &fn_def.name, num_args, empty());
+            self.fn_arities.insert(hash, hash_script);
+        }
+
         self.functions.insert(
             hash_script,
             (
@@ -355,18 +548,53 @@ impl Module {
     /// ```
     pub fn contains_fn(&self, hash_fn: u64, public_only: bool) -> bool {
         if public_only {
-            self.functions
-                .get(&hash_fn)
+            self.get_fn_entry(hash_fn)
                 .map(|(_, access, _, _)| match access {
                     FnAccess::Public => true,
                     FnAccess::Private => false,
                 })
                 .unwrap_or(false)
         } else {
-            self.functions.contains_key(&hash_fn)
+            self.get_fn_entry(hash_fn).is_some()
         }
     }
 
+    /// Get the entry of a function, including a script-defined function called with
+    /// some of its default parameter values left out.
+    fn get_fn_entry(&self, hash_fn: u64) -> Option<&(String, FnAccess, StaticVec<TypeId>, Func)> {
+        self.functions.get(&hash_fn).or_else(|| {
+            self.fn_arities
+                .get(&hash_fn)
+                .and_then(|hash| self.functions.get(hash))
+        })
+    }
+
+    /// Set the parameter names of external Rust functions with a particular name and number of
+    /// parameters, so that they can be called with named arguments.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rhai::Module;
+    ///
+    /// let mut module = Module::new();
+    /// module.set_fn_2("sub", |x: i64, y: i64| Ok(x - y));
+    /// module.set_fn_param_names("sub", &["x", "y"]);
+    /// ```
+    pub fn set_fn_param_names(&mut self, name: &str, param_names: &[&str]) -> &mut Self {
+        let hash = calc_fn_hash(empty(), name, param_names.len(), empty());
+        self.fn_param_names
+            .insert(hash, param_names.iter().map(|&s| s.into()).collect());
+        self
+    }
+
+    /// Get the parameter names of external Rust functions with a particular name and number of
+    /// parameters, if set.
+    pub(crate) fn get_fn_param_names(&self, name: &str, num_params: usize) -> Option<&[String]> {
+        let hash = calc_fn_hash(empty(), name, num_params, empty());
+        self.fn_param_names.get(&hash).map(|names| names.as_ref())
+    }
+
     /// Set a Rust function into the module, returning a hash key.
     ///
     /// If there is an existing Rust function of the same hash, it is replaced.
@@ -430,7 +658,7 @@ impl Module {
     ///                 // Pass parameter types via a slice with TypeId's
     ///                 &[std::any::TypeId::of::<i64>(), std::any::TypeId::of::<bool>()],
     ///                 // Fixed closure signature
-    ///                 |engine, lib, args| {
+    ///                 |context, args| {
     ///                     // 'args' is guaranteed to be the right length and of the correct types
     ///
     ///                     // Get the second parameter by 'consuming' it
@@ -455,14 +683,117 @@ impl Module {
         &mut self,
         name: impl Into<String>,
         arg_types: &[TypeId],
-        func: impl Fn(&Engine, &Module, &mut [&mut Dynamic]) -> FuncReturn<T> + SendSync + 'static,
+        func: impl Fn(NativeCallContext, &mut [&mut Dynamic]) -> FuncReturn<T> + SendSync + 'static,
     ) -> u64 {
-        let f = move |engine: &Engine, lib: &Module, args: &mut FnCallArgs| {
-            func(engine, lib, args).map(Dynamic::from)
+        let f = move |context: NativeCallContext, args: &mut FnCallArgs| {
+            func(context, args).map(Dynamic::from)
         };
         self.set_fn(name, Public, arg_types, Func::from_method(Box::new(f)))
     }
 
+    /// Set a variadic Rust function into the module, returning a hash key.
+    ///
+    /// The function can be called with any number of arguments after the fixed parameters
+    /// of the types in `arg_types`.  It is only called when no function of the same name
+    /// matches the number and types of the arguments exactly.
+    ///
+    /// There can only be one variadic function of each name and number of fixed parameters.
+    /// If there is an existing one, it is replaced.
+    ///
+    /// ## WARNING - Low Level API
+    ///
+    /// This function is very low level.
+    ///
+    /// Arguments are passed in as a mutable array of `&mut Dynamic` holding the fixed arguments,
+    /// which are guaranteed to be of the correct types, followed by all the trailing arguments.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rhai::Module;
+    ///
+    /// let mut module = Module::new();
+    /// let hash = module.set_variadic_fn("count",
+    ///                 // Types of the fixed parameters
+    ///                 &[std::any::TypeId::of::<i64>()],
+    ///                 |context, args| {
+    ///                     // 'args[0]' is guaranteed to be an 'i64'
+    ///                     let base = args[0].clone().cast::<i64>();
+    ///                     Ok(base + args.len() as i64 - 1)
+    ///                 });
+    ///
+    /// assert!(module.contains_variadic_fn("count"));
+    /// ```
+    pub fn set_variadic_fn<T: Variant + Clone>(
+        &mut self,
+        name: impl Into<String>,
+        arg_types: &[TypeId],
+        func: impl Fn(NativeCallContext, &mut [&mut Dynamic]) -> FuncReturn<T> + SendSync + 'static,
+    ) -> u64 {
+        let name = name.into();
+        let hash_fn = calc_variadic_fn_hash(empty(), &name, arg_types.len());
+        let f = move |context: NativeCallContext, args: &mut FnCallArgs| {
+            func(context, args).map(Dynamic::from)
+        };
+
+        self.variadic_functions.insert(
+            hash_fn,
+            (
+                name,
+                Public,
+                arg_types.iter().cloned().collect(),
+                Func::from_pure(Box::new(f)),
+            ),
+        );
+
+        self.indexed = false;
+
+        hash_fn
+    }
+
+    /// Does a variadic function of a particular name exist in the module?
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rhai::Module;
+    ///
+    /// let mut module = Module::new();
+    /// module.set_variadic_fn("count", &[], |_, args| Ok(args.len() as i64));
+    /// assert!(module.contains_variadic_fn("count"));
+    /// ```
+    pub fn contains_variadic_fn(&self, name: &str) -> bool {
+        self.variadic_functions
+            .values()
+            .any(|(fn_name, _, _, _)| fn_name == name)
+    }
+
+    /// Get a variadic function that can be called with a particular list of arguments.
+    ///
+    /// The one with the most fixed parameters matching the arguments is the best match.
+    pub(crate) fn get_variadic_fn(
+        &self,
+        name: &str,
+        args: &FnCallArgs,
+        public_only: bool,
+    ) -> Option<&Func> {
+        if self.variadic_functions.is_empty() {
+            return None;
+        }
+
+        (0..=args.len()).rev().find_map(|num_fixed| {
+            self.variadic_functions
+                .get(&calc_variadic_fn_hash(empty(), name, num_fixed))
+                .and_then(|(_, access, params, f)| match access {
+                    _ if !public_only => Some((params, f)),
+                    FnAccess::Public => Some((params, f)),
+                    FnAccess::Private => None,
+                })
+                .filter(|(params, f)| variadic_fn_accepts(params, f, args))
+                .map(|(_, f)| f)
+        })
+    }
+
     /// Set a Rust function taking no parameters into the module, returning a hash key.
     ///
     /// If there is a similar existing Rust function, it is replaced.
@@ -481,7 +812,7 @@ impl Module {
         name: impl Into<String>,
         func: impl Fn() -> FuncReturn<T> + SendSync + 'static,
     ) -> u64 {
-        let f = move |_: &Engine, _: &Module, _: &mut FnCallArgs| func().map(Dynamic::from);
+        let f = move |_: NativeCallContext, _: &mut FnCallArgs| func().map(Dynamic::from);
         let arg_types = [];
         self.set_fn(name, Public, &arg_types, Func::from_pure(Box::new(f)))
     }
@@ -504,7 +835,7 @@ impl Module {
         name: impl Into<String>,
         func: impl Fn(A) -> FuncReturn<T> + SendSync + 'static,
     ) -> u64 {
-        let f = move |_: &Engine, _: &Module, args: &mut FnCallArgs| {
+        let f = move |_: NativeCallContext, args: &mut FnCallArgs| {
             func(mem::take(args[0]).cast::<A>()).map(Dynamic::from)
         };
         let arg_types = [TypeId::of::<A>()];
@@ -529,7 +860,7 @@ impl Module {
         name: impl Into<String>,
         func: impl Fn(&mut A) -> FuncReturn<T> + SendSync + 'static,
     ) -> u64 {
-        let f = move |_: &Engine, _: &Module, args: &mut FnCallArgs| {
+        let f = move |_: NativeCallContext, args: &mut FnCallArgs| {
             func(&mut args[0].write_lock::<A>().unwrap()).map(Dynamic::from)
         };
         let arg_types = [TypeId::of::<A>()];
@@ -578,7 +909,7 @@ impl Module {
         name: impl Into<String>,
         func: impl Fn(A, B) -> FuncReturn<T> + SendSync + 'static,
     ) -> u64 {
-        let f = move |_: &Engine, _: &Module, args: &mut FnCallArgs| {
+        let f = move |_: NativeCallContext, args: &mut FnCallArgs| {
             let a = mem::take(args[0]).cast::<A>();
             let b = mem::take(args[1]).cast::<B>();
 
@@ -609,7 +940,7 @@ impl Module {
         name: impl Into<String>,
         func: impl Fn(&mut A, B) -> FuncReturn<T> + SendSync + 'static,
     ) -> u64 {
-        let f = move |_: &Engine, _: &Module, args: &mut FnCallArgs| {
+        let f = move |_: NativeCallContext, args: &mut FnCallArgs| {
             let b = mem::take(args[1]).cast::<B>();
             let mut a = args[0].write_lock::<A>().unwrap();
 
@@ -695,7 +1026,7 @@ impl Module {
         name: impl Into<String>,
         func: impl Fn(A, B, C) -> FuncReturn<T> + SendSync + 'static,
     ) -> u64 {
-        let f = move |_: &Engine, _: &Module, args: &mut FnCallArgs| {
+        let f = move |_: NativeCallContext, args: &mut FnCallArgs| {
             let a = mem::take(args[0]).cast::<A>();
             let b = mem::take(args[1]).cast::<B>();
             let c = mem::take(args[2]).cast::<C>();
@@ -732,7 +1063,7 @@ impl Module {
         name: impl Into<String>,
         func: impl Fn(&mut A, B, C) -> FuncReturn<T> + SendSync + 'static,
     ) -> u64 {
-        let f = move |_: &Engine, _: &Module, args: &mut FnCallArgs| {
+        let f = move |_: NativeCallContext, args: &mut FnCallArgs| {
             let b = mem::take(args[1]).cast::<B>();
             let c = mem::take(args[2]).cast::<C>();
             let mut a = args[0].write_lock::<A>().unwrap();
@@ -766,7 +1097,7 @@ impl Module {
         &mut self,
         func: impl Fn(&mut A, B, C) -> FuncReturn<()> + SendSync + 'static,
     ) -> u64 {
-        let f = move |_: &Engine, _: &Module, args: &mut FnCallArgs| {
+        let f = move |_: NativeCallContext, args: &mut FnCallArgs| {
             let b = mem::take(args[1]).cast::<B>();
             let c = mem::take(args[2]).cast::<C>();
             let mut a = args[0].write_lock::<A>().unwrap();
@@ -844,7 +1175,7 @@ impl Module {
         name: impl Into<String>,
         func: impl Fn(A, B, C, D) -> FuncReturn<T> + SendSync + 'static,
     ) -> u64 {
-        let f = move |_: &Engine, _: &Module, args: &mut FnCallArgs| {
+        let f = move |_: NativeCallContext, args: &mut FnCallArgs| {
             let a = mem::take(args[0]).cast::<A>();
             let b = mem::take(args[1]).cast::<B>();
             let c = mem::take(args[2]).cast::<C>();
@@ -888,7 +1219,7 @@ impl Module {
         name: impl Into<String>,
         func: impl Fn(&mut A, B, C, D) -> FuncReturn<T> + SendSync + 'static,
     ) -> u64 {
-        let f = move |_: &Engine, _: &Module, args: &mut FnCallArgs| {
+        let f = move |_: NativeCallContext, args: &mut FnCallArgs| {
             let b = mem::take(args[1]).cast::<B>();
             let c = mem::take(args[2]).cast::<C>();
             let d = mem::take(args[3]).cast::<D>();
@@ -910,8 +1241,7 @@ impl Module {
     /// The `u64` hash is calculated by the function `crate::calc_fn_hash`.
     /// It is also returned by the `set_fn_XXX` calls.
     pub(crate) fn get_fn(&self, hash_fn: u64, public_only: bool) -> Option<&Func> {
-        self.functions
-            .get(&hash_fn)
+        self.get_fn_entry(hash_fn)
             .and_then(|(_, access, _, f)| match access {
                 _ if !public_only => Some(f),
                 FnAccess::Public => Some(f),
@@ -928,6 +1258,115 @@ impl Module {
         self.all_functions.get(&hash_qualified_fn)
     }
 
+    /// Get an iterator over the overloads of a script-defined function with typed parameters
+    /// that can be called with a particular number of arguments.
+    ///
+    /// The `u64` hash is calculated by the function `crate::calc_fn_hash`, with the function name
+    /// and the number of arguments.
+    #[cfg(not(feature = "no_function"))]
+    pub(crate) fn iter_fn_overloads(
+        &self,
+        hash_script: u64,
+        public_only: bool,
+    ) -> impl Iterator<Item = &Func> {
+        self.fn_overloads
+            .get(&hash_script)
+            .into_iter()
+            .flatten()
+            .filter_map(move |&hash| self.get_fn(hash, public_only))
+    }
+
+    /// Get an iterator over the overloads of a script-defined variadic function with typed
+    /// parameters that can be called with a particular number of arguments.
+    #[cfg(not(feature = "no_function"))]
+    pub(crate) fn iter_variadic_fn_overloads<'a>(
+        &'a self,
+        name: &'a str,
+        num_args: usize,
+        public_only: bool,
+    ) -> impl Iterator<Item = &'a Func> {
+        let num_args = if self.variadic_overloads.is_empty() {
+            None
+        } else {
+            Some(num_args)
+        };
+
+        num_args
+            .into_iter()
+            .flat_map(|num_args| 0..=num_args)
+            .filter_map(move |num_fixed| {
+                self.variadic_overloads
+                    .get(&calc_variadic_fn_hash(empty(), name, num_fixed))
+            })
+            .flatten()
+            .filter_map(move |hash| match self.variadic_functions.get(hash) {
+                Some((_, FnAccess::Private, _, _)) if public_only => None,
+                Some((_, _, _, f)) => Some(f),
+                None => None,
+            })
+    }
+
+    /// Get an iterator over the overloads of a modules-qualified script-defined function with
+    /// typed parameters.
+    ///
+    /// The `u64` hash is calculated by the function `crate::calc_fn_hash` and must match
+    /// the hash calculated by `index_all_sub_modules`.
+    #[cfg(not(feature = "no_function"))]
+    pub(crate) fn iter_qualified_fn_overloads(
+        &self,
+        hash_qualified_fn: u64,
+    ) -> impl Iterator<Item = &Func> {
+        self.all_fn_overloads
+            .get(&hash_qualified_fn)
+            .into_iter()
+            .flatten()
+    }
+
+    /// Get an iterator over the overloads of a modules-qualified script-defined variadic function
+    /// with typed parameters that can be called with a particular number of arguments.
+    #[cfg(not(feature = "no_function"))]
+    pub(crate) fn iter_qualified_variadic_fn_overloads<'a>(
+        &'a self,
+        modules: impl Iterator<Item = &'a str> + Clone + 'a,
+        name: &'a str,
+        num_args: usize,
+    ) -> impl Iterator<Item = &'a Func> {
+        let num_args = if self.all_variadic_overloads.is_empty() {
+            None
+        } else {
+            Some(num_args)
+        };
+
+        num_args
+            .into_iter()
+            .flat_map(|num_args| 0..=num_args)
+            .filter_map(move |num_fixed| {
+                self.all_variadic_overloads.get(&calc_variadic_fn_hash(
+                    modules.clone(),
+                    name,
+                    num_fixed,
+                ))
+            })
+            .flatten()
+    }
+
+    /// Get a modules-qualified variadic function that can be called with a particular list of arguments.
+    ///
+    /// The one with the most fixed parameters matching the arguments is the best match.
+    pub(crate) fn get_qualified_variadic_fn<'a>(
+        &self,
+        modules: impl Iterator<Item = &'a str> + Clone,
+        name: &str,
+        args: &FnCallArgs,
+    ) -> Option<&Func> {
+        (0..=args.len()).rev().find_map(|num_fixed| {
+            self.all_variadic_functions
+                .get(&calc_variadic_fn_hash(modules.clone(), name, num_fixed))
+                .filter(|(params, f)| variadic_fn_accepts(params, f, args))
+                .map(|(_, f)| f)
+        })
+    }
+
     /// Merge another module into this module.
     pub fn merge(&mut self, other: &Self) -> &mut Self {
         self.merge_filtered(other, |_, _, _| true)
@@ -942,27 +1381,195 @@ impl Module {
         self.variables
             .extend(other.variables.iter().map(|(k, v)| (k.clone(), v.clone())));
 
+        let merged = |v: &Func| match v {
+            #[cfg(not(feature = "no_function"))]
+            Func::Script(ref f) => _filter(f.access, f.name.as_str(), f.params.len()),
+            _ => true,
+        };
+        let is_merged =
+            |hash: &u64| matches!(other.functions.get(hash), Some((_, _, _, v)) if merged(v));
+
         self.functions.extend(
             other
                 .functions
                 .iter()
-                .filter(|(_, (_, _, _, v))| match v {
-                    #[cfg(not(feature = "no_function"))]
-                    Func::Script(ref f) => _filter(f.access, f.name.as_str(), f.params.len()),
-                    _ => true,
-                })
+                .filter(|(_, (_, _, _, v))| merged(v))
                 .map(|(&k, v)| (k, v.clone())),
         );
+        self.variadic_functions.extend(
+            other
+                .variadic_functions
+                .iter()
+                .filter(|(_, (_, _, _, v))| merged(v))
+                .map(|(&k, v)| (k, v.clone())),
+        );
+        // Default parameter values of the functions replaced no longer apply
+        self.fn_arities.retain(|_, hash| !is_merged(hash));
+        self.fn_arities
+            .extend(other.fn_arities.iter().filter(|(_, hash)| is_merged(hash)));
+        other.fn_overloads.iter().for_each(|(&k, hashes)| {
+            let overloads = self.fn_overloads.entry(k).or_default();
+            hashes.iter().for_each(|hash| {
+                if !overloads.contains(hash) {
+                    overloads.push(*hash);
+                }
+            });
+        });
+        other.variadic_overloads.iter().for_each(|(&k, hashes)| {
+            let overloads = self.variadic_overloads.entry(k).or_default();
+            hashes.iter().for_each(|hash| {
+                if !overloads.contains(hash) {
+                    overloads.push(*hash);
+                }
+            });
+        });
+        self.fn_param_names
+            .extend(other.fn_param_names.iter().map(|(&k, v)| (k, v.clone())));
 
         self.type_iterators
             .extend(other.type_iterators.iter().map(|(&k, v)| (k, v.clone())));
 
         self.all_functions.clear();
+        self.all_variadic_functions.clear();
+        self.all_fn_overloads.clear();
+        self.all_variadic_overloads.clear();
+        self.all_variables.clear();
+        self.indexed = false;
+        self
+    }
+
+    /// Copy all variables, public functions and sub-modules of another module into this module.
+    #[cfg(not(feature = "no_module"))]
+    pub(crate) fn re_export_all(&mut self, other: &Self) -> &mut Self {
+        self.deterministic &= other.deterministic;
+
+        self.variables
+            .extend(other.variables.iter().map(|(k, v)| (k.clone(), v.clone())));
+
+        self.functions.extend(
+            other
+                .functions
+                .iter()
+                .filter(|(_, (_, access, _, _))| *access == FnAccess::Public)
+                .map(|(&k, v)| (k, v.clone())),
+        );
+        self.variadic_functions.extend(
+            other
+                .variadic_functions
+                .iter()
+                .filter(|(_, (_, access, _, _))| *access == FnAccess::Public)
+                .map(|(&k, v)| (k, v.clone())),
+        );
+        // Default parameter values of the functions replaced no longer apply
+        let is_exported = |hash: &u64| matches!(other.functions.get(hash), Some((_, access, _, _)) if *access == FnAccess::Public);
+        self.fn_arities.retain(|_, hash| !is_exported(hash));
+        self.fn_arities.extend(
+            other
+                .fn_arities
+                .iter()
+                .filter(|(_, hash)| is_exported(hash)),
+        );
+        other.fn_overloads.iter().for_each(|(&k, hashes)| {
+            let overloads = self.fn_overloads.entry(k).or_default();
+            hashes.iter().for_each(|hash| {
+                if !overloads.contains(hash) {
+                    overloads.push(*hash);
+                }
+            });
+        });
+        other.variadic_overloads.iter().for_each(|(&k, hashes)| {
+            let overloads = self.variadic_overloads.entry(k).or_default();
+            hashes.iter().for_each(|hash| {
+                if !overloads.contains(hash) {
+                    overloads.push(*hash);
+                }
+            });
+        });
+        self.fn_param_names
+            .extend(other.fn_param_names.iter().map(|(&k, v)| (k, v.clone())));
+
+        self.modules
+            .extend(other.modules.iter().map(|(k, m)| (k.clone(), m.clone())));
+
+        self.all_functions.clear();
+        self.all_variadic_functions.clear();
+        self.all_fn_overloads.clear();
+        self.all_variadic_overloads.clear();
         self.all_variables.clear();
         self.indexed = false;
         self
     }
 
+    /// Copy the variable, all public functions and the sub-module with a particular name from
+    /// another module into this module, under a new name.
+    ///
+    /// Returns `false` if there is no variable, public function or sub-module with that name.
+    #[cfg(not(feature = "no_module"))]
+    pub(crate) fn re_export(&mut self, other: &Self, name: &str, alias: &str) -> bool {
+        let mut found = false;
+
+        if let Some(value) = other.variables.get(name) {
+            self.variables.insert(alias.into(), value.clone());
+            found = true;
+        }
+
+        if let Some(module) = other.modules.get(name) {
+            self.deterministic &= module.deterministic;
+            self.modules.insert(alias.into(), module.clone());
+            found = true;
+        }
+
+        for (fn_name, access, params, func) in other.functions.values() {
+            if fn_name != name || *access == FnAccess::Private {
+                continue;
+            }
+
+            found = true;
+
+            #[cfg(not(feature = "no_function"))]
+            if func.is_script() {
+                let mut fn_def = func.get_fn_def().clone();
+                fn_def.name = alias.into();
+                self.set_script_fn(fn_def);
+                continue;
+            }
+
+            self.deterministic &= other.deterministic;
+            self.set_fn(alias, *access, params, func.clone());
+        }
+
+        for (fn_name, access, params, func) in other.variadic_functions.values() {
+            if fn_name != name || *access == FnAccess::Private {
+                continue;
+            }
+
+            found = true;
+
+            #[cfg(not(feature = "no_function"))]
+            if func.is_script() {
+                let mut fn_def = func.get_fn_def().clone();
+                fn_def.name = alias.into();
+                self.set_script_fn(fn_def);
+                continue;
+            }
+
+            self.deterministic &= other.deterministic;
+            self.variadic_functions.insert(
+                calc_variadic_fn_hash(empty(), alias, variadic_fn_num_fixed(params, func)),
+                (alias.into(), *access, params.clone(), func.clone()),
+            );
+        }
+
+        self.all_functions.clear();
+        self.all_variadic_functions.clear();
+        self.all_fn_overloads.clear();
+        self.all_variadic_overloads.clear();
+        self.all_variables.clear();
+        self.indexed = false;
+
+        found
+    }
+
     /// Filter out the functions, retaining only some based on a filter predicate.
     #[cfg(not(feature = "no_function"))]
     pub(crate) fn retain_functions(
@@ -973,8 +1580,15 @@ impl Module {
             Func::Script(ref f) => filter(f.access, f.name.as_str(), f.params.len()),
             _ => true,
         });
+        self.variadic_functions.retain(|_, (_, _, _, v)| match v {
+            Func::Script(ref f) => filter(f.access, f.name.as_str(), f.params.len()),
+            _ => true,
+        });
 
         self.all_functions.clear();
+        self.all_variadic_functions.clear();
+        self.all_fn_overloads.clear();
+        self.all_variadic_overloads.clear();
         self.all_variables.clear();
         self.indexed = false;
         self
@@ -1010,6 +1624,7 @@ impl Module {
     pub fn iter_script_fn<'a>(&'a self) -> impl Iterator<Item = Shared<ScriptFnDef>> + 'a {
         self.functions
             .values()
+            .chain(self.variadic_functions.values())
             .map(|(_, _, _, f)| f)
             .filter(|f| f.is_script())
             .map(|f| f.get_shared_fn_def())
@@ -1032,11 +1647,50 @@ impl Module {
     /// # }
     /// ```
     #[cfg(not(feature = "no_module"))]
-    pub fn eval_ast_as_new(mut scope: Scope, ast: &AST, engine: &Engine) -> FuncReturn<Self> {
+    pub fn eval_ast_as_new(scope: Scope, ast: &AST, engine: &Engine) -> FuncReturn<Self> {
+        Self::eval_ast_with_state(scope, ast, engine, State::new())
+    }
+
+    /// Create a new `Module` by evaluating an `AST` imported by a running script.
+    ///
+    /// The module is evaluated under the time limit and cancellation token of the importing
+    /// script, as held in the `ImportContext` passed to `ModuleResolver::resolve_import`.
+    /// `key` identifies the module in the `ImportContext` of imports made by the module itself.
+    #[cfg(not(feature = "no_module"))]
+    pub fn eval_ast_as_import(
+        scope: Scope,
+        ast: &AST,
+        engine: &Engine,
+        context: &ImportContext,
+        key: impl Into<String>,
+    ) -> FuncReturn<Self> {
+        let mut state = State::new();
+        state.import_chain = context.chain.clone();
+        state.import_chain.push(key.into());
+        #[cfg(not(feature = "no_std"))]
+        {
+            state.started = context.started;
+        }
+        state.cancel = context.cancel.clone();
+
+        Self::eval_ast_with_state(scope, ast, engine, state)
+    }
+
+    /// Create a new `Module` by evaluating an `AST` with a `State`.
+    #[cfg(not(feature = "no_module"))]
+    fn eval_ast_with_state(
+        mut scope: Scope,
+        ast: &AST,
+        engine: &Engine,
+        mut state: State,
+    ) -> FuncReturn<Self> {
         let mut mods = Imports::new();
 
+        // Re-exporting members of modules not deterministic clears this
+        state.exports.set_deterministic(true);
+
         // Run the script
-        engine.eval_ast_with_scope_raw(&mut scope, &mut mods, &ast)?;
+        engine.eval_ast_with_scope_raw(&mut scope, &mut mods, &mut state, &ast)?;
 
         // Create new module
         let mut module = Module::new();
@@ -1052,11 +1706,18 @@ impl Module {
 
         // Modules left in the scope become sub-modules
         mods.into_iter().for_each(|(alias, m)| {
+            let m = shared_try_take(m).unwrap_or_else(|m| m.as_ref().clone());
             module.modules.insert(alias.to_string(), m);
         });
 
         module.merge(ast.lib());
 
+        // The module only holds external Rust functions of other modules
+        module.deterministic = module.modules.values().all(Module::is_deterministic);
+
+        // Members re-exported from other modules
+        module.re_export_all(&state.exports);
+
         Ok(module)
     }
 
@@ -1070,11 +1731,22 @@ impl Module {
             qualifiers: &mut Vec<&'a str>,
             variables: &mut Vec<(u64, Dynamic)>,
             functions: &mut Vec<(u64, Func)>,
+            variadic_functions: &mut Vec<(u64, (StaticVec<TypeId>, Func))>,
+            fn_overloads: &mut Vec<(u64, Func)>,
+            variadic_overloads: &mut Vec<(u64, Func)>,
         ) {
             for (name, m) in &module.modules {
                 // Index all the sub-modules first.
                 qualifiers.push(name);
-                index_module(m, qualifiers, variables, functions);
+                index_module(
+                    m,
+                    qualifiers,
+                    variables,
+                    functions,
+                    variadic_functions,
+                    fn_overloads,
+                    variadic_overloads,
+                );
                 qualifiers.pop();
             }
 
@@ -1095,14 +1767,22 @@ impl Module {
                 #[cfg(not(feature = "no_function"))]
                 if func.is_script() {
                     let fn_def = func.get_shared_fn_def();
-                    // Qualifiers + function name + number of arguments.
-                    let hash_qualified_script = calc_fn_hash(
-                        qualifiers.iter().map(|&v| v),
-                        &fn_def.name,
-                        fn_def.params.len(),
-                        empty(),
-                    );
-                    functions.push((hash_qualified_script, fn_def.into()));
+                    // Qualifiers + function name + number of arguments,
+                    // for each number of arguments the function can be called with.
+                    for num_args in fn_def.num_required_params()..=fn_def.params.len() {
+                        let hash_qualified_script = calc_fn_hash(
+                            qualifiers.iter().map(|&v| v),
+                            &fn_def.name,
+                            num_args,
+                            empty(),
+                        );
+                        // Overloads with typed parameters share the same hash
+                        if fn_def.is_typed() {
+                            fn_overloads.push((hash_qualified_script, fn_def.clone().into()));
+                        } else {
+                            functions.push((hash_qualified_script, fn_def.clone().into()));
+                        }
+                    }
                     continue;
                 }
 
@@ -1119,6 +1799,29 @@ impl Module {
 
                 functions.push((hash_qualified_fn, func.clone()));
             }
+            // Index all variadic functions - qualifiers + function name
+            for (name, access, params, func) in module.variadic_functions.values() {
+                match access {
+                    // Private functions are not exported
+                    FnAccess::Private => continue,
+                    FnAccess::Public => (),
+                }
+
+                let hash_qualified_fn = calc_variadic_fn_hash(
+                    qualifiers.iter().copied(),
+                    name,
+                    variadic_fn_num_fixed(params, func),
+                );
+
+                // Overloads with typed parameters share the same hash
+                #[cfg(not(feature = "no_function"))]
+                if func.is_script() && func.get_fn_def().is_typed() {
+                    variadic_overloads.push((hash_qualified_fn, func.clone()));
+                    continue;
+                }
+
+                variadic_functions.push((hash_qualified_fn, (params.clone(), func.clone())));
+            }
         }
 
         if self.indexed {
@@ -1128,13 +1831,36 @@ impl Module {
         let mut qualifiers: Vec<_> = Default::default();
         let mut variables: Vec<_> = Default::default();
         let mut functions: Vec<_> = Default::default();
+        let mut variadic_functions: Vec<_> = Default::default();
+        let mut fn_overloads: Vec<(u64, Func)> = Default::default();
+        let mut variadic_overloads: Vec<(u64, Func)> = Default::default();
 
         qualifiers.push("root");
 
-        index_module(self, &mut qualifiers, &mut variables, &mut functions);
+        index_module(
+            self,
+            &mut qualifiers,
+            &mut variables,
+            &mut functions,
+            &mut variadic_functions,
+            &mut fn_overloads,
+            &mut variadic_overloads,
+        );
 
         self.all_variables = variables.into_iter().collect();
         self.all_functions = functions.into_iter().collect();
+        self.all_variadic_functions = variadic_functions.into_iter().collect();
+        self.all_fn_overloads.clear();
+        fn_overloads.into_iter().for_each(|(hash, func)| {
+            self.all_fn_overloads.entry(hash).or_default().push(func);
+        });
+        self.all_variadic_overloads.clear();
+        variadic_overloads.into_iter().for_each(|(hash, func)| {
+            self.all_variadic_overloads
+                .entry(hash)
+                .or_default()
+                .push(func);
+        });
         self.indexed = true;
     }
 
@@ -1156,6 +1882,31 @@ impl Module {
     }
 }
 
+/// Number of fixed (or required) parameters of a variadic function, part of its hash key.
+fn variadic_fn_num_fixed(params: &[TypeId], func: &Func) -> usize {
+    #[cfg(not(feature = "no_function"))]
+    if func.is_script() {
+        return func.get_fn_def().num_required_params();
+    }
+
+    #[cfg(feature = "no_function")]
+    let _ = func;
+
+    params.len()
+}
+
+/// Can a variadic function be called with a particular list of arguments?
+///
+/// There must be at least as many arguments as fixed (or required) parameters,
+/// and the arguments for the fixed parameters of a Rust function must be of the correct types.
+fn variadic_fn_accepts(params: &[TypeId], func: &Func, args: &FnCallArgs) -> bool {
+    args.len() >= variadic_fn_num_fixed(params, func)
+        && params
+            .iter()
+            .zip(args.iter())
+            .all(|(&t, a)| t == TypeId::of::<Dynamic>() || t == a.type_id())
+}
+
 /// [INTERNALS] A chain of module names to qualify a variable or function call.
 /// Exported under the `internals` feature only.
 ///
@@ -1220,19 +1971,103 @@ impl ModuleRef {
     }
 }
 
+/// The script importing a module, as seen by a `ModuleResolver`.
+///
+/// Not available under the `no_module` feature.
+#[cfg(not(feature = "no_module"))]
+#[derive(Debug, Clone, Default)]
+pub struct ImportContext {
+    /// Keys of the modules being imported, outermost first, ending with the module doing the import.
+    pub(crate) chain: Vec<String>,
+    /// Time when the importing script started running.  Only tracked when there is a time limit.
+    #[cfg(not(feature = "no_std"))]
+    pub(crate) started: Option<Instant>,
+    /// Token to cancel the importing script, if any.
+    pub(crate) cancel: Option<CancellationToken>,
+}
+
+#[cfg(not(feature = "no_module"))]
+impl ImportContext {
+    /// Create a new `ImportContext` for a module not imported by any running script.
+    pub fn new() -> Self {
+        Default::default()
+    }
+    /// Get the keys of the modules being imported, outermost first.
+    /// The last one is the module doing the import, if any.
+    pub fn chain(&self) -> &[String] {
+        &self.chain
+    }
+    /// Get the key of the module doing the import, if the import is not made by the main script.
+    pub fn importer(&self) -> Option<&str> {
+        self.chain.last().map(String::as_str)
+    }
+    /// Get the token to cancel the importing script, if any.
+    pub fn cancel_token(&self) -> Option<&CancellationToken> {
+        self.cancel.as_ref()
+    }
+    /// Return `EvalAltResult::ErrorModuleNotFound`, listing the chain of imports, if importing
+    /// the module with the key would be circular.
+    pub fn check_circular(&self, key: &str, pos: Position) -> Result<(), Box<EvalAltResult>> {
+        if self.chain.iter().any(|k| k == key) {
+            let keys: Vec<_> = self
+                .chain
+                .iter()
+                .map(String::as_str)
+                .chain(Some(key))
+                .collect();
+
+            EvalAltResult::ErrorModuleNotFound(keys.join(" -> "), pos).into()
+        } else {
+            Ok(())
+        }
+    }
+}
+
 /// Trait that encapsulates a module resolution service.
 pub trait ModuleResolver: SendSync {
     /// Resolve a module based on a path string.
     fn resolve(&self, _: &Engine, path: &str, pos: Position) -> Result<Module, Box<EvalAltResult>>;
+
+    /// Resolve a module based on a path string, imported by a running script.
+    ///
+    /// Resolvers that evaluate scripts should evaluate them via `Module::eval_ast_as_import`,
+    /// so that the time limit and cancellation token of the importing script also apply to the
+    /// module.  The default calls `resolve`.
+    #[cfg(not(feature = "no_module"))]
+    fn resolve_import(
+        &self,
+        engine: &Engine,
+        _: &ImportContext,
+        path: &str,
+        pos: Position,
+    ) -> Result<Module, Box<EvalAltResult>> {
+        self.resolve(engine, path, pos)
+    }
+
+    /// Get the key identifying the module resolved from a path string.
+    ///
+    /// When an `Engine` shares imported modules, paths with the same key are evaluated only once.
+    /// The default is the path string itself.  Resolvers that resolve the same path string to
+    /// different modules (e.g. relative to the importing module) must return different keys.
+    #[cfg(not(feature = "no_module"))]
+    fn module_key(&self, _: &ImportContext, path: &str) -> String {
+        path.into()
+    }
 }
 
 /// Re-export module resolvers.
 #[cfg(not(feature = "no_module"))]
 pub mod resolvers {
+    #[cfg(not(feature = "no_std"))]
+    pub use super::archive::ArchiveModuleResolver;
     pub use super::collection::ModuleResolversCollection;
     #[cfg(not(feature = "no_std"))]
     #[cfg(not(target_arch = "wasm32"))]
     pub use super::file::FileModuleResolver;
+    #[cfg(not(feature = "no_std"))]
+    #[cfg(not(target_arch = "wasm32"))]
+    #[cfg(feature = "sync")]
+    pub use super::file::FileWatcher;
     pub use super::stat::StaticModuleResolver;
 }
 #[cfg(feature = "no_module")]
@@ -1244,17 +2079,131 @@ pub mod resolvers {}
 #[cfg(not(target_arch = "wasm32"))]
 mod file {
     use super::*;
-    use crate::stdlib::path::PathBuf;
+    use crate::fn_native::{Callback, Shared};
+    use crate::stdlib::{
+        collections::hash_map::DefaultHasher,
+        fs,
+        hash::{Hash, Hasher},
+        path::{Path, PathBuf},
+        time::SystemTime,
+    };
+
+    #[cfg(not(feature = "sync"))]
+    use crate::stdlib::cell::RefMut;
+
+    #[cfg(feature = "sync")]
+    use crate::stdlib::{
+        sync::{
+            mpsc::{self, RecvTimeoutError, Sender},
+            RwLockWriteGuard,
+        },
+        thread::{self, JoinHandle},
+        time::Duration,
+    };
+
+    /// A compiled script file cached by a `FileModuleResolver`.
+    #[derive(Debug, Clone)]
+    struct CacheEntry {
+        /// The compiled script.
+        ast: AST,
+        /// Last modification time of the script file, if available.
+        modified: Option<SystemTime>,
+        /// Hash of the script source.
+        hash: u64,
+        /// Tick when the script was last used, for LRU eviction.
+        last_used: u64,
+    }
+
+    /// Compiled script files cached by a `FileModuleResolver`, shared by all its clones.
+    #[derive(Default)]
+    struct FileCache {
+        entries: HashMap<PathBuf, CacheEntry>,
+        /// Maximum number of script files cached, zero for unlimited.
+        max_size: usize,
+        /// Counter incremented on every cache access.
+        ticks: u64,
+        /// Callback closure for when a changed script file is reloaded.
+        on_reload: Option<Shared<Callback<Path, ()>>>,
+    }
+
+    impl fmt::Debug for FileCache {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.debug_struct("FileCache")
+                .field("entries", &self.entries)
+                .field("max_size", &self.max_size)
+                .finish()
+        }
+    }
+
+    impl FileCache {
+        /// Remove the least recently used script files until within the maximum cache size.
+        fn evict(&mut self) {
+            while self.max_size > 0 && self.entries.len() > self.max_size {
+                let oldest = self
+                    .entries
+                    .iter()
+                    .min_by_key(|(_, entry)| entry.last_used)
+                    .map(|(path, _)| path.clone())
+                    .unwrap();
+                self.entries.remove(&oldest);
+            }
+        }
+    }
+
+    /// Get the last modification time of a file, if available.
+    fn modified_time(path: &Path) -> Option<SystemTime> {
+        fs::metadata(path).and_then(|m| m.modified()).ok()
+    }
+
+    /// Handle to a thread started by `FileModuleResolver::watch`, which reloads changed script
+    /// files in the background.  The thread stops when the handle is dropped.
+    ///
+    /// Only available under the `sync` feature.
+    #[cfg(feature = "sync")]
+    #[derive(Debug)]
+    pub struct FileWatcher {
+        /// Dropping the sender wakes the thread up and stops it.
+        stop: Option<Sender<()>>,
+        thread: Option<JoinHandle<()>>,
+    }
+
+    #[cfg(feature = "sync")]
+    impl Drop for FileWatcher {
+        fn drop(&mut self) {
+            self.stop.take();
+
+            if let Some(thread) = self.thread.take() {
+                let _ = thread.join();
+            }
+        }
+    }
 
     /// Module resolution service that loads module script files from the file system.
     ///
     /// Script files are cached so they are are not reloaded and recompiled in subsequent requests.
+    /// A cached script file is recompiled when its modification time and contents change.
+    /// All clones of a `FileModuleResolver` share the same cache, so keep a clone after setting it
+    /// into an `Engine` to manage the cache.
     ///
     /// The `new_with_path` and `new_with_path_and_extension` constructor functions
     /// allow specification of a base directory with module path used as a relative path offset
     /// to the base directory. The script file is then forced to be in a specified extension
     /// (default `.rhai`).
     ///
+    /// Module paths are resolved as follows:
+    ///
+    /// * A path starting with `./` or `../` is relative to the directory of the script file
+    ///   containing the `import` statement, or to the base directory if there is none.
+    /// * A path starting with an alias added via `add_alias` (e.g. `@shared/x`) is relative to
+    ///   the directory of that alias.
+    /// * Any other path is searched in the base directory, then in all the directories added
+    ///   via `add_search_path`, in order.
+    ///
+    /// A path that points to a directory loads the `index` script file inside it.
+    ///
+    /// Circular imports are reported as `EvalAltResult::ErrorModuleNotFound` with the full chain
+    /// of script files.
+    ///
     /// # Examples
     ///
     /// ```
@@ -1268,16 +2217,17 @@ mod file {
     /// let mut engine = Engine::new();
     /// engine.set_module_resolver(Some(resolver));
     /// ```
-    #[derive(Debug)]
+    #[derive(Debug, Clone)]
     pub struct FileModuleResolver {
-        path: PathBuf,
+        paths: Vec<PathBuf>,
         extension: String,
+        aliases: Vec<(String, PathBuf)>,
 
         #[cfg(not(feature = "sync"))]
-        cache: RefCell<HashMap<PathBuf, AST>>,
+        cache: Shared<RefCell<FileCache>>,
 
         #[cfg(feature = "sync")]
-        cache: RwLock<HashMap<PathBuf, AST>>,
+        cache: Shared<RwLock<FileCache>>,
     }
 
     impl Default for FileModuleResolver {
@@ -1328,8 +2278,9 @@ mod file {
             extension: E,
         ) -> Self {
             Self {
-                path: path.into(),
+                paths: vec![path.into()],
                 extension: extension.into(),
+                aliases: Default::default(),
                 cache: Default::default(),
             }
         }
@@ -1353,6 +2304,157 @@ mod file {
             Default::default()
         }
 
+        /// Add a directory to search for module script files, after the base directory and all
+        /// the directories added before.
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// use rhai::module_resolvers::FileModuleResolver;
+        ///
+        /// let mut resolver = FileModuleResolver::new_with_path("./scripts");
+        ///
+        /// // 'import "utils"' loads './scripts/utils.rhai' if it exists,
+        /// // otherwise './vendor/utils.rhai'
+        /// resolver.add_search_path("./vendor");
+        /// ```
+        pub fn add_search_path<P: Into<PathBuf>>(&mut self, path: P) {
+            self.paths.push(path.into());
+        }
+
+        /// Add an alias for a directory.  Module paths starting with the alias followed by `/`
+        /// are resolved relative to that directory.
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// use rhai::module_resolvers::FileModuleResolver;
+        ///
+        /// let mut resolver = FileModuleResolver::new();
+        ///
+        /// // 'import "@shared/utils"' loads './lib/shared/utils.rhai'
+        /// resolver.add_alias("@shared", "./lib/shared");
+        /// ```
+        pub fn add_alias<A: Into<String>, P: Into<PathBuf>>(&mut self, alias: A, path: P) {
+            self.aliases.push((alias.into(), path.into()));
+        }
+
+        /// Set the maximum number of compiled script files to cache, with zero being unlimited
+        /// (the default).  The least recently used script files are removed first.
+        pub fn set_max_cache_size(&mut self, max_size: usize) {
+            let mut cache = self.cache();
+            cache.max_size = max_size;
+            cache.evict();
+        }
+
+        /// The maximum number of compiled script files to cache, zero if unlimited.
+        pub fn max_cache_size(&self) -> usize {
+            self.cache().max_size
+        }
+
+        /// Remove all compiled script files from the cache.
+        pub fn clear_cache(&self) {
+            self.cache().entries.clear();
+        }
+
+        /// Remove a compiled script file from the cache, so it is reloaded the next time it is
+        /// imported.  Returns `true` if the script file was cached.
+        pub fn invalidate<P: AsRef<Path>>(&self, path: P) -> bool {
+            let path = path.as_ref();
+            let path = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+            self.cache().entries.remove(&path).is_some()
+        }
+
+        /// Register a callback that is called with the path of a cached script file whenever
+        /// it is reloaded because it has changed.
+        pub fn on_reload(&mut self, callback: impl Fn(&Path) + SendSync + 'static) {
+            self.cache().on_reload = Some(Shared::new(Box::new(callback)));
+        }
+
+        /// Check all cached script files for changes, and recompile those that have changed.
+        /// Script files that can no longer be loaded are removed from the cache.
+        ///
+        /// If any script file has changed, all the shared modules of the `Engine` are discarded
+        /// (see `Engine::set_share_modules`), as they may hold on to modules evaluated from the
+        /// old script file.
+        ///
+        /// Returns the paths of all the script files that have changed.
+        ///
+        /// Call this periodically (e.g. from a timer), or use `watch` to call it in the background,
+        /// to pick up changes before a module is next imported.  When modules are shared, this is
+        /// the only way to pick up changes, because shared modules are not imported again.
+        pub fn reload_changed(&self, engine: &Engine) -> Vec<PathBuf> {
+            let stale: Vec<_> = self
+                .cache()
+                .entries
+                .iter()
+                .filter(|(path, entry)| {
+                    entry.modified.is_none() || entry.modified != modified_time(path)
+                })
+                .map(|(path, _)| path.clone())
+                .collect();
+
+            let changed: Vec<_> = stale
+                .into_iter()
+                .filter(|path| {
+                    self.load(engine, path)
+                        .map_or(true, |(_, reloaded)| reloaded)
+                })
+                .collect();
+
+            if !changed.is_empty() {
+                engine.clear_shared_modules();
+            }
+
+            changed
+        }
+
+        /// Watch the cached script files for changes, by calling `reload_changed` with the `Engine`
+        /// every `interval` on a background thread.  Use `on_reload` to be told of the script files
+        /// reloaded - the callback is called on the background thread.
+        ///
+        /// Watching stops when the returned `FileWatcher` is dropped.
+        ///
+        /// Only available under the `sync` feature.
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// use rhai::Engine;
+        /// use rhai::module_resolvers::FileModuleResolver;
+        /// use std::sync::Arc;
+        /// use std::time::Duration;
+        ///
+        /// let mut resolver = FileModuleResolver::new_with_path("./scripts");
+        /// resolver.on_reload(|path| println!("Reloaded {}", path.display()));
+        ///
+        /// let mut engine = Engine::new();
+        /// engine.set_module_resolver(Some(resolver.clone()));
+        /// let engine = Arc::new(engine);
+        ///
+        /// let watcher = resolver.watch(engine.clone(), Duration::from_secs(1));
+        ///
+        /// // ... evaluate scripts with 'engine' ...
+        ///
+        /// drop(watcher);      // stop watching
+        /// ```
+        #[cfg(feature = "sync")]
+        pub fn watch(&self, engine: Shared<Engine>, interval: Duration) -> FileWatcher {
+            let resolver = self.clone();
+            let (stop, stopped) = mpsc::channel::<()>();
+
+            let thread = thread::spawn(move || {
+                while let Err(RecvTimeoutError::Timeout) = stopped.recv_timeout(interval) {
+                    resolver.reload_changed(&engine);
+                }
+            });
+
+            FileWatcher {
+                stop: Some(stop),
+                thread: Some(thread),
+            }
+        }
+
         /// Create a `Module` from a file path.
         pub fn create_module<P: Into<PathBuf>>(
             &self,
@@ -1361,6 +2463,138 @@ mod file {
         ) -> Result<Module, Box<EvalAltResult>> {
             self.resolve(engine, path, Default::default())
         }
+
+        /// Lock the cache.
+        #[cfg(not(feature = "sync"))]
+        fn cache(&self) -> RefMut<'_, FileCache> {
+            self.cache.borrow_mut()
+        }
+
+        /// Lock the cache.
+        #[cfg(feature = "sync")]
+        fn cache(&self) -> RwLockWriteGuard<'_, FileCache> {
+            self.cache.write().unwrap()
+        }
+
+        /// Load a script file from the cache, recompiling it if it has changed.
+        ///
+        /// Also returns `true` if the script file was cached but has changed.
+        fn load(&self, engine: &Engine, path: &Path) -> Result<(AST, bool), Box<EvalAltResult>> {
+            let modified = modified_time(path);
+
+            // See if it is cached and not modified
+            {
+                let mut cache = self.cache();
+                cache.ticks += 1;
+                let tick = cache.ticks;
+
+                if let Some(entry) = cache.entries.get_mut(path) {
+                    if entry.modified.is_some() && entry.modified == modified {
+                        entry.last_used = tick;
+                        return Ok((entry.ast.clone(), false));
+                    }
+                }
+            }
+
+            // Load the file and compile it if its contents have changed
+            let result = Engine::read_file(path.to_path_buf()).and_then(|source| {
+                let mut hasher = DefaultHasher::new();
+                source.hash(&mut hasher);
+                let hash = hasher.finish();
+
+                {
+                    let mut cache = self.cache();
+                    let tick = cache.ticks;
+
+                    if let Some(entry) = cache.entries.get_mut(path) {
+                        if entry.hash == hash {
+                            entry.modified = modified;
+                            entry.last_used = tick;
+                            return Ok((entry.ast.clone(), false));
+                        }
+                    }
+                }
+
+                let ast = engine.compile(&source)?;
+
+                // Put it into the cache
+                let mut cache = self.cache();
+                let entry = CacheEntry {
+                    ast: ast.clone(),
+                    modified,
+                    hash,
+                    last_used: cache.ticks,
+                };
+                let reloaded = cache.entries.insert(path.to_path_buf(), entry).is_some();
+                cache.evict();
+
+                Ok((ast, reloaded))
+            });
+
+            let reloaded = match &result {
+                Ok((_, reloaded)) => *reloaded,
+                Err(_) => self.cache().entries.remove(path).is_some(),
+            };
+
+            if reloaded {
+                // Do not hold the lock during the callback
+                let callback = self.cache().on_reload.clone();
+
+                if let Some(callback) = callback {
+                    callback(path);
+                }
+            }
+
+            result
+        }
+
+        /// Find the script file for a module path.
+        ///
+        /// If no script file exists, the first place searched is returned.
+        fn find_file(&self, context: &ImportContext, path: &str) -> PathBuf {
+            let bases: Vec<PathBuf> = if path.starts_with("./") || path.starts_with("../") {
+                // Relative to the importing script file
+                let dir = context
+                    .importer()
+                    .and_then(|file| Path::new(file).parent())
+                    .map(Path::to_path_buf);
+
+                match dir {
+                    Some(dir) => vec![dir.join(path)],
+                    None => self.paths.iter().map(|p| p.join(path)).collect(),
+                }
+            } else if let Some((alias, dir)) = self
+                .aliases
+                .iter()
+                .filter(|(alias, _)| {
+                    path.starts_with(alias.as_str()) && path[alias.len()..].starts_with('/')
+                })
+                .max_by_key(|(alias, _)| alias.len())
+            {
+                vec![dir.join(&path[alias.len() + 1..])]
+            } else {
+                self.paths.iter().map(|p| p.join(path)).collect()
+            };
+
+            let candidates: Vec<PathBuf> = bases
+                .iter()
+                .flat_map(|base| {
+                    let mut file_path = base.clone();
+                    file_path.set_extension(&self.extension); // Force extension
+
+                    let mut index_path = base.join("index");
+                    index_path.set_extension(&self.extension);
+
+                    vec![file_path, index_path]
+                })
+                .collect();
+
+            candidates
+                .iter()
+                .find(|p| p.is_file())
+                .unwrap_or(&candidates[0])
+                .clone()
+        }
     }
 
     impl ModuleResolver for FileModuleResolver {
@@ -1369,51 +2603,291 @@ mod file {
             engine: &Engine,
             path: &str,
             pos: Position,
+        ) -> Result<Module, Box<EvalAltResult>> {
+            self.resolve_import(engine, &ImportContext::new(), path, pos)
+        }
+        fn resolve_import(
+            &self,
+            engine: &Engine,
+            context: &ImportContext,
+            path: &str,
+            pos: Position,
         ) -> Result<Module, Box<EvalAltResult>> {
             // Construct the script file path
-            let mut file_path = self.path.clone();
-            file_path.push(path);
-            file_path.set_extension(&self.extension); // Force extension
+            let file_path = self.find_file(context, path);
+            let file_path = file_path.canonicalize().unwrap_or(file_path);
+            let key = file_path.to_string_lossy().into_owned();
+
+            // Guard against circular imports
+            context.check_circular(&key, pos)?;
+
+            let (ast, _) = self
+                .load(engine, &file_path)
+                .map_err(|err| err.new_position(pos))?;
+
+            // Evaluate the module, with relative imports inside it based off its own directory
+            Module::eval_ast_as_import(Default::default(), &ast, engine, context, key)
+                .map_err(|err| err.new_position(pos))
+        }
+        fn module_key(&self, context: &ImportContext, path: &str) -> String {
+            let file_path = self.find_file(context, path);
+            let file_path = file_path.canonicalize().unwrap_or(file_path);
+            file_path.to_string_lossy().into_owned()
+        }
+    }
+}
+
+/// Module resolver for script sources bundled in memory or in a zip archive.
+#[cfg(not(feature = "no_module"))]
+#[cfg(not(feature = "no_std"))]
+mod archive {
+    use super::*;
+
+    #[cfg(feature = "zip")]
+    use crate::stdlib::io::{self, Read, Seek};
+
+    /// Normalize a `/`-separated path, removing all `.` and `..` segments.
+    fn normalize_path(path: &str) -> String {
+        let mut segments: Vec<&str> = Vec::new();
 
-            let scope = Default::default();
+        for segment in path.split('/') {
+            match segment {
+                "" | "." => (),
+                ".." => {
+                    segments.pop();
+                }
+                s => segments.push(s),
+            }
+        }
+
+        segments.join("/")
+    }
+
+    /// Module resolution service that serves modules from script sources bundled in memory,
+    /// e.g. embedded into the binary via `include_str!`, or loaded from a zip archive.
+    ///
+    /// Scripts are compiled the first time they are imported, and cached so they are not
+    /// recompiled in subsequent requests.
+    ///
+    /// Script paths inside the bundle are separated by `/`.  Module paths are resolved as follows:
+    ///
+    /// * A path starting with `./` or `../` is relative to the directory of the script containing
+    ///   the `import` statement, or to the root of the bundle if there is none.
+    /// * Any other path is relative to the root of the bundle.
+    ///
+    /// The script file extension (default `.rhai`) is added to the module path.  A path that points
+    /// to a directory loads the `index` script inside it.
+    ///
+    /// Not available under the `no_std` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<rhai::EvalAltResult>> {
+    /// use rhai::Engine;
+    /// use rhai::module_resolvers::ArchiveModuleResolver;
+    ///
+    /// let mut resolver = ArchiveModuleResolver::new();
+    /// resolver.insert("lib/index.rhai", r#"import "./util" as util; let x = util::y + 1; export x;"#);
+    /// resolver.insert("lib/util.rhai", "let y = 41; export y;");
+    ///
+    /// let mut engine = Engine::new();
+    /// engine.set_module_resolver(Some(resolver));
+    ///
+    /// assert_eq!(engine.eval::<i64>(r#"import "lib" as lib; lib::x"#)?, 42);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[derive(Debug)]
+    pub struct ArchiveModuleResolver {
+        sources: HashMap<String, String>,
+        extension: String,
+
+        #[cfg(not(feature = "sync"))]
+        cache: RefCell<HashMap<String, AST>>,
+
+        #[cfg(feature = "sync")]
+        cache: RwLock<HashMap<String, AST>>,
+    }
+
+    impl Default for ArchiveModuleResolver {
+        fn default() -> Self {
+            Self::new_with_extension("rhai")
+        }
+    }
+
+    impl ArchiveModuleResolver {
+        /// Create a new, empty `ArchiveModuleResolver` with script file extension `.rhai`.
+        pub fn new() -> Self {
+            Default::default()
+        }
+
+        /// Create a new, empty `ArchiveModuleResolver` with a specific script file extension.
+        pub fn new_with_extension<E: Into<String>>(extension: E) -> Self {
+            Self {
+                sources: Default::default(),
+                extension: extension.into(),
+                cache: Default::default(),
+            }
+        }
+
+        /// Create a new `ArchiveModuleResolver` with all the script files (with file extension
+        /// `.rhai`) inside a zip archive.
+        ///
+        /// The whole archive is read at once, but scripts are only compiled when imported.
+        ///
+        /// Requires the `zip` feature.
+        #[cfg(feature = "zip")]
+        pub fn from_zip<R: Read + Seek>(reader: R) -> io::Result<Self> {
+            Self::from_zip_with_extension(reader, "rhai")
+        }
+
+        /// Create a new `ArchiveModuleResolver` with all the script files (with a specific file
+        /// extension) inside a zip archive.
+        ///
+        /// The whole archive is read at once, but scripts are only compiled when imported.
+        ///
+        /// Requires the `zip` feature.
+        #[cfg(feature = "zip")]
+        pub fn from_zip_with_extension<R: Read + Seek, E: Into<String>>(
+            reader: R,
+            extension: E,
+        ) -> io::Result<Self> {
+            let mut resolver = Self::new_with_extension(extension);
+            let suffix = format!(".{}", resolver.extension);
+
+            let mut archive = zip::ZipArchive::new(reader)?;
+
+            for index in 0..archive.len() {
+                let mut file = archive.by_index(index)?;
+
+                if !file.is_file() || !file.name().ends_with(&suffix) {
+                    continue;
+                }
+
+                let path = file.name().to_string();
+                let mut source = String::new();
+                file.read_to_string(&mut source)?;
+
+                resolver.insert(path, source);
+            }
+
+            Ok(resolver)
+        }
+
+        /// Add a script source into the bundle.  The path should include the file extension.
+        pub fn insert<P: AsRef<str>, S: Into<String>>(&mut self, path: P, source: S) {
+            let path = normalize_path(path.as_ref());
+
+            #[cfg(not(feature = "sync"))]
+            self.cache.get_mut().remove(&path);
+            #[cfg(feature = "sync")]
+            self.cache.get_mut().unwrap().remove(&path);
+
+            self.sources.insert(path, source.into());
+        }
+
+        /// Does the bundle contain a script source at a path?
+        pub fn contains_path(&self, path: &str) -> bool {
+            self.sources.contains_key(&normalize_path(path))
+        }
+
+        /// Get an iterator of all the paths of script sources in the bundle.
+        pub fn paths(&self) -> impl Iterator<Item = &str> {
+            self.sources.keys().map(String::as_str)
+        }
+
+        /// Find the script source for a module path.
+        fn find_source(&self, context: &ImportContext, path: &str) -> Option<String> {
+            let path = if path.starts_with("./") || path.starts_with("../") {
+                // Relative to the importing script
+                let dir = context
+                    .importer()
+                    .and_then(|p| p.rfind('/').map(|i| &p[..i]))
+                    .unwrap_or_default();
+
+                normalize_path(&format!("{}/{}", dir, path))
+            } else {
+                normalize_path(path)
+            };
+
+            let file_path = if path.ends_with(&format!(".{}", self.extension)) {
+                path.clone()
+            } else {
+                format!("{}.{}", path, self.extension)
+            };
+            let index_path = normalize_path(&format!("{}/index.{}", path, self.extension));
+
+            Some(file_path)
+                .into_iter()
+                .chain(Some(index_path))
+                .find(|p| self.sources.contains_key(p))
+        }
+    }
+
+    impl ModuleResolver for ArchiveModuleResolver {
+        fn resolve(
+            &self,
+            engine: &Engine,
+            path: &str,
+            pos: Position,
+        ) -> Result<Module, Box<EvalAltResult>> {
+            self.resolve_import(engine, &ImportContext::new(), path, pos)
+        }
+        fn resolve_import(
+            &self,
+            engine: &Engine,
+            context: &ImportContext,
+            path: &str,
+            pos: Position,
+        ) -> Result<Module, Box<EvalAltResult>> {
+            let source_path = self
+                .find_source(context, path)
+                .ok_or_else(|| EvalAltResult::ErrorModuleNotFound(path.to_string(), pos))?;
+
+            // Guard against circular imports
+            context.check_circular(&source_path, pos)?;
 
             // See if it is cached
-            let (module, ast) = {
+            let ast = {
                 #[cfg(not(feature = "sync"))]
                 let c = self.cache.borrow();
                 #[cfg(feature = "sync")]
                 let c = self.cache.read().unwrap();
 
-                match c.get(&file_path) {
-                    Some(ast) => (
-                        Module::eval_ast_as_new(scope, ast, engine)
-                            .map_err(|err| err.new_position(pos))?,
-                        None,
-                    ),
-                    None => {
-                        // Load the file and compile it if not found
-                        let ast = engine
-                            .compile_file(file_path.clone())
-                            .map_err(|err| err.new_position(pos))?;
-
-                        (
-                            Module::eval_ast_as_new(scope, &ast, engine)
-                                .map_err(|err| err.new_position(pos))?,
-                            Some(ast),
-                        )
-                    }
-                }
+                c.get(&source_path).cloned()
             };
 
-            if let Some(ast) = ast {
-                // Put it into the cache
-                #[cfg(not(feature = "sync"))]
-                self.cache.borrow_mut().insert(file_path, ast);
-                #[cfg(feature = "sync")]
-                self.cache.write().unwrap().insert(file_path, ast);
-            }
+            let ast = match ast {
+                Some(ast) => ast,
+                None => {
+                    // Compile the script if not found
+                    let ast = engine
+                        .compile(&self.sources[&source_path])
+                        .map_err(|err| Box::<EvalAltResult>::from(err).new_position(pos))?;
+
+                    // Put it into the cache
+                    #[cfg(not(feature = "sync"))]
+                    self.cache
+                        .borrow_mut()
+                        .insert(source_path.clone(), ast.clone());
+                    #[cfg(feature = "sync")]
+                    self.cache
+                        .write()
+                        .unwrap()
+                        .insert(source_path.clone(), ast.clone());
+
+                    ast
+                }
+            };
 
-            Ok(module)
+            // Evaluate the module, with relative imports inside it based off its own directory
+            Module::eval_ast_as_import(Default::default(), &ast, engine, context, source_path)
+                .map_err(|err| err.new_position(pos))
+        }
+        fn module_key(&self, context: &ImportContext, path: &str) -> String {
+            self.find_source(context, path)
+                .unwrap_or_else(|| path.into())
         }
     }
 }
@@ -1596,5 +3070,29 @@ mod collection {
 
             EvalAltResult::ErrorModuleNotFound(path.into(), pos).into()
         }
+
+        fn resolve_import(
+            &self,
+            engine: &Engine,
+            context: &ImportContext,
+            path: &str,
+            pos: Position,
+        ) -> Result<Module, Box<EvalAltResult>> {
+            for resolver in self.0.iter() {
+                if let Ok(module) = resolver.resolve_import(engine, context, path, pos) {
+                    return Ok(module);
+                }
+            }
+
+            EvalAltResult::ErrorModuleNotFound(path.into(), pos).into()
+        }
+
+        fn module_key(&self, context: &ImportContext, path: &str) -> String {
+            self.0
+                .iter()
+                .map(|resolver| resolver.module_key(context, path))
+                .collect::<Vec<_>>()
+                .join("\n")
+        }
     }
 }
@@ -7,7 +7,7 @@ use crate::engine::{
 };
 use crate::fn_native::FnPtr;
 use crate::module::Module;
-use crate::parser::{map_dynamic_to_expr, Expr, ReturnType, ScriptFnDef, Stmt, AST};
+use crate::parser::{map_dynamic_to_expr, Expr, Pattern, ReturnType, ScriptFnDef, Stmt, AST};
 use crate::scope::{Entry as ScopeEntry, EntryType as ScopeEntryType, Scope};
 use crate::utils::StaticVec;
 
@@ -240,7 +240,7 @@ fn optimize_stmt(stmt: Stmt, state: &mut State, preserve_result: bool) -> Stmt {
         },
         // for id in expr { block }
         Stmt::For(x) => Stmt::For(Box::new((
-            x.0,
+            optimize_pattern(x.0, state),
             optimize_expr(x.1, state),
             optimize_stmt(x.2, state, false),
             x.3,
@@ -253,6 +253,12 @@ fn optimize_stmt(stmt: Stmt, state: &mut State, preserve_result: bool) -> Stmt {
         ))),
         // let id;
         stmt @ Stmt::Let(_) => stmt,
+        // let pattern = expr;
+        Stmt::LetPattern(x) => Stmt::LetPattern(Box::new((
+            optimize_pattern(x.0, state),
+            optimize_expr(x.1, state),
+            x.2,
+        ))),
         // import expr as id;
         #[cfg(not(feature = "no_module"))]
         Stmt::Import(x) => Stmt::Import(Box::new((optimize_expr(x.0, state), x.1, x.2))),
@@ -353,7 +359,9 @@ fn optimize_stmt(stmt: Stmt, state: &mut State, preserve_result: bool) -> Stmt {
                     Stmt::Noop(pos)
                 }
                 // Only one let statement - leave it alone
-                [Stmt::Let(_)] => Stmt::Block(Box::new((result.into(), pos))),
+                [Stmt::Let(_)] | [Stmt::LetPattern(_)] => {
+                    Stmt::Block(Box::new((result.into(), pos)))
+                }
                 // Only one import statement - leave it alone
                 #[cfg(not(feature = "no_module"))]
                 [Stmt::Import(_)] => Stmt::Block(Box::new((result.into(), pos))),
@@ -378,6 +386,42 @@ fn optimize_stmt(stmt: Stmt, state: &mut State, preserve_result: bool) -> Stmt {
     }
 }
 
+/// Optimize the default values in a destructuring pattern.
+fn optimize_pattern(pattern: Pattern, state: &mut State) -> Pattern {
+    match pattern {
+        Pattern::Variable(_) => pattern,
+        #[cfg(not(feature = "no_index"))]
+        Pattern::Array(x) => {
+            let (items, rest, pos) = *x;
+            let items = items
+                .into_iter()
+                .map(|(p, def)| {
+                    (
+                        optimize_pattern(p, state),
+                        def.map(|expr| optimize_expr(expr, state)),
+                    )
+                })
+                .collect();
+            Pattern::Array(Box::new((items, rest, pos)))
+        }
+        #[cfg(not(feature = "no_object"))]
+        Pattern::Map(x) => {
+            let (items, rest, pos) = *x;
+            let items = items
+                .into_iter()
+                .map(|(key, p, def)| {
+                    (
+                        key,
+                        optimize_pattern(p, state),
+                        def.map(|expr| optimize_expr(expr, state)),
+                    )
+                })
+                .collect();
+            Pattern::Map(Box::new((items, rest, pos)))
+        }
+    }
+}
+
 /// Optimize an expression.
 fn optimize_expr(expr: Expr, state: &mut State) -> Expr {
     // These keywords are handled specially
@@ -419,7 +463,7 @@ fn optimize_expr(expr: Expr, state: &mut State) -> Expr {
                     .unwrap_or_else(|| Expr::Unit(pos))
             }
             // lhs.rhs
-            (lhs, rhs) => Expr::Dot(Box::new((optimize_expr(lhs, state), optimize_expr(rhs, state), x.2)))
+            (lhs, rhs) => Expr::Dot(Box::new((optimize_expr(lhs, state), optimize_expr(rhs, state), x.2, x.3)))
         }
 
         // lhs[rhs]
@@ -453,7 +497,7 @@ fn optimize_expr(expr: Expr, state: &mut State) -> Expr {
                 Expr::CharConstant(Box::new((s.0.chars().nth(i.0 as usize).unwrap(), s.1)))
             }
             // lhs[rhs]
-            (lhs, rhs) => Expr::Index(Box::new((optimize_expr(lhs, state), optimize_expr(rhs, state), x.2))),
+            (lhs, rhs) => Expr::Index(Box::new((optimize_expr(lhs, state), optimize_expr(rhs, state), x.2, x.3))),
         },
         // [ items .. ]
         #[cfg(not(feature = "no_index"))]
@@ -540,6 +584,21 @@ fn optimize_expr(expr: Expr, state: &mut State) -> Expr {
             // lhs || rhs
             (lhs, rhs) => Expr::Or(Box::new((optimize_expr(lhs, state), optimize_expr(rhs, state), x.2))),
         },
+        // lhs ?? rhs
+        Expr::Coalesce(x) => match (x.0, x.1) {
+            // () ?? rhs -> rhs
+            (Expr::Unit(_), rhs) => {
+                state.set_dirty();
+                optimize_expr(rhs, state)
+            }
+            // constant ?? rhs -> constant
+            (lhs, _) if lhs.is_constant() => {
+                state.set_dirty();
+                lhs
+            }
+            // lhs ?? rhs
+            (lhs, rhs) => Expr::Coalesce(Box::new((optimize_expr(lhs, state), optimize_expr(rhs, state), x.2))),
+        },
 
         // Do not call some special keywords
         Expr::FnCall(mut x) if DONT_EVAL_KEYWORDS.contains(&(x.0).0.as_ref())=> {
@@ -571,15 +630,17 @@ fn optimize_expr(expr: Expr, state: &mut State) -> Expr {
                 && state.optimization_level == OptimizationLevel::Full // full optimizations
                 && x.3.iter().all(|expr| expr.is_constant()) // all arguments are constants
         => {
-            let ((name, _, _, pos), _, _, args, def_value) = x.as_mut();
+            let ((name, _, _, _, pos), _, _, args, def_value) = x.as_mut();
 
             // First search in functions lib (can override built-in)
             // Cater for both normal function call style and method call style (one additional arguments)
             let has_script_fn = cfg!(not(feature = "no_function")) && state.lib.iter_fn().find(|(_, _, _, f)| {
                 if !f.is_script() { return false; }
                 let fn_def = f.get_fn_def();
-                fn_def.name == name && (args.len()..=args.len() + 1).contains(&fn_def.params.len())
-            }).is_some();
+                fn_def.name == name
+                    && fn_def.num_required_params() <= args.len() + 1
+                    && args.len() <= fn_def.params.len()
+            }).is_some() || state.lib.contains_variadic_fn(name);
 
             if has_script_fn {
                 // A script-defined function overrides the built-in function - do not make the call
@@ -625,6 +686,13 @@ fn optimize_expr(expr: Expr, state: &mut State) -> Expr {
             Expr::FnCall(x)
         }
 
+        // func(expr, ..., name: expr, ...)
+        Expr::FnCallNamed(mut x) => {
+            x.1 = x.1.into_iter().map(|a| optimize_expr(a, state)).collect();
+            x.2 = x.2.into_iter().map(|(name, a)| (name, optimize_expr(a, state))).collect();
+            Expr::FnCallNamed(x)
+        }
+
         // constant-name
         Expr::Variable(x) if x.1.is_none() && state.contains_constant(&(x.0).0) => {
             let (name, pos) = x.0;
@@ -636,6 +704,9 @@ fn optimize_expr(expr: Expr, state: &mut State) -> Expr {
             expr
         }
 
+        // yield expr
+        Expr::Yield(x) => Expr::Yield(Box::new((optimize_expr(x.0, state), x.1))),
+
         // Custom syntax
         #[cfg(feature = "internals")]
         Expr::Custom(x) => Expr::Custom(Box::new((
@@ -703,7 +774,7 @@ fn optimize(
                         // Keep all variable declarations at this level
                         // and always keep the last return value
                         let keep = match stmt {
-                            Stmt::Let(_) => true,
+                            Stmt::Let(_) | Stmt::LetPattern(_) => true,
                             #[cfg(not(feature = "no_module"))]
                             Stmt::Import(_) => true,
                             _ => i == num_statements - 1,
@@ -764,8 +835,12 @@ pub fn optimize_into_ast(
                         access: fn_def.access,
                         body: Default::default(),
                         params: fn_def.params.clone(),
+                        param_types: fn_def.param_types.clone(),
+                        defaults: fn_def.defaults.clone(),
+                        rest_param: fn_def.rest_param.clone(),
                         #[cfg(not(feature = "no_closure"))]
                         externals: fn_def.externals.clone(),
+                        is_generator: fn_def.is_generator,
                         pos: fn_def.pos,
                     }
                     .into()
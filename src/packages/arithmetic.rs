@@ -7,6 +7,19 @@ use crate::{result::EvalAltResult, token::Position};
 #[cfg(not(feature = "no_float"))]
 use crate::parser::FLOAT;
 
+#[cfg(feature = "decimal")]
+use rust_decimal::Decimal;
+
+#[cfg(feature = "decimal")]
+#[cfg(not(feature = "no_float"))]
+use rust_decimal::prelude::FromPrimitive;
+
+#[cfg(feature = "bigint")]
+use num_bigint::{BigInt, Sign};
+
+#[cfg(feature = "bigint")]
+use num_traits::{Signed, ToPrimitive};
+
 use num_traits::{
     identities::Zero, CheckedAdd, CheckedDiv, CheckedMul, CheckedNeg, CheckedRem, CheckedShl,
     CheckedShr, CheckedSub,
@@ -267,6 +280,66 @@ pub fn pow_f_i_u(x: FLOAT, y: INT) -> FuncReturn<FLOAT> {
     Ok(x.powi(y as i32))
 }
 
+// Convert a floating-point number into a decimal number
+#[cfg(feature = "decimal")]
+#[cfg(not(feature = "no_float"))]
+pub fn float_to_decimal(x: FLOAT) -> FuncReturn<Decimal> {
+    Decimal::from_f64(x).ok_or_else(|| {
+        EvalAltResult::ErrorArithmetic(
+            format!("Cannot convert to decimal: {}", x),
+            Position::none(),
+        )
+        .into()
+    })
+}
+
+// Convert a big integer into an integer, if it fits
+#[cfg(feature = "bigint")]
+pub fn bigint_to_int(x: &BigInt) -> Option<INT> {
+    #[cfg(not(feature = "only_i32"))]
+    return x.to_i64();
+    #[cfg(feature = "only_i32")]
+    return x.to_i32();
+}
+// Big integer arithmetic and bit operators - cannot overflow, but dividing by zero,
+// negative powers and shifts, and overly large powers and shifts are still errors
+#[cfg(feature = "bigint")]
+pub fn bigint_op(op: &str, x: &BigInt, y: &BigInt) -> FuncReturn<Option<BigInt>> {
+    let error = |msg: &str| -> FuncReturn<_> {
+        EvalAltResult::ErrorArithmetic(format!("{}: {} {} {}", msg, x, op, y), Position::none())
+            .into()
+    };
+
+    Ok(Some(match op {
+        "+" => x + y,
+        "-" => x - y,
+        "*" => x * y,
+        "/" if y.is_zero() => return error("Division by zero"),
+        "/" => x / y,
+        "%" if y.is_zero() => return error("Modulo division by zero"),
+        "%" => x % y,
+        "~" if y.is_negative() => return error("Integer raised to a negative index"),
+        "~" => match y.to_u32() {
+            Some(y) => x.pow(y),
+            None => return error("Integer raised to too large an index"),
+        },
+        "<<" if y.is_negative() => return error("Left-shift by a negative number"),
+        "<<" => match y.to_u32() {
+            Some(y) => x << y,
+            None => return error("Left-shift by too many bits"),
+        },
+        ">>" if y.is_negative() => return error("Right-shift by a negative number"),
+        ">>" => match y.to_u32() {
+            Some(y) => x >> y,
+            None => return error("Right-shift by too many bits"),
+        },
+        "&" => x & y,
+        "|" => x | y,
+        "^" => x ^ y,
+        _ => return Ok(None),
+    }))
+}
+
 macro_rules! reg_unary {
     ($lib:expr, $op:expr, $func:ident, $($par:ty),*) => {
         $( $lib.set_fn_1($op, $func::<$par>); )*
@@ -277,6 +350,19 @@ macro_rules! reg_op {
         $( $lib.set_fn_2($op, $func::<$par>); )*
     };
 }
+#[cfg(feature = "decimal")]
+macro_rules! reg_decimal_op {
+    ($lib:expr, $op:expr, $func:ident) => {
+        $lib.set_fn_2($op, |x: Decimal, y: INT| $func(x, Decimal::from(y)));
+        $lib.set_fn_2($op, |x: INT, y: Decimal| $func(Decimal::from(x), y));
+
+        #[cfg(not(feature = "no_float"))]
+        {
+            $lib.set_fn_2($op, |x: Decimal, y: FLOAT| $func(x, float_to_decimal(y)?));
+            $lib.set_fn_2($op, |x: FLOAT, y: Decimal| $func(float_to_decimal(x)?, y));
+        }
+    };
+}
 macro_rules! reg_sign {
     ($lib:expr, $op:expr, $ret:ty, $($par:ty),*) => {
         $( $lib.set_fn_1($op, |value: $par| -> Result<$ret, _> {
@@ -385,6 +471,54 @@ def_package!(crate:ArithmeticPackage:"Basic arithmetic", lib, {
         reg_unary!(lib, "abs", abs_u, f32, f64);
     }
 
+    // Decimal arithmetic with integers and floating-point numbers, converted to decimal
+    #[cfg(feature = "decimal")]
+    {
+        if cfg!(not(feature = "unchecked")) {
+            reg_decimal_op!(lib, "+", add);
+            reg_decimal_op!(lib, "-", sub);
+            reg_decimal_op!(lib, "*", mul);
+            reg_decimal_op!(lib, "/", div);
+            reg_decimal_op!(lib, "%", modulo);
+        } else {
+            reg_decimal_op!(lib, "+", add_u);
+            reg_decimal_op!(lib, "-", sub_u);
+            reg_decimal_op!(lib, "*", mul_u);
+            reg_decimal_op!(lib, "/", div_u);
+            reg_decimal_op!(lib, "%", modulo_u);
+        }
+    }
+
+    // Decimal unary - no need to check
+    #[cfg(feature = "decimal")]
+    {
+        reg_unary!(lib, "-", neg_u, Decimal);
+        reg_unary!(lib, "abs", abs_u, Decimal);
+        lib.set_fn_1("sign", |x: Decimal| {
+            Ok(if x.is_zero() {
+                0
+            } else if x.is_sign_negative() {
+                -1
+            } else {
+                1
+            } as INT)
+        });
+    }
+
+    // Big integer unary - cannot overflow
+    #[cfg(feature = "bigint")]
+    {
+        reg_unary!(lib, "-", neg_u, BigInt);
+        reg_unary!(lib, "abs", abs_u, BigInt);
+        lib.set_fn_1("sign", |x: BigInt| {
+            Ok(match x.sign() {
+                Sign::Minus => -1,
+                Sign::NoSign => 0,
+                Sign::Plus => 1,
+            } as INT)
+        });
+    }
+
     // Checked unary
     if cfg!(not(feature = "unchecked")) {
         reg_unary!(lib, "-", neg, INT);
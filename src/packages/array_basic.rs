@@ -2,9 +2,9 @@
 
 use crate::any::{Dynamic, Variant};
 use crate::def_package;
-use crate::engine::{Array, Engine};
-use crate::fn_native::FnPtr;
-use crate::module::{FuncReturn, Module};
+use crate::engine::Array;
+use crate::fn_native::{FnPtr, NativeCallContext};
+use crate::module::FuncReturn;
 use crate::parser::{ImmutableString, INT};
 
 #[cfg(not(feature = "unchecked"))]
@@ -12,6 +12,12 @@ use crate::{result::EvalAltResult, token::Position};
 
 use crate::stdlib::{any::TypeId, boxed::Box};
 
+#[cfg(feature = "decimal")]
+use rust_decimal::Decimal;
+
+#[cfg(feature = "bigint")]
+use num_bigint::BigInt;
+
 #[cfg(not(feature = "unchecked"))]
 use crate::stdlib::string::ToString;
 
@@ -31,21 +37,20 @@ fn ins<T: Variant + Clone>(list: &mut Array, position: INT, item: T) -> FuncRetu
     Ok(())
 }
 fn pad<T: Variant + Clone>(
-    _engine: &Engine,
-    _: &Module,
+    _context: NativeCallContext,
     args: &mut [&mut Dynamic],
 ) -> FuncReturn<()> {
     let len = *args[1].read_lock::<INT>().unwrap();
 
     // Check if array will be over max size limit
     #[cfg(not(feature = "unchecked"))]
-    if _engine.limits.max_array_size > 0
+    if _context.engine().limits.max_array_size > 0
         && len > 0
-        && (len as usize) > _engine.limits.max_array_size
+        && (len as usize) > _context.engine().limits.max_array_size
     {
         return EvalAltResult::ErrorDataTooLarge(
             "Size of array".to_string(),
-            _engine.limits.max_array_size,
+            _context.engine().limits.max_array_size,
             len as usize,
             Position::none(),
         )
@@ -124,6 +129,20 @@ def_package!(crate:BasicArrayPackage:"Basic array utilities.", lib, {
         reg_tri!(lib, "insert", ins, f32, f64);
     }
 
+    #[cfg(feature = "decimal")]
+    {
+        reg_op!(lib, "push", push, Decimal);
+        reg_pad!(lib, "pad", pad, Decimal);
+        reg_tri!(lib, "insert", ins, Decimal);
+    }
+
+    #[cfg(feature = "bigint")]
+    {
+        reg_op!(lib, "push", push, BigInt);
+        reg_pad!(lib, "pad", pad, BigInt);
+        reg_tri!(lib, "insert", ins, BigInt);
+    }
+
     lib.set_fn_1_mut(
         "pop",
         |list: &mut Array| Ok(list.pop().unwrap_or_else(|| ().into())),
@@ -1,6 +1,23 @@
 use crate::def_package;
 use crate::module::FuncReturn;
 
+#[cfg(feature = "decimal")]
+use crate::parser::INT;
+
+#[cfg(feature = "decimal")]
+#[cfg(not(feature = "no_float"))]
+use crate::parser::FLOAT;
+
+#[cfg(feature = "decimal")]
+use rust_decimal::Decimal;
+
+#[cfg(feature = "decimal")]
+#[cfg(not(feature = "no_float"))]
+use rust_decimal::prelude::FromPrimitive;
+
+#[cfg(feature = "decimal")]
+use crate::stdlib::cmp::Ordering;
+
 // Comparison operators
 pub fn lt<T: PartialOrd>(x: T, y: T) -> FuncReturn<bool> {
     Ok(x < y)
@@ -21,6 +38,22 @@ pub fn ne<T: PartialEq>(x: T, y: T) -> FuncReturn<bool> {
     Ok(x != y)
 }
 
+// Compare a decimal number with a floating-point number - NaN is unordered
+#[cfg(feature = "decimal")]
+#[cfg(not(feature = "no_float"))]
+fn cmp_decimal_float(x: Decimal, y: FLOAT) -> Option<Ordering> {
+    if y.is_nan() {
+        None
+    } else if let Some(y) = Decimal::from_f64(y) {
+        Some(x.cmp(&y))
+    } else if y > 0.0 {
+        // Too large for a decimal
+        Some(Ordering::Less)
+    } else {
+        Some(Ordering::Greater)
+    }
+}
+
 // Logic operators
 fn not(x: bool) -> FuncReturn<bool> {
     Ok(!x)
@@ -32,6 +65,28 @@ macro_rules! reg_op {
     };
 }
 
+#[cfg(feature = "decimal")]
+macro_rules! reg_decimal_cmp {
+    ($lib:expr, $op:expr, $test:expr) => {
+        $lib.set_fn_2($op, |x: Decimal, y: INT| {
+            Ok($test(Some(x.cmp(&Decimal::from(y)))))
+        });
+        $lib.set_fn_2($op, |x: INT, y: Decimal| {
+            Ok($test(Some(Decimal::from(x).cmp(&y))))
+        });
+
+        #[cfg(not(feature = "no_float"))]
+        {
+            $lib.set_fn_2($op, |x: Decimal, y: FLOAT| {
+                Ok($test(cmp_decimal_float(x, y)))
+            });
+            $lib.set_fn_2($op, |x: FLOAT, y: Decimal| {
+                Ok($test(cmp_decimal_float(y, x).map(Ordering::reverse)))
+            });
+        }
+    };
+}
+
 def_package!(crate:LogicPackage:"Logical operators.", lib, {
     if cfg!(not(feature = "only_i32")) && cfg!(not(feature = "only_i64")) {
         reg_op!(lib, "<", lt, i8, u8, i16, u16, i32, u32, u64);
@@ -61,5 +116,18 @@ def_package!(crate:LogicPackage:"Logical operators.", lib, {
         reg_op!(lib, "!=", ne, f32);
     }
 
+    // Decimal numbers compared with integers and floating-point numbers
+    #[cfg(feature = "decimal")]
+    {
+        use Ordering::{Equal, Greater, Less};
+
+        reg_decimal_cmp!(lib, "<", |c: Option<Ordering>| c == Some(Less));
+        reg_decimal_cmp!(lib, "<=", |c: Option<Ordering>| matches!(c, Some(Less) | Some(Equal)));
+        reg_decimal_cmp!(lib, ">", |c: Option<Ordering>| c == Some(Greater));
+        reg_decimal_cmp!(lib, ">=", |c: Option<Ordering>| matches!(c, Some(Greater) | Some(Equal)));
+        reg_decimal_cmp!(lib, "==", |c: Option<Ordering>| c == Some(Equal));
+        reg_decimal_cmp!(lib, "!=", |c: Option<Ordering>| c != Some(Equal));
+    }
+
     lib.set_fn_1("!", not);
 });
@@ -1,18 +1,92 @@
 #![cfg(not(feature = "no_object"))]
 
 use crate::any::Dynamic;
+use crate::calc_fn_hash;
 use crate::def_package;
-use crate::engine::Map;
+use crate::engine::{Engine, Map, State};
+use crate::fn_native::NativeCallContext;
 use crate::module::FuncReturn;
 use crate::parser::{ImmutableString, INT};
+use crate::record::Record;
 
-use crate::stdlib::vec::Vec;
+use crate::stdlib::{any::TypeId, iter::empty, vec::Vec};
 
-fn map_get_keys(map: &mut Map) -> FuncReturn<Vec<Dynamic>> {
-    Ok(map.iter().map(|(k, _)| k.clone().into()).collect())
+/// Get the entries of an object map, sorted by key in deterministic mode unless the map preserves
+/// insertion order.
+fn map_entries<'a>(_engine: &Engine, map: &'a Map) -> Vec<(&'a ImmutableString, &'a Dynamic)> {
+    #[cfg(not(feature = "ordered_map"))]
+    let mut entries: Vec<_> = map.iter().collect();
+    #[cfg(feature = "ordered_map")]
+    let entries: Vec<_> = map.iter().collect();
+
+    #[cfg(not(feature = "ordered_map"))]
+    if _engine.is_deterministic() {
+        entries.sort_by_key(|(k, _)| *k);
+    }
+
+    entries
 }
-fn map_get_values(map: &mut Map) -> FuncReturn<Vec<Dynamic>> {
-    Ok(map.iter().map(|(_, v)| v.clone()).collect())
+fn map_get_keys(context: NativeCallContext, args: &mut [&mut Dynamic]) -> FuncReturn<Vec<Dynamic>> {
+    let map = args[0].read_lock::<Map>().unwrap();
+    Ok(map_entries(context.engine(), &map)
+        .into_iter()
+        .map(|(k, _)| k.clone().into())
+        .collect())
+}
+fn map_get_values(
+    context: NativeCallContext,
+    args: &mut [&mut Dynamic],
+) -> FuncReturn<Vec<Dynamic>> {
+    let map = args[0].read_lock::<Map>().unwrap();
+    Ok(map_entries(context.engine(), &map)
+        .into_iter()
+        .map(|(_, v)| v.clone())
+        .collect())
+}
+/// Two records are equal if they are of the same record type and the `==` operator returns
+/// `true` for the values of every field.
+fn record_eq(context: NativeCallContext, args: &mut [&mut Dynamic]) -> FuncReturn<bool> {
+    let x = args[0].read_lock::<Record>().unwrap().clone();
+    let y = args[1].read_lock::<Record>().unwrap().clone();
+
+    if x.type_name() != y.type_name() || x.fields().len() != y.fields().len() {
+        return Ok(false);
+    }
+
+    let mut state = State::new();
+    state.cancel = context.cancel_token().cloned();
+
+    for (name, value) in x.fields() {
+        let other = match y.get(name) {
+            Some(other) => other,
+            None => return Ok(false),
+        };
+
+        let args = &mut [&mut value.clone(), &mut other.clone()];
+
+        // Qualifiers (none) + function name + number of arguments + argument `TypeId`'s.
+        let hash = calc_fn_hash(empty(), "==", args.len(), args.iter().map(|a| a.type_id()));
+
+        let (result, _) = context.engine().call_native_fn(
+            &mut state,
+            context.lib(),
+            "==",
+            hash,
+            args,
+            false,
+            false,
+            Some(false),
+        )?;
+
+        if !result.as_bool().unwrap_or(false) {
+            return Ok(false);
+        }
+    }
+
+    Ok(true)
+}
+fn record_ne(context: NativeCallContext, args: &mut [&mut Dynamic]) -> FuncReturn<bool> {
+    record_eq(context, args).map(|eq| !eq)
 }
 
 def_package!(crate:BasicMapPackage:"Basic object map utilities.", lib, {
@@ -27,7 +101,14 @@ def_package!(crate:BasicMapPackage:"Basic object map utilities.", lib, {
     });
     lib.set_fn_2_mut(
         "remove",
-        |x: &mut Map, name: ImmutableString| Ok(x.remove(&name).unwrap_or_else(|| ().into())),
+        |x: &mut Map, name: ImmutableString| {
+            #[cfg(not(feature = "ordered_map"))]
+            let value = x.remove(&name);
+            #[cfg(feature = "ordered_map")]
+            let value = x.shift_remove(&name);
+
+            Ok(value.unwrap_or_else(|| ().into()))
+        },
     );
     lib.set_fn_2_mut(
         "mixin",
@@ -70,10 +151,14 @@ def_package!(crate:BasicMapPackage:"Basic object map utilities.", lib, {
 
     // Register map access functions
     if cfg!(not(feature = "no_index")) {
-        lib.set_fn_1_mut("keys", map_get_keys);
+        lib.set_raw_fn("keys", &[TypeId::of::<Map>()], map_get_keys);
     }
 
     if cfg!(not(feature = "no_index")) {
-        lib.set_fn_1_mut("values", map_get_values);
+        lib.set_raw_fn("values", &[TypeId::of::<Map>()], map_get_values);
     }
+
+    // Register record comparison operators
+    lib.set_raw_fn("==", &[TypeId::of::<Record>(), TypeId::of::<Record>()], record_eq);
+    lib.set_raw_fn("!=", &[TypeId::of::<Record>(), TypeId::of::<Record>()], record_ne);
 });
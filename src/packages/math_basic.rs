@@ -4,16 +4,54 @@ use crate::parser::INT;
 #[cfg(not(feature = "no_float"))]
 use crate::parser::FLOAT;
 
-#[cfg(not(feature = "no_float"))]
+#[cfg(any(not(feature = "no_float"), feature = "decimal", feature = "bigint"))]
 use crate::{result::EvalAltResult, token::Position};
 
+#[cfg(any(feature = "decimal", feature = "bigint"))]
+use crate::module::FuncReturn;
+
+#[cfg(any(feature = "decimal", feature = "bigint"))]
+use crate::parser::ImmutableString;
+
+#[cfg(feature = "decimal")]
+use rust_decimal::{Decimal, RoundingStrategy};
+
+#[cfg(feature = "decimal")]
+#[cfg(not(feature = "no_float"))]
+use crate::packages::arithmetic::float_to_decimal;
+
+#[cfg(feature = "bigint")]
+use crate::packages::arithmetic::bigint_to_int;
+
+#[cfg(feature = "bigint")]
+use num_bigint::BigInt;
+
+#[cfg(feature = "bigint")]
+use num_traits::Num;
+
+#[cfg(feature = "bigint")]
+#[cfg(not(feature = "no_float"))]
+use num_traits::FromPrimitive;
+
+#[cfg(any(
+    feature = "decimal",
+    all(feature = "bigint", not(feature = "no_float"))
+))]
+use num_traits::ToPrimitive;
+
 #[cfg(feature = "no_std")]
 #[cfg(not(feature = "no_float"))]
 use num_traits::float::Float;
 
-#[cfg(not(feature = "no_float"))]
+#[cfg(any(not(feature = "no_float"), feature = "decimal", feature = "bigint"))]
 use crate::stdlib::format;
 
+#[cfg(any(feature = "decimal", feature = "bigint"))]
+use crate::stdlib::boxed::Box;
+
+#[cfg(feature = "bigint")]
+use crate::stdlib::string::String;
+
 #[allow(dead_code)]
 #[cfg(feature = "only_i32")]
 pub const MAX_INT: INT = i32::MAX;
@@ -21,6 +59,120 @@ pub const MAX_INT: INT = i32::MAX;
 #[cfg(not(feature = "only_i32"))]
 pub const MAX_INT: INT = i64::MAX;
 
+/// Maximum number of decimal places of a `Decimal`.
+#[cfg(feature = "decimal")]
+const MAX_DECIMAL_SCALE: INT = 28;
+
+// Parse a string into a decimal number
+#[cfg(feature = "decimal")]
+fn parse_decimal(s: &str) -> FuncReturn<Decimal> {
+    Decimal::from_str_exact(s.trim()).map_err(|err| {
+        EvalAltResult::ErrorArithmetic(
+            format!("Invalid decimal number '{}': {}", s, err),
+            Position::none(),
+        )
+        .into()
+    })
+}
+// Convert a decimal number into a floating-point number
+#[cfg(feature = "decimal")]
+#[cfg(not(feature = "no_float"))]
+fn decimal_to_float(x: Decimal) -> FuncReturn<FLOAT> {
+    x.to_f64().ok_or_else(|| {
+        EvalAltResult::ErrorArithmetic(
+            format!("Cannot convert to floating-point: {}", x),
+            Position::none(),
+        )
+        .into()
+    })
+}
+// Convert a decimal number into an integer, truncating the fractional part
+#[cfg(feature = "decimal")]
+fn decimal_to_int(x: Decimal) -> FuncReturn<INT> {
+    #[cfg(not(feature = "only_i32"))]
+    let n = x.trunc().to_i64();
+    #[cfg(feature = "only_i32")]
+    let n = x.trunc().to_i32();
+
+    n.ok_or_else(|| {
+        EvalAltResult::ErrorArithmetic(format!("Integer overflow: to_int({})", x), Position::none())
+            .into()
+    })
+}
+// Check the number of decimal places
+#[cfg(feature = "decimal")]
+fn decimal_scale(dp: INT) -> Result<u32, Box<EvalAltResult>> {
+    if !(0..=MAX_DECIMAL_SCALE).contains(&dp) {
+        EvalAltResult::ErrorArithmetic(
+            format!(
+                "Number of decimal places must be between 0 and {}: {}",
+                MAX_DECIMAL_SCALE, dp
+            ),
+            Position::none(),
+        )
+        .into()
+    } else {
+        Ok(dp as u32)
+    }
+}
+// Map the name of a rounding mode to a rounding strategy
+#[cfg(feature = "decimal")]
+fn rounding_strategy(mode: &str) -> Result<RoundingStrategy, Box<EvalAltResult>> {
+    Ok(match mode {
+        "half_even" => RoundingStrategy::MidpointNearestEven,
+        "half_up" => RoundingStrategy::MidpointAwayFromZero,
+        "half_down" => RoundingStrategy::MidpointTowardZero,
+        "up" => RoundingStrategy::AwayFromZero,
+        "down" => RoundingStrategy::ToZero,
+        "ceiling" => RoundingStrategy::ToPositiveInfinity,
+        "floor" => RoundingStrategy::ToNegativeInfinity,
+        _ => {
+            return EvalAltResult::ErrorArithmetic(
+                format!("Invalid rounding mode: '{}'", mode),
+                Position::none(),
+            )
+            .into()
+        }
+    })
+}
+
+// Check the radix of a big integer in text form
+#[cfg(feature = "bigint")]
+fn bigint_radix(radix: INT) -> Result<u32, Box<EvalAltResult>> {
+    if !(2..=36).contains(&radix) {
+        EvalAltResult::ErrorArithmetic(
+            format!("Radix must be between 2 and 36: {}", radix),
+            Position::none(),
+        )
+        .into()
+    } else {
+        Ok(radix as u32)
+    }
+}
+// Parse a string into a big integer
+#[cfg(feature = "bigint")]
+fn parse_bigint(s: &str, radix: INT) -> FuncReturn<BigInt> {
+    BigInt::from_str_radix(s.trim(), bigint_radix(radix)?).map_err(|err| {
+        EvalAltResult::ErrorArithmetic(
+            format!("Invalid big integer '{}': {}", s, err),
+            Position::none(),
+        )
+        .into()
+    })
+}
+// Convert a floating-point number into a big integer, truncating the fractional part
+#[cfg(feature = "bigint")]
+#[cfg(not(feature = "no_float"))]
+fn float_to_bigint(x: FLOAT) -> FuncReturn<BigInt> {
+    BigInt::from_f64(x).ok_or_else(|| {
+        EvalAltResult::ErrorArithmetic(
+            format!("Cannot convert to big integer: {}", x),
+            Position::none(),
+        )
+        .into()
+    })
+}
+
 def_package!(crate:BasicMathPackage:"Basic mathematic functions.", lib, {
     #[cfg(not(feature = "no_float"))]
     {
@@ -138,4 +290,81 @@ def_package!(crate:BasicMathPackage:"Basic mathematic functions.", lib, {
             lib.set_fn_1("to_int", |x: f64| Ok(x as INT));
         }
     }
+
+    #[cfg(feature = "decimal")]
+    {
+        // Decimal constructors and conversion functions
+        lib.set_fn_1("decimal", |s: ImmutableString| parse_decimal(&s));
+        lib.set_fn_1("decimal", |x: INT| Ok(Decimal::from(x)));
+        lib.set_fn_1("to_decimal", |x: INT| Ok(Decimal::from(x)));
+        lib.set_fn_1("to_int", decimal_to_int);
+
+        #[cfg(not(feature = "no_float"))]
+        {
+            lib.set_fn_1("decimal", float_to_decimal);
+            lib.set_fn_1("to_decimal", float_to_decimal);
+            lib.set_fn_1("to_float", decimal_to_float);
+        }
+
+        // Rounding and scale
+        lib.set_fn_1("floor", |x: Decimal| Ok(x.floor()));
+        lib.set_fn_1("ceiling", |x: Decimal| Ok(x.ceil()));
+        lib.set_fn_1("round", |x: Decimal| Ok(x.round()));
+        lib.set_fn_2("round", |x: Decimal, dp: INT| Ok(x.round_dp(decimal_scale(dp)?)));
+        lib.set_fn_3(
+            "round",
+            |x: Decimal, dp: INT, mode: ImmutableString| {
+                Ok(x.round_dp_with_strategy(decimal_scale(dp)?, rounding_strategy(&mode)?))
+            },
+        );
+        lib.set_fn_1("int", |x: Decimal| Ok(x.trunc()));
+        lib.set_fn_1("fraction", |x: Decimal| Ok(x.fract()));
+        lib.set_fn_1("normalize", |x: Decimal| Ok(x.normalize()));
+        lib.set_fn_1("scale", |x: Decimal| Ok(x.scale() as INT));
+        lib.set_fn_2_mut(
+            "set_scale",
+            |x: &mut Decimal, dp: INT| {
+                x.rescale(decimal_scale(dp)?);
+                Ok(())
+            },
+        );
+
+        #[cfg(not(feature = "no_object"))]
+        {
+            lib.set_getter_fn("floor", |x: &mut Decimal| Ok(x.floor()));
+            lib.set_getter_fn("ceiling", |x: &mut Decimal| Ok(x.ceil()));
+            lib.set_getter_fn("round", |x: &mut Decimal| Ok(x.round()));
+            lib.set_getter_fn("int", |x: &mut Decimal| Ok(x.trunc()));
+            lib.set_getter_fn("fraction", |x: &mut Decimal| Ok(x.fract()));
+            lib.set_getter_fn("scale", |x: &mut Decimal| Ok(x.scale() as INT));
+        }
+    }
+
+    #[cfg(feature = "bigint")]
+    {
+        // Big integer constructors and conversion functions
+        lib.set_fn_1("bigint", |s: ImmutableString| parse_bigint(&s, 10));
+        lib.set_fn_2("bigint", |s: ImmutableString, radix: INT| parse_bigint(&s, radix));
+        lib.set_fn_1("bigint", |x: INT| Ok(BigInt::from(x)));
+        lib.set_fn_1("to_bigint", |x: INT| Ok(BigInt::from(x)));
+        lib.set_fn_1("to_int", |x: BigInt| {
+            bigint_to_int(&x).ok_or_else(|| {
+                EvalAltResult::ErrorArithmetic(
+                    format!("Integer overflow: to_int({})", x),
+                    Position::none(),
+                )
+                .into()
+            })
+        });
+        lib.set_fn_2("to_string", |x: BigInt, radix: INT| -> FuncReturn<String> {
+            Ok(x.to_str_radix(bigint_radix(radix)?))
+        });
+
+        #[cfg(not(feature = "no_float"))]
+        {
+            lib.set_fn_1("bigint", float_to_bigint);
+            lib.set_fn_1("to_bigint", float_to_bigint);
+            lib.set_fn_1("to_float", |x: BigInt| Ok(x.to_f64().unwrap_or(FLOAT::NAN)));
+        }
+    }
 });
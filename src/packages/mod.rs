@@ -1,6 +1,6 @@
 //! Module containing all built-in _packages_ available to Rhai, plus facilities to define custom packages.
 
-use crate::fn_native::{CallableFunction, IteratorFn, Shared};
+use crate::fn_native::{CallableFunction, FnCallArgs, IteratorFn, Shared};
 use crate::module::Module;
 use crate::utils::StaticVec;
 
@@ -73,6 +73,44 @@ impl PackagesCollection {
             .find(|f| f.is_some())
             .flatten()
     }
+    /// Get a variadic function that can be called with a particular list of arguments.
+    pub fn get_variadic_fn(
+        &self,
+        name: &str,
+        args: &FnCallArgs,
+        public_only: bool,
+    ) -> Option<&CallableFunction> {
+        self.0
+            .iter()
+            .find_map(|p| p.get_variadic_fn(name, args, public_only))
+    }
+    /// Is the package containing the specified function hash key deterministic?
+    /// Returns `None` if no package contains the function.
+    pub fn is_deterministic_fn(&self, hash: u64, public_only: bool) -> Option<bool> {
+        self.0
+            .iter()
+            .find(|p| p.contains_fn(hash, public_only))
+            .map(|p| p.is_deterministic())
+    }
+    /// Is the package containing a variadic function that can be called with a particular list
+    /// of arguments deterministic?  Returns `None` if no package contains such a function.
+    pub fn is_deterministic_variadic_fn(
+        &self,
+        name: &str,
+        args: &FnCallArgs,
+        public_only: bool,
+    ) -> Option<bool> {
+        self.0
+            .iter()
+            .find(|p| p.get_variadic_fn(name, args, public_only).is_some())
+            .map(|p| p.is_deterministic())
+    }
+    /// Get the parameter names of functions with a particular name and number of parameters.
+    pub fn get_fn_param_names(&self, name: &str, num_params: usize) -> Option<&[String]> {
+        self.0
+            .iter()
+            .find_map(|p| p.get_fn_param_names(name, num_params))
+    }
     /// Does the specified TypeId iterator exist in the `PackagesCollection`?
     #[allow(dead_code)]
     pub fn contains_iter(&self, id: TypeId) -> bool {
@@ -130,6 +168,7 @@ macro_rules! def_package {
             pub fn new() -> Self {
                 let mut module = $root::Module::new_with_capacity(512);
                 <Self as $root::packages::Package>::init(&mut module);
+                module.set_deterministic(true);
                 Self(module.into())
             }
         }
@@ -10,7 +10,23 @@ use crate::engine::Array;
 #[cfg(not(feature = "no_object"))]
 use crate::engine::Map;
 
+#[cfg(not(feature = "no_object"))]
+use crate::record::Record;
+
+#[cfg(feature = "decimal")]
+use rust_decimal::Decimal;
+
+#[cfg(feature = "bigint")]
+use num_bigint::BigInt;
+
+#[cfg(any(not(feature = "no_index"), not(feature = "no_object")))]
+use crate::{
+    any::{Dynamic, StableFormat},
+    fn_native::NativeCallContext,
+};
+
 use crate::stdlib::{
+    any::TypeId,
     fmt::{Debug, Display},
     format,
     string::ToString,
@@ -23,9 +39,21 @@ fn to_debug<T: Debug>(x: &mut T) -> FuncReturn<ImmutableString> {
 fn to_string<T: Display>(x: &mut T) -> FuncReturn<ImmutableString> {
     Ok(x.to_string().into())
 }
-#[cfg(not(feature = "no_object"))]
-fn format_map(x: &mut Map) -> FuncReturn<ImmutableString> {
-    Ok(format!("#{:?}", x).into())
+/// Format an array or object map, with the properties of object maps in a stable order in
+/// deterministic mode.
+#[cfg(any(not(feature = "no_index"), not(feature = "no_object")))]
+fn format_container(
+    context: NativeCallContext,
+    args: &mut [&mut Dynamic],
+) -> FuncReturn<ImmutableString> {
+    let value = args[0].clone().flatten();
+
+    Ok(if context.engine().is_deterministic() {
+        format!("{:?}", StableFormat(&value))
+    } else {
+        format!("{:?}", value)
+    }
+    .into())
 }
 
 macro_rules! reg_op {
@@ -70,18 +98,35 @@ def_package!(crate:BasicStringPackage:"Basic string utilities, including printin
         reg_op!(lib, KEYWORD_DEBUG, to_debug, f32, f64);
     }
 
+    #[cfg(feature = "decimal")]
+    {
+        reg_op!(lib, KEYWORD_PRINT, to_string, Decimal);
+        reg_op!(lib, FN_TO_STRING, to_string, Decimal);
+        reg_op!(lib, KEYWORD_DEBUG, to_debug, Decimal);
+    }
+
+    #[cfg(feature = "bigint")]
+    {
+        reg_op!(lib, KEYWORD_PRINT, to_string, BigInt);
+        reg_op!(lib, FN_TO_STRING, to_string, BigInt);
+        reg_op!(lib, KEYWORD_DEBUG, to_debug, BigInt);
+    }
+
     #[cfg(not(feature = "no_index"))]
     {
-        reg_op!(lib, KEYWORD_PRINT, to_debug, Array);
-        reg_op!(lib, FN_TO_STRING, to_debug, Array);
-        reg_op!(lib, KEYWORD_DEBUG, to_debug, Array);
+        lib.set_raw_fn(KEYWORD_PRINT, &[TypeId::of::<Array>()], format_container);
+        lib.set_raw_fn(FN_TO_STRING, &[TypeId::of::<Array>()], format_container);
+        lib.set_raw_fn(KEYWORD_DEBUG, &[TypeId::of::<Array>()], format_container);
     }
 
     #[cfg(not(feature = "no_object"))]
     {
-        lib.set_fn_1_mut(KEYWORD_PRINT, format_map);
-        lib.set_fn_1_mut(FN_TO_STRING, format_map);
-        lib.set_fn_1_mut(KEYWORD_DEBUG, format_map);
+        lib.set_raw_fn(KEYWORD_PRINT, &[TypeId::of::<Map>()], format_container);
+        lib.set_raw_fn(FN_TO_STRING, &[TypeId::of::<Map>()], format_container);
+        lib.set_raw_fn(KEYWORD_DEBUG, &[TypeId::of::<Map>()], format_container);
+        reg_op!(lib, KEYWORD_PRINT, to_string, Record);
+        reg_op!(lib, FN_TO_STRING, to_string, Record);
+        reg_op!(lib, KEYWORD_DEBUG, to_string, Record);
     }
 
     lib.set_fn_2("+", |s: ImmutableString, ch: char| Ok(s + ch));
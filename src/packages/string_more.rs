@@ -1,8 +1,7 @@
 use crate::any::Dynamic;
 use crate::def_package;
-use crate::engine::Engine;
-use crate::fn_native::FnPtr;
-use crate::module::{FuncReturn, Module};
+use crate::fn_native::{FnPtr, NativeCallContext};
+use crate::module::FuncReturn;
 use crate::parser::{ImmutableString, INT};
 use crate::utils::StaticVec;
 
@@ -12,6 +11,15 @@ use crate::{result::EvalAltResult, token::Position};
 #[cfg(not(feature = "no_index"))]
 use crate::engine::Array;
 
+#[cfg(not(feature = "no_object"))]
+use crate::record::Record;
+
+#[cfg(feature = "decimal")]
+use rust_decimal::Decimal;
+
+#[cfg(feature = "bigint")]
+use num_bigint::BigInt;
+
 use crate::stdlib::{
     any::TypeId,
     boxed::Box,
@@ -111,12 +119,30 @@ def_package!(crate:MoreStringPackage:"Additional string utilities, including str
         reg_op!(lib, "+", prepend, f32, f64);
     }
 
+    #[cfg(feature = "decimal")]
+    {
+        reg_op!(lib, "+", append, Decimal);
+        reg_op!(lib, "+", prepend, Decimal);
+    }
+
+    #[cfg(feature = "bigint")]
+    {
+        reg_op!(lib, "+", append, BigInt);
+        reg_op!(lib, "+", prepend, BigInt);
+    }
+
     #[cfg(not(feature = "no_index"))]
     {
         lib.set_fn_2_mut("+", |x: &mut ImmutableString, y: Array| Ok(format!("{}{:?}", x, y)));
         lib.set_fn_2_mut("+", |x: &mut Array, y: ImmutableString| Ok(format!("{:?}{}", x, y)));
     }
 
+    #[cfg(not(feature = "no_object"))]
+    {
+        reg_op!(lib, "+", append, Record);
+        reg_op!(lib, "+", prepend, Record);
+    }
+
     lib.set_fn_1_mut("len", |s: &mut ImmutableString| Ok(s.chars().count() as INT));
 
     #[cfg(not(feature = "no_object"))]
@@ -225,15 +251,15 @@ def_package!(crate:MoreStringPackage:"Additional string utilities, including str
     lib.set_raw_fn(
         "pad",
         &[TypeId::of::<ImmutableString>(), TypeId::of::<INT>(), TypeId::of::<char>()],
-        |_engine: &Engine, _: &Module, args: &mut [&mut Dynamic]| {
+        |_context: NativeCallContext, args: &mut [&mut Dynamic]| {
             let len = *args[1].read_lock::< INT>().unwrap();
 
             // Check if string will be over max size limit
             #[cfg(not(feature = "unchecked"))]
-            if _engine.limits.max_string_size > 0 && len > 0 && (len as usize) > _engine.limits.max_string_size {
+            if _context.engine().limits.max_string_size > 0 && len > 0 && (len as usize) > _context.engine().limits.max_string_size {
                 return EvalAltResult::ErrorDataTooLarge(
                     "Length of string".to_string(),
-                    _engine.limits.max_string_size,
+                    _context.engine().limits.max_string_size,
                     len as usize,
                     Position::none(),
                 ).into();
@@ -253,10 +279,10 @@ def_package!(crate:MoreStringPackage:"Additional string utilities, including str
                     }
 
                     #[cfg(not(feature = "unchecked"))]
-                    if _engine.limits.max_string_size > 0 && s.len() > _engine.limits.max_string_size {
+                    if _context.engine().limits.max_string_size > 0 && s.len() > _context.engine().limits.max_string_size {
                         return EvalAltResult::ErrorDataTooLarge(
                             "Length of string".to_string(),
-                            _engine.limits.max_string_size,
+                            _context.engine().limits.max_string_size,
                             s.len(),
                             Position::none(),
                         ).into();
@@ -5,9 +5,14 @@ use super::logic::{eq, gt, gte, lt, lte, ne};
 #[cfg(not(feature = "unchecked"))]
 use super::math_basic::MAX_INT;
 
+use crate::any::Dynamic;
 use crate::def_package;
+use crate::fn_native::NativeCallContext;
 use crate::result::EvalAltResult;
 
+#[cfg(not(feature = "no_object"))]
+use crate::engine::make_getter;
+
 #[cfg(not(feature = "no_float"))]
 use crate::parser::FLOAT;
 
@@ -18,6 +23,8 @@ use crate::parser::INT;
 #[cfg(not(feature = "unchecked"))]
 use crate::token::Position;
 
+use crate::stdlib::any::TypeId;
+
 #[cfg(not(target_arch = "wasm32"))]
 use crate::stdlib::time::Instant;
 
@@ -26,7 +33,9 @@ use instant::Instant;
 
 def_package!(crate:BasicTimePackage:"Basic timing utilities.", lib, {
     // Register date/time functions
-    lib.set_fn_0("timestamp", || Ok(Instant::now()));
+    lib.set_raw_fn("timestamp", &[], |context: NativeCallContext, _: &mut [&mut Dynamic]| {
+        Ok(context.engine().now())
+    });
 
     lib.set_fn_2(
         "-",
@@ -82,13 +91,15 @@ def_package!(crate:BasicTimePackage:"Basic timing utilities.", lib, {
     lib.set_fn_2("!=", ne::<Instant>);
 
     #[cfg(not(feature = "no_float"))]
-    fn elapsed (timestamp: &mut Instant) -> Result<FLOAT, Box<EvalAltResult>> {
-        Ok(timestamp.elapsed().as_secs_f64())
+    fn elapsed(context: NativeCallContext, args: &mut [&mut Dynamic]) -> Result<FLOAT, Box<EvalAltResult>> {
+        let timestamp = *args[0].read_lock::<Instant>().unwrap();
+        Ok((context.engine().now() - timestamp).as_secs_f64())
     }
 
     #[cfg(feature = "no_float")]
-    fn elapsed (timestamp: &mut Instant) -> Result<INT, Box<EvalAltResult>> {
-        let seconds = timestamp.elapsed().as_secs();
+    fn elapsed(context: NativeCallContext, args: &mut [&mut Dynamic]) -> Result<INT, Box<EvalAltResult>> {
+        let timestamp = *args[0].read_lock::<Instant>().unwrap();
+        let seconds = (context.engine().now() - timestamp).as_secs();
 
         #[cfg(not(feature = "unchecked"))]
         if seconds > (MAX_INT as u64) {
@@ -101,8 +112,8 @@ def_package!(crate:BasicTimePackage:"Basic timing utilities.", lib, {
         Ok(seconds as INT)
     }
 
-    lib.set_fn_1_mut("elapsed", elapsed);
+    lib.set_raw_fn("elapsed", &[TypeId::of::<Instant>()], elapsed);
 
     #[cfg(not(feature = "no_object"))]
-    lib.set_getter_fn("elapsed", elapsed);
+    lib.set_raw_fn(make_getter("elapsed"), &[TypeId::of::<Instant>()], elapsed);
 });
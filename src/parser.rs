@@ -2,7 +2,10 @@
 
 use crate::any::{Dynamic, Union};
 use crate::calc_fn_hash;
-use crate::engine::{Engine, KEYWORD_THIS, MARKER_BLOCK, MARKER_EXPR, MARKER_IDENT};
+use crate::engine::{
+    Engine, KEYWORD_FN_PTR, KEYWORD_FN_PTR_CALL, KEYWORD_THIS, MARKER_BLOCK, MARKER_EXPR,
+    MARKER_IDENT,
+};
 use crate::error::{LexError, ParseError, ParseErrorType};
 use crate::fn_native::{FnPtr, Shared};
 use crate::module::{Module, ModuleRef};
@@ -12,20 +15,32 @@ use crate::syntax::FnCustomSyntaxEval;
 use crate::token::{is_keyword_function, is_valid_identifier, Position, Token, TokenStream};
 use crate::utils::{StaticVec, StraightHasherBuilder};
 
+use crate::engine::KEYWORD_FN_PTR_CURRY;
+
+#[cfg(not(feature = "no_function"))]
+use crate::{
+    engine::FN_ANONYMOUS,
+    utils::{calc_typed_fn_hash, calc_variadic_fn_hash},
+};
+
+#[cfg(not(feature = "no_object"))]
+use crate::engine::{make_getter, make_setter, Map};
+
+#[cfg(not(feature = "no_object"))]
 #[cfg(not(feature = "no_function"))]
-use crate::engine::{FN_ANONYMOUS, KEYWORD_FN_PTR_CURRY};
+use crate::record::make_record_fn_name;
 
 #[cfg(not(feature = "no_object"))]
-use crate::engine::{make_getter, make_setter};
+use crate::record::RecordMethods;
 
 use crate::stdlib::{
     borrow::Cow,
     boxed::Box,
     char,
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     fmt, format,
     hash::{Hash, Hasher},
-    iter::empty,
+    iter::{empty, once},
     num::NonZeroUsize,
     ops::Add,
     string::{String, ToString},
@@ -37,9 +52,6 @@ use crate::stdlib::{
 #[cfg(not(feature = "no_function"))]
 use crate::stdlib::collections::hash_map::DefaultHasher;
 
-#[cfg(not(feature = "no_closure"))]
-use crate::stdlib::collections::HashSet;
-
 #[cfg(feature = "no_std")]
 #[cfg(not(feature = "no_function"))]
 use ahash::AHasher;
@@ -363,15 +375,50 @@ pub struct ScriptFnDef {
     pub access: FnAccess,
     /// Names of function parameters.
     pub params: StaticVec<String>,
+    /// Declared types of function parameters, `None` for untyped parameters.
+    /// Empty if no parameter has a declared type.
+    pub param_types: StaticVec<Option<String>>,
+    /// Default values of the trailing optional parameters.
+    pub defaults: StaticVec<Expr>,
+    /// Name of the rest parameter collecting all trailing arguments into an array, if any.
+    pub rest_param: Option<String>,
     /// Access to external variables.
     #[cfg(not(feature = "no_closure"))]
     pub externals: HashSet<String>,
     /// Function body.
     pub body: Stmt,
+    /// Is this a generator function (i.e. its body contains `yield`)?
+    pub is_generator: bool,
     /// Position of the function definition.
     pub pos: Position,
 }
 
+impl ScriptFnDef {
+    /// Number of parameters that must be passed to the function, i.e. those without default values.
+    pub fn num_required_params(&self) -> usize {
+        self.params.len() - self.defaults.len()
+    }
+
+    /// Does the function have parameters with declared types?
+    pub fn is_typed(&self) -> bool {
+        !self.param_types.is_empty()
+    }
+
+    /// Get the declared type of a parameter, if any.
+    pub fn param_type(&self, index: usize) -> Option<&str> {
+        self.param_types
+            .get(index)
+            .and_then(|t| t.as_ref().map(String::as_str))
+    }
+
+    /// Get the default value of a parameter, if any.
+    pub fn default_value(&self, index: usize) -> Option<&Expr> {
+        index
+            .checked_sub(self.num_required_params())
+            .and_then(|index| self.defaults.get(index))
+    }
+}
+
 impl fmt::Display for ScriptFnDef {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
@@ -384,9 +431,13 @@ impl fmt::Display for ScriptFnDef {
             self.name,
             self.params
                 .iter()
-                .map(|s| s.as_str())
+                .enumerate()
+                .map(|(index, s)| match self.param_type(index) {
+                    Some(typ) => format!("{}: {}", s, typ),
+                    None => s.to_string(),
+                })
                 .collect::<Vec<_>>()
-                .join(",")
+                .join(", ")
         )
     }
 }
@@ -419,8 +470,23 @@ struct ParseState<'e> {
     /// All consequent calls to `access_var` will not be affected
     #[cfg(not(feature = "no_closure"))]
     allow_capture: bool,
-    /// Encapsulates a local stack with variable names to simulate an actual runtime scope.
-    modules: Vec<String>,
+    /// Names of variables declared outside of the closure being parsed, which can be captured.
+    #[cfg(not(feature = "no_closure"))]
+    outer_vars: HashSet<String>,
+    /// Encapsulates a local stack with module names, and their paths if constant,
+    /// to simulate an actual runtime scope.
+    modules: Vec<(String, Option<String>)>,
+    /// Calls to functions not qualified by modules, which are checked against the sandbox profile
+    /// (if any) after parsing, when all script-defined functions are known.
+    calls: Vec<(String, Position)>,
+    /// Names of the record types declared.
+    #[cfg(not(feature = "no_object"))]
+    #[cfg(not(feature = "no_function"))]
+    structs: Vec<String>,
+    /// Has a `yield` expression been encountered?
+    has_yield: bool,
+    /// Is the body of a closure being parsed?  Closures cannot be generators, so `yield` is not allowed.
+    is_closure: bool,
     /// Maximum levels of expression nesting.
     #[cfg(not(feature = "unchecked"))]
     max_expr_depth: usize,
@@ -446,8 +512,16 @@ impl<'e> ParseState<'e> {
             externals: Default::default(),
             #[cfg(not(feature = "no_closure"))]
             allow_capture: true,
+            #[cfg(not(feature = "no_closure"))]
+            outer_vars: Default::default(),
             stack: Default::default(),
             modules: Default::default(),
+            calls: Default::default(),
+            #[cfg(not(feature = "no_object"))]
+            #[cfg(not(feature = "no_function"))]
+            structs: Default::default(),
+            has_yield: false,
+            is_closure: false,
         }
     }
 
@@ -479,6 +553,15 @@ impl<'e> ParseState<'e> {
         index
     }
 
+    /// Is the name a variable declared outside of the closure being parsed?
+    #[inline(always)]
+    fn is_outer_var(&self, _name: &str) -> bool {
+        #[cfg(not(feature = "no_closure"))]
+        return self.outer_vars.contains(_name);
+        #[cfg(feature = "no_closure")]
+        return false;
+    }
+
     /// Find a module by name in the `ParseState`, searching in reverse.
     /// The return value is the offset to be deducted from `Stack::len`,
     /// i.e. the top element of the `ParseState` is offset 1.
@@ -488,9 +571,71 @@ impl<'e> ParseState<'e> {
             .iter()
             .rev()
             .enumerate()
-            .find(|(_, n)| *n == name)
+            .find(|(_, (n, _))| *n == name)
             .and_then(|(i, _)| NonZeroUsize::new(i + 1))
     }
+
+    /// Check a function call against the sandbox profile, if any.
+    ///
+    /// Calls qualified by modules imported via constant paths are checked immediately.
+    /// Other calls are recorded and checked after parsing.
+    fn check_call(
+        &mut self,
+        name: &str,
+        modules: Option<&ModuleRef>,
+        pos: Position,
+    ) -> Result<(), ParseError> {
+        let sandbox = match &self.engine.sandbox {
+            Some(sandbox) => sandbox,
+            None => return Ok(()),
+        };
+
+        let namespace = match modules {
+            Some(modules) => {
+                let (root, _) = &modules[0];
+
+                // Modules imported via non-constant paths are checked at runtime
+                let path = self
+                    .modules
+                    .iter()
+                    .rev()
+                    .find(|(n, _)| n == root)
+                    .and_then(|(_, p)| p.as_ref());
+
+                match path {
+                    Some(path) => Some(
+                        once(path.as_str())
+                            .chain(modules.iter().skip(1).map(|(m, _)| m.as_str()))
+                            .collect::<Vec<_>>()
+                            .join(Token::DoubleColon.syntax().as_ref()),
+                    ),
+                    None => return Ok(()),
+                }
+            }
+            None if is_keyword_function(name) => None,
+            None => {
+                self.calls.push((name.into(), pos));
+                return Ok(());
+            }
+        };
+
+        match sandbox.check_function(namespace.as_deref(), name) {
+            Some(err) => Err(PERR::SandboxViolation(err).into_err(pos)),
+            None => Ok(()),
+        }
+    }
+
+    /// Check a module path against the sandbox profile, if any.
+    #[cfg(not(feature = "no_module"))]
+    fn check_module(&self, expr: &Expr) -> Result<(), ParseError> {
+        match (&self.engine.sandbox, expr) {
+            (Some(sandbox), Expr::StringConstant(x)) => match sandbox.check_module(&x.0) {
+                Some(err) => Err(PERR::SandboxViolation(err).into_err(x.1)),
+                None => Ok(()),
+            },
+            _ => Ok(()),
+        }
+    }
 }
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
@@ -535,6 +680,102 @@ impl ParseSettings {
     }
 }
 
+/// [INTERNALS] An item in an `export` statement.
+/// Exported under the `internals` feature only.
+///
+/// ## WARNING
+///
+/// This type is volatile and may change.
+#[cfg(not(feature = "no_module"))]
+#[derive(Debug, Clone, Hash)]
+pub enum ExportItem {
+    /// id as name
+    Variable((String, Position), Option<(String, Position)>),
+    /// let id = expr, or const id = expr
+    Declaration(Box<Stmt>),
+    /// *
+    AllVariables(Position),
+    /// module::id as name, or module::* if there is no id
+    Member(
+        (String, Position),
+        Option<(String, Position)>,
+        Option<(String, Position)>,
+    ),
+    /// use expr as name
+    Module(Expr, Option<(String, Position)>),
+}
+
+/// [INTERNALS] A destructuring pattern in a `let` statement or a `for` loop.
+/// Exported under the `internals` feature only.
+///
+/// ## WARNING
+///
+/// This type is volatile and may change.
+#[derive(Debug, Clone, Hash)]
+pub enum Pattern {
+    /// id
+    Variable(Box<(String, Position)>),
+    /// [ pattern = default, ..., ..rest ]
+    #[cfg(not(feature = "no_index"))]
+    Array(
+        Box<(
+            StaticVec<(Pattern, Option<Expr>)>,
+            Option<(String, Position)>,
+            Position,
+        )>,
+    ),
+    /// #{ key: pattern = default, ..., ..rest }
+    #[cfg(not(feature = "no_object"))]
+    Map(
+        Box<(
+            StaticVec<((ImmutableString, Position), Pattern, Option<Expr>)>,
+            Option<(String, Position)>,
+            Position,
+        )>,
+    ),
+}
+
+impl Pattern {
+    /// Get the `Position` of this pattern.
+    pub fn position(&self) -> Position {
+        match self {
+            Self::Variable(x) => x.1,
+            #[cfg(not(feature = "no_index"))]
+            Self::Array(x) => x.2,
+            #[cfg(not(feature = "no_object"))]
+            Self::Map(x) => x.2,
+        }
+    }
+
+    /// Get the names of all the variables bound by this pattern,
+    /// in the order that they are added to the scope.
+    pub(crate) fn names(&self) -> StaticVec<&str> {
+        let mut names = StaticVec::new();
+        self.collect_names(&mut names);
+        names
+    }
+
+    fn collect_names<'a>(&'a self, names: &mut StaticVec<&'a str>) {
+        match self {
+            Self::Variable(x) => names.push(&x.0),
+            #[cfg(not(feature = "no_index"))]
+            Self::Array(x) => {
+                x.0.iter().for_each(|(p, _)| p.collect_names(names));
+                if let Some((rest, _)) = &x.1 {
+                    names.push(rest);
+                }
+            }
+            #[cfg(not(feature = "no_object"))]
+            Self::Map(x) => {
+                x.0.iter().for_each(|(_, p, _)| p.collect_names(names));
+                if let Some((rest, _)) = &x.1 {
+                    names.push(rest);
+                }
+            }
+        }
+    }
+}
+
 /// [INTERNALS] A Rhai statement.
 /// Exported under the `internals` feature only.
 ///
@@ -550,10 +791,12 @@ pub enum Stmt {
     While(Box<(Expr, Stmt, Position)>),
     /// loop { stmt }
     Loop(Box<(Stmt, Position)>),
-    /// for id in expr { stmt }
-    For(Box<(String, Expr, Stmt, Position)>),
+    /// for pattern in expr { stmt }
+    For(Box<(Pattern, Expr, Stmt, Position)>),
     /// let id = expr
     Let(Box<((String, Position), Option<Expr>, Position)>),
+    /// let [pattern] = expr, or let #{pattern} = expr
+    LetPattern(Box<(Pattern, Expr, Position)>),
     /// const id = expr
     Const(Box<((String, Position), Expr, Position)>),
     /// { stmt; ... }
@@ -569,14 +812,9 @@ pub enum Stmt {
     /// import expr as module
     #[cfg(not(feature = "no_module"))]
     Import(Box<(Expr, Option<(String, Position)>, Position)>),
-    /// expr id as name, ...
+    /// export item, ...
     #[cfg(not(feature = "no_module"))]
-    Export(
-        Box<(
-            StaticVec<((String, Position), Option<(String, Position)>)>,
-            Position,
-        )>,
-    ),
+    Export(Box<(StaticVec<ExportItem>, Position)>),
     /// Convert a variable to shared.
     #[cfg(not(feature = "no_closure"))]
     Share(Box<(String, Position)>),
@@ -594,6 +832,7 @@ impl Stmt {
         match self {
             Stmt::Noop(pos) | Stmt::Continue(pos) | Stmt::Break(pos) => *pos,
             Stmt::Let(x) => (x.0).1,
+            Stmt::LetPattern(x) => x.2,
             Stmt::Const(x) => (x.0).1,
             Stmt::ReturnWithVal(x) => (x.0).1,
             Stmt::Block(x) => x.1,
@@ -618,6 +857,7 @@ impl Stmt {
         match self {
             Stmt::Noop(pos) | Stmt::Continue(pos) | Stmt::Break(pos) => *pos = new_pos,
             Stmt::Let(x) => (x.0).1 = new_pos,
+            Stmt::LetPattern(x) => x.2 = new_pos,
             Stmt::Const(x) => (x.0).1 = new_pos,
             Stmt::ReturnWithVal(x) => (x.0).1 = new_pos,
             Stmt::Block(x) => x.1 = new_pos,
@@ -654,6 +894,7 @@ impl Stmt {
             Stmt::Noop(_) => false,
 
             Stmt::Let(_)
+            | Stmt::LetPattern(_)
             | Stmt::Const(_)
             | Stmt::Expr(_)
             | Stmt::Continue(_)
@@ -680,7 +921,7 @@ impl Stmt {
             Stmt::While(x) => x.0.is_pure() && x.1.is_pure(),
             Stmt::Loop(x) => x.0.is_pure(),
             Stmt::For(x) => x.1.is_pure() && x.2.is_pure(),
-            Stmt::Let(_) | Stmt::Const(_) => false,
+            Stmt::Let(_) | Stmt::LetPattern(_) | Stmt::Const(_) => false,
             Stmt::Block(x) => x.0.iter().all(Stmt::is_pure),
             Stmt::Continue(_) | Stmt::Break(_) | Stmt::ReturnWithVal(_) => false,
 
@@ -774,40 +1015,57 @@ pub enum Expr {
     Stmt(Box<(Stmt, Position)>),
     /// Wrapped expression - should not be optimized away.
     Expr(Box<Expr>),
-    /// func(expr, ... ) - ((function name, native_only, capture, position), optional modules, hash, arguments, optional default value)
+    /// func(expr, ... ) - ((function name, native_only, capture, pipe, position), optional modules, hash, arguments, optional default value)
     /// Use `Cow<'static, str>` because a lot of operators (e.g. `==`, `>=`) are implemented as function calls
     /// and the function names are predictable, so no need to allocate a new `String`.
     FnCall(
         Box<(
-            (Cow<'static, str>, bool, bool, Position),
+            (Cow<'static, str>, bool, bool, bool, Position),
             Option<Box<ModuleRef>>,
             u64,
             StaticVec<Expr>,
             Option<bool>,
         )>,
     ),
+    /// func(expr, ..., name: expr, ...) - ((function name, capture, position), positional arguments, named arguments)
+    FnCallNamed(
+        Box<(
+            (ImmutableString, bool, Position),
+            StaticVec<Expr>,
+            StaticVec<((String, Position), Expr)>,
+        )>,
+    ),
     /// expr op= expr
     Assignment(Box<(Expr, Cow<'static, str>, Expr, Position)>),
-    /// lhs.rhs
-    Dot(Box<(Expr, Expr, Position)>),
-    /// expr[expr]
-    Index(Box<(Expr, Expr, Position)>),
+    /// lhs.rhs | lhs?.rhs (optional)
+    Dot(Box<(Expr, Expr, Position, bool)>),
+    /// expr[expr] | expr?[expr] (optional)
+    Index(Box<(Expr, Expr, Position, bool)>),
     /// [ expr, ... ]
     Array(Box<(StaticVec<Expr>, Position)>),
     /// #{ name:expr, ... }
     Map(Box<(StaticVec<((ImmutableString, Position), Expr)>, Position)>),
+    /// Record construction - (record type name, object map literal of the fields, methods)
+    ///
+    /// Only found in the bodies of the constructors of record types declared with `struct`.
+    #[cfg(not(feature = "no_object"))]
+    Record(Box<(ImmutableString, Expr, RecordMethods)>),
     /// lhs in rhs
     In(Box<(Expr, Expr, Position)>),
     /// lhs && rhs
     And(Box<(Expr, Expr, Position)>),
     /// lhs || rhs
     Or(Box<(Expr, Expr, Position)>),
+    /// lhs ?? rhs
+    Coalesce(Box<(Expr, Expr, Position)>),
     /// true
     True(Position),
     /// false
     False(Position),
     /// ()
     Unit(Position),
+    /// yield expr
+    Yield(Box<(Expr, Position)>),
     /// Custom syntax
     Custom(Box<(CustomExpr, Position)>),
 }
@@ -851,7 +1109,7 @@ impl Expr {
                 Dynamic(Union::Map(Box::new(
                     x.0.iter()
                         .map(|((k, _), v)| (k.clone(), v.get_constant_value()))
-                        .collect::<HashMap<_, _>>(),
+                        .collect::<Map>(),
                 )))
             }
 
@@ -898,18 +1156,22 @@ impl Expr {
             Self::FnPointer(x) => x.1,
             Self::Array(x) => x.1,
             Self::Map(x) => x.1,
+            #[cfg(not(feature = "no_object"))]
+            Self::Record(x) => x.1.position(),
             Self::Property(x) => x.1,
             Self::Stmt(x) => x.1,
             Self::Variable(x) => (x.0).1,
-            Self::FnCall(x) => (x.0).3,
+            Self::FnCall(x) => (x.0).4,
+            Self::FnCallNamed(x) => (x.0).2,
             Self::Assignment(x) => x.0.position(),
 
-            Self::And(x) | Self::Or(x) | Self::In(x) => x.2,
+            Self::And(x) | Self::Or(x) | Self::In(x) | Self::Coalesce(x) => x.2,
 
             Self::True(pos) | Self::False(pos) | Self::Unit(pos) => *pos,
 
             Self::Dot(x) | Self::Index(x) => x.0.position(),
 
+            Self::Yield(x) => x.1,
             Self::Custom(x) => x.1,
         }
     }
@@ -930,19 +1192,26 @@ impl Expr {
             Self::FnPointer(x) => x.1 = new_pos,
             Self::Array(x) => x.1 = new_pos,
             Self::Map(x) => x.1 = new_pos,
+            #[cfg(not(feature = "no_object"))]
+            Self::Record(x) => {
+                x.1.set_position(new_pos);
+            }
             Self::Variable(x) => (x.0).1 = new_pos,
             Self::Property(x) => x.1 = new_pos,
             Self::Stmt(x) => x.1 = new_pos,
-            Self::FnCall(x) => (x.0).3 = new_pos,
+            Self::FnCall(x) => (x.0).4 = new_pos,
+            Self::FnCallNamed(x) => (x.0).2 = new_pos,
             Self::And(x) => x.2 = new_pos,
             Self::Or(x) => x.2 = new_pos,
             Self::In(x) => x.2 = new_pos,
+            Self::Coalesce(x) => x.2 = new_pos,
             Self::True(pos) => *pos = new_pos,
             Self::False(pos) => *pos = new_pos,
             Self::Unit(pos) => *pos = new_pos,
             Self::Assignment(x) => x.3 = new_pos,
             Self::Dot(x) => x.2 = new_pos,
             Self::Index(x) => x.2 = new_pos,
+            Self::Yield(x) => x.1 = new_pos,
             Self::Custom(x) => x.1 = new_pos,
         }
 
@@ -958,7 +1227,12 @@ impl Expr {
 
             Self::Array(x) => x.0.iter().all(Self::is_pure),
 
-            Self::Index(x) | Self::And(x) | Self::Or(x) | Self::In(x) => {
+            Self::Index(x) => {
+                let (lhs, rhs, _, _) = x.as_ref();
+                lhs.is_pure() && rhs.is_pure()
+            }
+
+            Self::And(x) | Self::Or(x) | Self::In(x) | Self::Coalesce(x) => {
                 let (lhs, rhs, _) = x.as_ref();
                 lhs.is_pure() && rhs.is_pure()
             }
@@ -1018,26 +1292,32 @@ impl Expr {
             | Self::In(_)
             | Self::And(_)
             | Self::Or(_)
+            | Self::Coalesce(_)
             | Self::True(_)
             | Self::False(_)
             | Self::Unit(_)
-            | Self::Assignment(_) => false,
+            | Self::Assignment(_)
+            | Self::Yield(_) => false,
+
+            #[cfg(not(feature = "no_object"))]
+            Self::Record(_) => false,
 
             Self::StringConstant(_)
             | Self::Stmt(_)
             | Self::FnCall(_)
+            | Self::FnCallNamed(_)
             | Self::Dot(_)
             | Self::Index(_)
             | Self::Array(_)
             | Self::Map(_) => match token {
                 #[cfg(not(feature = "no_index"))]
-                Token::LeftBracket => true,
+                Token::LeftBracket | Token::QuestionBracket => true,
                 _ => false,
             },
 
             Self::Variable(_) => match token {
                 #[cfg(not(feature = "no_index"))]
-                Token::LeftBracket => true,
+                Token::LeftBracket | Token::QuestionBracket => true,
                 Token::LeftParen => true,
                 Token::Bang => true,
                 Token::DoubleColon => true,
@@ -1046,7 +1326,7 @@ impl Expr {
 
             Self::Property(_) => match token {
                 #[cfg(not(feature = "no_index"))]
-                Token::LeftBracket => true,
+                Token::LeftBracket | Token::QuestionBracket => true,
                 Token::LeftParen => true,
                 _ => false,
             },
@@ -1136,6 +1416,8 @@ fn parse_fn_call(
     mut modules: Option<Box<ModuleRef>>,
     settings: ParseSettings,
 ) -> Result<Expr, ParseError> {
+    state.check_call(&id, modules.as_deref(), settings.pos)?;
+
     let (token, token_pos) = input.peek().unwrap();
 
     #[cfg(not(feature = "unchecked"))]
@@ -1175,7 +1457,7 @@ fn parse_fn_call(
             };
 
             return Ok(Expr::FnCall(Box::new((
-                (id.into(), false, capture, settings.pos),
+                (id.into(), false, capture, false, settings.pos),
                 modules,
                 hash_script,
                 args,
@@ -1187,15 +1469,65 @@ fn parse_fn_call(
     }
 
     let settings = settings.level_up();
+    let mut named_args: StaticVec<((String, Position), Expr)> = StaticVec::new();
 
     loop {
         match input.peek().unwrap() {
             // id(...args, ) - handle trailing comma
             (Token::RightParen, _) => (),
-            _ => args.push(parse_expr(input, state, lib, settings)?),
+            _ => match parse_expr(input, state, lib, settings)? {
+                // id(...args, name: expr)
+                Expr::Variable(x) if x.1.is_none() && input.peek().unwrap().0 == Token::Colon => {
+                    eat_token(input, Token::Colon);
+
+                    let ((name, pos), _, _, _) = *x;
+
+                    // The name is not used as a variable, so do not capture it
+                    #[cfg(not(feature = "no_closure"))]
+                    if state.externals.get(&name) == Some(&pos) {
+                        state.externals.remove(&name);
+                    }
+
+                    if named_args.iter().any(|((n, _), _)| *n == name) {
+                        return Err(PERR::MalformedCallExpr(format!(
+                            "Duplicated named argument '{}'",
+                            name
+                        ))
+                        .into_err(pos));
+                    }
+
+                    let expr = parse_expr(input, state, lib, settings)?;
+                    named_args.push(((name, pos), expr));
+                }
+                // id(...name: expr, expr)
+                expr if !named_args.is_empty() => {
+                    return Err(PERR::MalformedCallExpr(
+                        "Positional arguments cannot follow named arguments".into(),
+                    )
+                    .into_err(expr.position()))
+                }
+                expr => args.push(expr),
+            },
         }
 
         match input.peek().unwrap() {
+            // id(...args, name: expr)
+            (Token::RightParen, _) if !named_args.is_empty() => {
+                eat_token(input, Token::RightParen);
+
+                if modules.is_some() {
+                    return Err(PERR::MalformedCallExpr(
+                        "Named arguments are not supported for module-qualified functions".into(),
+                    )
+                    .into_err(settings.pos));
+                }
+
+                return Ok(Expr::FnCallNamed(Box::new((
+                    (id.into(), capture, settings.pos),
+                    args,
+                    named_args,
+                ))));
+            }
             // id(...args)
             (Token::RightParen, _) => {
                 eat_token(input, Token::RightParen);
@@ -1217,7 +1549,7 @@ fn parse_fn_call(
                 };
 
                 return Ok(Expr::FnCall(Box::new((
-                    (id.into(), false, capture, settings.pos),
+                    (id.into(), false, capture, false, settings.pos),
                     modules,
                     hash_script,
                     args,
@@ -1252,12 +1584,14 @@ fn parse_fn_call(
 
 /// Parse an indexing chain.
 /// Indexing binds to the right, so this call parses all possible levels of indexing following in the input.
+/// `optional` is set for `?[`, which evaluates to `()` when `lhs` is `()`.
 #[cfg(not(feature = "no_index"))]
 fn parse_index_chain(
     input: &mut TokenStream,
     state: &mut ParseState,
     lib: &mut FunctionsLib,
     lhs: Expr,
+    optional: bool,
     mut settings: ParseSettings,
 ) -> Result<Expr, ParseError> {
     #[cfg(not(feature = "unchecked"))]
@@ -1401,14 +1735,22 @@ fn parse_index_chain(
             // Any more indexing following?
             match input.peek().unwrap() {
                 // If another indexing level, right-bind it
-                (Token::LeftBracket, _) => {
+                (Token::LeftBracket, _) | (Token::QuestionBracket, _) => {
                     let prev_pos = settings.pos;
-                    settings.pos = eat_token(input, Token::LeftBracket);
+                    let (token, pos) = input.next().unwrap();
+                    settings.pos = pos;
+                    let next_optional = token == Token::QuestionBracket;
                     // Recursively parse the indexing chain, right-binding each
-                    let idx_expr =
-                        parse_index_chain(input, state, lib, idx_expr, settings.level_up())?;
+                    let idx_expr = parse_index_chain(
+                        input,
+                        state,
+                        lib,
+                        idx_expr,
+                        next_optional,
+                        settings.level_up(),
+                    )?;
                     // Indexing binds to right
-                    Ok(Expr::Index(Box::new((lhs, idx_expr, prev_pos))))
+                    Ok(Expr::Index(Box::new((lhs, idx_expr, prev_pos, optional))))
                 }
                 // Otherwise terminate the indexing chain
                 _ => {
@@ -1417,9 +1759,14 @@ fn parse_index_chain(
                         // inside brackets to be mis-parsed as another level of indexing, or a
                         // dot expression/function call to be mis-parsed as following the indexing chain.
                         Expr::Index(_) | Expr::Dot(_) | Expr::FnCall(_) => Ok(Expr::Index(
-                            Box::new((lhs, Expr::Expr(Box::new(idx_expr)), settings.pos)),
+                            Box::new((lhs, Expr::Expr(Box::new(idx_expr)), settings.pos, optional)),
                         )),
-                        _ => Ok(Expr::Index(Box::new((lhs, idx_expr, settings.pos)))),
+                        _ => Ok(Expr::Index(Box::new((
+                            lhs,
+                            idx_expr,
+                            settings.pos,
+                            optional,
+                        )))),
                     }
                 }
             }
@@ -1738,7 +2085,12 @@ fn parse_primary(
             // Indexing
             #[cfg(not(feature = "no_index"))]
             (expr, Token::LeftBracket) => {
-                parse_index_chain(input, state, lib, expr, settings.level_up())?
+                parse_index_chain(input, state, lib, expr, false, settings.level_up())?
+            }
+            // Optional indexing
+            #[cfg(not(feature = "no_index"))]
+            (expr, Token::QuestionBracket) => {
+                parse_index_chain(input, state, lib, expr, true, settings.level_up())?
             }
             // Unknown postfix operator
             (expr, token) => unreachable!(
@@ -1822,7 +2174,7 @@ fn parse_unary(
                     args.push(expr);
 
                     Ok(Expr::FnCall(Box::new((
-                        (op.into(), true, false, pos),
+                        (op.into(), true, false, false, pos),
                         None,
                         hash,
                         args,
@@ -1847,13 +2199,38 @@ fn parse_unary(
             let hash = calc_fn_hash(empty(), op, 2, empty());
 
             Ok(Expr::FnCall(Box::new((
-                (op.into(), true, false, pos),
+                (op.into(), true, false, false, pos),
                 None,
                 hash,
                 args,
                 Some(false), // NOT operator, when operating on invalid operand, defaults to false
             ))))
         }
+        // custom-op expr
+        Token::Custom(s)
+            if state
+                .engine
+                .custom_prefix_operators
+                .as_ref()
+                .map(|c| c.contains(s))
+                .unwrap_or(false) =>
+        {
+            let op = s.clone();
+            let pos = eat_token(input, Token::Custom(op.clone()));
+            let mut args = StaticVec::new();
+            args.push(parse_unary(input, state, lib, settings.level_up())?);
+
+            let hash = calc_fn_hash(empty(), &op, 1, empty());
+
+            // Accept non-native functions for custom operators
+            Ok(Expr::FnCall(Box::new((
+                (op.into(), false, false, false, pos),
+                None,
+                hash,
+                args,
+                None,
+            ))))
+        }
         // | ...
         #[cfg(not(feature = "no_function"))]
         Token::Pipe | Token::Or => {
@@ -1865,6 +2242,16 @@ fn parse_unary(
                 state.max_function_expr_depth,
             );
 
+            new_state.is_closure = true;
+
+            #[cfg(not(feature = "no_closure"))]
+            {
+                new_state.outer_vars = state.outer_vars.clone();
+                new_state
+                    .outer_vars
+                    .extend(state.stack.iter().map(|(name, _)| name.clone()));
+            }
+
             let settings = ParseSettings {
                 allow_if_expr: true,
                 allow_stmt_expr: true,
@@ -1877,6 +2264,7 @@ fn parse_unary(
             };
 
             let (expr, func) = parse_anon_fn(input, &mut new_state, lib, settings)?;
+            state.calls.append(&mut new_state.calls);
 
             #[cfg(not(feature = "no_closure"))]
             new_state.externals.iter().for_each(|(closure, pos)| {
@@ -1890,6 +2278,31 @@ fn parse_unary(
 
             Ok(expr)
         }
+        // yield expr
+        Token::Yield => {
+            let pos = eat_token(input, Token::Yield);
+
+            if state.is_closure {
+                return Err(
+                    PERR::BadInput("'yield' cannot be used in closures".to_string()).into_err(pos),
+                );
+            }
+
+            state.has_yield = true;
+
+            let expr = match input.peek().unwrap() {
+                // `yield` without a value
+                (Token::EOF, _)
+                | (Token::SemiColon, _)
+                | (Token::Comma, _)
+                | (Token::RightParen, _)
+                | (Token::RightBracket, _)
+                | (Token::RightBrace, _) => Expr::Unit(pos),
+                _ => parse_expr(input, state, lib, settings.level_up())?,
+            };
+
+            Ok(Expr::Yield(Box::new((expr, pos))))
+        }
         // <EOF>
         Token::EOF => Err(PERR::UnexpectedEOF.into_err(settings.pos)),
         // All other tokens
@@ -1995,17 +2408,24 @@ fn parse_op_assignment_stmt(
 }
 
 /// Make a dot expression.
+/// `optional` is set for `?.`, which evaluates to `()` when `lhs` is `()`.
 #[cfg(not(feature = "no_object"))]
-fn make_dot_expr(lhs: Expr, rhs: Expr, op_pos: Position) -> Result<Expr, ParseError> {
+fn make_dot_expr(
+    lhs: Expr,
+    rhs: Expr,
+    op_pos: Position,
+    optional: bool,
+) -> Result<Expr, ParseError> {
     Ok(match (lhs, rhs) {
         // idx_lhs[idx_expr].rhs
         // Attach dot chain to the bottom level of indexing chain
         (Expr::Index(x), rhs) => {
-            let (idx_lhs, idx_expr, pos) = *x;
+            let (idx_lhs, idx_expr, pos, idx_optional) = *x;
             Expr::Index(Box::new((
                 idx_lhs,
-                make_dot_expr(idx_expr, rhs, op_pos)?,
+                make_dot_expr(idx_expr, rhs, op_pos, optional)?,
                 pos,
+                idx_optional,
             )))
         }
         // lhs.id
@@ -2016,30 +2436,42 @@ fn make_dot_expr(lhs: Expr, rhs: Expr, op_pos: Position) -> Result<Expr, ParseEr
             let setter = make_setter(&name);
             let rhs = Expr::Property(Box::new(((name.into(), getter, setter), pos)));
 
-            Expr::Dot(Box::new((lhs, rhs, op_pos)))
+            Expr::Dot(Box::new((lhs, rhs, op_pos, optional)))
         }
         // lhs.module::id - syntax error
         (_, Expr::Variable(x)) if x.1.is_some() => {
             return Err(PERR::PropertyExpected.into_err(x.1.unwrap()[0].1));
         }
         // lhs.prop
-        (lhs, prop @ Expr::Property(_)) => Expr::Dot(Box::new((lhs, prop, op_pos))),
+        (lhs, prop @ Expr::Property(_)) => Expr::Dot(Box::new((lhs, prop, op_pos, optional))),
         // lhs.dot_lhs.dot_rhs
         (lhs, Expr::Dot(x)) => {
-            let (dot_lhs, dot_rhs, pos) = *x;
+            let (dot_lhs, dot_rhs, pos, dot_optional) = *x;
             Expr::Dot(Box::new((
                 lhs,
-                Expr::Dot(Box::new((dot_lhs.into_property(), dot_rhs, pos))),
+                Expr::Dot(Box::new((
+                    dot_lhs.into_property(),
+                    dot_rhs,
+                    pos,
+                    dot_optional,
+                ))),
                 op_pos,
+                optional,
             )))
         }
         // lhs.idx_lhs[idx_rhs]
         (lhs, Expr::Index(x)) => {
-            let (dot_lhs, dot_rhs, pos) = *x;
+            let (dot_lhs, dot_rhs, pos, idx_optional) = *x;
             Expr::Dot(Box::new((
                 lhs,
-                Expr::Index(Box::new((dot_lhs.into_property(), dot_rhs, pos))),
+                Expr::Index(Box::new((
+                    dot_lhs.into_property(),
+                    dot_rhs,
+                    pos,
+                    idx_optional,
+                ))),
                 op_pos,
+                optional,
             )))
         }
         // lhs.func!(...)
@@ -2047,10 +2479,10 @@ fn make_dot_expr(lhs: Expr, rhs: Expr, op_pos: Position) -> Result<Expr, ParseEr
             return Err(PERR::MalformedCapture(
                 "method-call style does not support capturing".into(),
             )
-            .into_err((x.0).3))
+            .into_err((x.0).4))
         }
         // lhs.func(...)
-        (lhs, func @ Expr::FnCall(_)) => Expr::Dot(Box::new((lhs, func, op_pos))),
+        (lhs, func @ Expr::FnCall(_)) => Expr::Dot(Box::new((lhs, func, op_pos, optional))),
         // lhs.rhs
         (_, rhs) => return Err(PERR::PropertyExpected.into_err(rhs.position())),
     })
@@ -2206,6 +2638,103 @@ fn make_in_expr(lhs: Expr, rhs: Expr, op_pos: Position) -> Result<Expr, ParseErr
     Ok(Expr::In(Box::new((lhs, rhs, op_pos))))
 }
 
+/// Make a pipeline expression, which passes the left-hand side as the first argument.
+fn make_pipe_expr(
+    state: &mut ParseState,
+    lhs: Expr,
+    rhs: Expr,
+    op_pos: Position,
+) -> Result<Expr, ParseError> {
+    match rhs {
+        // x |> Fn(...), x |> curry(...) - function pointer expressions
+        Expr::FnCall(x)
+            if x.1.is_none() && ((x.0).0 == KEYWORD_FN_PTR || (x.0).0 == KEYWORD_FN_PTR_CURRY) =>
+        {
+            Ok(make_fn_ptr_call(Expr::FnCall(x), lhs, op_pos))
+        }
+        // x |> call(fp, ...) -> call(fp, x, ...)
+        Expr::FnCall(mut x)
+            if x.1.is_none() && (x.0).0 == KEYWORD_FN_PTR_CALL && !x.3.is_empty() =>
+        {
+            x.3.insert(1, lhs);
+            x.2 = calc_fn_hash(empty(), KEYWORD_FN_PTR_CALL, x.3.len(), empty());
+            Ok(Expr::FnCall(x))
+        }
+        // x |> func(...) -> func(x, ...)
+        Expr::FnCall(mut x) => {
+            x.3.insert(0, lhs);
+            let ((name, _, _, pipe, _pos), modules, hash, args, _) = x.as_mut();
+
+            *hash = if let Some(modules) = modules {
+                let qualifiers = modules.iter().map(|(m, _)| m.as_str());
+                calc_fn_hash(qualifiers, name, args.len(), empty())
+            } else {
+                // If the function is not found, it may be a function pointer held in a variable
+                if !is_keyword_function(name) {
+                    *pipe = true;
+
+                    // Capture the variable holding the function pointer, if any
+                    #[cfg(not(feature = "no_closure"))]
+                    if state.is_outer_var(name) && !state.externals.contains_key(name.as_ref()) {
+                        state.externals.insert(name.to_string(), *_pos);
+                    }
+                }
+                calc_fn_hash(empty(), name, args.len(), empty())
+            };
+            Ok(Expr::FnCall(x))
+        }
+        // x |> func -> func(x)
+        Expr::Variable(x) if x.1.is_none() && (x.0).0 != KEYWORD_THIS => {
+            let ((name, pos), _, _, _index) = *x;
+
+            // The name is not used as a variable, so do not capture it,
+            // unless it is a variable declared outside the closure being parsed
+            #[cfg(not(feature = "no_closure"))]
+            if _index.is_none()
+                && !state.is_outer_var(&name)
+                && state.externals.get(&name) == Some(&pos)
+            {
+                state.externals.remove(&name);
+            }
+
+            state.check_call(&name, None, pos)?;
+
+            let hash = calc_fn_hash(empty(), &name, 1, empty());
+            let mut args = StaticVec::new();
+            args.push(lhs);
+
+            // If the function is not found, it may be a function pointer held in a variable
+            let pipe = !is_keyword_function(&name);
+
+            Ok(Expr::FnCall(Box::new((
+                (name.into(), false, false, pipe, pos),
+                None,
+                hash,
+                args,
+                None,
+            ))))
+        }
+        // x |> fn_ptr -> call(fn_ptr, x)
+        expr => Ok(make_fn_ptr_call(expr, lhs, op_pos)),
+    }
+}
+
+/// Make a call to a function pointer expression, i.e. `call(fn_ptr, arg)`.
+fn make_fn_ptr_call(fn_ptr: Expr, arg: Expr, pos: Position) -> Expr {
+    let hash = calc_fn_hash(empty(), KEYWORD_FN_PTR_CALL, 2, empty());
+    let mut args = StaticVec::new();
+    args.push(fn_ptr);
+    args.push(arg);
+
+    Expr::FnCall(Box::new((
+        (KEYWORD_FN_PTR_CALL.into(), false, false, false, pos),
+        None,
+        hash,
+        args,
+        None,
+    )))
+}
+
 /// Parse a binary expression.
 fn parse_binary_op(
     input: &mut TokenStream,
@@ -2226,7 +2755,15 @@ fn parse_binary_op(
         let (current_op, _) = input.peek().unwrap();
         let custom = state.engine.custom_keywords.as_ref();
         let precedence = current_op.precedence(custom);
-        let bind_right = current_op.is_bind_right();
+        let bind_right = match current_op {
+            Token::Custom(s) => state
+                .engine
+                .custom_bind_right
+                .as_ref()
+                .map(|c| c.contains(s))
+                .unwrap_or(false),
+            token => token.is_bind_right(),
+        };
 
         // Bind left to the parent lhs expression if precedence is higher
         // If same precedence, then check if the operator binds right
@@ -2236,7 +2773,9 @@ fn parse_binary_op(
 
         let (op_token, pos) = input.next().unwrap();
 
-        if cfg!(not(feature = "no_object")) && op_token == Token::Period {
+        if cfg!(not(feature = "no_object"))
+            && (op_token == Token::Period || op_token == Token::Elvis)
+        {
             if let (Token::Identifier(_), _) = input.peek().unwrap() {
                 // prevents capturing of the object properties as vars: xxx.<var>
                 #[cfg(not(feature = "no_closure"))]
@@ -2268,7 +2807,7 @@ fn parse_binary_op(
         let cmp_def = Some(false);
         let op = op_token.syntax();
         let hash = calc_fn_hash(empty(), &op, 2, empty());
-        let op = (op, true, false, pos);
+        let op = (op, true, false, false, pos);
 
         let mut args = StaticVec::new();
         args.push(root);
@@ -2312,12 +2851,29 @@ fn parse_binary_op(
                 let current_lhs = args.pop().unwrap();
                 make_in_expr(current_lhs, rhs, pos)?
             }
+            Token::PipeForward => {
+                let rhs = args.pop().unwrap();
+                let current_lhs = args.pop().unwrap();
+                make_pipe_expr(state, current_lhs, rhs, pos)?
+            }
+
+            Token::DoubleQuestion => {
+                let rhs = args.pop().unwrap();
+                let current_lhs = args.pop().unwrap();
+                Expr::Coalesce(Box::new((current_lhs, rhs, pos)))
+            }
 
             #[cfg(not(feature = "no_object"))]
             Token::Period => {
                 let rhs = args.pop().unwrap();
                 let current_lhs = args.pop().unwrap();
-                make_dot_expr(current_lhs, rhs, pos)?
+                make_dot_expr(current_lhs, rhs, pos, false)?
+            }
+            #[cfg(not(feature = "no_object"))]
+            Token::Elvis => {
+                let rhs = args.pop().unwrap();
+                let current_lhs = args.pop().unwrap();
+                make_dot_expr(current_lhs, rhs, pos, true)?
             }
 
             Token::Custom(s)
@@ -2329,7 +2885,7 @@ fn parse_binary_op(
                     .unwrap_or(false) =>
             {
                 // Accept non-native functions for custom operators
-                let op = (op.0, false, op.2, op.3);
+                let op = (op.0, false, op.2, op.3, op.4);
                 Expr::FnCall(Box::new((op, None, hash, args, None)))
             }
 
@@ -2562,19 +3118,8 @@ fn parse_for(
     #[cfg(not(feature = "unchecked"))]
     settings.ensure_level_within_max_limit(state.max_expr_depth)?;
 
-    // for name ...
-    let name = match input.next().unwrap() {
-        // Variable name
-        (Token::Identifier(s), _) => s,
-        // Reserved keyword
-        (Token::Reserved(s), pos) if is_valid_identifier(s.chars()) => {
-            return Err(PERR::Reserved(s).into_err(pos));
-        }
-        // Bad identifier
-        (Token::LexError(err), pos) => return Err(err.into_err(pos)),
-        // Not a variable name
-        (_, pos) => return Err(PERR::VariableExpected.into_err(pos)),
-    };
+    // for name ... | for [pattern] ... | for #{pattern} ...
+    let pattern = parse_pattern(input, state, lib, settings.level_up())?;
 
     // for name in ...
     match input.next().unwrap() {
@@ -2593,45 +3138,300 @@ fn parse_for(
     let expr = parse_expr(input, state, lib, settings.level_up())?;
 
     let prev_stack_len = state.stack.len();
-    state.stack.push((name.clone(), ScopeEntryType::Normal));
+    state.stack.extend(
+        pattern
+            .names()
+            .into_iter()
+            .map(|name| (name.to_string(), ScopeEntryType::Normal)),
+    );
 
     settings.is_breakable = true;
     let body = parse_block(input, state, lib, settings.level_up())?;
 
     state.stack.truncate(prev_stack_len);
 
-    Ok(Stmt::For(Box::new((name, expr, body, token_pos))))
+    Ok(Stmt::For(Box::new((pattern, expr, body, token_pos))))
 }
 
-/// Parse a variable definition statement.
-fn parse_let(
+/// Parse a destructuring pattern: a variable name, an array pattern or an object map pattern.
+fn parse_pattern(
     input: &mut TokenStream,
     state: &mut ParseState,
     lib: &mut FunctionsLib,
-    var_type: ScopeEntryType,
-    mut settings: ParseSettings,
-) -> Result<Stmt, ParseError> {
-    // let/const... (specified in `var_type`)
-    let token_pos = input.next().unwrap().1;
-    settings.pos = token_pos;
-
+    settings: ParseSettings,
+) -> Result<Pattern, ParseError> {
     #[cfg(not(feature = "unchecked"))]
     settings.ensure_level_within_max_limit(state.max_expr_depth)?;
 
-    // let name ...
-    let (name, pos) = match input.next().unwrap() {
-        (Token::Identifier(s), pos) => (s, pos),
-        (Token::Reserved(s), pos) if is_valid_identifier(s.chars()) => {
-            return Err(PERR::Reserved(s).into_err(pos));
-        }
-        (Token::LexError(err), pos) => return Err(err.into_err(pos)),
-        (_, pos) => return Err(PERR::VariableExpected.into_err(pos)),
-    };
+    match input.next().unwrap() {
+        // Variable name
+        (Token::Identifier(s), pos) => Ok(Pattern::Variable(Box::new((s, pos)))),
+        // [ pattern, ... ]
+        #[cfg(not(feature = "no_index"))]
+        (Token::LeftBracket, pos) => {
+            const MISSING_RBRACKET: &str = "to end this array pattern";
 
-    // let name = ...
-    if match_token(input, Token::Equals)? {
-        // let name = expr
-        let init_value = parse_expr(input, state, lib, settings.level_up())?;
+            let mut items = StaticVec::new();
+            let mut rest = None;
+
+            loop {
+                match input.peek().unwrap() {
+                    (Token::RightBracket, _) => {
+                        eat_token(input, Token::RightBracket);
+                        break;
+                    }
+                    // ..rest
+                    (Token::Period, _) => {
+                        rest = parse_pattern_rest(input)?;
+
+                        match input.next().unwrap() {
+                            (Token::RightBracket, _) => break,
+                            (Token::LexError(err), pos) => return Err(err.into_err(pos)),
+                            (_, pos) => {
+                                return Err(PERR::MissingToken(
+                                    Token::RightBracket.into(),
+                                    MISSING_RBRACKET.into(),
+                                )
+                                .into_err(pos))
+                            }
+                        }
+                    }
+                    _ => {
+                        let pattern = parse_pattern(input, state, lib, settings.level_up())?;
+                        let default = parse_pattern_default(input, state, lib, settings)?;
+                        items.push((pattern, default));
+                    }
+                }
+
+                match input.peek().unwrap() {
+                    (Token::Comma, _) => {
+                        eat_token(input, Token::Comma);
+                    }
+                    (Token::RightBracket, _) => (),
+                    (Token::LexError(err), pos) => return Err(err.clone().into_err(*pos)),
+                    (_, pos) => {
+                        return Err(PERR::MissingToken(
+                            Token::RightBracket.into(),
+                            MISSING_RBRACKET.into(),
+                        )
+                        .into_err(*pos))
+                    }
+                }
+            }
+
+            Ok(Pattern::Array(Box::new((items, rest, pos))))
+        }
+        // #{ key: pattern, ... }
+        #[cfg(not(feature = "no_object"))]
+        (Token::MapStart, pos) => {
+            const MISSING_RBRACE: &str = "to end this object map pattern";
+
+            let mut items = StaticVec::new();
+            let mut rest = None;
+
+            loop {
+                match input.peek().unwrap() {
+                    (Token::RightBrace, _) => {
+                        eat_token(input, Token::RightBrace);
+                        break;
+                    }
+                    // ..rest
+                    (Token::Period, _) => {
+                        rest = parse_pattern_rest(input)?;
+
+                        match input.next().unwrap() {
+                            (Token::RightBrace, _) => break,
+                            (Token::LexError(err), pos) => return Err(err.into_err(pos)),
+                            (_, pos) => {
+                                return Err(PERR::MissingToken(
+                                    Token::RightBrace.into(),
+                                    MISSING_RBRACE.into(),
+                                )
+                                .into_err(pos))
+                            }
+                        }
+                    }
+                    _ => (),
+                }
+
+                let (key, key_pos) = match input.next().unwrap() {
+                    (Token::Identifier(s), pos) => (s, pos),
+                    (Token::StringConstant(s), pos) => (s, pos),
+                    (Token::Reserved(s), pos) if is_valid_identifier(s.chars()) => {
+                        return Err(PERR::Reserved(s).into_err(pos));
+                    }
+                    (Token::LexError(err), pos) => return Err(err.into_err(pos)),
+                    (_, pos) => return Err(PERR::PropertyExpected.into_err(pos)),
+                };
+
+                // key: pattern | key
+                let pattern = if match_token(input, Token::Colon)? {
+                    parse_pattern(input, state, lib, settings.level_up())?
+                } else if is_valid_identifier(key.chars()) {
+                    Pattern::Variable(Box::new((key.clone(), key_pos)))
+                } else {
+                    return Err(PERR::MissingToken(
+                        Token::Colon.into(),
+                        format!(
+                            "to follow the property '{}' in this object map pattern",
+                            key
+                        ),
+                    )
+                    .into_err(input.peek().unwrap().1));
+                };
+                let default = parse_pattern_default(input, state, lib, settings)?;
+                items.push((
+                    (Into::<ImmutableString>::into(key), key_pos),
+                    pattern,
+                    default,
+                ));
+
+                match input.peek().unwrap() {
+                    (Token::Comma, _) => {
+                        eat_token(input, Token::Comma);
+                    }
+                    (Token::RightBrace, _) => (),
+                    (Token::LexError(err), pos) => return Err(err.clone().into_err(*pos)),
+                    (_, pos) => {
+                        return Err(PERR::MissingToken(
+                            Token::RightBrace.into(),
+                            MISSING_RBRACE.into(),
+                        )
+                        .into_err(*pos))
+                    }
+                }
+            }
+
+            // Check for duplicating properties
+            items
+                .iter()
+                .enumerate()
+                .try_for_each(|(i, ((k1, _), _, _))| {
+                    items
+                        .iter()
+                        .skip(i + 1)
+                        .find(|((k2, _), _, _)| k2 == k1)
+                        .map_or_else(|| Ok(()), |((k2, pos), _, _)| Err((k2, *pos)))
+                })
+                .map_err(|(key, pos)| PERR::DuplicatedProperty(key.to_string()).into_err(pos))?;
+
+            Ok(Pattern::Map(Box::new((items, rest, pos))))
+        }
+        // Reserved keyword
+        (Token::Reserved(s), pos) if is_valid_identifier(s.chars()) => {
+            Err(PERR::Reserved(s).into_err(pos))
+        }
+        // Bad identifier
+        (Token::LexError(err), pos) => Err(err.into_err(pos)),
+        // Not a variable name
+        (_, pos) => Err(PERR::VariableExpected.into_err(pos)),
+    }
+}
+
+/// Parse the `..rest` (or `..`) item at the end of a destructuring pattern.
+#[cfg(any(not(feature = "no_index"), not(feature = "no_object")))]
+fn parse_pattern_rest(input: &mut TokenStream) -> Result<Option<(String, Position)>, ParseError> {
+    eat_token(input, Token::Period);
+
+    match input.next().unwrap() {
+        (Token::Period, _) => (),
+        (Token::LexError(err), pos) => return Err(err.into_err(pos)),
+        (_, pos) => {
+            return Err(PERR::MissingToken(
+                Token::Period.into(),
+                "to form '..' for the rest of the pattern".into(),
+            )
+            .into_err(pos))
+        }
+    }
+
+    match input.peek().unwrap() {
+        (Token::Identifier(_), _) => match input.next().unwrap() {
+            (Token::Identifier(s), pos) => Ok(Some((s, pos))),
+            _ => unreachable!(),
+        },
+        (Token::Reserved(s), pos) if is_valid_identifier(s.chars()) => {
+            Err(PERR::Reserved(s.clone()).into_err(*pos))
+        }
+        _ => Ok(None),
+    }
+}
+
+/// Parse the optional `= default` following an item in a destructuring pattern.
+#[cfg(any(not(feature = "no_index"), not(feature = "no_object")))]
+fn parse_pattern_default(
+    input: &mut TokenStream,
+    state: &mut ParseState,
+    lib: &mut FunctionsLib,
+    settings: ParseSettings,
+) -> Result<Option<Expr>, ParseError> {
+    if match_token(input, Token::Equals)? {
+        Ok(Some(parse_expr(input, state, lib, settings.level_up())?))
+    } else {
+        Ok(None)
+    }
+}
+
+/// Parse a variable definition statement.
+fn parse_let(
+    input: &mut TokenStream,
+    state: &mut ParseState,
+    lib: &mut FunctionsLib,
+    var_type: ScopeEntryType,
+    mut settings: ParseSettings,
+) -> Result<Stmt, ParseError> {
+    // let/const... (specified in `var_type`)
+    let token_pos = input.next().unwrap().1;
+    settings.pos = token_pos;
+
+    #[cfg(not(feature = "unchecked"))]
+    settings.ensure_level_within_max_limit(state.max_expr_depth)?;
+
+    // let [pattern] = expr | let #{pattern} = expr
+    match input.peek().unwrap() {
+        (Token::LeftBracket, _) | (Token::MapStart, _) if var_type == ScopeEntryType::Normal => {
+            let pattern = parse_pattern(input, state, lib, settings.level_up())?;
+
+            match input.next().unwrap() {
+                (Token::Equals, _) => (),
+                (Token::LexError(err), pos) => return Err(err.into_err(pos)),
+                (_, pos) => {
+                    return Err(PERR::MissingToken(
+                        Token::Equals.into(),
+                        "to provide the value to destructure".into(),
+                    )
+                    .into_err(pos))
+                }
+            }
+
+            let init_value = parse_expr(input, state, lib, settings.level_up())?;
+
+            state.stack.extend(
+                pattern
+                    .names()
+                    .into_iter()
+                    .map(|name| (name.to_string(), ScopeEntryType::Normal)),
+            );
+
+            return Ok(Stmt::LetPattern(Box::new((pattern, init_value, token_pos))));
+        }
+        _ => (),
+    }
+
+    // let name ...
+    let (name, pos) = match input.next().unwrap() {
+        (Token::Identifier(s), pos) => (s, pos),
+        (Token::Reserved(s), pos) if is_valid_identifier(s.chars()) => {
+            return Err(PERR::Reserved(s).into_err(pos));
+        }
+        (Token::LexError(err), pos) => return Err(err.into_err(pos)),
+        (_, pos) => return Err(PERR::VariableExpected.into_err(pos)),
+    };
+
+    // let name = ...
+    if match_token(input, Token::Equals)? {
+        // let name = expr
+        let init_value = parse_expr(input, state, lib, settings.level_up())?;
 
         match var_type {
             // let name = expr
@@ -2672,6 +3472,34 @@ fn parse_let(
     }
 }
 
+/// Get the path of a module if it is a constant string.
+#[cfg(not(feature = "no_module"))]
+fn constant_path(expr: &Expr) -> Option<String> {
+    match expr {
+        Expr::StringConstant(x) => Some(x.0.to_string()),
+        _ => None,
+    }
+}
+
+/// Check calls to functions not defined in the script against the sandbox profile, if any.
+fn check_calls(state: &ParseState, lib: &FunctionsLib) -> Result<(), ParseError> {
+    let sandbox = match &state.engine.sandbox {
+        Some(sandbox) => sandbox,
+        None => return Ok(()),
+    };
+
+    let defined: HashSet<_> = lib.values().map(|f| f.name.as_str()).collect();
+
+    state
+        .calls
+        .iter()
+        .filter(|(name, _)| !defined.contains(name.as_str()))
+        .try_for_each(|(name, pos)| match sandbox.check_function(None, name) {
+            Some(err) => Err(PERR::SandboxViolation(err).into_err(*pos)),
+            None => Ok(()),
+        })
+}
+
 /// Parse an import statement.
 #[cfg(not(feature = "no_module"))]
 fn parse_import(
@@ -2689,6 +3517,7 @@ fn parse_import(
 
     // import expr ...
     let expr = parse_expr(input, state, lib, settings.level_up())?;
+    state.check_module(&expr)?;
 
     // import expr as ...
     if !match_token(input, Token::As)? {
@@ -2705,7 +3534,7 @@ fn parse_import(
         (_, pos) => return Err(PERR::VariableExpected.into_err(pos)),
     };
 
-    state.modules.push(name.clone());
+    state.modules.push((name.clone(), constant_path(&expr)));
 
     Ok(Stmt::Import(Box::new((
         expr,
@@ -2718,42 +3547,100 @@ fn parse_import(
 #[cfg(not(feature = "no_module"))]
 fn parse_export(
     input: &mut TokenStream,
-    _state: &mut ParseState,
-    _lib: &mut FunctionsLib,
+    state: &mut ParseState,
+    lib: &mut FunctionsLib,
     mut settings: ParseSettings,
 ) -> Result<Stmt, ParseError> {
     let token_pos = eat_token(input, Token::Export);
     settings.pos = token_pos;
 
     #[cfg(not(feature = "unchecked"))]
-    settings.ensure_level_within_max_limit(_state.max_expr_depth)?;
+    settings.ensure_level_within_max_limit(state.max_expr_depth)?;
+
+    fn parse_name(input: &mut TokenStream) -> Result<(String, Position), ParseError> {
+        match input.next().unwrap() {
+            (Token::Identifier(s), pos) => Ok((s, pos)),
+            (Token::Reserved(s), pos) if is_valid_identifier(s.chars()) => {
+                Err(PERR::Reserved(s).into_err(pos))
+            }
+            (Token::LexError(err), pos) => Err(err.into_err(pos)),
+            (_, pos) => Err(PERR::VariableExpected.into_err(pos)),
+        }
+    }
+
+    fn parse_rename(input: &mut TokenStream) -> Result<Option<(String, Position)>, ParseError> {
+        if match_token(input, Token::As)? {
+            parse_name(input).map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+
+    let item = match input.peek().unwrap() {
+        // export let id = expr, or export const id = expr
+        (Token::Let, _) => Some(ExportItem::Declaration(Box::new(parse_let(
+            input,
+            state,
+            lib,
+            ScopeEntryType::Normal,
+            settings.level_up(),
+        )?))),
+        (Token::Const, _) => Some(ExportItem::Declaration(Box::new(parse_let(
+            input,
+            state,
+            lib,
+            ScopeEntryType::Constant,
+            settings.level_up(),
+        )?))),
+        // export use expr as name
+        (Token::Reserved(s), _) if s == "use" => {
+            input.next().unwrap();
+            let expr = parse_expr(input, state, lib, settings.level_up())?;
+            state.check_module(&expr)?;
+            let rename = parse_rename(input)?;
+
+            if let Some((name, _)) = &rename {
+                state.modules.push((name.clone(), constant_path(&expr)));
+            }
+
+            Some(ExportItem::Module(expr, rename))
+        }
+        // export *
+        (Token::Multiply, pos) => {
+            let pos = *pos;
+            eat_token(input, Token::Multiply);
+            Some(ExportItem::AllVariables(pos))
+        }
+        _ => None,
+    };
+
+    if let Some(item) = item {
+        return Ok(Stmt::Export(Box::new((
+            Some(item).into_iter().collect(),
+            token_pos,
+        ))));
+    }
 
     let mut exports = StaticVec::new();
 
     loop {
-        let (id, id_pos) = match input.next().unwrap() {
-            (Token::Identifier(s), pos) => (s.clone(), pos),
-            (Token::Reserved(s), pos) if is_valid_identifier(s.chars()) => {
-                return Err(PERR::Reserved(s).into_err(pos));
-            }
-            (Token::LexError(err), pos) => return Err(err.into_err(pos)),
-            (_, pos) => return Err(PERR::VariableExpected.into_err(pos)),
-        };
+        let (id, id_pos) = parse_name(input)?;
 
-        let rename = if match_token(input, Token::As)? {
-            match input.next().unwrap() {
-                (Token::Identifier(s), pos) => Some((s.clone(), pos)),
-                (Token::Reserved(s), pos) if is_valid_identifier(s.chars()) => {
-                    return Err(PERR::Reserved(s).into_err(pos));
-                }
-                (Token::LexError(err), pos) => return Err(err.into_err(pos)),
-                (_, pos) => return Err(PERR::VariableExpected.into_err(pos)),
+        if match_token(input, Token::DoubleColon)? {
+            // export module::*
+            if match_token(input, Token::Multiply)? {
+                exports.push(ExportItem::Member((id, id_pos), None, None));
+            } else {
+                // export module::id as name
+                let member = parse_name(input)?;
+                let rename = parse_rename(input)?;
+                exports.push(ExportItem::Member((id, id_pos), Some(member), rename));
             }
         } else {
-            None
-        };
-
-        exports.push(((id, id_pos), rename));
+            // export id as name
+            let rename = parse_rename(input)?;
+            exports.push(ExportItem::Variable((id, id_pos), rename));
+        }
 
         match input.peek().unwrap() {
             (Token::Comma, _) => {
@@ -2771,15 +3658,29 @@ fn parse_export(
     }
 
     // Check for duplicating parameters
-    exports
+    let exported_names: Vec<_> = exports
+        .iter()
+        .filter_map(|item| match item {
+            ExportItem::Variable((id, pos), _) => Some((id, *pos)),
+            ExportItem::Member(_, Some((id, pos)), rename) => Some(
+                rename
+                    .as_ref()
+                    .map(|(name, pos)| (name, *pos))
+                    .unwrap_or((id, *pos)),
+            ),
+            _ => None,
+        })
+        .collect();
+
+    exported_names
         .iter()
         .enumerate()
-        .try_for_each(|(i, ((id1, _), _))| {
-            exports
+        .try_for_each(|(i, (id1, _))| {
+            exported_names
                 .iter()
                 .skip(i + 1)
-                .find(|((id2, _), _)| id2 == id1)
-                .map_or_else(|| Ok(()), |((id2, pos), _)| Err((id2, *pos)))
+                .find(|(id2, _)| id2 == id1)
+                .map_or_else(|| Ok(()), |(id2, pos)| Err((id2, *pos)))
         })
         .map_err(|(id2, pos)| PERR::DuplicatedExport(id2.to_string()).into_err(pos))?;
 
@@ -2937,11 +3838,9 @@ fn parse_stmt(
                     };
 
                     let func = parse_fn(input, &mut new_state, lib, access, settings)?;
+                    state.calls.append(&mut new_state.calls);
 
-                    // Qualifiers (none) + function name + number of arguments.
-                    let hash = calc_fn_hash(empty(), &func.name, func.params.len(), empty());
-
-                    lib.insert(hash, func);
+                    lib.insert(calc_script_fn_hash(&func), func);
 
                     Ok(None)
                 }
@@ -2954,6 +3853,17 @@ fn parse_stmt(
             }
         }
 
+        // struct ...
+        #[cfg(not(feature = "no_object"))]
+        #[cfg(not(feature = "no_function"))]
+        Token::Struct if !settings.is_global => {
+            Err(PERR::WrongStructDefinition.into_err(settings.pos))
+        }
+
+        #[cfg(not(feature = "no_object"))]
+        #[cfg(not(feature = "no_function"))]
+        Token::Struct => parse_struct(input, state, lib, settings).map(|_| None),
+
         Token::If => parse_if(input, state, lib, settings.level_up()).map(Some),
         Token::While => parse_while(input, state, lib, settings.level_up()).map(Some),
         Token::Loop => parse_loop(input, state, lib, settings.level_up()).map(Some),
@@ -3054,6 +3964,10 @@ fn parse_fn(
     };
 
     let mut params = Vec::new();
+    let mut param_types: StaticVec<_> = Default::default();
+    let mut defaults = StaticVec::new();
+    #[cfg_attr(feature = "no_index", allow(unused_mut))]
+    let mut rest_param: Option<String> = None;
 
     if !match_token(input, Token::RightParen)? {
         let sep_err = format!("to separate the parameters of function '{}'", name);
@@ -3062,9 +3976,83 @@ fn parse_fn(
             match input.next().unwrap() {
                 (Token::RightParen, _) => break,
                 (Token::Identifier(s), pos) => {
+                    // name: type
+                    if match_token(input, Token::Colon)? {
+                        match input.next().unwrap() {
+                            (Token::Identifier(typ), _) => param_types.push(Some(typ)),
+                            (Token::LexError(err), pos) => return Err(err.into_err(pos)),
+                            (_, pos) => {
+                                return Err(PERR::MissingToken(
+                                    "type name".into(),
+                                    format!("for the type of parameter '{}'", s),
+                                )
+                                .into_err(pos))
+                            }
+                        }
+                    } else {
+                        param_types.push(None);
+                    }
+
+                    // Default values can refer to the parameters before, but not this one
+                    if match_token(input, Token::Equals)? {
+                        defaults.push(parse_expr(input, state, lib, settings.level_up())?);
+                    } else if !defaults.is_empty() {
+                        return Err(PERR::MissingToken(
+                            Token::Equals.into(),
+                            format!(
+                                "to give parameter '{}' a default value, as it follows a parameter with one",
+                                s
+                            ),
+                        )
+                        .into_err(input.peek().unwrap().1));
+                    }
+
                     state.stack.push((s.clone(), ScopeEntryType::Normal));
                     params.push((s, pos))
                 }
+                // ...rest
+                #[cfg(not(feature = "no_index"))]
+                (Token::Period, _) => {
+                    for _ in 0..2 {
+                        match input.next().unwrap() {
+                            (Token::Period, _) => (),
+                            (Token::LexError(err), pos) => return Err(err.into_err(pos)),
+                            (_, pos) => {
+                                return Err(PERR::MissingToken(
+                                    Token::Period.into(),
+                                    "to form '...' for the rest parameter".into(),
+                                )
+                                .into_err(pos))
+                            }
+                        }
+                    }
+
+                    match input.next().unwrap() {
+                        (Token::Identifier(s), pos) => params.push((s, pos)),
+                        (Token::LexError(err), pos) => return Err(err.into_err(pos)),
+                        (_, pos) => return Err(PERR::VariableExpected.into_err(pos)),
+                    }
+
+                    match input.next().unwrap() {
+                        (Token::RightParen, _) => (),
+                        (Token::LexError(err), pos) => return Err(err.into_err(pos)),
+                        (_, pos) => {
+                            return Err(PERR::MissingToken(
+                                Token::RightParen.into(),
+                                format!(
+                                "as the rest parameter must be the last parameter of function '{}'",
+                                name
+                            ),
+                            )
+                            .into_err(pos))
+                        }
+                    }
+
+                    let (s, _) = params.last().unwrap();
+                    state.stack.push((s.clone(), ScopeEntryType::Normal));
+                    rest_param = Some(s.clone());
+                    break;
+                }
                 (Token::LexError(err), pos) => return Err(err.into_err(pos)),
                 (_, pos) => {
                     return Err(PERR::MissingToken(
@@ -3110,14 +4098,24 @@ fn parse_fn(
         (_, pos) => return Err(PERR::FnMissingBody(name).into_err(*pos)),
     };
 
-    let params: StaticVec<_> = params.into_iter().map(|(p, _)| p).collect();
+    let mut params: StaticVec<_> = params.into_iter().map(|(p, _)| p).collect();
+
+    // The rest parameter is kept separately
+    if rest_param.is_some() {
+        params.pop();
+    }
+
+    // Only keep the parameter types if any is declared
+    if param_types.iter().all(Option::is_none) {
+        param_types.clear();
+    }
 
     #[cfg(not(feature = "no_closure"))]
     let externals = state
         .externals
         .iter()
         .map(|(name, _)| name)
-        .filter(|name| !params.contains(name))
+        .filter(|name| !params.contains(name) && rest_param.as_ref() != Some(name))
         .cloned()
         .collect();
 
@@ -3125,13 +4123,269 @@ fn parse_fn(
         name: name.into(),
         access,
         params,
+        param_types,
+        defaults,
+        rest_param,
         #[cfg(not(feature = "no_closure"))]
         externals,
         body,
+        is_generator: state.has_yield,
         pos: settings.pos,
     })
 }
 
+/// Calculate the hash of a script-defined function in the functions library.
+#[cfg(not(feature = "no_function"))]
+fn calc_script_fn_hash(func: &ScriptFnDef) -> u64 {
+    if func.rest_param.is_some() {
+        let hash = calc_variadic_fn_hash(empty(), &func.name, func.num_required_params());
+
+        // Overloads with typed parameters are told apart by the parameter types
+        if func.is_typed() {
+            hash ^ calc_typed_fn_hash(
+                &func.name,
+                func.param_types
+                    .iter()
+                    .map(|t| t.as_ref().map(String::as_str)),
+            )
+        } else {
+            hash
+        }
+    } else if func.is_typed() {
+        // Function name + parameter types.
+        calc_typed_fn_hash(
+            &func.name,
+            func.param_types
+                .iter()
+                .map(|t| t.as_ref().map(String::as_str)),
+        )
+    } else {
+        // Qualifiers (none) + function name + number of arguments.
+        calc_fn_hash(empty(), &func.name, func.params.len(), empty())
+    }
+}
+
+/// Parse a record type definition.
+///
+/// The record type is turned into script-defined functions added to the functions library:
+/// a constructor named after the record type taking the fields as parameters, plus the methods
+/// and property accessors, named after the record type so that they can only be called on
+/// instances of it.
+#[cfg(not(feature = "no_object"))]
+#[cfg(not(feature = "no_function"))]
+fn parse_struct(
+    input: &mut TokenStream,
+    state: &mut ParseState,
+    lib: &mut FunctionsLib,
+    mut settings: ParseSettings,
+) -> Result<(), ParseError> {
+    // struct ...
+    settings.pos = eat_token(input, Token::Struct);
+
+    #[cfg(not(feature = "unchecked"))]
+    settings.ensure_level_within_max_limit(state.max_expr_depth)?;
+
+    // struct name ...
+    let name = match input.next().unwrap() {
+        (Token::Identifier(s), pos) if state.structs.contains(&s) => {
+            return Err(PERR::DuplicatedStruct(s).into_err(pos));
+        }
+        (Token::Identifier(s), _) => s,
+        (Token::Reserved(s), pos) if is_valid_identifier(s.chars()) => {
+            return Err(PERR::Reserved(s).into_err(pos));
+        }
+        (Token::LexError(err), pos) => return Err(err.into_err(pos)),
+        (_, pos) => return Err(PERR::VariableExpected.into_err(pos)),
+    };
+
+    state.structs.push(name.clone());
+
+    // struct name { ...
+    match input.next().unwrap() {
+        (Token::LeftBrace, _) => (),
+        (Token::LexError(err), pos) => return Err(err.into_err(pos)),
+        (_, pos) => {
+            return Err(PERR::MissingToken(
+                Token::LeftBrace.into(),
+                format!("to start the declaration of record type '{}'", name),
+            )
+            .into_err(pos))
+        }
+    }
+
+    let fn_settings = ParseSettings {
+        allow_if_expr: true,
+        allow_stmt_expr: true,
+        allow_anonymous_fn: true,
+        is_global: false,
+        is_function_scope: true,
+        is_breakable: false,
+        level: 0,
+        pos: settings.pos,
+    };
+
+    // The default values of fields are evaluated inside the constructor
+    let mut ctor_state = ParseState::new(
+        state.engine,
+        #[cfg(not(feature = "unchecked"))]
+        state.max_function_expr_depth,
+        #[cfg(not(feature = "unchecked"))]
+        state.max_function_expr_depth,
+    );
+
+    let mut fields: StaticVec<(String, Position)> = Default::default();
+    let mut defaults = StaticVec::new();
+    let mut methods = Module::new();
+
+    loop {
+        let (token, pos) = input.next().unwrap();
+
+        // get prop() { ... } | set prop(value) { ... }
+        let accessor = match token {
+            Token::Identifier(ref s) if s == "get" || s == "set" => match input.peek().unwrap() {
+                (Token::Identifier(_), _) => Some(s == "get"),
+                _ => None,
+            },
+            _ => None,
+        };
+
+        match (token, accessor) {
+            (Token::RightBrace, _) => break,
+
+            // fn method(...) { ... } | get prop() { ... } | set prop(value) { ... }
+            (Token::Fn, _) | (_, Some(_)) => {
+                let mut new_state = ParseState::new(
+                    state.engine,
+                    #[cfg(not(feature = "unchecked"))]
+                    state.max_function_expr_depth,
+                    #[cfg(not(feature = "unchecked"))]
+                    state.max_function_expr_depth,
+                );
+
+                let settings = ParseSettings { pos, ..fn_settings };
+                let mut func = parse_fn(input, &mut new_state, lib, FnAccess::Public, settings)?;
+                state.calls.append(&mut new_state.calls);
+
+                let fn_name = match accessor {
+                    Some(true) if func.params.is_empty() && func.rest_param.is_none() => {
+                        make_getter(&func.name)
+                    }
+                    Some(false) if func.params.len() == 1 && func.rest_param.is_none() => {
+                        make_setter(&func.name)
+                    }
+                    Some(is_getter) => {
+                        return Err(PERR::BadInput(format!(
+                            "Property {} '{}' of record type '{}' must take {}",
+                            if is_getter { "getter" } else { "setter" },
+                            func.name,
+                            name,
+                            if is_getter {
+                                "no parameters"
+                            } else {
+                                "exactly one parameter"
+                            }
+                        ))
+                        .into_err(func.pos))
+                    }
+                    None => func.name.to_string(),
+                };
+
+                func.name = make_record_fn_name(&name, &fn_name).into();
+                methods.set_script_fn(func.clone());
+                lib.insert(calc_script_fn_hash(&func), func);
+            }
+
+            // field | field = expr
+            (Token::Identifier(s), _) => {
+                if fields.iter().any(|(f, _)| *f == s) {
+                    return Err(PERR::StructDuplicatedField(name, s).into_err(pos));
+                }
+
+                // Default values can refer to the fields before, but not this one
+                if match_token(input, Token::Equals)? {
+                    let settings = ParseSettings { pos, ..fn_settings };
+                    defaults.push(parse_expr(input, &mut ctor_state, lib, settings)?);
+                } else if !defaults.is_empty() {
+                    return Err(PERR::MissingToken(
+                        Token::Equals.into(),
+                        format!(
+                            "to give field '{}' a default value, as it follows a field with one",
+                            s
+                        ),
+                    )
+                    .into_err(input.peek().unwrap().1));
+                }
+
+                ctor_state.stack.push((s.clone(), ScopeEntryType::Normal));
+                fields.push((s, pos));
+
+                match input.peek().unwrap() {
+                    (Token::Comma, _) => {
+                        eat_token(input, Token::Comma);
+                    }
+                    (Token::RightBrace, _) => (),
+                    (Token::LexError(_), _) => (),
+                    (_, pos) => {
+                        return Err(PERR::MissingToken(
+                            Token::Comma.into(),
+                            format!("to separate the fields of record type '{}'", name),
+                        )
+                        .into_err(*pos))
+                    }
+                }
+            }
+
+            (Token::Reserved(s), _) if is_valid_identifier(s.chars()) => {
+                return Err(PERR::Reserved(s).into_err(pos));
+            }
+            (Token::LexError(err), _) => return Err(err.into_err(pos)),
+            (Token::EOF, _) => {
+                return Err(PERR::MissingToken(
+                    Token::RightBrace.into(),
+                    format!("to end the declaration of record type '{}'", name),
+                )
+                .into_err(pos))
+            }
+            (_, _) => return Err(PERR::PropertyExpected.into_err(pos)),
+        }
+    }
+
+    state.calls.append(&mut ctor_state.calls);
+
+    // The constructor simply collects its parameters into the fields of a new record
+    let values = fields
+        .iter()
+        .map(|(field, pos)| {
+            let value = Expr::Variable(Box::new(((field.clone(), *pos), None, 0, None)));
+            ((field.as_str().into(), *pos), value)
+        })
+        .collect();
+
+    let body = Stmt::Expr(Box::new(Expr::Record(Box::new((
+        name.clone().into(),
+        Expr::Map(Box::new((values, settings.pos))),
+        RecordMethods::new(methods),
+    )))));
+
+    let func = ScriptFnDef {
+        name: name.into(),
+        access: FnAccess::Public,
+        params: fields.into_iter().map(|(field, _)| field).collect(),
+        param_types: Default::default(),
+        defaults,
+        rest_param: None,
+        #[cfg(not(feature = "no_closure"))]
+        externals: Default::default(),
+        body,
+        is_generator: false,
+        pos: settings.pos,
+    };
+
+    lib.insert(calc_script_fn_hash(&func), func);
+
+    Ok(())
+}
+
 /// Creates a curried expression from a list of external variables
 #[cfg(not(feature = "no_function"))]
 fn make_curry_from_externals(
@@ -3164,14 +4418,14 @@ fn make_curry_from_externals(
     let hash = calc_fn_hash(empty(), KEYWORD_FN_PTR_CURRY, num_externals, empty());
 
     let fn_call = Expr::FnCall(Box::new((
-        (KEYWORD_FN_PTR_CURRY.into(), false, false, pos),
+        (KEYWORD_FN_PTR_CURRY.into(), false, false, false, pos),
         None,
         hash,
         args,
         None,
     )));
 
-    let expr = Expr::Dot(Box::new((fn_expr, fn_call, pos)));
+    let expr = Expr::Dot(Box::new((fn_expr, fn_call, pos, false)));
 
     // If there are captured variables, convert the entire expression into a statement block,
     // then insert the relevant `Share` statements.
@@ -3302,9 +4556,13 @@ fn parse_anon_fn(
         name: fn_name.clone(),
         access: FnAccess::Public,
         params,
+        param_types: Default::default(),
+        defaults: Default::default(),
+        rest_param: None,
         #[cfg(not(feature = "no_closure"))]
         externals: Default::default(),
         body,
+        is_generator: false,
         pos: settings.pos,
     };
 
@@ -3357,6 +4615,8 @@ impl Engine {
             }
         }
 
+        check_calls(&state, &functions)?;
+
         let expr = vec![Stmt::Expr(Box::new(expr))];
 
         Ok(
@@ -3426,6 +4686,8 @@ impl Engine {
             }
         }
 
+        check_calls(&state, &functions)?;
+
         Ok((statements, functions.into_iter().map(|(_, v)| v).collect()))
     }
 
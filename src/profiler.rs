@@ -0,0 +1,281 @@
+//! Module defining a profiler for calls to script-defined and native Rust functions.
+
+use crate::fn_native::Shared;
+
+use crate::stdlib::{
+    cmp::Reverse, collections::HashMap, fmt::Write, format, string::String, vec::Vec,
+};
+
+#[cfg(not(feature = "sync"))]
+use crate::stdlib::cell::RefCell;
+
+#[cfg(feature = "sync")]
+use crate::stdlib::sync::RwLock;
+
+#[cfg(not(feature = "no_std"))]
+use crate::stdlib::time::Duration;
+
+#[cfg(not(feature = "no_std"))]
+#[cfg(not(target_arch = "wasm32"))]
+use crate::stdlib::time::Instant;
+
+#[cfg(not(feature = "no_std"))]
+#[cfg(target_arch = "wasm32")]
+use instant::Instant;
+
+/// Statistics of all the calls to one function, collected by a `Profiler`.
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct ProfileEntry {
+    /// Name of the function.  Script-defined functions include their parameters, e.g. `foo(x,y)`.
+    pub name: String,
+    /// Is the function a native Rust function?
+    pub is_native: bool,
+    /// Number of calls to the function.
+    pub calls: u64,
+    /// Number of operations performed inside the function, including all the functions it calls.
+    /// Recursive calls are only counted once.
+    pub operations: u64,
+    /// Number of operations performed inside the function itself, excluding all the functions it calls.
+    pub self_operations: u64,
+    /// Total wall time spent inside the function, including all the functions it calls.
+    /// Recursive calls are only counted once.
+    ///
+    /// Not available under the `no_std` feature.
+    #[cfg(not(feature = "no_std"))]
+    pub time: Duration,
+}
+
+/// [INTERNALS] A function call being profiled.
+/// Exported under the `internals` feature only.
+///
+/// ## WARNING
+///
+/// This type is volatile and may change.
+#[derive(Debug, Clone)]
+pub struct ProfileFrame {
+    /// Name of the function.
+    name: String,
+    /// Is the function a native Rust function?
+    is_native: bool,
+    /// Number of operations performed before the function was called.
+    operations: u64,
+    /// Number of operations performed inside functions called by this function.
+    child_operations: u64,
+    /// Time when the function was called.
+    #[cfg(not(feature = "no_std"))]
+    started: Instant,
+}
+
+/// Statistics collected by a `Profiler`.
+#[derive(Debug, Clone, Default)]
+struct ProfileData {
+    /// Statistics of each function, keyed by name.
+    entries: HashMap<String, ProfileEntry>,
+    /// Operations performed inside each call stack, excluding called functions, keyed by the
+    /// names of the functions in the call stack separated by `;`.
+    stacks: HashMap<String, u64>,
+}
+
+/// A profiler which records the number of calls, operations and wall time of every function
+/// called by scripts.
+///
+/// All clones of a `Profiler` share the same statistics, so keep a clone after setting it into
+/// an `Engine` via `Engine::set_profiler` to read the results.
+///
+/// # Example
+///
+/// ```
+/// # fn main() -> Result<(), Box<rhai::EvalAltResult>> {
+/// # #[cfg(not(feature = "no_function"))]
+/// # {
+/// use rhai::{Engine, Profiler, INT};
+///
+/// let mut engine = Engine::new();
+/// let profiler = Profiler::new();
+///
+/// engine.set_profiler(profiler.clone());
+///
+/// engine.eval::<INT>("fn double(x) { x * 2 } double(double(21))")?;
+///
+/// let entries = profiler.entries();
+///
+/// assert_eq!(entries[0].name, "double(x)");
+/// assert_eq!(entries[0].calls, 2);
+///
+/// println!("{}", profiler.report());
+/// # }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Profiler(
+    #[cfg(not(feature = "sync"))] Shared<RefCell<ProfileData>>,
+    #[cfg(feature = "sync")] Shared<RwLock<ProfileData>>,
+);
+
+impl Profiler {
+    /// Create a new `Profiler`.
+    pub fn new() -> Self {
+        Default::default()
+    }
+    /// Get the statistics of all the functions called, sorted by the number of operations
+    /// performed, largest first.
+    pub fn entries(&self) -> Vec<ProfileEntry> {
+        #[cfg(not(feature = "sync"))]
+        let data = self.0.borrow();
+        #[cfg(feature = "sync")]
+        let data = self.0.read().unwrap();
+
+        let mut entries: Vec<_> = data.entries.values().cloned().collect();
+        entries.sort_by(|a, b| {
+            (Reverse(a.operations), &a.name).cmp(&(Reverse(b.operations), &b.name))
+        });
+        entries
+    }
+    /// Get a human-readable report of the statistics of all the functions called.
+    pub fn report(&self) -> String {
+        let mut report = String::new();
+
+        #[cfg(not(feature = "no_std"))]
+        writeln!(
+            report,
+            "{:<32} {:>10} {:>12} {:>12} {:>14}",
+            "function", "calls", "operations", "self", "time"
+        )
+        .unwrap();
+        #[cfg(feature = "no_std")]
+        writeln!(
+            report,
+            "{:<32} {:>10} {:>12} {:>12}",
+            "function", "calls", "operations", "self"
+        )
+        .unwrap();
+
+        for entry in self.entries() {
+            let name = if entry.is_native {
+                entry.name + " [native]"
+            } else {
+                entry.name
+            };
+
+            write!(
+                report,
+                "{:<32} {:>10} {:>12} {:>12}",
+                name, entry.calls, entry.operations, entry.self_operations
+            )
+            .unwrap();
+            #[cfg(not(feature = "no_std"))]
+            write!(report, " {:>14}", format!("{:?}", entry.time)).unwrap();
+            report.push('\n');
+        }
+
+        report
+    }
+    /// Get the operations performed inside each call stack in the _folded stacks_ format,
+    /// which can be turned into a flame graph by tools such as
+    /// [`inferno`](https://crates.io/crates/inferno) or
+    /// [`flamegraph.pl`](https://github.com/brendangregg/FlameGraph).
+    ///
+    /// Each line contains the names of the functions in a call stack separated by `;`, followed by
+    /// a space and the number of operations performed inside the last function.
+    pub fn folded_stacks(&self) -> String {
+        #[cfg(not(feature = "sync"))]
+        let data = self.0.borrow();
+        #[cfg(feature = "sync")]
+        let data = self.0.read().unwrap();
+
+        let mut stacks: Vec<_> = data.stacks.iter().collect();
+        stacks.sort();
+
+        let mut folded = String::new();
+        stacks
+            .into_iter()
+            .for_each(|(stack, operations)| writeln!(folded, "{} {}", stack, operations).unwrap());
+        folded
+    }
+    /// Clear all the statistics collected.
+    pub fn reset(&self) {
+        #[cfg(not(feature = "sync"))]
+        let mut data = self.0.borrow_mut();
+        #[cfg(feature = "sync")]
+        let mut data = self.0.write().unwrap();
+
+        data.entries.clear();
+        data.stacks.clear();
+    }
+
+    /// Start profiling a function call.
+    /// Called right after the operation for the call itself is counted, so that it is attributed
+    /// to the function.
+    pub(crate) fn enter(
+        stack: &mut Vec<ProfileFrame>,
+        name: String,
+        is_native: bool,
+        operations: u64,
+    ) {
+        stack.push(ProfileFrame {
+            name,
+            is_native,
+            operations: operations.saturating_sub(1),
+            child_operations: 0,
+            #[cfg(not(feature = "no_std"))]
+            started: Instant::now(),
+        });
+    }
+    /// Finish profiling the last function call started, and record its statistics.
+    pub(crate) fn exit(&self, stack: &mut Vec<ProfileFrame>, operations: u64) {
+        let ProfileFrame {
+            name,
+            is_native,
+            operations: start_operations,
+            child_operations,
+            #[cfg(not(feature = "no_std"))]
+            started,
+        } = stack.pop().expect("function call should be profiled");
+
+        let total_operations = operations - start_operations;
+        let self_operations = total_operations - child_operations;
+
+        // Count recursive calls only once, at the outermost call
+        let is_recursive = stack.iter().any(|f| f.name == name);
+
+        let path = stack
+            .iter()
+            .map(|f| f.name.as_str())
+            .chain(Some(name.as_str()))
+            .collect::<Vec<_>>()
+            .join(";");
+
+        if let Some(parent) = stack.last_mut() {
+            parent.child_operations += total_operations;
+        }
+
+        #[cfg(not(feature = "sync"))]
+        let mut data = self.0.borrow_mut();
+        #[cfg(feature = "sync")]
+        let mut data = self.0.write().unwrap();
+
+        *data.stacks.entry(path).or_default() += self_operations;
+
+        let entry = data
+            .entries
+            .entry(name.clone())
+            .or_insert_with(|| ProfileEntry {
+                name,
+                is_native,
+                ..Default::default()
+            });
+
+        entry.calls += 1;
+        entry.self_operations += self_operations;
+
+        if !is_recursive {
+            entry.operations += total_operations;
+
+            #[cfg(not(feature = "no_std"))]
+            {
+                entry.time += started.elapsed();
+            }
+        }
+    }
+}
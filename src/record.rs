@@ -0,0 +1,177 @@
+//! Module defining instances of record types declared in script with `struct`.
+
+use crate::any::{Dynamic, MapFormat};
+use crate::engine::Map;
+use crate::fn_native::Shared;
+use crate::module::Module;
+use crate::parser::ImmutableString;
+
+#[cfg(not(feature = "no_function"))]
+use crate::utils::StaticVec;
+
+use crate::stdlib::{
+    fmt,
+    hash::{Hash, Hasher},
+};
+
+/// The methods and property accessors declared with a record type.
+///
+/// Every record carries the methods of its record type, so that they can still be called after
+/// the record is passed to a script which does not declare the record type.
+///
+/// Hashed by the definitions of the methods, so the same declaration always hashes the same.
+#[derive(Debug, Clone, Default)]
+pub struct RecordMethods(Shared<Module>);
+
+impl RecordMethods {
+    /// Create a new table of methods from a module holding the functions implementing them.
+    pub(crate) fn new(methods: Module) -> Self {
+        Self(methods.into())
+    }
+}
+
+impl Hash for RecordMethods {
+    fn hash<H: Hasher>(&self, _state: &mut H) {
+        // Methods are not stored in any particular order, so sort them first
+        #[cfg(not(feature = "no_function"))]
+        {
+            let mut methods: StaticVec<_> = self.0.iter_script_fn().collect();
+
+            methods.sort_by(|a, b| {
+                a.name
+                    .cmp(&b.name)
+                    .then_with(|| a.params.len().cmp(&b.params.len()))
+                    .then_with(|| a.param_types.iter().cmp(b.param_types.iter()))
+            });
+
+            methods.iter().for_each(|fn_def| {
+                fn_def.name.hash(_state);
+                fn_def.params.hash(_state);
+                fn_def.param_types.hash(_state);
+                fn_def.defaults.hash(_state);
+                fn_def.rest_param.hash(_state);
+                fn_def.body.hash(_state);
+            });
+        }
+    }
+}
+
+/// An instance of a record type declared in script with `struct`.
+///
+/// A record holds a fixed set of named fields.  Unlike an object map, fields cannot be added
+/// or removed after construction, and `type_of` reports the name of the record type instead
+/// of `map`.
+///
+/// Not available under the `no_object` feature.
+///
+/// # Example
+///
+/// ```
+/// # #[cfg(not(feature = "no_function"))]
+/// # fn main() -> Result<(), Box<rhai::EvalAltResult>> {
+/// use rhai::{Engine, Record};
+///
+/// let engine = Engine::new();
+///
+/// let point = engine.eval::<Record>(
+///     r"
+///         struct Point { x, y = 0 }
+///         Point(42)
+///     ",
+/// )?;
+///
+/// assert_eq!(point.type_name(), "Point");
+/// assert_eq!(point.get("x").unwrap().as_int().unwrap(), 42);
+/// assert_eq!(point.get("y").unwrap().as_int().unwrap(), 0);
+/// # Ok(())
+/// # }
+/// # #[cfg(feature = "no_function")]
+/// # fn main() {}
+/// ```
+#[derive(Debug, Clone)]
+pub struct Record {
+    /// Name of the record type.
+    type_name: ImmutableString,
+    /// Values of the fields.
+    fields: Map,
+    /// Methods and property accessors of the record type, if declared in script.
+    methods: Option<Shared<Module>>,
+}
+
+impl Record {
+    /// Create a new record of a particular record type with a set of fields.
+    pub fn new(type_name: impl Into<ImmutableString>, fields: Map) -> Self {
+        Self {
+            type_name: type_name.into(),
+            fields,
+            methods: None,
+        }
+    }
+
+    /// Attach the methods and property accessors declared with the record type.
+    pub(crate) fn with_methods(mut self, methods: &RecordMethods) -> Self {
+        self.methods = Some(methods.0.clone());
+        self
+    }
+
+    /// Get the name of the record type.
+    pub fn type_name(&self) -> &str {
+        &self.type_name
+    }
+
+    /// Get the fields of the record.
+    pub fn fields(&self) -> &Map {
+        &self.fields
+    }
+
+    /// Get the value of a field, if the record has it.
+    pub fn get(&self, name: &str) -> Option<&Dynamic> {
+        self.fields.get(name)
+    }
+
+    /// Get a mutable reference to the value of a field, if the record has it.
+    ///
+    /// Fields cannot be added to a record, so there is no way to insert a new field.
+    pub fn get_mut(&mut self, name: &str) -> Option<&mut Dynamic> {
+        self.fields.get_mut(name)
+    }
+
+    /// Does the record have a particular field?
+    pub fn contains(&self, name: &str) -> bool {
+        self.fields.contains_key(name)
+    }
+
+    /// Consume the record, returning its fields as an object map.
+    pub fn into_fields(self) -> Map {
+        self.fields
+    }
+}
+
+impl fmt::Display for Record {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {:?}", self.type_name, MapFormat(&self.fields))
+    }
+}
+
+/// Get the name of the record type of a value, if it is a record.
+///
+/// Shared values are looked through.
+pub(crate) fn record_type_name(value: &Dynamic) -> Option<ImmutableString> {
+    value
+        .read_lock::<Record>()
+        .map(|record| record.type_name.clone())
+}
+
+/// Get the methods and property accessors carried by a value, if it is a record.
+///
+/// Shared values are looked through.
+pub(crate) fn record_methods(value: &Dynamic) -> Option<Shared<Module>> {
+    value
+        .read_lock::<Record>()
+        .and_then(|record| record.methods.clone())
+}
+
+/// Make the name of the function implementing a method or a property accessor of a record type.
+pub(crate) fn make_record_fn_name(type_name: &str, fn_name: &str) -> String {
+    format!("{}.{}", type_name, fn_name)
+}
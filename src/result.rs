@@ -80,6 +80,8 @@ pub enum EvalAltResult {
     ErrorMismatchOutputType(String, String, Position),
     /// Inappropriate member access.
     ErrorDotExpr(String, Position),
+    /// A value does not match a destructuring pattern. Wrapped value describes the mismatch.
+    ErrorPatternMismatch(String, Position),
     /// Arithmetic error encountered. Wrapped value is the error message.
     ErrorArithmetic(String, Position),
     /// Number of operations over maximum limit.
@@ -92,6 +94,16 @@ pub enum EvalAltResult {
     ErrorDataTooLarge(String, usize, usize, Position),
     /// The script is prematurely terminated.
     ErrorTerminated(Position),
+    /// A function, keyword or module is not allowed by the sandbox profile.
+    /// Wrapped value describes the item not allowed.
+    ErrorSandboxViolation(String, Position),
+    /// A native function not marked deterministic is called in deterministic mode.
+    /// Wrapped value is the function name.
+    ErrorNotDeterministic(String, Position),
+    /// A resumed script did not reach the same `yield` expressions, with the same values,
+    /// as when it was suspended.  Usually caused by a function with side effects or a result that
+    /// changes between runs.
+    ErrorResumeDiverged(Position),
     /// Run-time error encountered. Wrapped value is the error message.
     ErrorRuntime(String, Position),
 
@@ -102,6 +114,11 @@ pub enum EvalAltResult {
     /// Not an error: Value returned from a script via the `return` keyword.
     /// Wrapped value is the result value.
     Return(Dynamic, Position),
+    /// Not an error: Script suspended via the `yield` keyword.
+    /// Wrapped value is the yielded value.
+    ///
+    /// Only resumable evaluations (e.g. `Engine::eval_resumable`) can continue a suspended script.
+    Yield(Dynamic, Position),
 }
 
 impl EvalAltResult {
@@ -147,16 +164,21 @@ impl EvalAltResult {
             Self::ErrorMismatchOutputType(_, _, _) => "Output type is incorrect",
             Self::ErrorInExpr(_) => "Malformed 'in' expression",
             Self::ErrorDotExpr(_, _) => "Malformed dot expression",
+            Self::ErrorPatternMismatch(_, _) => "Value does not match the destructuring pattern",
             Self::ErrorArithmetic(_, _) => "Arithmetic error",
             Self::ErrorTooManyOperations(_) => "Too many operations",
             Self::ErrorTooManyModules(_) => "Too many modules imported",
             Self::ErrorStackOverflow(_) => "Stack overflow",
             Self::ErrorDataTooLarge(_, _, _, _) => "Data size exceeds maximum limit",
             Self::ErrorTerminated(_) => "Script terminated.",
+            Self::ErrorSandboxViolation(_, _) => "Not allowed by the sandbox profile",
+            Self::ErrorNotDeterministic(_, _) => "Function not marked deterministic",
+            Self::ErrorResumeDiverged(_) => "Resumed script did not follow its previous run",
             Self::ErrorRuntime(_, _) => "Runtime error",
             Self::ErrorLoopBreak(true, _) => "Break statement not inside a loop",
             Self::ErrorLoopBreak(false, _) => "Continue statement not inside a loop",
             Self::Return(_, _) => "[Not Error] Function returns value",
+            Self::Yield(_, _) => "[Not Error] Script suspended by yield",
         }
     }
 }
@@ -188,6 +210,8 @@ impl fmt::Display for EvalAltResult {
 
             Self::ErrorDotExpr(s, _) if !s.is_empty() => write!(f, "{}", s)?,
 
+            Self::ErrorPatternMismatch(s, _) => write!(f, "{}: {}", desc, s)?,
+
             Self::ErrorIndexingType(_, _)
             | Self::ErrorNumericIndexExpr(_)
             | Self::ErrorStringIndexExpr(_)
@@ -201,7 +225,8 @@ impl fmt::Display for EvalAltResult {
             | Self::ErrorTooManyOperations(_)
             | Self::ErrorTooManyModules(_)
             | Self::ErrorStackOverflow(_)
-            | Self::ErrorTerminated(_) => f.write_str(desc)?,
+            | Self::ErrorTerminated(_)
+            | Self::ErrorResumeDiverged(_) => f.write_str(desc)?,
 
             Self::ErrorRuntime(s, _) => f.write_str(if s.is_empty() { desc } else { s })?,
 
@@ -210,9 +235,13 @@ impl fmt::Display for EvalAltResult {
                 write!(f, "{} (expecting {}): {}", desc, s, r)?
             }
             Self::ErrorArithmetic(s, _) => f.write_str(s)?,
+            Self::ErrorSandboxViolation(s, _) => {
+                write!(f, "{} is not allowed by the sandbox profile", s)?
+            }
+            Self::ErrorNotDeterministic(s, _) => write!(f, "{}: '{}'", desc, s)?,
 
             Self::ErrorLoopBreak(_, _) => f.write_str(desc)?,
-            Self::Return(_, _) => f.write_str(desc)?,
+            Self::Return(_, _) | Self::Yield(_, _) => f.write_str(desc)?,
 
             Self::ErrorBooleanArgMismatch(op, _) => {
                 write!(f, "{} operator expects boolean operands", op)?
@@ -299,15 +328,20 @@ impl EvalAltResult {
             | Self::ErrorMismatchOutputType(_, _, pos)
             | Self::ErrorInExpr(pos)
             | Self::ErrorDotExpr(_, pos)
+            | Self::ErrorPatternMismatch(_, pos)
             | Self::ErrorArithmetic(_, pos)
             | Self::ErrorTooManyOperations(pos)
             | Self::ErrorTooManyModules(pos)
             | Self::ErrorStackOverflow(pos)
             | Self::ErrorDataTooLarge(_, _, _, pos)
             | Self::ErrorTerminated(pos)
+            | Self::ErrorSandboxViolation(_, pos)
+            | Self::ErrorNotDeterministic(_, pos)
+            | Self::ErrorResumeDiverged(pos)
             | Self::ErrorRuntime(_, pos)
             | Self::ErrorLoopBreak(_, pos)
-            | Self::Return(_, pos) => *pos,
+            | Self::Return(_, pos)
+            | Self::Yield(_, pos) => *pos,
         }
     }
 
@@ -340,15 +374,20 @@ impl EvalAltResult {
             | Self::ErrorMismatchOutputType(_, _, pos)
             | Self::ErrorInExpr(pos)
             | Self::ErrorDotExpr(_, pos)
+            | Self::ErrorPatternMismatch(_, pos)
             | Self::ErrorArithmetic(_, pos)
             | Self::ErrorTooManyOperations(pos)
             | Self::ErrorTooManyModules(pos)
             | Self::ErrorStackOverflow(pos)
             | Self::ErrorDataTooLarge(_, _, _, pos)
             | Self::ErrorTerminated(pos)
+            | Self::ErrorSandboxViolation(_, pos)
+            | Self::ErrorNotDeterministic(_, pos)
+            | Self::ErrorResumeDiverged(pos)
             | Self::ErrorRuntime(_, pos)
             | Self::ErrorLoopBreak(_, pos)
-            | Self::Return(_, pos) => *pos = new_position,
+            | Self::Return(_, pos)
+            | Self::Yield(_, pos) => *pos = new_position,
         }
     }
 
@@ -0,0 +1,379 @@
+//! Module that defines resumable evaluation of scripts via `yield`.
+
+use crate::any::{Dynamic, Union, Variant};
+use crate::engine::{Engine, Imports, State};
+use crate::fn_native::Shared;
+use crate::module::Module;
+use crate::parser::{ImmutableString, ScriptFnDef, AST};
+use crate::result::EvalAltResult;
+use crate::scope::Scope;
+use crate::token::Position;
+use crate::utils::StaticVec;
+
+use crate::stdlib::{boxed::Box, mem, string::ToString, vec::Vec};
+
+/// Result of a resumable evaluation.
+#[derive(Debug, Clone)]
+pub enum Resumable {
+    /// The script ran to completion.  Wrapped value is the result of the script.
+    Completed(Dynamic),
+    /// The script suspended via `yield`.
+    Suspended(Box<Suspended>),
+}
+
+impl Resumable {
+    /// Has the script run to completion?
+    pub fn is_completed(&self) -> bool {
+        match self {
+            Self::Completed(_) => true,
+            Self::Suspended(_) => false,
+        }
+    }
+}
+
+/// Handle to a script that is suspended at a `yield` expression.
+///
+/// Use `Engine::resume` to continue running the script.
+///
+/// # Replay
+///
+/// A suspended script holds no native stack.  Instead, the handle keeps the compiled `AST`,
+/// a copy of the original `Scope`, and all the values the script has been resumed with.
+/// When resumed, the script is run again from the beginning, with each `yield` evaluating to
+/// the value it was previously resumed with, until it reaches the point it was suspended at.
+///
+/// # Limitations
+///
+/// Because of replay:
+///
+/// * Each resume re-runs all the work done before the current `yield`, so a script that
+///   suspends `n` times does `O(n²)` work in total.
+///
+/// * `print` and `debug` output is skipped during replay, but registered native functions are
+///   called again, so their side effects are repeated.
+///
+/// * The script must take the same path on every replay.  If it reaches a different `yield`,
+///   yields a different value, or finishes before reaching the point it was suspended at
+///   (e.g. because a native function returns a different result), resuming fails with
+///   `EvalAltResult::ErrorResumeDiverged`.
+#[derive(Debug, Clone)]
+pub struct Suspended {
+    /// The script being run.
+    pub(crate) ast: AST,
+    /// Copy of the `Scope` the script originally ran with.
+    pub(crate) scope: Scope<'static>,
+    /// Values the script has been resumed with, in order.
+    pub(crate) resume_values: Vec<Dynamic>,
+    /// Values passed to, and positions of, the `yield` expressions the script has been resumed from.
+    pub(crate) yielded: Vec<(Dynamic, Position)>,
+    /// Value passed to `yield`.
+    pub(crate) value: Dynamic,
+    /// Position of the `yield` expression.
+    pub(crate) pos: Position,
+}
+
+impl Suspended {
+    /// Get the value passed to the `yield` expression that suspended the script.
+    pub fn value(&self) -> &Dynamic {
+        &self.value
+    }
+    /// Get the position of the `yield` expression that suspended the script.
+    pub fn position(&self) -> Position {
+        self.pos
+    }
+    /// Get the number of times the script has been resumed.
+    pub fn num_resumed(&self) -> usize {
+        self.resume_values.len()
+    }
+}
+
+/// Values yielded by a call to a generator function, i.e. a script-defined function containing
+/// `yield`.
+///
+/// Calling a generator function does not run it.  Instead, the arguments are kept in a
+/// `Generator`, which produces the yielded values one at a time when iterated by a `for` loop.
+///
+/// Like a suspended script, a generator holds no native stack: to produce each value, the
+/// function is run again from the beginning up to the next `yield`, so a generator that yields
+/// `n` values does `O(n²)` work in total.  `print` and `debug` output is skipped up to the
+/// previous `yield`, but registered native functions are called again.
+#[derive(Debug, Clone)]
+pub struct Generator {
+    /// Name the generator function was called by.
+    pub(crate) fn_name: ImmutableString,
+    /// The generator function.
+    pub(crate) fn_def: Shared<ScriptFnDef>,
+    /// Values of the parameters, followed by the array of rest arguments, if any.
+    pub(crate) args: StaticVec<Dynamic>,
+    /// Copy of the object the generator function was called on, if any.
+    pub(crate) this: Option<Dynamic>,
+}
+
+impl Engine {
+    /// Run a generator function from the beginning up to the `yield` at `index`, returning the
+    /// value yielded there, or `None` if the function finishes before reaching it.
+    pub(crate) fn step_generator(
+        &self,
+        mods: &mut Imports,
+        state: &mut State,
+        lib: &Module,
+        generator: &Generator,
+        index: usize,
+        level: usize,
+    ) -> Result<Option<Dynamic>, Box<EvalAltResult>> {
+        self.inc_operations(state)?;
+
+        // Check for stack overflow
+        #[cfg(not(feature = "unchecked"))]
+        if level > self.limits.max_call_stack_depth {
+            return Err(Box::new(
+                EvalAltResult::ErrorStackOverflow(Position::none()),
+            ));
+        }
+
+        let Generator {
+            fn_name,
+            fn_def,
+            args,
+            this,
+        } = generator;
+
+        let orig_scope_level = state.scope_level;
+        state.scope_level += 1;
+
+        let prev_mods_len = mods.len();
+
+        // Put arguments into scope as variables
+        let mut scope = Scope::new();
+        fn_def
+            .params
+            .iter()
+            .chain(fn_def.rest_param.iter())
+            .zip(args.iter())
+            .for_each(|(name, value)| {
+                scope.push(name.clone(), value.clone());
+            });
+
+        let mut this = this.clone();
+        let orig_generator = state.generator.replace((index, None));
+
+        // Evaluate the function at one higher level of call depth - the `yield` at `index`
+        // stops the function by returning
+        let result = self
+            .eval_stmt(
+                &mut scope,
+                mods,
+                state,
+                lib,
+                &mut this.as_mut(),
+                &fn_def.body,
+                level + 1,
+            )
+            .or_else(|err| match *err {
+                EvalAltResult::Return(_, _) => Ok(Default::default()),
+                EvalAltResult::ErrorInFunctionCall(name, err, _) => {
+                    EvalAltResult::ErrorInFunctionCall(
+                        format!("{} > {}", fn_name, name),
+                        err,
+                        Position::none(),
+                    )
+                    .into()
+                }
+                _ => EvalAltResult::ErrorInFunctionCall(fn_name.to_string(), err, Position::none())
+                    .into(),
+            });
+
+        let (_, value) = mem::replace(&mut state.generator, orig_generator).unwrap();
+
+        mods.truncate(prev_mods_len);
+        state.scope_level = orig_scope_level;
+        state.frame_memory.truncate(level + 1);
+
+        result.map(|_| value)
+    }
+
+    /// Evaluate a string as a script that can suspend itself via `yield`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<rhai::EvalAltResult>> {
+    /// use rhai::{Engine, Resumable};
+    ///
+    /// let engine = Engine::new();
+    ///
+    /// let suspended = match engine.eval_resumable("let x = yield 40; x + 2")? {
+    ///     Resumable::Suspended(s) => s,
+    ///     Resumable::Completed(_) => unreachable!(),
+    /// };
+    ///
+    /// assert_eq!(suspended.value().clone().cast::<i64>(), 40);
+    ///
+    /// match engine.resume(suspended, 40_i64)? {
+    ///     Resumable::Completed(result) => assert_eq!(result.cast::<i64>(), 42),
+    ///     Resumable::Suspended(_) => unreachable!(),
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn eval_resumable(&self, script: &str) -> Result<Resumable, Box<EvalAltResult>> {
+        self.eval_resumable_with_scope(&Scope::new(), script)
+    }
+
+    /// Evaluate a string with own scope as a script that can suspend itself via `yield`.
+    ///
+    /// The scope is copied and is not modified by the script.
+    pub fn eval_resumable_with_scope(
+        &self,
+        scope: &Scope,
+        script: &str,
+    ) -> Result<Resumable, Box<EvalAltResult>> {
+        let ast = self.compile_with_scope(scope, script)?;
+        self.eval_ast_with_scope_resumable(scope, &ast)
+    }
+
+    /// Evaluate an `AST` as a script that can suspend itself via `yield`.
+    pub fn eval_ast_resumable(&self, ast: &AST) -> Result<Resumable, Box<EvalAltResult>> {
+        self.eval_ast_with_scope_resumable(&Scope::new(), ast)
+    }
+
+    /// Evaluate an `AST` with own scope as a script that can suspend itself via `yield`.
+    ///
+    /// The scope is copied and is not modified by the script.
+    pub fn eval_ast_with_scope_resumable(
+        &self,
+        scope: &Scope,
+        ast: &AST,
+    ) -> Result<Resumable, Box<EvalAltResult>> {
+        let mut initial = Scope::new();
+
+        initial.extend(
+            scope
+                .to_iter()
+                .collect::<Vec<_>>()
+                .into_iter()
+                .rev()
+                .map(|entry| {
+                    (
+                        entry.name.to_string(),
+                        entry.typ,
+                        entry.value.flatten_clone(),
+                    )
+                }),
+        );
+
+        self.eval_resumable_raw(initial, ast.clone(), Default::default(), Default::default())
+    }
+
+    /// Resume a suspended script, with the `yield` expression it was suspended at
+    /// evaluating to `value`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<rhai::EvalAltResult>> {
+    /// use rhai::{Engine, Resumable};
+    ///
+    /// let engine = Engine::new();
+    ///
+    /// let mut state = engine.eval_resumable("let x = 0; loop { x += yield x; }")?;
+    ///
+    /// for n in 1..=3 {
+    ///     state = match state {
+    ///         Resumable::Suspended(s) => engine.resume(s, n as i64)?,
+    ///         Resumable::Completed(_) => unreachable!(),
+    ///     };
+    /// }
+    ///
+    /// match state {
+    ///     Resumable::Suspended(s) => assert_eq!(s.value().clone().cast::<i64>(), 6),
+    ///     Resumable::Completed(_) => unreachable!(),
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn resume<T: Variant + Clone>(
+        &self,
+        suspended: Box<Suspended>,
+        value: T,
+    ) -> Result<Resumable, Box<EvalAltResult>> {
+        let Suspended {
+            ast,
+            scope,
+            mut resume_values,
+            mut yielded,
+            value: yield_value,
+            pos,
+        } = *suspended;
+
+        resume_values.push(Dynamic::from(value));
+        yielded.push((yield_value, pos));
+        self.eval_resumable_raw(scope, ast, resume_values, yielded)
+    }
+
+    /// Run a script from the beginning, replaying all previous `yield` expressions.
+    pub(crate) fn eval_resumable_raw(
+        &self,
+        initial: Scope<'static>,
+        ast: AST,
+        resume_values: Vec<Dynamic>,
+        yielded: Vec<(Dynamic, Position)>,
+    ) -> Result<Resumable, Box<EvalAltResult>> {
+        let mut scope = initial.clone();
+        let mut mods = Default::default();
+        let mut state = State::new();
+        state.resume_values = resume_values;
+        state.yielded = yielded;
+
+        let result = ast.statements().iter().try_fold(().into(), |_, stmt| {
+            self.eval_stmt(
+                &mut scope,
+                &mut mods,
+                &mut state,
+                ast.lib(),
+                &mut None,
+                stmt,
+                0,
+            )
+        });
+
+        match result {
+            // Finishing before reaching the point the script was suspended at
+            Ok(_) if state.is_replaying() => {
+                EvalAltResult::ErrorResumeDiverged(Position::none()).into()
+            }
+            Ok(out) => Ok(Resumable::Completed(out)),
+            Err(err) => match *err {
+                EvalAltResult::Return(_, _) if state.is_replaying() => {
+                    EvalAltResult::ErrorResumeDiverged(Position::none()).into()
+                }
+                EvalAltResult::Return(out, _) => Ok(Resumable::Completed(out)),
+                EvalAltResult::Yield(value, pos) => Ok(Resumable::Suspended(Box::new(Suspended {
+                    ast,
+                    scope: initial,
+                    resume_values: state.resume_values,
+                    yielded: state.yielded,
+                    value,
+                    pos,
+                }))),
+                _ => Err(err),
+            },
+        }
+    }
+}
+
+/// Is the value passed to a `yield` expression during replay the same as in the original run?
+///
+/// Values of primitive types are compared, other values only by type.
+pub(crate) fn is_same_yield(prev: &Dynamic, value: &Dynamic) -> bool {
+    match (&prev.0, &value.0) {
+        (Union::Unit(_), Union::Unit(_)) => true,
+        (Union::Bool(x), Union::Bool(y)) => x == y,
+        (Union::Str(x), Union::Str(y)) => x == y,
+        (Union::Char(x), Union::Char(y)) => x == y,
+        (Union::Int(x), Union::Int(y)) => x == y,
+        #[cfg(not(feature = "no_float"))]
+        (Union::Float(x), Union::Float(y)) => x == y,
+        _ => prev.type_name() == value.type_name(),
+    }
+}
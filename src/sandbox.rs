@@ -0,0 +1,314 @@
+//! Module defining sandbox profiles which restrict what scripts are allowed to do.
+
+use crate::token::{is_keyword_function, is_valid_identifier};
+
+#[cfg(not(feature = "unchecked"))]
+use crate::engine::Limits;
+
+use crate::stdlib::{collections::HashSet, format, string::String};
+
+#[cfg(not(feature = "unchecked"))]
+#[cfg(not(feature = "no_std"))]
+use crate::stdlib::time::Duration;
+
+/// A list of allowed and denied items.
+#[derive(Debug, Clone, Default)]
+struct Rules {
+    /// Allowed items.  `None` allows all items not denied.
+    allowed: Option<HashSet<String>>,
+    /// Denied items.
+    denied: HashSet<String>,
+}
+
+impl Rules {
+    /// Allow an item.
+    fn allow(&mut self, item: impl Into<String>) {
+        self.allowed
+            .get_or_insert_with(Default::default)
+            .insert(item.into());
+    }
+    /// Deny an item.
+    fn deny(&mut self, item: impl Into<String>) {
+        self.denied.insert(item.into());
+    }
+    /// Is an item allowed?  An item matches a list if any of its keys is in the list.
+    fn permits(&self, keys: &[&str]) -> bool {
+        let matches = |list: &HashSet<String>| keys.iter().any(|&k| list.contains(k));
+
+        !matches(&self.denied) && self.allowed.as_ref().map(matches).unwrap_or(true)
+    }
+}
+
+/// A named set of capabilities granted to scripts run by an `Engine`.
+///
+/// A profile contains allow and deny lists for functions, keywords and modules.  An item is allowed
+/// if it is not denied and, when there is an allow list, it is on the allow list.
+///
+/// * Functions are named either by their name (e.g. `"sqrt"`), by the path of the module they are
+///   imported from plus their name (e.g. `"crypto::encrypt"`), or by the module path plus `*`
+///   (e.g. `"crypto::*"`) for all functions in a module.  Functions defined in the script itself,
+///   operators and property accessors are always allowed.
+/// * Keywords include both language keywords (e.g. `import`, `while`) and keywords that are called
+///   like functions (e.g. `eval`, `Fn`, `call`, `print`).
+/// * Modules are named by the path in the `import` statement.
+///
+/// A profile also holds maximum limits, which are imposed on the `Engine` when the profile is set
+/// via `Engine::set_sandbox`.
+///
+/// Violations are reported as `ParseErrorType::SandboxViolation` when a script is compiled.
+/// Calls to functions and imports of modules that cannot be checked at compile time (e.g. via
+/// function pointers or non-constant module paths) fail with `EvalAltResult::ErrorSandboxViolation`
+/// when the script runs.
+///
+/// # Example
+///
+/// ```
+/// use rhai::{Engine, ParseErrorType, SandboxProfile};
+///
+/// let mut profile = SandboxProfile::new("untrusted");
+/// profile.deny_keyword("eval").deny_keyword("import");
+///
+/// let mut engine = Engine::new();
+/// engine.set_sandbox(profile);
+///
+/// let err = engine.compile("eval(\"40 + 2\")").expect_err("should error");
+///
+/// assert!(matches!(err.0.as_ref(), ParseErrorType::SandboxViolation(_)));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct SandboxProfile {
+    /// Name of the profile.
+    name: String,
+    /// Functions allowed to be called.
+    functions: Rules,
+    /// Keywords allowed to be used.
+    keywords: Rules,
+    /// Module paths allowed to be imported.
+    modules: Rules,
+    /// Maximum limits.
+    #[cfg(not(feature = "unchecked"))]
+    limits: Option<Limits>,
+}
+
+impl SandboxProfile {
+    /// Create a new `SandboxProfile` which allows everything.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            ..Default::default()
+        }
+    }
+    /// Get the name of the profile.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Allow calling a function.  Once any function is allowed, all other functions are denied.
+    pub fn allow_function(&mut self, name: impl Into<String>) -> &mut Self {
+        self.functions.allow(name);
+        self
+    }
+    /// Deny calling a function.
+    pub fn deny_function(&mut self, name: impl Into<String>) -> &mut Self {
+        self.functions.deny(name);
+        self
+    }
+    /// Allow using a keyword.  Once any keyword is allowed, all other keywords are denied.
+    pub fn allow_keyword(&mut self, keyword: impl Into<String>) -> &mut Self {
+        self.keywords.allow(keyword);
+        self
+    }
+    /// Deny using a keyword.
+    pub fn deny_keyword(&mut self, keyword: impl Into<String>) -> &mut Self {
+        self.keywords.deny(keyword);
+        self
+    }
+    /// Allow importing a module.  Once any module is allowed, all other modules are denied.
+    pub fn allow_module(&mut self, path: impl Into<String>) -> &mut Self {
+        self.modules.allow(path);
+        self
+    }
+    /// Deny importing a module.
+    pub fn deny_module(&mut self, path: impl Into<String>) -> &mut Self {
+        self.modules.deny(path);
+        self
+    }
+
+    /// Is a function allowed to be called?
+    ///
+    /// `namespace` is the path of the module containing the function, if it is imported from a module,
+    /// followed by the names of sub-modules, if any, separated by `::`.
+    pub fn is_function_allowed(&self, namespace: Option<&str>, name: &str) -> bool {
+        if is_keyword_function(name) {
+            self.is_keyword_allowed(name)
+        } else if !is_valid_identifier(name.chars()) {
+            // Operators, getters, setters and indexers
+            true
+        } else if let Some(namespace) = namespace {
+            let qualified = format!("{}::{}", namespace, name);
+            let all = format!("{}::*", namespace);
+            self.functions.permits(&[name, &qualified, &all])
+        } else {
+            self.functions.permits(&[name])
+        }
+    }
+    /// Is a keyword allowed to be used?
+    pub fn is_keyword_allowed(&self, keyword: &str) -> bool {
+        self.keywords.permits(&[keyword])
+    }
+    /// Is a module allowed to be imported?
+    pub fn is_module_allowed(&self, path: &str) -> bool {
+        self.modules.permits(&[path])
+    }
+
+    /// Check a function call, returning a description of the violation, if any.
+    pub(crate) fn check_function(&self, namespace: Option<&str>, name: &str) -> Option<String> {
+        if self.is_function_allowed(namespace, name) {
+            None
+        } else if is_keyword_function(name) {
+            Some(format!("keyword '{}'", name))
+        } else if let Some(namespace) = namespace {
+            Some(format!("function '{}::{}'", namespace, name))
+        } else {
+            Some(format!("function '{}'", name))
+        }
+    }
+    /// Check a keyword, returning a description of the violation, if any.
+    pub(crate) fn check_keyword(&self, keyword: &str) -> Option<String> {
+        if self.is_keyword_allowed(keyword) {
+            None
+        } else {
+            Some(format!("keyword '{}'", keyword))
+        }
+    }
+    /// Check a module path, returning a description of the violation, if any.
+    pub(crate) fn check_module(&self, path: &str) -> Option<String> {
+        if self.is_module_allowed(path) {
+            None
+        } else {
+            Some(format!("module '{}'", path))
+        }
+    }
+
+    /// Get the maximum limits, creating them (all unlimited) if none.
+    #[cfg(not(feature = "unchecked"))]
+    fn limits_mut(&mut self) -> &mut Limits {
+        self.limits.get_or_insert(Limits {
+            max_call_stack_depth: usize::MAX,
+            max_expr_depth: 0,
+            max_function_expr_depth: 0,
+            max_operations: 0,
+            max_modules: usize::MAX,
+            max_string_size: 0,
+            max_array_size: 0,
+            max_map_size: 0,
+            max_memory: 0,
+            #[cfg(not(feature = "no_std"))]
+            max_duration: None,
+        })
+    }
+    /// Set the maximum levels of function calls.
+    #[cfg(not(feature = "unchecked"))]
+    pub fn set_max_call_levels(&mut self, levels: usize) -> &mut Self {
+        self.limits_mut().max_call_stack_depth = levels;
+        self
+    }
+    /// Set the maximum number of operations (0 for unlimited).
+    #[cfg(not(feature = "unchecked"))]
+    pub fn set_max_operations(&mut self, operations: u64) -> &mut Self {
+        self.limits_mut().max_operations = operations;
+        self
+    }
+    /// Set the maximum number of imported modules.
+    #[cfg(not(feature = "unchecked"))]
+    pub fn set_max_modules(&mut self, modules: usize) -> &mut Self {
+        self.limits_mut().max_modules = modules;
+        self
+    }
+    /// Set the maximum depths of expressions (0 for unlimited).
+    #[cfg(not(feature = "unchecked"))]
+    pub fn set_max_expr_depths(
+        &mut self,
+        max_expr_depth: usize,
+        max_function_expr_depth: usize,
+    ) -> &mut Self {
+        let limits = self.limits_mut();
+        limits.max_expr_depth = max_expr_depth;
+        limits.max_function_expr_depth = max_function_expr_depth;
+        self
+    }
+    /// Set the maximum length of strings (0 for unlimited).
+    #[cfg(not(feature = "unchecked"))]
+    pub fn set_max_string_size(&mut self, max_size: usize) -> &mut Self {
+        self.limits_mut().max_string_size = max_size;
+        self
+    }
+    /// Set the maximum length of arrays (0 for unlimited).
+    #[cfg(not(feature = "unchecked"))]
+    #[cfg(not(feature = "no_index"))]
+    pub fn set_max_array_size(&mut self, max_size: usize) -> &mut Self {
+        self.limits_mut().max_array_size = max_size;
+        self
+    }
+    /// Set the maximum length of object maps (0 for unlimited).
+    #[cfg(not(feature = "unchecked"))]
+    #[cfg(not(feature = "no_object"))]
+    pub fn set_max_map_size(&mut self, max_size: usize) -> &mut Self {
+        self.limits_mut().max_map_size = max_size;
+        self
+    }
+    /// Set the maximum approximate number of bytes of data held by a script (0 for unlimited).
+    #[cfg(not(feature = "unchecked"))]
+    pub fn set_max_memory(&mut self, max_size: usize) -> &mut Self {
+        self.limits_mut().max_memory = max_size;
+        self
+    }
+    /// Set the maximum amount of time a script is allowed to run.
+    ///
+    /// Not available under the `no_std` feature.
+    #[cfg(not(feature = "unchecked"))]
+    #[cfg(not(feature = "no_std"))]
+    pub fn set_max_duration(&mut self, max_duration: Duration) -> &mut Self {
+        self.limits_mut().max_duration = Some(max_duration);
+        self
+    }
+
+    /// Lower the limits of an `Engine` to the maximum limits of the profile.
+    #[cfg(not(feature = "unchecked"))]
+    pub(crate) fn restrict_limits(&self, limits: &mut Limits) {
+        // Zero means unlimited
+        fn cap<T: Copy + Ord + Default>(limit: T, max: T) -> T {
+            if max == T::default() {
+                limit
+            } else if limit == T::default() {
+                max
+            } else {
+                limit.min(max)
+            }
+        }
+
+        let max = match &self.limits {
+            Some(max) => max,
+            None => return,
+        };
+
+        limits.max_call_stack_depth = limits.max_call_stack_depth.min(max.max_call_stack_depth);
+        limits.max_expr_depth = cap(limits.max_expr_depth, max.max_expr_depth);
+        limits.max_function_expr_depth =
+            cap(limits.max_function_expr_depth, max.max_function_expr_depth);
+        limits.max_operations = cap(limits.max_operations, max.max_operations);
+        limits.max_modules = limits.max_modules.min(max.max_modules);
+        limits.max_string_size = cap(limits.max_string_size, max.max_string_size);
+        limits.max_array_size = cap(limits.max_array_size, max.max_array_size);
+        limits.max_map_size = cap(limits.max_map_size, max.max_map_size);
+        limits.max_memory = cap(limits.max_memory, max.max_memory);
+
+        #[cfg(not(feature = "no_std"))]
+        {
+            limits.max_duration = match (limits.max_duration, max.max_duration) {
+                (Some(limit), Some(max)) => Some(limit.min(max)),
+                (limit, max) => limit.or(max),
+            };
+        }
+    }
+}
@@ -58,6 +58,9 @@ pub struct Entry<'a> {
 /// allowing for automatic _shadowing_.
 ///
 /// Currently, `Scope` is neither `Send` nor `Sync`. Turn on the `sync` feature to make it `Send + Sync`.
+///
+/// With the `serde` feature, `Scope` implements `Serialize` and `Deserialize`, keeping constants
+/// constant.  Values of custom types cannot be serialized.
 #[derive(Debug, Clone, Default)]
 pub struct Scope<'a>(Vec<Entry<'a>>);
 
@@ -375,8 +378,115 @@ impl<'a> Scope<'a> {
         self
     }
 
+    /// Get a mutable reference to the value of an entry in the Scope, starting from the last.
+    ///
+    /// Returns `None` if no entry matching the specified name is found, or if the entry is a constant.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rhai::Scope;
+    ///
+    /// let mut my_scope = Scope::new();
+    ///
+    /// my_scope.push("x", 42_i64);
+    /// my_scope.push_constant("y", 123_i64);
+    ///
+    /// *my_scope.get_mut("x").unwrap() = 0_i64.into();
+    /// assert_eq!(my_scope.get_value::<i64>("x").unwrap(), 0);
+    ///
+    /// assert!(my_scope.get_mut("y").is_none());
+    /// assert!(my_scope.get_mut("z").is_none());
+    /// ```
+    pub fn get_mut(&mut self, name: &str) -> Option<&mut Dynamic> {
+        match self.get_index(name) {
+            Some((index, EntryType::Normal)) => Some(&mut self.0.get_mut(index).unwrap().value),
+            Some((_, EntryType::Constant)) | None => None,
+        }
+    }
+
+    /// Remove the named entry from the Scope, starting from the last, and return its value.
+    /// Similarly-named but older entries that were shadowed by it become visible again.
+    ///
+    /// Returns `None` if no entry matching the specified name is found.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rhai::Scope;
+    ///
+    /// let mut my_scope = Scope::new();
+    ///
+    /// my_scope.push("x", 42_i64);
+    /// my_scope.push("y", 123_i64);
+    /// my_scope.push("x", 0_i64);
+    ///
+    /// assert_eq!(my_scope.remove("x").unwrap().cast::<i64>(), 0);
+    /// assert_eq!(my_scope.get_value::<i64>("x").unwrap(), 42);
+    /// assert_eq!(my_scope.len(), 2);
+    ///
+    /// assert!(my_scope.remove("z").is_none());
+    /// ```
+    pub fn remove(&mut self, name: &str) -> Option<Dynamic> {
+        self.get_index(name)
+            .map(|(index, _)| self.0.remove(index).value)
+    }
+
+    /// Take a snapshot of the Scope, which can be restored later via `restore`.
+    ///
+    /// The values of shared variables (e.g. captured by closures) are copied, so later changes
+    /// to them do not affect the snapshot.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<rhai::EvalAltResult>> {
+    /// use rhai::{Engine, Scope};
+    ///
+    /// let engine = Engine::new();
+    /// let mut my_scope = Scope::new();
+    ///
+    /// my_scope.push("x", 42_i64);
+    ///
+    /// let snapshot = my_scope.snapshot();
+    ///
+    /// engine.consume_with_scope(&mut my_scope, "x = 0; let y = 1;")?;
+    /// assert_eq!(my_scope.len(), 2);
+    /// assert_eq!(snapshot.get_value::<i64>("x").unwrap(), 42);
+    ///
+    /// my_scope.restore(&snapshot);
+    /// assert_eq!(my_scope.len(), 1);
+    /// assert_eq!(my_scope.get_value::<i64>("x").unwrap(), 42);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn snapshot(&self) -> Self {
+        Self(
+            self.0
+                .iter()
+                .map(|entry| Entry {
+                    value: entry.value.flatten_clone(),
+                    ..entry.clone()
+                })
+                .collect(),
+        )
+    }
+
+    /// Restore the Scope to a snapshot taken via `snapshot`, discarding all current entries.
+    pub fn restore(&mut self, snapshot: &Self) -> &mut Self {
+        *self = snapshot.snapshot();
+        self
+    }
+
+    /// Get the name and value of an entry in the Scope.
+    #[cfg(not(feature = "unchecked"))]
+    pub(crate) fn get_by_index(&self, index: usize) -> (&str, &Dynamic) {
+        let entry = self.0.get(index).expect("invalid index in Scope");
+        (&entry.name, &entry.value)
+    }
+
     /// Get a mutable reference to an entry in the Scope.
-    pub(crate) fn get_mut(&mut self, index: usize) -> (&mut Dynamic, EntryType) {
+    pub(crate) fn get_mut_by_index(&mut self, index: usize) -> (&mut Dynamic, EntryType) {
         let entry = self.0.get_mut(index).expect("invalid index in Scope");
         (&mut entry.value, entry.typ)
     }
@@ -389,6 +499,16 @@ impl<'a> Scope<'a> {
         self
     }
 
+    /// Set an alias for every entry in the Scope that does not have one, using its own name.
+    #[cfg(not(feature = "no_module"))]
+    pub(crate) fn set_all_entry_aliases(&mut self) -> &mut Self {
+        self.0
+            .iter_mut()
+            .filter(|entry| entry.alias.is_none())
+            .for_each(|entry| entry.alias = Some(Box::new(entry.name.to_string())));
+        self
+    }
+
     /// Clone the Scope, keeping only the last instances of each variable name.
     /// Shadowed variables are omitted in the copy.
     pub(crate) fn flatten_clone(&self) -> Self {
@@ -412,6 +532,17 @@ impl<'a> Scope<'a> {
         self.0.into_iter()
     }
 
+    /// Get an iterator to entries in the Scope, together with their types.
+    /// Shared values are not expanded.
+    #[cfg(feature = "serde")]
+    pub(crate) fn iter_entries(&self) -> impl Iterator<Item = (&str, EntryType, &Dynamic)> {
+        self.0.iter().map(
+            |Entry {
+                 name, typ, value, ..
+             }| (name.as_ref(), *typ, value),
+        )
+    }
+
     /// Get an iterator to entries in the Scope in reverse order.
     pub(crate) fn to_iter(&self) -> impl Iterator<Item = &Entry> {
         self.0.iter().rev() // Always search a Scope in reverse order
@@ -15,8 +15,14 @@ use serde::Deserialize;
 #[cfg(not(feature = "no_index"))]
 use crate::engine::Array;
 
+#[cfg(feature = "bigint")]
+use num_bigint::BigInt;
+
+#[cfg(feature = "bigint")]
+use num_traits::ToPrimitive;
+
 #[cfg(not(feature = "no_object"))]
-use crate::engine::Map;
+use crate::{engine::Map, record::Record};
 
 #[cfg(not(feature = "no_object"))]
 use serde::de::{EnumAccess, VariantAccess};
@@ -74,6 +80,25 @@ impl<'de> DynamicDeserializer<'de> {
             visitor.visit_i32(v)
         }
     }
+    /// Deserialize a big integer as a primitive integer, or as a string if it is too large.
+    #[cfg(feature = "bigint")]
+    fn deserialize_bigint<V: Visitor<'de>>(
+        &self,
+        v: &BigInt,
+        visitor: V,
+    ) -> Result<V::Value, Box<EvalAltResult>> {
+        if let Some(n) = v.to_i64() {
+            visitor.visit_i64(n)
+        } else if let Some(n) = v.to_u64() {
+            visitor.visit_u64(n)
+        } else if let Some(n) = v.to_i128() {
+            visitor.visit_i128(n)
+        } else if let Some(n) = v.to_u128() {
+            visitor.visit_u128(n)
+        } else {
+            visitor.visit_string(v.to_string())
+        }
+    }
 }
 
 /// Deserialize a `Dynamic` value into a Rust type that implements `serde::Deserialize`.
@@ -155,6 +180,10 @@ impl<'de> Deserializer<'de> for &mut DynamicDeserializer<'de> {
             Union::Int(_) => self.deserialize_i32(visitor),
             #[cfg(not(feature = "no_float"))]
             Union::Float(_) => self.deserialize_f64(visitor),
+            #[cfg(feature = "decimal")]
+            Union::Decimal(_) => self.deserialize_str(visitor),
+            #[cfg(feature = "bigint")]
+            Union::BigInt(x) => self.deserialize_bigint(x, visitor),
             #[cfg(not(feature = "no_index"))]
             Union::Array(_) => self.deserialize_seq(visitor),
             #[cfg(not(feature = "no_object"))]
@@ -164,6 +193,9 @@ impl<'de> Deserializer<'de> for &mut DynamicDeserializer<'de> {
             #[cfg(not(feature = "no_std"))]
             Union::Variant(value) if value.is::<Instant>() => self.type_error(),
 
+            #[cfg(not(feature = "no_object"))]
+            Union::Variant(value) if value.is::<Record>() => self.deserialize_map(visitor),
+
             Union::Variant(value) if value.is::<i8>() => self.deserialize_i8(visitor),
             Union::Variant(value) if value.is::<i16>() => self.deserialize_i16(visitor),
             Union::Variant(value) if value.is::<i32>() => self.deserialize_i32(visitor),
@@ -219,6 +251,11 @@ impl<'de> Deserializer<'de> for &mut DynamicDeserializer<'de> {
     }
 
     fn deserialize_i64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Box<EvalAltResult>> {
+        #[cfg(feature = "bigint")]
+        if let Union::BigInt(x) = &self.value.0 {
+            return self.deserialize_bigint(x, visitor);
+        }
+
         if let Ok(v) = self.value.as_int() {
             self.deserialize_int(v, visitor)
         } else if cfg!(not(feature = "only_i32")) {
@@ -231,6 +268,11 @@ impl<'de> Deserializer<'de> for &mut DynamicDeserializer<'de> {
     }
 
     fn deserialize_i128<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Box<EvalAltResult>> {
+        #[cfg(feature = "bigint")]
+        if let Union::BigInt(x) = &self.value.0 {
+            return self.deserialize_bigint(x, visitor);
+        }
+
         if let Ok(v) = self.value.as_int() {
             self.deserialize_int(v, visitor)
         } else if cfg!(not(feature = "only_i32")) {
@@ -273,6 +315,11 @@ impl<'de> Deserializer<'de> for &mut DynamicDeserializer<'de> {
     }
 
     fn deserialize_u64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Box<EvalAltResult>> {
+        #[cfg(feature = "bigint")]
+        if let Union::BigInt(x) = &self.value.0 {
+            return self.deserialize_bigint(x, visitor);
+        }
+
         if let Ok(v) = self.value.as_int() {
             self.deserialize_int(v, visitor)
         } else {
@@ -283,6 +330,11 @@ impl<'de> Deserializer<'de> for &mut DynamicDeserializer<'de> {
     }
 
     fn deserialize_u128<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Box<EvalAltResult>> {
+        #[cfg(feature = "bigint")]
+        if let Union::BigInt(x) = &self.value.0 {
+            return self.deserialize_bigint(x, visitor);
+        }
+
         if let Ok(v) = self.value.as_int() {
             self.deserialize_int(v, visitor)
         } else {
@@ -321,6 +373,17 @@ impl<'de> Deserializer<'de> for &mut DynamicDeserializer<'de> {
     }
 
     fn deserialize_str<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Box<EvalAltResult>> {
+        // Decimal numbers are deserialized as strings to avoid losing precision
+        #[cfg(feature = "decimal")]
+        if let Union::Decimal(x) = &self.value.0 {
+            return visitor.visit_string(x.to_string());
+        }
+        // Big integers are deserialized as strings in full
+        #[cfg(feature = "bigint")]
+        if let Union::BigInt(x) = &self.value.0 {
+            return visitor.visit_string(x.to_string());
+        }
+
         self.value.downcast_ref::<ImmutableString>().map_or_else(
             || self.type_error(),
             |x| visitor.visit_borrowed_str(x.as_str()),
@@ -397,11 +460,16 @@ impl<'de> Deserializer<'de> for &mut DynamicDeserializer<'de> {
     }
 
     fn deserialize_map<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value, Box<EvalAltResult>> {
+        // The fields of a record are deserialized like an object map
         #[cfg(not(feature = "no_object"))]
-        return self.value.downcast_ref::<Map>().map_or_else(
-            || self.type_error(),
-            |map| _visitor.visit_map(IterateMap::new(map.keys(), map.values())),
-        );
+        return self
+            .value
+            .downcast_ref::<Map>()
+            .or_else(|| self.value.downcast_ref::<Record>().map(Record::fields))
+            .map_or_else(
+                || self.type_error(),
+                |map| _visitor.visit_map(IterateMap::new(map.keys(), map.values())),
+            );
 
         #[cfg(feature = "no_object")]
         return self.type_error();
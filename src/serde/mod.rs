@@ -1,5 +1,8 @@
 //! Helper module defining serialization/deserialization support for [`serde`](https://crates.io/crates/serde).
 
 pub mod de;
+mod scope;
 pub mod ser;
+mod serialize;
+pub mod snapshot;
 mod str;
@@ -0,0 +1,48 @@
+//! Implement serialization support of `Scope` for [`serde`](https://crates.io/crates/serde).
+
+use super::snapshot::SnapshotValue;
+use crate::scope::{EntryType, Scope};
+
+use serde::de::{Deserialize, Deserializer, Error as DeError};
+use serde::ser::{Error as SerError, Serialize, SerializeSeq, Serializer};
+
+use crate::stdlib::{string::String, vec::Vec};
+
+/// Serialize a `Scope` as a sequence of entries - (name, constant?, value) - in the order they
+/// were pushed, so that shadowed variables are kept.
+///
+/// Values of custom types cannot be serialized.
+impl Serialize for Scope<'_> {
+    fn serialize<S: Serializer>(&self, ser: S) -> Result<S::Ok, S::Error> {
+        let mut seq = ser.serialize_seq(Some(self.len()))?;
+
+        for (name, typ, value) in self.iter_entries() {
+            let value = SnapshotValue::from_dynamic(value, None)
+                .map_err(|err| S::Error::custom(err.to_string()))?;
+            seq.serialize_element(&(name, typ == EntryType::Constant, value))?;
+        }
+
+        seq.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for Scope<'_> {
+    fn deserialize<D: Deserializer<'de>>(de: D) -> Result<Self, D::Error> {
+        let entries: Vec<(String, bool, SnapshotValue)> = Deserialize::deserialize(de)?;
+        let mut scope = Scope::new();
+
+        for (name, is_constant, value) in entries {
+            let value = value
+                .to_dynamic(None)
+                .map_err(|err| D::Error::custom(err.to_string()))?;
+
+            if is_constant {
+                scope.push_constant_dynamic(name, value);
+            } else {
+                scope.push_dynamic(name, value);
+            }
+        }
+
+        Ok(scope)
+    }
+}
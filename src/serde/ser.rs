@@ -7,7 +7,19 @@ use crate::token::Position;
 #[cfg(not(feature = "no_index"))]
 use crate::engine::Array;
 #[cfg(not(feature = "no_object"))]
-use crate::engine::Map;
+use crate::{engine::Map, record::Record};
+
+#[cfg(not(feature = "no_object"))]
+use super::serialize::RECORD_STRUCT_NAME;
+
+#[cfg(feature = "bigint")]
+use crate::parser::INT;
+
+#[cfg(feature = "bigint")]
+use num_bigint::BigInt;
+
+#[cfg(feature = "bigint")]
+use crate::stdlib::convert::TryFrom;
 
 use serde::ser::{
     Error, SerializeMap, SerializeSeq, SerializeStruct, SerializeTuple, SerializeTupleStruct,
@@ -26,6 +38,19 @@ use crate::stdlib::{boxed::Box, fmt, string::ToString};
 #[cfg(not(feature = "no_object"))]
 use crate::stdlib::mem;
 
+/// Convert an integer that does not fit into `INT` into a `BigInt`.
+#[cfg(feature = "bigint")]
+fn to_bigint_if_too_large<T: Copy + Into<BigInt>>(v: T) -> Option<Dynamic>
+where
+    INT: TryFrom<T>,
+{
+    if INT::try_from(v).is_err() {
+        Some(v.into().into())
+    } else {
+        None
+    }
+}
+
 /// Serializer for `Dynamic` which is kept as a reference.
 pub struct DynamicSerializer {
     /// Buffer to hold a temporary key.
@@ -146,6 +171,11 @@ impl Serializer for &mut DynamicSerializer {
     }
 
     fn serialize_i64(self, v: i64) -> Result<Self::Ok, Box<EvalAltResult>> {
+        #[cfg(feature = "bigint")]
+        if let Some(v) = to_bigint_if_too_large(v) {
+            return Ok(v);
+        }
+
         #[cfg(not(feature = "only_i32"))]
         return Ok(v.into());
         #[cfg(feature = "only_i32")]
@@ -157,6 +187,11 @@ impl Serializer for &mut DynamicSerializer {
     }
 
     fn serialize_i128(self, v: i128) -> Result<Self::Ok, Box<EvalAltResult>> {
+        #[cfg(feature = "bigint")]
+        if let Some(v) = to_bigint_if_too_large(v) {
+            return Ok(v);
+        }
+
         #[cfg(not(feature = "only_i32"))]
         if v > i64::MAX as i128 {
             return Ok(Dynamic::from(v));
@@ -186,6 +221,11 @@ impl Serializer for &mut DynamicSerializer {
     }
 
     fn serialize_u32(self, v: u32) -> Result<Self::Ok, Box<EvalAltResult>> {
+        #[cfg(feature = "bigint")]
+        if let Some(v) = to_bigint_if_too_large(v) {
+            return Ok(v);
+        }
+
         #[cfg(not(feature = "only_i32"))]
         return self.serialize_i64(i64::from(v));
         #[cfg(feature = "only_i32")]
@@ -197,6 +237,11 @@ impl Serializer for &mut DynamicSerializer {
     }
 
     fn serialize_u64(self, v: u64) -> Result<Self::Ok, Box<EvalAltResult>> {
+        #[cfg(feature = "bigint")]
+        if let Some(v) = to_bigint_if_too_large(v) {
+            return Ok(v);
+        }
+
         #[cfg(not(feature = "only_i32"))]
         if v > i64::MAX as u64 {
             return Ok(Dynamic::from(v));
@@ -212,6 +257,11 @@ impl Serializer for &mut DynamicSerializer {
     }
 
     fn serialize_u128(self, v: u128) -> Result<Self::Ok, Box<EvalAltResult>> {
+        #[cfg(feature = "bigint")]
+        if let Some(v) = to_bigint_if_too_large(v) {
+            return Ok(v);
+        }
+
         #[cfg(not(feature = "only_i32"))]
         if v > i64::MAX as u128 {
             return Ok(Dynamic::from(v));
@@ -279,7 +329,20 @@ impl Serializer for &mut DynamicSerializer {
         _name: &'static str,
         value: &T,
     ) -> Result<Self::Ok, Box<EvalAltResult>> {
-        value.serialize(&mut *self)
+        let value = value.serialize(&mut *self)?;
+
+        // A record is serialized as a map from the name of its record type to its fields
+        #[cfg(not(feature = "no_object"))]
+        if _name == RECORD_STRUCT_NAME {
+            if let Some((type_name, fields)) = value.read_lock::<Map>().and_then(|map| {
+                let (type_name, fields) = map.iter().next()?;
+                Some((type_name.clone(), fields.read_lock::<Map>()?.clone()))
+            }) {
+                return Ok(Dynamic::from(Record::new(type_name, fields)));
+            }
+        }
+
+        Ok(value)
     }
 
     fn serialize_newtype_variant<T: ?Sized + Serialize>(
@@ -0,0 +1,138 @@
+//! Implement `serde::Serialize` for `Dynamic`, so that values can be serialized into any format.
+
+use crate::any::{Dynamic, Union};
+
+use serde::ser::{Error, Serialize, SerializeMap, SerializeSeq, Serializer};
+
+#[cfg(not(feature = "no_object"))]
+use crate::{engine::Map, record::Record};
+
+#[cfg(feature = "bigint")]
+use num_traits::ToPrimitive;
+
+use crate::stdlib::format;
+
+#[cfg(any(feature = "decimal", feature = "bigint"))]
+use crate::stdlib::string::ToString;
+
+/// Name of the newtype struct a `Record` is serialized as.
+///
+/// `to_dynamic` recognizes it and turns the serialized record back into a `Record`.
+#[cfg(not(feature = "no_object"))]
+pub(crate) const RECORD_STRUCT_NAME: &str = "$rhai::Record$";
+
+/// Wrapper to serialize an object map without turning it into a `Dynamic`.
+#[cfg(not(feature = "no_object"))]
+struct MapBody<'a>(&'a Map);
+
+#[cfg(not(feature = "no_object"))]
+impl Serialize for MapBody<'_> {
+    fn serialize<S: Serializer>(&self, ser: S) -> Result<S::Ok, S::Error> {
+        let mut map = ser.serialize_map(Some(self.0.len()))?;
+        for (key, value) in self.0.iter() {
+            map.serialize_entry(key.as_str(), value)?;
+        }
+        map.end()
+    }
+}
+
+/// Serialize a `Dynamic` value according to the type of value it holds.
+///
+/// Object maps are serialized as maps, arrays as sequences and function pointers as the names
+/// of their functions.  Decimal numbers, and big integers that are too large to fit into a
+/// primitive integer, are serialized as strings.
+///
+/// Values of custom types cannot be serialized.
+impl Serialize for Dynamic {
+    fn serialize<S: Serializer>(&self, ser: S) -> Result<S::Ok, S::Error> {
+        match &self.0 {
+            Union::Unit(_) => ser.serialize_unit(),
+            Union::Bool(v) => ser.serialize_bool(*v),
+            Union::Str(v) => ser.serialize_str(v.as_str()),
+            Union::Char(v) => ser.serialize_char(*v),
+            #[cfg(not(feature = "only_i32"))]
+            Union::Int(v) => ser.serialize_i64(*v),
+            #[cfg(feature = "only_i32")]
+            Union::Int(v) => ser.serialize_i32(*v),
+            #[cfg(not(feature = "no_float"))]
+            Union::Float(v) => ser.serialize_f64(*v),
+            #[cfg(feature = "decimal")]
+            Union::Decimal(v) => ser.serialize_str(&v.to_string()),
+            #[cfg(feature = "bigint")]
+            Union::BigInt(v) => {
+                if let Some(n) = v.to_i64() {
+                    ser.serialize_i64(n)
+                } else if let Some(n) = v.to_u64() {
+                    ser.serialize_u64(n)
+                } else if let Some(n) = v.to_i128() {
+                    ser.serialize_i128(n)
+                } else if let Some(n) = v.to_u128() {
+                    ser.serialize_u128(n)
+                } else {
+                    ser.serialize_str(&v.to_string())
+                }
+            }
+            #[cfg(not(feature = "no_index"))]
+            Union::Array(v) => {
+                let mut seq = ser.serialize_seq(Some(v.len()))?;
+                for item in v.iter() {
+                    seq.serialize_element(item)?;
+                }
+                seq.end()
+            }
+            #[cfg(not(feature = "no_object"))]
+            Union::Map(v) => MapBody(v).serialize(ser),
+            Union::FnPtr(v) => ser.serialize_str(v.fn_name()),
+            #[cfg(not(feature = "no_closure"))]
+            Union::Shared(_) => self.flatten_clone().serialize(ser),
+
+            #[cfg(not(feature = "no_object"))]
+            Union::Variant(v) if v.is::<Record>() => {
+                self.downcast_ref::<Record>().unwrap().serialize(ser)
+            }
+
+            Union::Variant(v) if v.is::<i8>() => ser.serialize_i8(*self.downcast_ref().unwrap()),
+            Union::Variant(v) if v.is::<i16>() => ser.serialize_i16(*self.downcast_ref().unwrap()),
+            Union::Variant(v) if v.is::<i32>() => ser.serialize_i32(*self.downcast_ref().unwrap()),
+            Union::Variant(v) if v.is::<i64>() => ser.serialize_i64(*self.downcast_ref().unwrap()),
+            Union::Variant(v) if v.is::<i128>() => {
+                ser.serialize_i128(*self.downcast_ref().unwrap())
+            }
+            Union::Variant(v) if v.is::<u8>() => ser.serialize_u8(*self.downcast_ref().unwrap()),
+            Union::Variant(v) if v.is::<u16>() => ser.serialize_u16(*self.downcast_ref().unwrap()),
+            Union::Variant(v) if v.is::<u32>() => ser.serialize_u32(*self.downcast_ref().unwrap()),
+            Union::Variant(v) if v.is::<u64>() => ser.serialize_u64(*self.downcast_ref().unwrap()),
+            Union::Variant(v) if v.is::<u128>() => {
+                ser.serialize_u128(*self.downcast_ref().unwrap())
+            }
+            #[cfg(not(feature = "no_float"))]
+            Union::Variant(v) if v.is::<f32>() => ser.serialize_f32(*self.downcast_ref().unwrap()),
+
+            Union::Variant(_) => Err(S::Error::custom(format!(
+                "cannot serialize a value of type '{}'",
+                self.type_name()
+            ))),
+        }
+    }
+}
+
+/// Serialize a `Record` as a newtype struct wrapping a map with a single entry, which maps the
+/// name of the record type to the fields of the record.
+///
+/// The methods carried by the record are not serialized.
+#[cfg(not(feature = "no_object"))]
+impl Serialize for Record {
+    fn serialize<S: Serializer>(&self, ser: S) -> Result<S::Ok, S::Error> {
+        struct Body<'a>(&'a Record);
+
+        impl Serialize for Body<'_> {
+            fn serialize<S: Serializer>(&self, ser: S) -> Result<S::Ok, S::Error> {
+                let mut map = ser.serialize_map(Some(1))?;
+                map.serialize_entry(self.0.type_name(), &MapBody(self.0.fields()))?;
+                map.end()
+            }
+        }
+
+        ser.serialize_newtype_struct(RECORD_STRUCT_NAME, &Body(self))
+    }
+}
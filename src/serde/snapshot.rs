@@ -0,0 +1,528 @@
+//! Implement serializable snapshots of suspended scripts.
+
+use super::de::from_dynamic;
+use super::ser::to_dynamic;
+use crate::any::{Dynamic, Union, Variant};
+use crate::engine::Engine;
+use crate::fn_native::FnPtr;
+use crate::parser::{AST, INT};
+use crate::result::EvalAltResult;
+use crate::resume::Suspended;
+use crate::scope::{EntryType as ScopeEntryType, Scope};
+use crate::token::Position;
+
+#[cfg(not(feature = "no_float"))]
+use crate::parser::FLOAT;
+
+#[cfg(not(feature = "no_object"))]
+use crate::record::Record;
+
+#[cfg(feature = "decimal")]
+use rust_decimal::Decimal;
+
+#[cfg(feature = "bigint")]
+use num_bigint::BigInt;
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use crate::stdlib::{
+    any::TypeId,
+    boxed::Box,
+    convert::TryFrom,
+    format,
+    hash::{Hash, Hasher},
+    string::{String, ToString},
+    vec::Vec,
+};
+
+/// Conversion function between a custom type and a `Dynamic` value tree.
+type ConvertFn = fn(&Dynamic) -> Result<Dynamic, Box<EvalAltResult>>;
+
+/// A custom type registered via `Engine::register_serializable`.
+#[derive(Debug, Clone)]
+pub struct SerializableType {
+    /// Name of the type in a `Snapshot`.
+    name: String,
+    /// Convert a value of the type into a `Dynamic` value tree.
+    to_tree: ConvertFn,
+    /// Convert a `Dynamic` value tree back into a value of the type.
+    from_tree: ConvertFn,
+}
+
+/// A serializable `Dynamic` value.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) enum SnapshotValue {
+    Unit,
+    Bool(bool),
+    Str(String),
+    Char(char),
+    Int(INT),
+    #[cfg(not(feature = "no_float"))]
+    Float(FLOAT),
+    #[cfg(feature = "decimal")]
+    Decimal(String),
+    #[cfg(feature = "bigint")]
+    BigInt(String),
+    #[cfg(not(feature = "no_index"))]
+    Array(Vec<SnapshotValue>),
+    #[cfg(not(feature = "no_object"))]
+    Map(Vec<(String, SnapshotValue)>),
+    #[cfg(not(feature = "no_object"))]
+    Record(String, Vec<(String, SnapshotValue)>),
+    FnPtr(String, Vec<SnapshotValue>),
+    Custom(String, Box<SnapshotValue>),
+}
+
+impl SnapshotValue {
+    /// Convert a `Dynamic` value into a `SnapshotValue`.
+    ///
+    /// Values of custom types can only be converted with an `Engine` that registered them.
+    pub(crate) fn from_dynamic(
+        value: &Dynamic,
+        engine: Option<&Engine>,
+    ) -> Result<Self, Box<EvalAltResult>> {
+        Ok(match &value.0 {
+            Union::Unit(_) => Self::Unit,
+            Union::Bool(v) => Self::Bool(*v),
+            Union::Str(v) => Self::Str(v.to_string()),
+            Union::Char(v) => Self::Char(*v),
+            Union::Int(v) => Self::Int(*v),
+            #[cfg(not(feature = "no_float"))]
+            Union::Float(v) => Self::Float(*v),
+            #[cfg(feature = "decimal")]
+            Union::Decimal(v) => Self::Decimal(v.to_string()),
+            #[cfg(feature = "bigint")]
+            Union::BigInt(v) => Self::BigInt(v.to_string()),
+            #[cfg(not(feature = "no_index"))]
+            Union::Array(v) => Self::Array(
+                v.iter()
+                    .map(|v| Self::from_dynamic(v, engine))
+                    .collect::<Result<_, _>>()?,
+            ),
+            #[cfg(not(feature = "no_object"))]
+            Union::Map(v) => Self::Map(
+                v.iter()
+                    .map(|(k, v)| Ok((k.to_string(), Self::from_dynamic(v, engine)?)))
+                    .collect::<Result<_, Box<EvalAltResult>>>()?,
+            ),
+            Union::FnPtr(v) => Self::FnPtr(
+                v.fn_name().into(),
+                v.curry()
+                    .iter()
+                    .map(|v| Self::from_dynamic(v, engine))
+                    .collect::<Result<_, _>>()?,
+            ),
+            #[cfg(not(feature = "no_closure"))]
+            Union::Shared(_) => Self::from_dynamic(&value.flatten_clone(), engine)?,
+            #[cfg(not(feature = "no_object"))]
+            Union::Variant(v) if v.is::<Record>() => {
+                let record = value.downcast_ref::<Record>().unwrap();
+
+                Self::Record(
+                    record.type_name().into(),
+                    record
+                        .fields()
+                        .iter()
+                        .map(|(k, v)| Ok((k.to_string(), Self::from_dynamic(v, engine)?)))
+                        .collect::<Result<_, Box<EvalAltResult>>>()?,
+                )
+            }
+            Union::Variant(_) => {
+                let typ = engine
+                    .and_then(|engine| engine.serializable_types.as_ref())
+                    .and_then(|t| t.get(&value.type_id()))
+                    .ok_or_else(|| {
+                        let type_name = match engine {
+                            Some(engine) => engine.map_type_name(value.type_name()),
+                            None => value.type_name(),
+                        };
+                        EvalAltResult::ErrorMismatchOutputType(
+                            "serializable type".into(),
+                            type_name.into(),
+                            Position::none(),
+                        )
+                    })?;
+
+                let tree = (typ.to_tree)(value)?;
+                Self::Custom(
+                    typ.name.clone(),
+                    Box::new(Self::from_dynamic(&tree, engine)?),
+                )
+            }
+        })
+    }
+
+    /// Convert a `SnapshotValue` back into a `Dynamic` value.
+    ///
+    /// Values of custom types can only be converted with an `Engine` that registered them.
+    pub(crate) fn to_dynamic(
+        &self,
+        engine: Option<&Engine>,
+    ) -> Result<Dynamic, Box<EvalAltResult>> {
+        Ok(match self {
+            Self::Unit => ().into(),
+            Self::Bool(v) => (*v).into(),
+            Self::Str(v) => v.clone().into(),
+            Self::Char(v) => (*v).into(),
+            Self::Int(v) => (*v).into(),
+            #[cfg(not(feature = "no_float"))]
+            Self::Float(v) => (*v).into(),
+            #[cfg(feature = "decimal")]
+            Self::Decimal(v) => v
+                .parse::<Decimal>()
+                .map_err(|_| {
+                    EvalAltResult::ErrorMismatchOutputType(
+                        "decimal".into(),
+                        v.into(),
+                        Position::none(),
+                    )
+                })?
+                .into(),
+            #[cfg(feature = "bigint")]
+            Self::BigInt(v) => v
+                .parse::<BigInt>()
+                .map_err(|_| {
+                    EvalAltResult::ErrorMismatchOutputType(
+                        "bigint".into(),
+                        v.into(),
+                        Position::none(),
+                    )
+                })?
+                .into(),
+            #[cfg(not(feature = "no_index"))]
+            Self::Array(v) => Dynamic(Union::Array(Box::new(
+                v.iter()
+                    .map(|v| v.to_dynamic(engine))
+                    .collect::<Result<_, _>>()?,
+            ))),
+            #[cfg(not(feature = "no_object"))]
+            Self::Map(v) => Dynamic(Union::Map(Box::new(
+                v.iter()
+                    .map(|(k, v)| Ok((k.as_str().into(), v.to_dynamic(engine)?)))
+                    .collect::<Result<_, Box<EvalAltResult>>>()?,
+            ))),
+            #[cfg(not(feature = "no_object"))]
+            Self::Record(name, v) => Dynamic::from(Record::new(
+                name.as_str(),
+                v.iter()
+                    .map(|(k, v)| Ok((k.as_str().into(), v.to_dynamic(engine)?)))
+                    .collect::<Result<_, Box<EvalAltResult>>>()?,
+            )),
+            Self::FnPtr(name, curry) => FnPtr::new_unchecked(
+                name.as_str(),
+                curry
+                    .iter()
+                    .map(|v| v.to_dynamic(engine))
+                    .collect::<Result<_, _>>()?,
+            )
+            .into(),
+            Self::Custom(name, tree) => {
+                let typ = engine
+                    .and_then(|engine| engine.serializable_types.as_ref())
+                    .and_then(|t| t.values().find(|t| t.name == *name))
+                    .ok_or_else(|| {
+                        EvalAltResult::ErrorMismatchOutputType(
+                            "serializable type".into(),
+                            name.into(),
+                            Position::none(),
+                        )
+                    })?;
+
+                (typ.from_tree)(&tree.to_dynamic(engine)?)?
+            }
+        })
+    }
+}
+
+/// A serializable snapshot of a script suspended at a `yield` expression.
+///
+/// Requires the `serde` feature.
+///
+/// A suspended script is resumed by replaying it (see `Suspended`), so the snapshot only holds
+/// the original `Scope`, the values the script has been resumed with and the values it yielded.
+/// Call frames, the current `Scope`, the `State` (e.g. the number of operations) and all imported
+/// modules are not stored - they are rebuilt by the replay when the script is resumed.  The same
+/// limitations as replaying a `Suspended` apply.
+///
+/// Create a snapshot via `Engine::snapshot` and turn it back into a `Suspended` via `Engine::restore`,
+/// using the same `AST`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Snapshot {
+    /// Hash of the `AST`, to detect restoring against a different script.
+    ast_hash: u64,
+    /// Original scope - (name, constant?, value).
+    scope: Vec<(String, bool, SnapshotValue)>,
+    /// Values the script has been resumed with, in order.
+    resume_values: Vec<SnapshotValue>,
+    /// Values passed to, and positions of, the `yield` expressions the script has been resumed from.
+    yielded: Vec<(SnapshotValue, (usize, usize))>,
+    /// Value passed to `yield`.
+    value: SnapshotValue,
+    /// Line and character position of the `yield` expression - zero line means none.
+    pos: (usize, usize),
+}
+
+/// Convert a `Position` into a line and character position - zero line means none.
+fn to_snapshot_pos(pos: Position) -> (usize, usize) {
+    match (pos.line(), pos.position()) {
+        (Some(line), pos) => (line, pos.unwrap_or(0)),
+        (None, _) => (0, 0),
+    }
+}
+
+/// Convert a line and character position back into a `Position`.
+fn from_snapshot_pos(pos: (usize, usize)) -> Result<Position, Box<EvalAltResult>> {
+    match pos {
+        (0, _) => Ok(Position::none()),
+        (line, pos) => match (u16::try_from(line), u16::try_from(pos)) {
+            (Ok(line), Ok(pos)) => Ok(Position::new(line, pos)),
+            _ => EvalAltResult::ErrorRuntime(
+                format!(
+                    "Invalid position in snapshot: line {}, position {}",
+                    line, pos
+                ),
+                Position::none(),
+            )
+            .into(),
+        },
+    }
+}
+
+/// A 64-bit FNV-1a hasher.
+///
+/// Unlike the standard hashers, its results do not change between Rust releases, builds or
+/// platforms, so hashes stored in a `Snapshot` remain valid.
+///
+/// The only `u64` values in an `AST` are the pre-calculated hashes of function calls and
+/// variables.  They are calculated with the standard hasher (and `TypeId`'s), so they are not
+/// stable and are skipped.  The names, modules and number of arguments they are calculated from
+/// are part of the `AST` and are hashed instead.
+struct StableHasher(u64);
+
+impl StableHasher {
+    fn new() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl Hasher for StableHasher {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 ^= byte as u64;
+            self.0 = self.0.wrapping_mul(0x0000_0100_0000_01b3);
+        }
+    }
+    // Integers are written in little-endian, and pointer-sized integers as 64 bits,
+    // so that the results are independent of the platform
+    fn write_u16(&mut self, i: u16) {
+        self.write(&i.to_le_bytes())
+    }
+    fn write_u32(&mut self, i: u32) {
+        self.write(&i.to_le_bytes())
+    }
+    fn write_u64(&mut self, _: u64) {}
+    fn write_u128(&mut self, i: u128) {
+        self.write(&i.to_le_bytes())
+    }
+    fn write_usize(&mut self, i: usize) {
+        self.write(&(i as u64).to_le_bytes())
+    }
+    fn write_i16(&mut self, i: i16) {
+        self.write(&i.to_le_bytes())
+    }
+    fn write_i32(&mut self, i: i32) {
+        self.write(&i.to_le_bytes())
+    }
+    fn write_i64(&mut self, i: i64) {
+        self.write(&i.to_le_bytes())
+    }
+    fn write_i128(&mut self, i: i128) {
+        self.write(&i.to_le_bytes())
+    }
+    fn write_isize(&mut self, i: isize) {
+        self.write_i64(i as i64)
+    }
+}
+
+/// Calculate a hash of all the statements and script-defined functions in an `AST`.
+fn calc_ast_hash(ast: &AST) -> u64 {
+    let mut s = StableHasher::new();
+    ast.statements().hash(&mut s);
+
+    // Functions are not stored in any particular order, so combine them independent of order
+    #[cfg(not(feature = "no_function"))]
+    ast.lib()
+        .iter_script_fn()
+        .map(|fn_def| {
+            let mut s = StableHasher::new();
+            fn_def.name.hash(&mut s);
+            fn_def.params.hash(&mut s);
+            fn_def.body.hash(&mut s);
+            s.finish()
+        })
+        .fold(0_u64, |hash, fn_hash| hash ^ fn_hash)
+        .hash(&mut s);
+
+    s.finish()
+}
+
+impl Engine {
+    /// Register a custom type so that its values can be stored in a `Snapshot`.
+    ///
+    /// The name identifies the type inside a `Snapshot` and must not change between
+    /// taking a snapshot and restoring it.
+    ///
+    /// Values are converted via `rhai::ser::to_dynamic` and `rhai::de::from_dynamic`,
+    /// so the type must serialize into standard types (e.g. `INT`, `FLOAT`, strings,
+    /// arrays and object maps) or other registered types.
+    ///
+    /// Requires the `serde` feature.
+    pub fn register_serializable<T: Variant + Clone + Serialize + DeserializeOwned>(
+        &mut self,
+        name: &str,
+    ) -> &mut Self {
+        if self.serializable_types.is_none() {
+            self.serializable_types = Some(Default::default());
+        }
+
+        self.serializable_types.as_mut().unwrap().insert(
+            TypeId::of::<T>(),
+            SerializableType {
+                name: name.into(),
+                to_tree: |value| to_dynamic(value.downcast_ref::<T>().unwrap()),
+                from_tree: |value| from_dynamic::<T>(value).map(Dynamic::from),
+            },
+        );
+        self
+    }
+
+    /// Take a serializable `Snapshot` of a suspended script.
+    ///
+    /// Requires the `serde` feature.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<rhai::EvalAltResult>> {
+    /// use rhai::{Engine, Resumable, Snapshot, INT};
+    /// use rhai::{de::from_dynamic, ser::to_dynamic};
+    ///
+    /// let engine = Engine::new();
+    /// let ast = engine.compile("let x = yield 1; x + 1")?;
+    ///
+    /// let snapshot = match engine.eval_ast_resumable(&ast)? {
+    ///     Resumable::Suspended(s) => engine.snapshot(&s)?,
+    ///     Resumable::Completed(_) => unreachable!(),
+    /// };
+    ///
+    /// // Store the snapshot with any serde format, then load it back
+    /// let stored = to_dynamic(snapshot)?;
+    /// let snapshot: Snapshot = from_dynamic(&stored)?;
+    ///
+    /// let suspended = engine.restore(&ast, &snapshot)?;
+    ///
+    /// match engine.resume(suspended, 41 as INT)? {
+    ///     Resumable::Completed(result) => assert_eq!(result.cast::<INT>(), 42),
+    ///     Resumable::Suspended(_) => unreachable!(),
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn snapshot(&self, suspended: &Suspended) -> Result<Snapshot, Box<EvalAltResult>> {
+        let mut scope = suspended
+            .scope
+            .to_iter()
+            .map(|entry| {
+                Ok((
+                    entry.name.to_string(),
+                    entry.typ == ScopeEntryType::Constant,
+                    SnapshotValue::from_dynamic(&entry.value, Some(self))?,
+                ))
+            })
+            .collect::<Result<Vec<_>, Box<EvalAltResult>>>()?;
+
+        // Scope entries are iterated in reverse order
+        scope.reverse();
+
+        let resume_values = suspended
+            .resume_values
+            .iter()
+            .map(|v| SnapshotValue::from_dynamic(v, Some(self)))
+            .collect::<Result<_, _>>()?;
+
+        let yielded = suspended
+            .yielded
+            .iter()
+            .map(|(v, pos)| {
+                Ok((
+                    SnapshotValue::from_dynamic(v, Some(self))?,
+                    to_snapshot_pos(*pos),
+                ))
+            })
+            .collect::<Result<_, Box<EvalAltResult>>>()?;
+
+        Ok(Snapshot {
+            ast_hash: calc_ast_hash(&suspended.ast),
+            scope,
+            resume_values,
+            yielded,
+            value: SnapshotValue::from_dynamic(&suspended.value, Some(self))?,
+            pos: to_snapshot_pos(suspended.pos),
+        })
+    }
+
+    /// Restore a suspended script from a `Snapshot`, which must be taken from a script
+    /// compiled into the same `AST`.
+    ///
+    /// Requires the `serde` feature.
+    pub fn restore(
+        &self,
+        ast: &AST,
+        snapshot: &Snapshot,
+    ) -> Result<Box<Suspended>, Box<EvalAltResult>> {
+        if calc_ast_hash(ast) != snapshot.ast_hash {
+            return EvalAltResult::ErrorRuntime(
+                "Snapshot was not taken from the same script".into(),
+                Position::none(),
+            )
+            .into();
+        }
+
+        let mut scope = Scope::new();
+
+        for (name, is_constant, value) in &snapshot.scope {
+            let value = value.to_dynamic(Some(self))?;
+
+            if *is_constant {
+                scope.push_constant_dynamic(name.clone(), value);
+            } else {
+                scope.push_dynamic(name.clone(), value);
+            }
+        }
+
+        let resume_values = snapshot
+            .resume_values
+            .iter()
+            .map(|v| v.to_dynamic(Some(self)))
+            .collect::<Result<_, _>>()?;
+
+        let yielded = snapshot
+            .yielded
+            .iter()
+            .map(|(v, pos)| Ok((v.to_dynamic(Some(self))?, from_snapshot_pos(*pos)?)))
+            .collect::<Result<_, Box<EvalAltResult>>>()?;
+
+        Ok(Box::new(Suspended {
+            ast: ast.clone(),
+            scope,
+            resume_values,
+            yielded,
+            value: snapshot.value.to_dynamic(Some(self))?,
+            pos: from_snapshot_pos(snapshot.pos)?,
+        }))
+    }
+}
@@ -1,8 +1,12 @@
 //! Configuration settings for `Engine`.
 
 use crate::engine::Engine;
+use crate::fn_native::{shared_make_mut, SendSync};
 use crate::packages::PackageLibrary;
-use crate::token::{is_valid_identifier, Token};
+use crate::profiler::Profiler;
+use crate::sandbox::SandboxProfile;
+use crate::token::{is_custom_symbol_char, is_valid_identifier, Token};
+use crate::utils::StaticVec;
 
 #[cfg(not(feature = "no_module"))]
 use crate::module::ModuleResolver;
@@ -10,10 +14,18 @@ use crate::module::ModuleResolver;
 #[cfg(not(feature = "no_optimize"))]
 use crate::optimize::OptimizationLevel;
 
-use crate::stdlib::{format, string::String};
+use crate::stdlib::{boxed::Box, format, string::String};
 
-#[cfg(not(feature = "no_module"))]
-use crate::stdlib::boxed::Box;
+#[cfg(not(feature = "no_std"))]
+use crate::stdlib::time::Duration;
+
+#[cfg(not(feature = "no_std"))]
+#[cfg(not(target_arch = "wasm32"))]
+use crate::stdlib::time::Instant;
+
+#[cfg(not(feature = "no_std"))]
+#[cfg(target_arch = "wasm32")]
+use instant::Instant;
 
 impl Engine {
     /// Load a new package into the `Engine`.
@@ -21,8 +33,15 @@ impl Engine {
     /// When searching for functions, packages loaded later are preferred.
     /// In other words, loaded packages are searched in reverse order.
     pub fn load_package(&mut self, package: impl Into<PackageLibrary>) -> &mut Self {
+        let mut package = package.into();
+
+        // Packages loaded within `register_deterministic` are deterministic
+        if self.marking_deterministic && !package.is_deterministic() {
+            shared_make_mut(&mut package).set_deterministic(true);
+        }
+
         // Push the package to the top - packages are searched in reverse order
-        self.packages.push(package.into());
+        self.packages.push(package);
         self
     }
 
@@ -164,6 +183,202 @@ impl Engine {
         self.limits.max_map_size
     }
 
+    /// Set the maximum approximate number of bytes of data held by a script (0 for unlimited).
+    ///
+    /// This includes all variables in the scopes of all active function calls,
+    /// as well as the temporary value being calculated.
+    /// Checking the memory usage costs time proportional to the amount of data held.
+    #[cfg(not(feature = "unchecked"))]
+    pub fn set_max_memory(&mut self, max_size: usize) -> &mut Self {
+        self.limits.max_memory = if max_size == usize::MAX { 0 } else { max_size };
+        self
+    }
+
+    /// The maximum approximate number of bytes of data held by a script (0 for unlimited).
+    #[cfg(not(feature = "unchecked"))]
+    pub fn max_memory(&self) -> usize {
+        self.limits.max_memory
+    }
+
+    /// Set the maximum amount of time a script is allowed to run.
+    /// A script running for longer terminates with `EvalAltResult::ErrorTerminated`.
+    ///
+    /// Not available under the `no_std` feature.
+    #[cfg(not(feature = "unchecked"))]
+    #[cfg(not(feature = "no_std"))]
+    pub fn set_max_duration(&mut self, max_duration: Duration) -> &mut Self {
+        self.limits.max_duration = Some(max_duration);
+        self
+    }
+
+    /// Remove the time limit on running scripts.
+    ///
+    /// Not available under the `no_std` feature.
+    #[cfg(not(feature = "unchecked"))]
+    #[cfg(not(feature = "no_std"))]
+    pub fn clear_max_duration(&mut self) -> &mut Self {
+        self.limits.max_duration = None;
+        self
+    }
+
+    /// The maximum amount of time a script is allowed to run, if any.
+    ///
+    /// Not available under the `no_std` feature.
+    #[cfg(not(feature = "unchecked"))]
+    #[cfg(not(feature = "no_std"))]
+    pub fn max_duration(&self) -> Option<Duration> {
+        self.limits.max_duration
+    }
+
+    /// Record statistics of all function calls into a `Profiler`.
+    ///
+    /// All clones of a `Profiler` share the same statistics, so keep a clone to read the results.
+    pub fn set_profiler(&mut self, profiler: Profiler) -> &mut Self {
+        self.profiler = Some(profiler);
+        self
+    }
+
+    /// Stop recording statistics of function calls.
+    pub fn clear_profiler(&mut self) -> &mut Self {
+        self.profiler = None;
+        self
+    }
+
+    /// Restrict what scripts are allowed to do via a `SandboxProfile`.
+    ///
+    /// The maximum limits of the profile are imposed on the `Engine`; limits already lower are kept.
+    pub fn set_sandbox(&mut self, profile: SandboxProfile) -> &mut Self {
+        #[cfg(not(feature = "unchecked"))]
+        profile.restrict_limits(&mut self.limits);
+
+        self.sandbox = Some(profile);
+        self
+    }
+
+    /// Remove the `SandboxProfile` restricting scripts.
+    ///
+    /// Limits imposed by the profile are not restored.
+    pub fn clear_sandbox(&mut self) -> &mut Self {
+        self.sandbox = None;
+        self
+    }
+
+    /// The `SandboxProfile` restricting scripts, if any.
+    pub fn sandbox(&self) -> Option<&SandboxProfile> {
+        self.sandbox.as_ref()
+    }
+
+    /// Enable/disable deterministic execution mode, in which running the same script on the same
+    /// inputs always produces the same results.
+    ///
+    /// In deterministic mode:
+    ///
+    /// * `timestamp` reads the clock set via `set_clock`.  If no clock is set, a clock that never
+    ///   advances is set.
+    /// * The keys and values of object maps are returned in sorted key order.
+    /// * Scripts can only call functions registered, and packages loaded, within
+    ///   `register_deterministic`, as well as functions in imported modules marked deterministic
+    ///   via `Module::set_deterministic`.  Calling other external Rust functions fails with
+    ///   `EvalAltResult::ErrorNotDeterministic`.
+    pub fn set_deterministic(&mut self, enable: bool) -> &mut Self {
+        if enable {
+            #[cfg(not(feature = "no_std"))]
+            if self.clock.is_none() {
+                self.set_clock(Duration::default);
+            }
+        }
+
+        self.deterministic = enable;
+        self
+    }
+
+    /// Is deterministic execution mode enabled?
+    pub fn is_deterministic(&self) -> bool {
+        self.deterministic
+    }
+
+    /// Set the clock read by `timestamp`, as a function returning the time elapsed since the clock
+    /// is set.
+    ///
+    /// Not available under the `no_std` feature.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<rhai::EvalAltResult>> {
+    /// use std::sync::atomic::{AtomicU64, Ordering};
+    /// use std::sync::Arc;
+    /// use std::time::Duration;
+    /// use rhai::Engine;
+    ///
+    /// let ticks = Arc::new(AtomicU64::new(0));
+    /// let clock = ticks.clone();
+    ///
+    /// let mut engine = Engine::new();
+    ///
+    /// // Every reading of the clock advances it by one second
+    /// engine.set_clock(move || Duration::from_secs(clock.fetch_add(1, Ordering::SeqCst)));
+    ///
+    /// # #[cfg(not(feature = "no_float"))]
+    /// assert_eq!(engine.eval::<rhai::FLOAT>("let t = timestamp(); timestamp() - t")?, 1.0);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(not(feature = "no_std"))]
+    pub fn set_clock(&mut self, clock: impl Fn() -> Duration + SendSync + 'static) -> &mut Self {
+        self.clock = Some((Instant::now(), Box::new(move |_| clock())));
+        self
+    }
+
+    /// Read timestamps from the system clock again.
+    ///
+    /// Not available under the `no_std` feature.
+    #[cfg(not(feature = "no_std"))]
+    pub fn clear_clock(&mut self) -> &mut Self {
+        self.clock = None;
+        self
+    }
+
+    /// Promote integer arithmetic that would overflow to `BigInt` instead of raising an error.
+    ///
+    /// Applies to `+`, `-`, `*`, `/`, `%`, `~` and `<<` between two integers.  Results that fit
+    /// into an integer remain integers.
+    ///
+    /// Only available under the `bigint` feature.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), Box<rhai::EvalAltResult>> {
+    /// # #[cfg(not(feature = "only_i32"))]
+    /// # {
+    /// use rhai::{BigInt, Engine};
+    ///
+    /// let mut engine = Engine::new();
+    ///
+    /// engine.set_bigint_promotion(true);
+    ///
+    /// let x = engine.eval::<BigInt>("9223372036854775807 + 1")?;
+    ///
+    /// assert_eq!(x.to_string(), "9223372036854775808");
+    /// # }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "bigint")]
+    pub fn set_bigint_promotion(&mut self, enable: bool) -> &mut Self {
+        self.bigint_promotion = enable;
+        self
+    }
+
+    /// Is overflowing integer arithmetic promoted to `BigInt`?
+    ///
+    /// Only available under the `bigint` feature.
+    #[cfg(feature = "bigint")]
+    pub fn bigint_promotion(&self) -> bool {
+        self.bigint_promotion
+    }
+
     /// Set the module resolution service used by the `Engine`.
     ///
     /// Not available under the `no_module` feature.
@@ -176,6 +391,47 @@ impl Engine {
         self
     }
 
+    /// Evaluate each imported module only once and share it among all the scripts run on the `Engine`.
+    ///
+    /// A module is evaluated the first time it is imported.  Modules it imports in turn are
+    /// evaluated before the rest of its body runs.  Afterwards, every `import` of the same module
+    /// uses the same instance, which still counts towards the maximum number of modules.
+    ///
+    /// Turning this off discards all shared modules.
+    ///
+    /// Not available under the `no_module` feature.
+    #[cfg(not(feature = "no_module"))]
+    pub fn set_share_modules(&mut self, enable: bool) -> &mut Self {
+        self.shared_modules = if enable {
+            Some(self.shared_modules.take().unwrap_or_default())
+        } else {
+            None
+        };
+        self
+    }
+
+    /// Are imported modules evaluated only once and shared?
+    ///
+    /// Not available under the `no_module` feature.
+    #[cfg(not(feature = "no_module"))]
+    pub fn share_modules(&self) -> bool {
+        self.shared_modules.is_some()
+    }
+
+    /// Discard all shared modules, so that they are evaluated again the next time they are imported.
+    ///
+    /// Not available under the `no_module` feature.
+    #[cfg(not(feature = "no_module"))]
+    pub fn clear_shared_modules(&self) -> &Self {
+        if let Some(shared) = &self.shared_modules {
+            #[cfg(not(feature = "sync"))]
+            shared.borrow_mut().clear();
+            #[cfg(feature = "sync")]
+            shared.write().unwrap().clear();
+        }
+        self
+    }
+
     /// Disable a particular keyword or operator in the language.
     ///
     /// # Examples
@@ -227,7 +483,8 @@ impl Engine {
 
     /// Register a custom operator into the language.
     ///
-    /// The operator must be a valid identifier (i.e. it cannot be a symbol).
+    /// The operator is a left-associative binary operator.  It can be a valid identifier
+    /// (e.g. `foo`) or a symbol (e.g. `<$>`) that does not conflict with built-in symbols.
     ///
     /// # Examples
     ///
@@ -256,37 +513,260 @@ impl Engine {
         keyword: &str,
         precedence: u8,
     ) -> Result<&mut Self, String> {
+        self.register_custom_binary_operator(keyword, precedence, Associativity::Left)
+    }
+
+    /// Register a custom binary operator into the language, with a specific associativity.
+    ///
+    /// The operator can be a valid identifier (e.g. `foo`) or a symbol (e.g. `<$>`) that does
+    /// not conflict with built-in symbols.  Symbols are matched greedily, so the longest
+    /// registered symbol wins.
+    ///
+    /// A precedence of zero reserves the keyword without making it a binary operator.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> Result<(), Box<rhai::EvalAltResult>> {
+    /// use rhai::{Associativity, Engine, RegisterFn};
+    ///
+    /// let mut engine = Engine::new();
+    ///
+    /// // Register a right-associative custom operator '^@'
+    /// engine.register_custom_binary_operator("^@", 200, Associativity::Right).unwrap();
+    ///
+    /// engine.register_fn("^@", |x: i64, y: i64| x.pow(y as u32));
+    ///
+    /// // The same as: 2 ^@ (3 ^@ 2)
+    /// assert_eq!(engine.eval_expression::<i64>("2 ^@ 3 ^@ 2")?, 512);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn register_custom_binary_operator(
+        &mut self,
+        keyword: &str,
+        precedence: u8,
+        associativity: Associativity,
+    ) -> Result<&mut Self, String> {
+        self.check_custom_operator(keyword)?;
+
+        // Add to custom keywords
+        if self.custom_keywords.is_none() {
+            self.custom_keywords = Some(Default::default());
+        }
+
+        self.custom_keywords
+            .as_mut()
+            .unwrap()
+            .insert(keyword.into(), precedence);
+
+        match associativity {
+            Associativity::Left => {
+                if let Some(bind_right) = self.custom_bind_right.as_mut() {
+                    bind_right.remove(keyword);
+                }
+            }
+            Associativity::Right => {
+                if self.custom_bind_right.is_none() {
+                    self.custom_bind_right = Some(Default::default());
+                }
+
+                self.custom_bind_right
+                    .as_mut()
+                    .unwrap()
+                    .insert(keyword.into());
+            }
+        }
+
+        Ok(self)
+    }
+
+    /// Register a custom prefix (unary) operator into the language.
+    ///
+    /// The operator can be a valid identifier (e.g. `foo`) or a symbol (e.g. `<$>`) that does
+    /// not conflict with built-in symbols.  It maps to a function of the same name taking one
+    /// parameter, and binds as tightly as the built-in unary operators.
+    ///
+    /// The same operator can also be registered as a binary operator.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> Result<(), Box<rhai::EvalAltResult>> {
+    /// use rhai::{Engine, RegisterFn};
+    ///
+    /// let mut engine = Engine::new();
+    ///
+    /// // Register a custom prefix operator 'sqr'
+    /// engine.register_custom_prefix_operator("sqr").unwrap();
+    ///
+    /// engine.register_fn("sqr", |x: i64| x * x);
+    ///
+    /// assert_eq!(engine.eval_expression::<i64>("1 + sqr 3 * 2")?, 19);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn register_custom_prefix_operator(&mut self, keyword: &str) -> Result<&mut Self, String> {
+        self.check_custom_operator(keyword)?;
+
+        // Add to custom keywords, keeping the precedence of a binary operator with the same name
+        if self.custom_keywords.is_none() {
+            self.custom_keywords = Some(Default::default());
+        }
+
+        self.custom_keywords
+            .as_mut()
+            .unwrap()
+            .entry(keyword.into())
+            .or_insert(0);
+
+        if self.custom_prefix_operators.is_none() {
+            self.custom_prefix_operators = Some(Default::default());
+        }
+
+        self.custom_prefix_operators
+            .as_mut()
+            .unwrap()
+            .insert(keyword.into());
+
+        Ok(self)
+    }
+
+    /// Check that a keyword or symbol can be used as a custom operator.
+    fn check_custom_operator(&self, keyword: &str) -> Result<(), String> {
+        if keyword.is_empty() {
+            return Err("custom operator cannot be empty".into());
+        }
+
         if !is_valid_identifier(keyword.chars()) {
-            return Err(format!("not a valid identifier: '{}'", keyword).into());
+            // Symbols can only contain punctuation that does not delimit expressions
+            if let Some(ch) = keyword.chars().find(|&ch| !is_custom_symbol_char(ch)) {
+                return Err(format!(
+                    "not a valid identifier or symbol: '{}' (invalid character '{}')",
+                    keyword, ch
+                ));
+            }
+            if keyword.starts_with("//") || keyword.starts_with("/*") {
+                return Err(format!("'{}' conflicts with comments", keyword));
+            }
         }
 
         match Token::lookup_from_syntax(keyword) {
-            // Standard identifiers, reserved keywords and custom keywords are OK
+            // Standard identifiers, reserved keywords/symbols and custom keywords are OK
             None | Some(Token::Reserved(_)) | Some(Token::Custom(_)) => (),
-            // Disabled keywords are also OK
+            // Disabled keywords/operators are also OK, and replace the built-in ones
             Some(token)
-                if !self
+                if self
                     .disabled_symbols
                     .as_ref()
                     .map(|d| d.contains(token.syntax().as_ref()))
                     .unwrap_or(false) =>
             {
-                ()
+                return Ok(())
             }
             // Active standard keywords cannot be made custom
-            Some(_) => return Err(format!("'{}' is a reserved keyword", keyword).into()),
+            Some(token) if token.is_keyword() => {
+                return Err(format!("'{}' is a reserved keyword", keyword))
+            }
+            // Active standard operators cannot be made custom
+            Some(_) => return Err(format!("'{}' is a built-in operator", keyword)),
         }
 
-        // Add to custom keywords
-        if self.custom_keywords.is_none() {
-            self.custom_keywords = Some(Default::default());
+        if !is_valid_identifier(keyword.chars()) {
+            self.check_custom_symbol(keyword)?;
         }
 
-        self.custom_keywords
-            .as_mut()
-            .unwrap()
-            .insert(keyword.into(), precedence);
+        Ok(())
+    }
 
-        Ok(self)
+    /// Check that a custom symbol does not change how built-in symbols are tokenized.
+    fn check_custom_symbol(&self, symbol: &str) -> Result<(), String> {
+        // The symbol must not be the start of a built-in symbol (e.g. `?` for `??`)
+        let punctuation = || {
+            (0x21_u8..0x7f)
+                .map(char::from)
+                .filter(char::is_ascii_punctuation)
+        };
+        let extend = |s: String| {
+            punctuation().map(move |ch| {
+                let mut s = s.clone();
+                s.push(ch);
+                s
+            })
+        };
+
+        if let Some(builtin) = extend(symbol.into())
+            .chain(extend(symbol.into()).flat_map(extend))
+            .find(|s| self.is_builtin_symbol(s))
+        {
+            return Err(format!(
+                "'{}' conflicts with built-in operator '{}'",
+                symbol, builtin
+            ));
+        }
+
+        // The symbol must not start with a built-in symbol of more than one character (e.g. `<$>`)
+        if let Some((len, _)) = symbol
+            .char_indices()
+            .skip(2)
+            .find(|&(len, _)| self.is_builtin_symbol(&symbol[..len]))
+        {
+            return Err(format!(
+                "'{}' conflicts with built-in operator '{}'",
+                symbol,
+                &symbol[..len]
+            ));
+        }
+
+        // The symbol must not be made up entirely of built-in symbols (e.g. `+-` or `!!`)
+        if let Some(builtins) = self.split_builtin_symbols(symbol) {
+            let builtins: StaticVec<_> = builtins.iter().map(|s| format!("'{}'", s)).collect();
+
+            return Err(format!(
+                "'{}' conflicts with built-in operators {}",
+                symbol,
+                builtins.join(" followed by ")
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Split a symbol into active built-in symbols, if possible.
+    fn split_builtin_symbols<'a>(&self, symbol: &'a str) -> Option<StaticVec<&'a str>> {
+        if symbol.is_empty() {
+            return Some(Default::default());
+        }
+
+        (1..=symbol.len())
+            .rev()
+            .filter(|&len| self.is_builtin_symbol(&symbol[..len]))
+            .find_map(|len| {
+                self.split_builtin_symbols(&symbol[len..]).map(|mut rest| {
+                    rest.insert(0, &symbol[..len]);
+                    rest
+                })
+            })
     }
+
+    /// Is a symbol an active (i.e. not reserved and not disabled) built-in symbol?
+    fn is_builtin_symbol(&self, symbol: &str) -> bool {
+        match Token::lookup_from_syntax(symbol) {
+            None | Some(Token::Reserved(_)) | Some(Token::Custom(_)) => false,
+            Some(_) => !self
+                .disabled_symbols
+                .as_ref()
+                .map(|d| d.contains(symbol))
+                .unwrap_or(false),
+        }
+    }
+}
+
+/// Associativity of a custom binary operator.
+#[derive(Debug, Eq, PartialEq, Hash, Copy, Clone)]
+pub enum Associativity {
+    /// Operators of the same precedence bind to the left, i.e. `a op b op c` is `(a op b) op c`.
+    Left,
+    /// Operators of the same precedence bind to the right, i.e. `a op b op c` is `a op (b op c)`.
+    Right,
 }
@@ -87,6 +87,14 @@ pub struct EvalContext<'a, 'b: 'a, 's, 'm, 't, 'd: 't> {
     pub(crate) level: usize,
 }
 
+impl EvalContext<'_, '_, '_, '_, '_, '_> {
+    /// The current nesting level of function calls, with zero being the global level.
+    #[inline(always)]
+    pub fn call_level(&self) -> usize {
+        self.level
+    }
+}
+
 impl Engine {
     pub fn register_custom_syntax<S: AsRef<str> + ToString>(
         &mut self,
@@ -207,6 +207,12 @@ pub enum Token {
     Comma,
     /// `.`
     Period,
+    /// `?.`
+    Elvis,
+    /// `?[`
+    QuestionBracket,
+    /// `??`
+    DoubleQuestion,
     /// `#{`
     MapStart,
     /// `=`
@@ -249,6 +255,8 @@ pub enum Token {
     Pipe,
     /// `||`
     Or,
+    /// `|>`
+    PipeForward,
     /// `^`
     XOr,
     /// `&`
@@ -268,6 +276,8 @@ pub enum Token {
     Return,
     /// `throw`
     Throw,
+    /// `yield`
+    Yield,
     /// `+=`
     PlusAssign,
     /// `-=`
@@ -295,6 +305,12 @@ pub enum Token {
     /// Reserved under the `no_function` feature.
     #[cfg(not(feature = "no_function"))]
     Private,
+    /// `struct`
+    ///
+    /// Reserved under the `no_object` and `no_function` features.
+    #[cfg(not(feature = "no_object"))]
+    #[cfg(not(feature = "no_function"))]
+    Struct,
     /// `import`
     ///
     /// Reserved under the `no_module` feature.
@@ -356,6 +372,9 @@ impl Token {
                 DoubleColon => "::",
                 Comma => ",",
                 Period => ".",
+                Elvis => "?.",
+                QuestionBracket => "?[",
+                DoubleQuestion => "??",
                 MapStart => "#{",
                 Equals => "=",
                 True => "true",
@@ -377,12 +396,14 @@ impl Token {
                 NotEqualsTo => "!=",
                 Pipe => "|",
                 Or => "||",
+                PipeForward => "|>",
                 Ampersand => "&",
                 And => "&&",
                 Continue => "continue",
                 Break => "break",
                 Return => "return",
                 Throw => "throw",
+                Yield => "yield",
                 PlusAssign => "+=",
                 MinusAssign => "-=",
                 MultiplyAssign => "*=",
@@ -404,6 +425,9 @@ impl Token {
                 Fn => "fn",
                 #[cfg(not(feature = "no_function"))]
                 Private => "private",
+                #[cfg(not(feature = "no_object"))]
+                #[cfg(not(feature = "no_function"))]
+                Struct => "struct",
 
                 #[cfg(not(feature = "no_module"))]
                 Import => "import",
@@ -438,6 +462,9 @@ impl Token {
             "::" => DoubleColon,
             "," => Comma,
             "." => Period,
+            "?." => Elvis,
+            "?[" => QuestionBracket,
+            "??" => DoubleQuestion,
             "#{" => MapStart,
             "=" => Equals,
             "true" => True,
@@ -459,12 +486,14 @@ impl Token {
             "!=" => NotEqualsTo,
             "|" => Pipe,
             "||" => Or,
+            "|>" => PipeForward,
             "&" => Ampersand,
             "&&" => And,
             "continue" => Continue,
             "break" => Break,
             "return" => Return,
             "throw" => Throw,
+            "yield" => Yield,
             "+=" => PlusAssign,
             "-=" => MinusAssign,
             "*=" => MultiplyAssign,
@@ -486,6 +515,9 @@ impl Token {
             "fn" => Fn,
             #[cfg(not(feature = "no_function"))]
             "private" => Private,
+            #[cfg(not(feature = "no_object"))]
+            #[cfg(not(feature = "no_function"))]
+            "struct" => Struct,
 
             #[cfg(not(feature = "no_module"))]
             "import" => Import,
@@ -497,6 +529,9 @@ impl Token {
             #[cfg(feature = "no_function")]
             "fn" | "private" => Reserved(syntax.into()),
 
+            #[cfg(any(feature = "no_object", feature = "no_function"))]
+            "struct" => Reserved(syntax.into()),
+
             #[cfg(feature = "no_module")]
             "import" | "export" | "as" => Reserved(syntax.into()),
 
@@ -504,7 +539,7 @@ impl Token {
             | "new" | "use" | "module" | "package" | "var" | "static" | "shared" | "with"
             | "do" | "each" | "then" | "goto" | "exit" | "switch" | "match" | "case" | "try"
             | "catch" | "default" | "void" | "null" | "nil" | "spawn" | "go" | "sync" | "async"
-            | "await" | "yield" => Reserved(syntax.into()),
+            | "await" => Reserved(syntax.into()),
 
             KEYWORD_PRINT | KEYWORD_DEBUG | KEYWORD_TYPE_OF | KEYWORD_EVAL | KEYWORD_FN_PTR
             | KEYWORD_FN_PTR_CALL | KEYWORD_FN_PTR_CURRY | KEYWORD_IS_SHARED | KEYWORD_THIS => {
@@ -546,6 +581,9 @@ impl Token {
             Divide           |
             Comma            |
             Period           |
+            Elvis            |
+            QuestionBracket  | // ?[-expr] - is unary
+            DoubleQuestion   |
             Equals           |
             LessThan         |
             GreaterThan      |
@@ -556,6 +594,7 @@ impl Token {
             NotEqualsTo      |
             Pipe             |
             Or               |
+            PipeForward      |
             Ampersand        |
             And              |
             If               |
@@ -576,9 +615,11 @@ impl Token {
             ModuloAssign     |
             Return           |
             Throw            |
+            Yield            |
             PowerOf          |
             In               |
-            PowerOfAssign    => true,
+            PowerOfAssign    |
+            Custom(_)        => true,
 
             _ => false,
         }
@@ -594,6 +635,8 @@ impl Token {
             | RightShiftAssign | AndAssign | OrAssign | XOrAssign | ModuloAssign
             | PowerOfAssign => 0,
 
+            DoubleQuestion => 20,
+
             Or | XOr | Pipe => 30,
 
             And | Ampersand => 60,
@@ -604,13 +647,15 @@ impl Token {
 
             In => 130,
 
+            PipeForward => 140,
+
             Plus | Minus => 150,
 
             Divide | Multiply | PowerOf | Modulo => 180,
 
             LeftShift | RightShift => 210,
 
-            Period => 240,
+            Period | Elvis => 240,
 
             // Custom operators
             Custom(s) => custom.map_or(0, |c| *c.get(s).unwrap()),
@@ -630,7 +675,7 @@ impl Token {
             | PowerOfAssign => true,
 
             // Property access binds to the right
-            Period => true,
+            Period | Elvis => true,
 
             _ => false,
         }
@@ -643,9 +688,10 @@ impl Token {
         match self {
             LeftBrace | RightBrace | LeftParen | RightParen | LeftBracket | RightBracket | Plus
             | UnaryPlus | Minus | UnaryMinus | Multiply | Divide | Modulo | PowerOf | LeftShift
-            | RightShift | SemiColon | Colon | DoubleColon | Comma | Period | MapStart | Equals
-            | LessThan | GreaterThan | LessThanEqualsTo | GreaterThanEqualsTo | EqualsTo
-            | NotEqualsTo | Bang | Pipe | Or | XOr | Ampersand | And | PlusAssign | MinusAssign
+            | RightShift | SemiColon | Colon | DoubleColon | Comma | Period | Elvis
+            | QuestionBracket | DoubleQuestion | MapStart | Equals | LessThan | GreaterThan
+            | LessThanEqualsTo | GreaterThanEqualsTo | EqualsTo | NotEqualsTo | Bang | Pipe
+            | Or | PipeForward | XOr | Ampersand | And | PlusAssign | MinusAssign
             | MultiplyAssign | DivideAssign | LeftShiftAssign | RightShiftAssign | AndAssign
             | OrAssign | XOrAssign | ModuloAssign | PowerOfAssign => true,
 
@@ -661,11 +707,15 @@ impl Token {
             #[cfg(not(feature = "no_function"))]
             Fn | Private => true,
 
+            #[cfg(not(feature = "no_object"))]
+            #[cfg(not(feature = "no_function"))]
+            Struct => true,
+
             #[cfg(not(feature = "no_module"))]
             Import | Export | As => true,
 
             True | False | Let | Const | If | Else | While | Loop | For | In | Continue | Break
-            | Return | Throw => true,
+            | Return | Throw | Yield => true,
 
             _ => false,
         }
@@ -1336,6 +1386,10 @@ fn get_next_token_inner(
                 eat_next(stream, pos);
                 return Some((Token::OrAssign, start_pos));
             }
+            ('|', '>') => {
+                eat_next(stream, pos);
+                return Some((Token::PipeForward, start_pos));
+            }
             ('|', _) => return Some((Token::Pipe, start_pos)),
 
             ('&', '&') => {
@@ -1366,6 +1420,19 @@ fn get_next_token_inner(
             }
             ('~', _) => return Some((Token::PowerOf, start_pos)),
 
+            ('?', '.') => {
+                eat_next(stream, pos);
+                return Some((Token::Elvis, start_pos));
+            }
+            ('?', '[') => {
+                eat_next(stream, pos);
+                return Some((Token::QuestionBracket, start_pos));
+            }
+            ('?', '?') => {
+                eat_next(stream, pos);
+                return Some((Token::DoubleQuestion, start_pos));
+            }
+
             ('@', _) => return Some((Token::Reserved("@".into()), start_pos)),
 
             ('\0', _) => unreachable!(),
@@ -1468,6 +1535,14 @@ pub fn is_valid_identifier(name: impl Iterator<Item = char>) -> bool {
     first_alphabetic
 }
 
+/// Can this character be part of a custom operator symbol?
+pub fn is_custom_symbol_char(ch: char) -> bool {
+    match ch {
+        '(' | ')' | '[' | ']' | '{' | '}' | ',' | ';' | '"' | '\'' | '`' => false,
+        ch => ch.is_ascii_punctuation(),
+    }
+}
+
 #[cfg(feature = "unicode-xid-ident")]
 #[inline(always)]
 fn is_id_first_alphabetic(x: char) -> bool {
@@ -1499,11 +1574,24 @@ pub struct MultiInputsStream<'a> {
     streams: StaticVec<Peekable<Chars<'a>>>,
     /// The current stream index.
     index: usize,
+    /// Characters put back into the stream, in reverse order.
+    buf: StaticVec<char>,
+}
+
+impl MultiInputsStream<'_> {
+    /// Put characters back into the stream, to be read again in order.
+    fn unget(&mut self, chars: &[char]) {
+        chars.iter().rev().for_each(|&ch| self.buf.push(ch));
+    }
 }
 
 impl InputStream for MultiInputsStream<'_> {
     /// Get the next character
     fn get_next(&mut self) -> Option<char> {
+        if let Some(ch) = self.buf.pop() {
+            return Some(ch);
+        }
+
         loop {
             if self.index >= self.streams.len() {
                 // No more streams
@@ -1519,6 +1607,10 @@ impl InputStream for MultiInputsStream<'_> {
     }
     /// Peek the next character
     fn peek_next(&mut self) -> Option<char> {
+        if let Some(&ch) = self.buf.last() {
+            return Some(ch);
+        }
+
         loop {
             if self.index >= self.streams.len() {
                 // No more streams
@@ -1548,12 +1640,67 @@ pub struct TokenIterator<'a, 'e> {
     map: Option<Box<dyn Fn(Token) -> Token>>,
 }
 
+impl TokenIterator<'_, '_> {
+    /// Turn a symbol token into a custom operator if it starts a registered custom symbol,
+    /// consuming as many following characters as possible (i.e. the longest match wins).
+    fn scan_custom_symbol(&mut self, token: Token) -> Token {
+        let custom = match self.engine.custom_keywords.as_ref() {
+            Some(custom) => custom,
+            None => return token,
+        };
+
+        let mut symbol = match &token {
+            Token::Reserved(s) if !is_valid_identifier(s.chars()) => s.clone(),
+            Token::LexError(err) => match err.as_ref() {
+                LERR::UnexpectedInput(s) => s.clone(),
+                _ => return token,
+            },
+            token if token.is_operator() => token.syntax().into(),
+            _ => return token,
+        };
+
+        if !custom.keys().any(|k| k.starts_with(&symbol)) {
+            return token;
+        }
+
+        let start_pos = self.pos;
+        let mut eaten: StaticVec<char> = Default::default();
+
+        while let Some(ch) = self.stream.peek_next() {
+            symbol.push(ch);
+
+            if !custom.keys().any(|k| k.starts_with(&symbol)) {
+                symbol.pop();
+                break;
+            }
+
+            eaten.push(ch);
+            self.stream.get_next();
+            self.pos.advance();
+        }
+
+        if custom.contains_key(&symbol) {
+            let token = Token::Custom(symbol);
+            self.state.non_unary = !token.is_next_unary();
+            token
+        } else {
+            // Not a custom symbol - put back all the characters consumed
+            self.stream.unget(&eaten);
+            self.pos = start_pos;
+            token
+        }
+    }
+}
+
 impl<'a> Iterator for TokenIterator<'a, '_> {
     type Item = (Token, Position);
 
     fn next(&mut self) -> Option<Self::Item> {
+        let token = get_next_token(&mut self.stream, &mut self.state, &mut self.pos)
+            .map(|(token, pos)| (self.scan_custom_symbol(token), pos));
+
         let token = match (
-            get_next_token(&mut self.stream, &mut self.state, &mut self.pos),
+            token,
             self.engine.disabled_symbols.as_ref(),
             self.engine.custom_keywords.as_ref(),
         ) {
@@ -1604,6 +1751,8 @@ impl<'a> Iterator for TokenIterator<'a, '_> {
             }, pos)),
             // Custom keyword
             (Some((Token::Identifier(s), pos)), _, Some(custom)) if custom.contains_key(&s) => {
+                // Operators may follow a custom keyword, so update the state
+                self.state.non_unary = false;
                 Some((Token::Custom(s), pos))
             }
             // Custom standard keyword - must be disabled
@@ -1636,6 +1785,20 @@ impl<'a> Iterator for TokenIterator<'a, '_> {
             (r, _, _) => r,
         };
 
+        // Keyword not allowed by the sandbox profile
+        let token = match (token, self.engine.sandbox.as_ref()) {
+            (Some((token, pos)), Some(sandbox)) if token.is_keyword() => {
+                match sandbox.check_keyword(token.syntax().as_ref()) {
+                    Some(err) => Some((
+                        Token::LexError(Box::new(LexError::SandboxViolation(err))),
+                        pos,
+                    )),
+                    None => Some((token, pos)),
+                }
+            }
+            (token, _) => token,
+        };
+
         match token {
             None => None,
             Some((token, pos)) => {
@@ -1671,6 +1834,7 @@ pub fn lex<'a, 'e>(
         stream: MultiInputsStream {
             streams: input.iter().map(|s| s.chars().peekable()).collect(),
             index: 0,
+            buf: Default::default(),
         },
         map,
     }
@@ -9,7 +9,7 @@ use crate::stdlib::{
     cmp::Ordering,
     fmt,
     hash::{BuildHasher, Hash, Hasher},
-    iter::FromIterator,
+    iter::{empty, FromIterator},
     ops::{Add, AddAssign, Deref},
     str::FromStr,
     string::{String, ToString},
@@ -88,6 +88,37 @@ pub fn calc_fn_spec<'a>(
     s.finish()
 }
 
+/// Calculate a `u64` hash key from a module-qualified name of a variadic function
+/// and its number of fixed (or required) parameters.
+///
+/// The hash key does not depend on the types of the parameters, so there can only be
+/// one variadic function of each name and number of fixed parameters.
+pub fn calc_variadic_fn_hash<'a>(
+    modules: impl Iterator<Item = &'a str>,
+    fn_name: &str,
+    num_fixed: usize,
+) -> u64 {
+    calc_fn_spec(modules, fn_name, usize::MAX - num_fixed, empty())
+}
+
+/// Calculate a `u64` hash key from the name and the declared parameter types of a script-defined
+/// function with typed parameters.
+///
+/// Parameter types are passed in via type names from an iterator, with `None` for untyped parameters.
+pub fn calc_typed_fn_hash<'a>(
+    fn_name: &str,
+    param_types: impl Iterator<Item = Option<&'a str>>,
+) -> u64 {
+    #[cfg(feature = "no_std")]
+    let mut s: AHasher = Default::default();
+    #[cfg(not(feature = "no_std"))]
+    let mut s = DefaultHasher::new();
+
+    s.write(fn_name.as_bytes());
+    param_types.for_each(|t| t.hash(&mut s));
+    s.finish()
+}
+
 /// [INTERNALS] Alias to [`smallvec::SmallVec<[T; 4]>`](https://crates.io/crates/smallvec),
 /// which is a specialized `Vec` backed by a small, fixed-size array when there are <= 4 items stored.
 /// Exported under the `internals` feature only.
@@ -0,0 +1,114 @@
+#![cfg(not(feature = "no_module"))]
+use rhai::{module_resolvers::ArchiveModuleResolver, Engine, EvalAltResult, RegisterResultFn, INT};
+
+fn make_resolver() -> ArchiveModuleResolver {
+    let mut resolver = ArchiveModuleResolver::new();
+    resolver.insert(
+        "main.rhai",
+        r#"import "lib/a" as a; let x = a::x; export x;"#,
+    );
+    resolver.insert(
+        "lib/a.rhai",
+        r#"import "./b" as b; let x = b::y + 1; export x;"#,
+    );
+    resolver.insert(
+        "lib/b.rhai",
+        r#"import "../util" as u; let y = u::z * 10; export y;"#,
+    );
+    resolver.insert("util/index.rhai", "let z = 4; export z;");
+    resolver.insert("cycle/a.rhai", r#"import "./b" as b;"#);
+    resolver.insert("cycle/b.rhai", r#"import "./a" as a;"#);
+    resolver.insert("bad.rhai", "let x = ;");
+    resolver
+}
+
+#[test]
+fn test_archive_resolver() -> Result<(), Box<EvalAltResult>> {
+    let resolver = make_resolver();
+
+    assert!(resolver.contains_path("./lib/../lib/a.rhai"));
+    assert!(!resolver.contains_path("lib/a"));
+
+    let mut engine = Engine::new();
+    engine.set_module_resolver(Some(resolver));
+
+    assert_eq!(engine.eval::<INT>(r#"import "main" as m; m::x"#)?, 41);
+    assert_eq!(engine.eval::<INT>(r#"import "./lib/b" as b; b::y"#)?, 40);
+    assert_eq!(engine.eval::<INT>(r#"import "util" as u; u::z"#)?, 4);
+
+    assert!(matches!(
+        *engine.eval::<()>(r#"import "missing" as m;"#).expect_err("should error"),
+        EvalAltResult::ErrorModuleNotFound(path, _) if path == "missing"
+    ));
+
+    assert!(matches!(
+        *engine.eval::<()>(r#"import "cycle/a" as a;"#).expect_err("should error"),
+        EvalAltResult::ErrorModuleNotFound(chain, _)
+            if chain == "cycle/a.rhai -> cycle/b.rhai -> cycle/a.rhai"
+    ));
+
+    assert!(matches!(
+        *engine.eval::<()>(r#"import "bad" as b;"#).expect_err("should error"),
+        EvalAltResult::ErrorParsing(_, pos) if !pos.is_none()
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_archive_resolver_nested_engine() -> Result<(), Box<EvalAltResult>> {
+    let mut resolver = ArchiveModuleResolver::new();
+    resolver.insert("a.rhai", "let x = nested() + 1; export x;");
+
+    // A separate engine importing a module with the same path while the outer one is importing
+    let mut engine = Engine::new();
+    engine.register_result_fn("nested", || {
+        let mut resolver = ArchiveModuleResolver::new();
+        resolver.insert("a.rhai", "let x = 41; export x;");
+
+        let mut engine = Engine::new();
+        engine.set_module_resolver(Some(resolver));
+        engine
+            .eval::<INT>(r#"import "a" as a; a::x"#)
+            .map(Into::into)
+    });
+    engine.set_module_resolver(Some(resolver));
+
+    assert_eq!(engine.eval::<INT>(r#"import "a" as a; a::x"#)?, 42);
+
+    Ok(())
+}
+
+#[cfg(feature = "zip")]
+#[test]
+fn test_archive_resolver_zip() -> Result<(), Box<EvalAltResult>> {
+    use std::io::{Cursor, Write};
+    use zip::{write::FileOptions, CompressionMethod, ZipWriter};
+
+    let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+    let stored = FileOptions::default().compression_method(CompressionMethod::Stored);
+    let deflated = FileOptions::default().compression_method(CompressionMethod::Deflated);
+
+    zip.start_file("app/main.rhai", deflated).unwrap();
+    zip.write_all(br#"import "./helper" as h; let x = h::y * 2; export x;"#)
+        .unwrap();
+    zip.start_file("app/helper.rhai", stored).unwrap();
+    zip.write_all(b"let y = 21; export y;").unwrap();
+    zip.start_file("README.txt", stored).unwrap();
+    zip.write_all(b"not a script").unwrap();
+
+    let bundle = zip.finish().unwrap();
+
+    let resolver = ArchiveModuleResolver::from_zip(bundle).unwrap();
+
+    let mut paths: Vec<_> = resolver.paths().collect();
+    paths.sort();
+    assert_eq!(paths, vec!["app/helper.rhai", "app/main.rhai"]);
+
+    let mut engine = Engine::new();
+    engine.set_module_resolver(Some(resolver));
+
+    assert_eq!(engine.eval::<INT>(r#"import "app/main" as m; m::x"#)?, 42);
+
+    Ok(())
+}
@@ -0,0 +1,216 @@
+#![cfg(feature = "bigint")]
+
+use rhai::{BigInt, Engine, EvalAltResult, INT};
+
+#[test]
+fn test_bigint() -> Result<(), Box<EvalAltResult>> {
+    let engine = Engine::new();
+
+    assert_eq!(
+        engine.eval::<BigInt>(r#"bigint("123456789012345678901234567890") * bigint(10)"#)?,
+        "1234567890123456789012345678900".parse().unwrap()
+    );
+    assert_eq!(
+        engine.eval::<BigInt>(r#"let x = bigint(2); x ~= bigint(100); x"#)?,
+        BigInt::from(2).pow(100)
+    );
+    assert_eq!(
+        engine.eval::<BigInt>(r#"bigint(-7) / bigint(2)"#)?,
+        BigInt::from(-3)
+    );
+    assert_eq!(
+        engine.eval::<BigInt>(r#"bigint(-7) % bigint(2)"#)?,
+        BigInt::from(-1)
+    );
+    assert_eq!(
+        engine.eval::<BigInt>(r#"let x = bigint(-5); let y = -x; y"#)?,
+        BigInt::from(5)
+    );
+    assert_eq!(engine.eval::<INT>(r#"sign(bigint(-5))"#)?, -1);
+    assert_eq!(engine.eval::<String>(r#"type_of(bigint(1))"#)?, "bigint");
+    assert_eq!(
+        engine.eval::<String>(r#""id: " + bigint("18446744073709551616")"#)?,
+        "id: 18446744073709551616"
+    );
+
+    assert!(matches!(
+        *engine
+            .eval::<BigInt>(r#"bigint(1) / bigint(0)"#)
+            .expect_err("should error"),
+        EvalAltResult::ErrorArithmetic(_, _)
+    ));
+    assert!(matches!(
+        *engine
+            .eval::<BigInt>(r#"bigint(2) ~ bigint(-1)"#)
+            .expect_err("should error"),
+        EvalAltResult::ErrorArithmetic(_, _)
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_bigint_mixed() -> Result<(), Box<EvalAltResult>> {
+    let engine = Engine::new();
+
+    assert_eq!(
+        engine.eval::<BigInt>(r#"bigint("100000000000000000000") + 1"#)?,
+        "100000000000000000001".parse().unwrap()
+    );
+    assert_eq!(
+        engine.eval::<BigInt>(r#"let x = 1; x += bigint(2); x"#)?,
+        BigInt::from(3)
+    );
+    assert_eq!(
+        engine.eval::<BigInt>(r#"let x = bigint(1); x <<= 70; x >> 69"#)?,
+        BigInt::from(2)
+    );
+    assert_eq!(
+        engine.eval::<BigInt>(r#"(bigint(12) & 10) | (bigint(1) ^ 3)"#)?,
+        BigInt::from(10)
+    );
+
+    assert!(engine.eval::<bool>(r#"bigint(42) == 42"#)?);
+    assert!(engine.eval::<bool>(r#"41 < bigint(42)"#)?);
+    assert!(engine.eval::<bool>(r#"bigint("100000000000000000000") > 1"#)?);
+    assert!(!engine.eval::<bool>(r#"bigint(42) != 42"#)?);
+
+    Ok(())
+}
+
+#[test]
+fn test_bigint_conversions() -> Result<(), Box<EvalAltResult>> {
+    let engine = Engine::new();
+
+    assert_eq!(engine.eval::<INT>(r#"to_int(bigint("-42"))"#)?, -42);
+    assert_eq!(engine.eval::<BigInt>("to_bigint(42)")?, BigInt::from(42));
+    assert_eq!(
+        engine.eval::<BigInt>(r#"bigint("ffff", 16)"#)?,
+        BigInt::from(0xffff)
+    );
+    assert_eq!(
+        engine.eval::<String>(r#"to_string(bigint(255), 16)"#)?,
+        "ff"
+    );
+    assert_eq!(
+        engine.eval::<String>(r#"to_string(bigint("-12345678901234567890"))"#)?,
+        "-12345678901234567890"
+    );
+
+    assert!(matches!(
+        *engine
+            .eval::<INT>(r#"to_int(bigint("100000000000000000000"))"#)
+            .expect_err("should error"),
+        EvalAltResult::ErrorArithmetic(_, _)
+    ));
+    assert!(matches!(
+        *engine
+            .eval::<BigInt>(r#"bigint("12x")"#)
+            .expect_err("should error"),
+        EvalAltResult::ErrorArithmetic(_, _)
+    ));
+    assert!(matches!(
+        *engine
+            .eval::<BigInt>(r#"bigint("12", 37)"#)
+            .expect_err("should error"),
+        EvalAltResult::ErrorArithmetic(_, _)
+    ));
+
+    #[cfg(not(feature = "no_float"))]
+    {
+        assert_eq!(engine.eval::<f64>(r#"to_float(bigint(1024))"#)?, 1024.0);
+        assert_eq!(engine.eval::<BigInt>("bigint(-2.9)")?, BigInt::from(-2));
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_bigint_promotion() -> Result<(), Box<EvalAltResult>> {
+    let mut engine = Engine::new();
+
+    #[cfg(not(feature = "unchecked"))]
+    assert!(matches!(
+        *engine
+            .eval::<INT>("let x = 1; x << 100")
+            .expect_err("should error"),
+        EvalAltResult::ErrorArithmetic(_, _)
+    ));
+
+    engine.set_bigint_promotion(true);
+    assert!(engine.bigint_promotion());
+
+    let max = INT::MAX;
+
+    // Results that fit stay integers
+    assert_eq!(engine.eval::<INT>("40 + 2")?, 42);
+    assert_eq!(engine.eval::<INT>("let x = 1; x << 10")?, 1024);
+
+    assert_eq!(
+        engine.eval::<BigInt>(&format!("{} + 1", max))?,
+        BigInt::from(max) + 1
+    );
+    assert_eq!(
+        engine.eval::<BigInt>(&format!("let x = {}; x *= 2; x", max))?,
+        BigInt::from(max) * 2
+    );
+    assert_eq!(engine.eval::<INT>("let x = 2; x ~ 10")?, 1024);
+    assert_eq!(
+        engine.eval::<BigInt>("let x = 2; x ~ 100")?,
+        BigInt::from(2).pow(100)
+    );
+    assert_eq!(
+        engine.eval::<BigInt>("let x = 2; x ~= 100; x")?,
+        BigInt::from(2).pow(100)
+    );
+    assert_eq!(
+        engine.eval::<BigInt>("let x = 1; x << 100")?,
+        BigInt::from(1) << 100
+    );
+    assert_eq!(
+        engine.eval::<BigInt>(&format!("let x = {}; x += 1; x - 1", max))?,
+        BigInt::from(max)
+    );
+    assert!(engine.eval::<bool>(&format!("let x = {}; x + 1 > x", max))?);
+
+    // Errors other than overflow are not affected
+    #[cfg(not(feature = "unchecked"))]
+    assert!(matches!(
+        *engine.eval::<INT>("1 / 0").expect_err("should error"),
+        EvalAltResult::ErrorArithmetic(_, _)
+    ));
+
+    engine.set_bigint_promotion(false);
+
+    #[cfg(not(feature = "unchecked"))]
+    assert!(matches!(
+        *engine
+            .eval::<INT>(&format!("let x = {}; x + 1", max))
+            .expect_err("should error"),
+        EvalAltResult::ErrorArithmetic(_, _)
+    ));
+
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "serde")]
+fn test_bigint_serde() -> Result<(), Box<EvalAltResult>> {
+    use rhai::de::from_dynamic;
+    use rhai::ser::to_dynamic;
+
+    let engine = Engine::new();
+
+    let value = engine.eval(r#"bigint(42)"#)?;
+    assert_eq!(from_dynamic::<u64>(&value)?, 42);
+
+    let value = engine.eval(r#"bigint("18446744073709551615")"#)?;
+    assert_eq!(from_dynamic::<u64>(&value)?, u64::MAX);
+    assert_eq!(from_dynamic::<String>(&value)?, "18446744073709551615");
+
+    let value = to_dynamic(u64::MAX)?;
+    assert_eq!(value.type_name(), "bigint");
+    assert_eq!(value.cast::<BigInt>(), BigInt::from(u64::MAX));
+
+    Ok(())
+}
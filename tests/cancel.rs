@@ -0,0 +1,191 @@
+use rhai::{CancellationToken, Engine, EvalAltResult, NativeCallContext, RegisterResultFn, INT};
+
+#[cfg(not(feature = "no_module"))]
+use rhai::module_resolvers::ArchiveModuleResolver;
+use std::thread;
+use std::time::Duration;
+
+#[test]
+fn test_cancel_token() -> Result<(), Box<EvalAltResult>> {
+    let engine = Engine::new();
+    let token = CancellationToken::new();
+
+    assert_eq!(engine.eval_with_cancel::<INT>("40 + 2", &token)?, 42);
+
+    let handle = token.clone();
+
+    thread::spawn(move || {
+        thread::sleep(Duration::from_millis(50));
+        handle.cancel();
+    });
+
+    let err = engine
+        .eval_with_cancel::<()>("let x = 0;\nloop { x += 1; }", &token)
+        .expect_err("should error");
+
+    assert!(matches!(*err, EvalAltResult::ErrorTerminated(_)));
+    assert_eq!(err.position().line(), Some(2));
+
+    Ok(())
+}
+
+#[test]
+fn test_cancel_token_native() -> Result<(), Box<EvalAltResult>> {
+    let mut engine = Engine::new();
+    let token = CancellationToken::new();
+
+    let handle = token.clone();
+
+    engine.register_result_fn("work", move |x: INT| {
+        handle.check()?;
+        handle.cancel();
+        Ok((x + 1).into())
+    });
+
+    assert!(matches!(
+        *engine
+            .eval_with_cancel::<INT>("let x = work(1); work(x)", &token)
+            .expect_err("should error"),
+        EvalAltResult::ErrorTerminated(_)
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_cancel_token_native_context() -> Result<(), Box<EvalAltResult>> {
+    let mut engine = Engine::new();
+    let token = CancellationToken::new();
+
+    #[allow(deprecated)]
+    engine.register_raw_fn("spin", &[], |context: NativeCallContext, _| {
+        match context.cancel_token() {
+            // Run until the script is cancelled
+            Some(token) => loop {
+                token.check()?;
+                thread::sleep(Duration::from_millis(1));
+            },
+            None => Ok(false),
+        }
+    });
+
+    assert!(!engine.eval::<bool>("spin()")?);
+
+    let handle = token.clone();
+
+    thread::spawn(move || {
+        thread::sleep(Duration::from_millis(50));
+        handle.cancel();
+    });
+
+    assert!(matches!(
+        *engine
+            .eval_with_cancel::<bool>("spin()", &token)
+            .expect_err("should error"),
+        EvalAltResult::ErrorTerminated(_)
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_cancel_token_consume() -> Result<(), Box<EvalAltResult>> {
+    let engine = Engine::new();
+    let token = CancellationToken::new();
+
+    engine.consume_with_cancel("let x = 40 + 2;", &token)?;
+
+    token.cancel();
+
+    assert!(matches!(
+        *engine
+            .consume_with_cancel("let x = 40 + 2;", &token)
+            .expect_err("should error"),
+        EvalAltResult::ErrorTerminated(_)
+    ));
+
+    Ok(())
+}
+
+#[cfg(not(feature = "no_function"))]
+#[test]
+fn test_cancel_token_call_fn() -> Result<(), Box<EvalAltResult>> {
+    let engine = Engine::new();
+    let token = CancellationToken::new();
+    let ast = engine.compile("fn add(x, y) { x + y }")?;
+
+    let result: INT = engine.call_fn_with_cancel(
+        &mut rhai::Scope::new(),
+        &ast,
+        "add",
+        (40 as INT, 2 as INT),
+        &token,
+    )?;
+    assert_eq!(result, 42);
+
+    token.cancel();
+
+    assert!(matches!(
+        *engine
+            .call_fn_with_cancel::<_, INT>(
+                &mut rhai::Scope::new(),
+                &ast,
+                "add",
+                (40 as INT, 2 as INT),
+                &token
+            )
+            .expect_err("should error"),
+        EvalAltResult::ErrorTerminated(_)
+    ));
+
+    Ok(())
+}
+
+#[cfg(not(feature = "no_module"))]
+#[test]
+fn test_cancel_token_import() -> Result<(), Box<EvalAltResult>> {
+    let mut engine = Engine::new();
+    let token = CancellationToken::new();
+
+    let mut resolver = ArchiveModuleResolver::new();
+    resolver.insert("spin.rhai", "loop {}");
+    engine.set_module_resolver(Some(resolver));
+
+    let handle = token.clone();
+
+    thread::spawn(move || {
+        thread::sleep(Duration::from_millis(50));
+        handle.cancel();
+    });
+
+    assert!(matches!(
+        *engine
+            .eval_with_cancel::<()>(r#"import "spin" as spin;"#, &token)
+            .expect_err("should error"),
+        EvalAltResult::ErrorTerminated(_)
+    ));
+
+    Ok(())
+}
+
+#[cfg(not(feature = "unchecked"))]
+#[test]
+fn test_max_duration() -> Result<(), Box<EvalAltResult>> {
+    let mut engine = Engine::new();
+    engine.set_max_duration(Duration::from_millis(50));
+
+    assert_eq!(engine.max_duration(), Some(Duration::from_millis(50)));
+
+    assert_eq!(engine.eval::<INT>("40 + 2")?, 42);
+
+    assert!(matches!(
+        *engine.eval::<()>("loop {}").expect_err("should error"),
+        EvalAltResult::ErrorTerminated(_)
+    ));
+
+    engine.clear_max_duration();
+
+    assert_eq!(engine.max_duration(), None);
+
+    Ok(())
+}
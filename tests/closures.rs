@@ -1,5 +1,5 @@
 #![cfg(not(feature = "no_function"))]
-use rhai::{Dynamic, Engine, EvalAltResult, FnPtr, Module, RegisterFn, INT};
+use rhai::{Dynamic, Engine, EvalAltResult, FnPtr, NativeCallContext, RegisterFn, INT};
 use std::any::TypeId;
 
 #[test]
@@ -10,9 +10,9 @@ fn test_fn_ptr_curry_call() -> Result<(), Box<EvalAltResult>> {
     engine.register_raw_fn(
         "call_with_arg",
         &[TypeId::of::<FnPtr>(), TypeId::of::<INT>()],
-        |engine: &Engine, lib: &Module, args: &mut [&mut Dynamic]| {
+        |context: NativeCallContext, args: &mut [&mut Dynamic]| {
             let fn_ptr = std::mem::take(args[0]).cast::<FnPtr>();
-            fn_ptr.call_dynamic(engine, lib, None, [std::mem::take(args[1])])
+            fn_ptr.call_dynamic(context, None, [std::mem::take(args[1])])
         },
     );
 
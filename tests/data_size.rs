@@ -1,5 +1,5 @@
 #![cfg(not(feature = "unchecked"))]
-use rhai::{Engine, EvalAltResult, ParseErrorType};
+use rhai::{Engine, EvalAltResult, ParseErrorType, INT};
 
 #[cfg(not(feature = "no_index"))]
 use rhai::Array;
@@ -273,3 +273,112 @@ fn test_max_map_size() -> Result<(), Box<EvalAltResult>> {
 
     Ok(())
 }
+
+#[test]
+#[cfg(not(feature = "no_index"))]
+fn test_max_memory() -> Result<(), Box<EvalAltResult>> {
+    let mut engine = Engine::new();
+    engine.set_max_memory(10_000);
+
+    // Many medium arrays in the scope
+    assert!(matches!(
+        *engine
+            .eval::<()>(
+                r"
+                    let a = [];
+                    let b = [];
+                    for i in range(0, 500) { push(a, i); push(b, i); }
+                "
+            )
+            .expect_err("should error"),
+        EvalAltResult::ErrorDataTooLarge(_, 10_000, _, _)
+    ));
+
+    // Data held by callers
+    #[cfg(not(feature = "no_function"))]
+    assert!(matches!(
+        *engine
+            .eval::<()>(
+                r"
+                    fn grow(n) {
+                        let x = [];
+                        for i in range(0, 100) { push(x, i); }
+                        if n > 0 { grow(n - 1); }
+                    }
+                    grow(10);
+                "
+            )
+            .expect_err("should error"),
+        EvalAltResult::ErrorInFunctionCall(_, _, _)
+    ));
+
+    // Temporary values
+    assert!(matches!(
+        *engine
+            .eval::<INT>(
+                r"
+                    let x = [1, 2, 3, 4, 5, 6, 7, 8, 9, 10];
+                    for i in range(0, 8) { x += x; }
+                    len(x)
+                "
+            )
+            .expect_err("should error"),
+        EvalAltResult::ErrorDataTooLarge(_, 10_000, _, _)
+    ));
+
+    // Growth outside of the statements in a loop body
+    assert!(matches!(
+        *engine
+            .eval::<()>(
+                r"
+                    let a = [];
+                    while len(a) < 1000 && push(a, 42) == () { }
+                "
+            )
+            .expect_err("should error"),
+        EvalAltResult::ErrorDataTooLarge(_, 10_000, _, _)
+    ));
+
+    // Growth of a variable captured by a closure
+    #[cfg(not(feature = "no_object"))]
+    #[cfg(not(feature = "no_function"))]
+    #[cfg(not(feature = "no_closure"))]
+    assert!(matches!(
+        *engine
+            .eval::<()>(
+                r"
+                    let a = [];
+                    let f = |x| a += [x];
+                    for i in range(0, 1000) { call(f, i); }
+                "
+            )
+            .expect_err("should error"),
+        EvalAltResult::ErrorDataTooLarge(_, 10_000, _, _)
+    ));
+
+    assert_eq!(
+        engine.eval::<INT>(
+            r"
+                let x = [];
+                for i in range(0, 100) { push(x, i); }
+                len(x)
+            "
+        )?,
+        100
+    );
+
+    engine.set_max_memory(0);
+
+    assert_eq!(
+        engine.eval::<INT>(
+            r"
+                let x = [];
+                for i in range(0, 1000) { push(x, i); }
+                len(x)
+            "
+        )?,
+        1000
+    );
+
+    Ok(())
+}
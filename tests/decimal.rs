@@ -0,0 +1,197 @@
+#![cfg(feature = "decimal")]
+
+use rhai::{Decimal, Engine, EvalAltResult, INT};
+use std::str::FromStr;
+
+#[test]
+fn test_decimal() -> Result<(), Box<EvalAltResult>> {
+    let engine = Engine::new();
+
+    assert_eq!(
+        engine.eval::<Decimal>(r#"decimal("0.1") + decimal("0.2")"#)?,
+        Decimal::from_str("0.3").unwrap()
+    );
+    assert!(engine.eval::<bool>(r#"decimal("0.1") + decimal("0.2") == decimal("0.3")"#)?);
+    assert_eq!(
+        engine.eval::<Decimal>(r#"let x = decimal("12.34"); x *= decimal(3); x"#)?,
+        Decimal::from_str("37.02").unwrap()
+    );
+    assert_eq!(
+        engine.eval::<Decimal>(r#"decimal(10) / decimal(4)"#)?,
+        Decimal::from_str("2.5").unwrap()
+    );
+    assert_eq!(
+        engine.eval::<Decimal>(r#"abs(decimal("-1.5"))"#)?,
+        Decimal::from_str("1.5").unwrap()
+    );
+    assert_eq!(
+        engine.eval::<Decimal>(r#"let x = decimal("1.5"); let y = -x; y"#)?,
+        Decimal::from_str("-1.5").unwrap()
+    );
+    assert_eq!(engine.eval::<INT>(r#"sign(decimal("-0.01"))"#)?, -1);
+    assert!(engine.eval::<bool>(r#"decimal("1.10") > decimal("1.09")"#)?);
+    assert_eq!(engine.eval::<String>(r#"type_of(decimal(1))"#)?, "decimal");
+    assert_eq!(
+        engine.eval::<String>(r#""price: " + decimal("9.90")"#)?,
+        "price: 9.90"
+    );
+
+    assert!(matches!(
+        *engine
+            .eval::<Decimal>(r#"decimal("1.2.3")"#)
+            .expect_err("should error"),
+        EvalAltResult::ErrorArithmetic(_, _)
+    ));
+    #[cfg(not(feature = "unchecked"))]
+    assert!(matches!(
+        *engine
+            .eval::<Decimal>(r#"decimal(1) / decimal(0)"#)
+            .expect_err("should error"),
+        EvalAltResult::ErrorArithmetic(_, _)
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_decimal_mixed() -> Result<(), Box<EvalAltResult>> {
+    let engine = Engine::new();
+
+    assert_eq!(
+        engine.eval::<Decimal>(r#"decimal("1.25") * 3"#)?,
+        Decimal::from_str("3.75").unwrap()
+    );
+    assert_eq!(
+        engine.eval::<Decimal>(r#"3 * decimal("1.25")"#)?,
+        Decimal::from_str("3.75").unwrap()
+    );
+    assert_eq!(
+        engine.eval::<Decimal>(r#"decimal("1.25") + 1"#)?,
+        Decimal::from_str("2.25").unwrap()
+    );
+    assert_eq!(
+        engine.eval::<Decimal>(r#"1 - decimal("1.25")"#)?,
+        Decimal::from_str("-0.25").unwrap()
+    );
+    assert_eq!(
+        engine.eval::<Decimal>(r#"let x = decimal("1.25"); x += 1; x /= 2; x"#)?,
+        Decimal::from_str("1.125").unwrap()
+    );
+
+    assert!(engine.eval::<bool>(r#"decimal("1.25") > 1"#)?);
+    assert!(engine.eval::<bool>(r#"1 < decimal("1.25")"#)?);
+    assert!(engine.eval::<bool>(r#"decimal("2.00") == 2"#)?);
+    assert!(engine.eval::<bool>(r#"2 >= decimal("2")"#)?);
+
+    #[cfg(not(feature = "no_float"))]
+    {
+        assert_eq!(
+            engine.eval::<Decimal>(r#"decimal("1.25") + 0.5"#)?,
+            Decimal::from_str("1.75").unwrap()
+        );
+        assert_eq!(
+            engine.eval::<Decimal>(r#"0.5 * decimal("3")"#)?,
+            Decimal::from_str("1.5").unwrap()
+        );
+        assert!(engine.eval::<bool>(r#"decimal("1.25") < 2.0"#)?);
+        assert!(engine.eval::<bool>(r#"2.0 > decimal("1.25")"#)?);
+        assert!(engine.eval::<bool>(r#"decimal("1.5") == 1.5"#)?);
+        assert!(!engine.eval::<bool>(r#"decimal("1.5") <= sqrt(-1.0)"#)?);
+        assert!(engine.eval::<bool>(r#"decimal("1.5") < 10.0 ~ 300"#)?);
+        assert!(engine.eval::<bool>(r#"-(10.0 ~ 300) < decimal("1.5")"#)?);
+
+        assert!(matches!(
+            *engine
+                .eval::<Decimal>(r#"decimal("1.25") + sqrt(-1.0)"#)
+                .expect_err("should error"),
+            EvalAltResult::ErrorArithmetic(_, _)
+        ));
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_decimal_rounding() -> Result<(), Box<EvalAltResult>> {
+    let engine = Engine::new();
+
+    assert_eq!(
+        engine.eval::<Decimal>(r#"round(decimal("2.345"), 2)"#)?,
+        Decimal::from_str("2.34").unwrap()
+    );
+    assert_eq!(
+        engine.eval::<Decimal>(r#"round(decimal("2.345"), 2, "half_up")"#)?,
+        Decimal::from_str("2.35").unwrap()
+    );
+    assert_eq!(
+        engine.eval::<Decimal>(r#"round(decimal("-2.341"), 2, "floor")"#)?,
+        Decimal::from_str("-2.35").unwrap()
+    );
+    assert_eq!(
+        engine.eval::<Decimal>(r#"round(decimal("2.341"), 1, "up")"#)?,
+        Decimal::from_str("2.4").unwrap()
+    );
+    assert!(matches!(
+        *engine
+            .eval::<Decimal>(r#"round(decimal("2.345"), 2, "sideways")"#)
+            .expect_err("should error"),
+        EvalAltResult::ErrorArithmetic(_, _)
+    ));
+
+    assert_eq!(engine.eval::<INT>(r#"scale(decimal("1.500"))"#)?, 3);
+
+    #[cfg(not(feature = "no_object"))]
+    {
+        assert_eq!(engine.eval::<INT>(r#"decimal("1.500").scale"#)?, 3);
+        assert_eq!(
+            engine.eval::<String>(r#"let x = decimal("1.5"); x.set_scale(4); x.to_string()"#)?,
+            "1.5000"
+        );
+        assert!(matches!(
+            *engine
+                .eval::<()>(r#"let x = decimal("1.5"); x.set_scale(-1);"#)
+                .expect_err("should error"),
+            EvalAltResult::ErrorArithmetic(_, _)
+        ));
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_decimal_conversions() -> Result<(), Box<EvalAltResult>> {
+    let engine = Engine::new();
+
+    assert_eq!(engine.eval::<INT>(r#"to_int(decimal("-42.9"))"#)?, -42);
+    assert_eq!(engine.eval::<Decimal>("to_decimal(42)")?, Decimal::from(42));
+    assert!(matches!(
+        *engine
+            .eval::<INT>(r#"to_int(decimal("100000000000000000000"))"#)
+            .expect_err("should error"),
+        EvalAltResult::ErrorArithmetic(_, _)
+    ));
+
+    #[cfg(not(feature = "no_float"))]
+    {
+        assert_eq!(engine.eval::<f64>(r#"to_float(decimal("1.25"))"#)?, 1.25);
+        assert_eq!(
+            engine.eval::<Decimal>("decimal(1.25)")?,
+            Decimal::from_str("1.25").unwrap()
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "serde")]
+fn test_decimal_serde() -> Result<(), Box<EvalAltResult>> {
+    use rhai::de::from_dynamic;
+
+    let engine = Engine::new();
+
+    let value = engine.eval(r#"decimal("123.4500")"#)?;
+    assert_eq!(from_dynamic::<String>(&value)?, "123.4500");
+
+    Ok(())
+}
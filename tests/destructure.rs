@@ -0,0 +1,135 @@
+#![cfg(any(not(feature = "no_index"), not(feature = "no_object")))]
+use rhai::{Engine, EvalAltResult, INT};
+
+#[cfg(not(feature = "no_index"))]
+#[test]
+fn test_destructure_array() -> Result<(), Box<EvalAltResult>> {
+    let engine = Engine::new();
+
+    assert_eq!(
+        engine.eval::<INT>("let [a, b, c] = [1, 2, 3]; a * 100 + b * 10 + c")?,
+        123
+    );
+    assert_eq!(
+        engine.eval::<INT>("let [a, b, ..rest] = [1, 2, 3, 4, 5]; a + b + len(rest)")?,
+        6
+    );
+    assert_eq!(
+        engine.eval::<INT>("let [a, ..rest] = [1]; a + len(rest)")?,
+        1
+    );
+    assert_eq!(engine.eval::<INT>("let [a, ..] = [42, 1, 2]; a")?, 42);
+    assert_eq!(engine.eval::<INT>("let [a, b = 41] = [1]; a + b")?, 42);
+    assert_eq!(
+        engine.eval::<INT>("let [a, [b, c]] = [1, [2, 39]]; a + b + c")?,
+        42
+    );
+    assert_eq!(
+        engine.eval::<INT>("let x = 40; { let [a, b = x + 1] = [1]; a + b }")?,
+        42
+    );
+
+    assert!(matches!(
+        *engine
+            .eval::<INT>("let [a, b, c] = [1, 2]; a")
+            .expect_err("should error"),
+        EvalAltResult::ErrorPatternMismatch(_, _)
+    ));
+    assert!(matches!(
+        *engine
+            .eval::<INT>("let [a, b] = 42; a")
+            .expect_err("should error"),
+        EvalAltResult::ErrorPatternMismatch(_, _)
+    ));
+
+    Ok(())
+}
+
+#[cfg(not(feature = "no_object"))]
+#[test]
+fn test_destructure_map() -> Result<(), Box<EvalAltResult>> {
+    let engine = Engine::new();
+
+    assert_eq!(
+        engine.eval::<INT>("let #{x, y: yy} = #{x: 40, y: 2}; x + yy")?,
+        42
+    );
+    assert_eq!(
+        engine.eval::<INT>(r#"let #{"a b": ab, c = 2} = #{"a b": 40}; ab + c"#)?,
+        42
+    );
+    assert_eq!(
+        engine.eval::<INT>("let #{x, ..rest} = #{x: 1, y: 2, z: 3}; x + rest.len()")?,
+        3
+    );
+    assert_eq!(
+        engine.eval::<INT>("let #{a: #{b: #{c}}} = #{a: #{b: #{c: 42}}}; c")?,
+        42
+    );
+
+    assert!(matches!(
+        *engine
+            .eval::<INT>("let #{x, y} = #{x: 1}; x")
+            .expect_err("should error"),
+        EvalAltResult::ErrorPatternMismatch(_, _)
+    ));
+    assert!(engine.compile("let #{x, x} = #{x: 1};").is_err());
+    assert!(engine.compile("let #{x, y};").is_err());
+
+    #[cfg(not(feature = "no_index"))]
+    assert_eq!(
+        engine.eval::<INT>("let #{p: [a, b], q} = #{p: [1, 2], q: 39}; a + b + q")?,
+        42
+    );
+
+    Ok(())
+}
+
+#[cfg(not(feature = "no_index"))]
+#[test]
+fn test_destructure_for() -> Result<(), Box<EvalAltResult>> {
+    let engine = Engine::new();
+
+    assert_eq!(
+        engine.eval::<INT>(
+            r"
+                let sum = 0;
+                for [k, v] in [[1, 2], [3, 4], [5, 6]] {
+                    sum += k * v;
+                }
+                sum
+            "
+        )?,
+        44
+    );
+
+    assert_eq!(
+        engine.eval::<INT>(
+            r"
+                let sum = 0;
+                let d = 10;
+                for [a, b = d] in [[1], [2, 3]] {
+                    sum += a + b;
+                }
+                sum
+            "
+        )?,
+        16
+    );
+
+    #[cfg(not(feature = "no_object"))]
+    assert_eq!(
+        engine.eval::<INT>(
+            r"
+                let sum = 0;
+                for #{x, y} in [#{x: 1, y: 2}, #{x: 3, y: 4}] {
+                    sum += x * y;
+                }
+                sum
+            "
+        )?,
+        14
+    );
+
+    Ok(())
+}
@@ -0,0 +1,163 @@
+use rhai::{packages::PackageLibrary, Engine, EvalAltResult, Module, RegisterFn, INT};
+
+#[cfg(not(feature = "no_std"))]
+#[test]
+fn test_deterministic_clock() -> Result<(), Box<EvalAltResult>> {
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    let ticks = Arc::new(AtomicU64::new(0));
+    let clock = ticks.clone();
+
+    let mut engine = Engine::new();
+
+    // Every reading of the clock advances it by one second
+    engine.set_clock(move || Duration::from_secs(clock.fetch_add(1, Ordering::SeqCst)));
+
+    #[cfg(not(feature = "no_float"))]
+    assert_eq!(engine.eval::<f64>("let t = timestamp(); elapsed(t)")?, 1.0);
+    #[cfg(feature = "no_float")]
+    assert_eq!(engine.eval::<INT>("let t = timestamp(); elapsed(t)")?, 1);
+
+    assert_eq!(ticks.load(Ordering::SeqCst), 2);
+
+    engine.clear_clock();
+    assert!(engine.eval::<bool>("let t = timestamp(); t <= timestamp()")?);
+
+    let mut engine = Engine::new();
+    engine.set_deterministic(true);
+
+    // Without a clock, time does not advance in deterministic mode
+    assert!(
+        engine.eval::<bool>("let t = timestamp(); for x in range(0, 1000) {} t == timestamp()")?
+    );
+
+    Ok(())
+}
+
+#[cfg(not(feature = "no_object"))]
+#[cfg(not(feature = "ordered_map"))]
+#[test]
+fn test_deterministic_maps() -> Result<(), Box<EvalAltResult>> {
+    let mut engine = Engine::new();
+    engine.set_deterministic(true);
+
+    #[cfg(not(feature = "no_index"))]
+    assert_eq!(
+        engine.eval::<String>(
+            r#"
+                let map = #{ zeta: 1, alpha: 2, mu: 3, beta: 4 };
+                let s = "";
+                for k in map.keys() { s += k; s += ","; }
+                for v in map.values() { s += v; }
+                s
+            "#
+        )?,
+        "alpha,beta,mu,zeta,2431"
+    );
+
+    assert_eq!(
+        engine.eval::<String>("#{ c: 1, a: 2, b: #{ y: 3, x: 4 } }.to_string()")?,
+        r#"#{"a": 2, "b": #{"x": 4, "y": 3}, "c": 1}"#
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_deterministic_register() -> Result<(), Box<EvalAltResult>> {
+    let mut engine = Engine::new();
+    engine.set_deterministic(true);
+    assert!(engine.is_deterministic());
+
+    engine.register_deterministic(|engine| {
+        engine.register_fn("add", |x: INT, y: INT| x + y);
+    });
+
+    assert_eq!(engine.eval::<INT>("add(40, 2)")?, 42);
+
+    Ok(())
+}
+
+#[test]
+fn test_deterministic_register_unmarked() -> Result<(), Box<EvalAltResult>> {
+    let mut engine = Engine::new();
+    engine.register_fn("random", || 4 as INT);
+    engine.set_deterministic(true);
+    engine.register_fn("random2", || 4 as INT);
+
+    assert!(matches!(
+        *engine.eval::<INT>("random()").expect_err("should error"),
+        EvalAltResult::ErrorNotDeterministic(name, _) if name == "random"
+    ));
+    assert!(matches!(
+        *engine.eval::<INT>("random2()").expect_err("should error"),
+        EvalAltResult::ErrorNotDeterministic(name, _) if name == "random2"
+    ));
+
+    // Built-in functions are deterministic
+    assert_eq!(engine.eval::<INT>("abs(-42)")?, 42);
+
+    engine.set_deterministic(false);
+    assert_eq!(engine.eval::<INT>("random()")?, 4);
+
+    Ok(())
+}
+
+#[test]
+fn test_deterministic_packages() -> Result<(), Box<EvalAltResult>> {
+    let mut module = Module::new();
+    module.set_fn_0("random", || Ok(4 as INT));
+    let package: PackageLibrary = module.into();
+
+    let mut engine = Engine::new();
+    engine.set_deterministic(true);
+    engine.load_package(package.clone());
+
+    assert!(matches!(
+        *engine.eval::<INT>("random()").expect_err("should error"),
+        EvalAltResult::ErrorNotDeterministic(name, _) if name == "random"
+    ));
+
+    let mut engine = Engine::new();
+    engine.set_deterministic(true);
+    engine.register_deterministic(|engine| {
+        engine.load_package(package);
+    });
+
+    assert_eq!(engine.eval::<INT>("random()")?, 4);
+
+    Ok(())
+}
+
+#[cfg(not(feature = "no_module"))]
+#[test]
+fn test_deterministic_modules() -> Result<(), Box<EvalAltResult>> {
+    use rhai::module_resolvers::StaticModuleResolver;
+
+    let mut module = Module::new();
+    module.set_fn_0("random", || Ok(4 as INT));
+
+    let mut resolver = StaticModuleResolver::new();
+    resolver.insert("unmarked", module.clone());
+    module.set_deterministic(true);
+    resolver.insert("marked", module);
+
+    let mut engine = Engine::new();
+    engine.set_module_resolver(Some(resolver));
+    engine.set_deterministic(true);
+
+    assert!(matches!(
+        *engine
+            .eval::<INT>(r#"import "unmarked" as m; m::random()"#)
+            .expect_err("should error"),
+        EvalAltResult::ErrorNotDeterministic(name, _) if name == "m::random"
+    ));
+    assert_eq!(
+        engine.eval::<INT>(r#"import "marked" as m; m::random()"#)?,
+        4
+    );
+
+    Ok(())
+}
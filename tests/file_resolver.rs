@@ -0,0 +1,269 @@
+#![cfg(not(feature = "no_module"))]
+use rhai::{module_resolvers::FileModuleResolver, Engine, EvalAltResult, INT};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+/// Create a fresh directory of script files for a test.
+fn make_scripts(name: &str, files: &[(&str, &str)]) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("rhai-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+
+    for (path, script) in files {
+        let path = dir.join(path);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, script).unwrap();
+    }
+
+    dir
+}
+
+fn engine_with(resolver: FileModuleResolver) -> Engine {
+    let mut engine = Engine::new();
+    engine.set_module_resolver(Some(resolver));
+    engine
+}
+
+#[test]
+fn test_file_resolver_relative() -> Result<(), Box<EvalAltResult>> {
+    let dir = make_scripts(
+        "relative",
+        &[
+            (
+                "lib/a.rhai",
+                r#"import "./util" as u; let x = u::y + 1; export x;"#,
+            ),
+            (
+                "lib/util.rhai",
+                r#"import "../top" as t; let y = t::z * 10; export y;"#,
+            ),
+            ("top.rhai", "let z = 4; export z;"),
+        ],
+    );
+
+    let engine = engine_with(FileModuleResolver::new_with_path(&dir));
+
+    assert_eq!(engine.eval::<INT>(r#"import "lib/a" as a; a::x"#)?, 41);
+    assert_eq!(engine.eval::<INT>(r#"import "./top" as t; t::z"#)?, 4);
+
+    fs::remove_dir_all(dir).unwrap();
+    Ok(())
+}
+
+#[test]
+fn test_file_resolver_search_paths() -> Result<(), Box<EvalAltResult>> {
+    let dir = make_scripts(
+        "search",
+        &[
+            ("main/common.rhai", "let x = 1; export x;"),
+            ("vendor/common.rhai", "let x = 2; export x;"),
+            ("vendor/extra.rhai", "let x = 3; export x;"),
+            ("vendor/pkg/index.rhai", "let x = 4; export x;"),
+            ("shared/deep/util.rhai", "let x = 5; export x;"),
+        ],
+    );
+
+    let mut resolver = FileModuleResolver::new_with_path(dir.join("main"));
+    resolver.add_search_path(dir.join("vendor"));
+    resolver.add_alias("@shared", dir.join("shared"));
+
+    let engine = engine_with(resolver);
+
+    assert_eq!(engine.eval::<INT>(r#"import "common" as m; m::x"#)?, 1);
+    assert_eq!(engine.eval::<INT>(r#"import "extra" as m; m::x"#)?, 3);
+    assert_eq!(engine.eval::<INT>(r#"import "pkg" as m; m::x"#)?, 4);
+    assert_eq!(
+        engine.eval::<INT>(r#"import "@shared/deep/util" as m; m::x"#)?,
+        5
+    );
+
+    assert!(matches!(
+        *engine
+            .eval::<INT>(r#"import "missing" as m; m::x"#)
+            .expect_err("should error"),
+        EvalAltResult::ErrorReadingScriptFile(path, _, _) if path == dir.join("main").join("missing.rhai")
+    ));
+
+    fs::remove_dir_all(dir).unwrap();
+    Ok(())
+}
+
+#[test]
+fn test_file_resolver_cycle() {
+    let dir = make_scripts(
+        "cycle",
+        &[
+            ("a.rhai", r#"import "./b" as b;"#),
+            ("b.rhai", r#"import "./c" as c;"#),
+            ("c.rhai", r#"import "./a" as a;"#),
+        ],
+    );
+
+    let engine = engine_with(FileModuleResolver::new_with_path(&dir));
+
+    let err = engine
+        .eval::<()>(r#"import "a" as a;"#)
+        .expect_err("should error");
+
+    let file = |name: &str| {
+        Path::new(&dir)
+            .join(name)
+            .canonicalize()
+            .unwrap()
+            .display()
+            .to_string()
+    };
+
+    match *err {
+        EvalAltResult::ErrorModuleNotFound(chain, _) => assert_eq!(
+            chain,
+            [
+                file("a.rhai"),
+                file("b.rhai"),
+                file("c.rhai"),
+                file("a.rhai")
+            ]
+            .join(" -> ")
+        ),
+        err => panic!("wrong error: {}", err),
+    }
+
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn test_file_resolver_cache() -> Result<(), Box<EvalAltResult>> {
+    let dir = make_scripts(
+        "cache",
+        &[
+            ("a.rhai", "let x = 1; export x;"),
+            ("b.rhai", "let x = 10; export x;"),
+        ],
+    );
+    let file_a = dir.join("a.rhai").canonicalize().unwrap();
+    let file_b = dir.join("b.rhai").canonicalize().unwrap();
+
+    let reloaded = Arc::new(Mutex::new(Vec::<PathBuf>::new()));
+    let log = reloaded.clone();
+
+    let mut resolver = FileModuleResolver::new_with_path(&dir);
+    resolver.on_reload(move |path| log.lock().unwrap().push(path.to_path_buf()));
+
+    let engine = engine_with(resolver.clone());
+
+    assert_eq!(engine.eval::<INT>(r#"import "a" as a; a::x"#)?, 1);
+
+    // Changes are picked up on the next import
+    thread::sleep(Duration::from_millis(10));
+    fs::write(&file_a, "let x = 2; export x;").unwrap();
+    assert_eq!(engine.eval::<INT>(r#"import "a" as a; a::x"#)?, 2);
+    assert_eq!(*reloaded.lock().unwrap(), vec![file_a.clone()]);
+
+    // Reload changes before the next import
+    assert_eq!(engine.eval::<INT>(r#"import "b" as b; b::x"#)?, 10);
+    thread::sleep(Duration::from_millis(10));
+    fs::write(&file_b, "let x = 20; export x;").unwrap();
+    assert_eq!(resolver.reload_changed(&engine), vec![file_b.clone()]);
+    assert!(resolver.reload_changed(&engine).is_empty());
+    assert_eq!(
+        *reloaded.lock().unwrap(),
+        vec![file_a.clone(), file_b.clone()]
+    );
+    assert_eq!(engine.eval::<INT>(r#"import "b" as b; b::x"#)?, 20);
+
+    // Explicit invalidation
+    assert!(resolver.invalidate(&file_a));
+    assert!(!resolver.invalidate(&file_a));
+    resolver.clear_cache();
+    assert!(!resolver.invalidate(&file_b));
+
+    // LRU eviction
+    resolver.set_max_cache_size(1);
+    assert_eq!(resolver.max_cache_size(), 1);
+    engine.eval::<()>(r#"import "a" as a; import "b" as b;"#)?;
+    assert!(!resolver.invalidate(&file_a));
+    assert!(resolver.invalidate(&file_b));
+
+    fs::remove_dir_all(dir).unwrap();
+    Ok(())
+}
+
+#[test]
+fn test_file_resolver_reload_shared() -> Result<(), Box<EvalAltResult>> {
+    let dir = make_scripts(
+        "reload-shared",
+        &[
+            (
+                "main.rhai",
+                r#"import "./lib" as lib; let x = lib::x; export x;"#,
+            ),
+            ("lib.rhai", "let x = 1; export x;"),
+        ],
+    );
+    let file_lib = dir.join("lib.rhai").canonicalize().unwrap();
+
+    let resolver = FileModuleResolver::new_with_path(&dir);
+    let mut engine = engine_with(resolver.clone());
+    engine.set_share_modules(true);
+
+    assert_eq!(engine.eval::<INT>(r#"import "main" as m; m::x"#)?, 1);
+
+    // Shared modules are not imported again, so changes are not picked up by themselves
+    thread::sleep(Duration::from_millis(10));
+    fs::write(&file_lib, "let x = 2; export x;").unwrap();
+    assert_eq!(engine.eval::<INT>(r#"import "main" as m; m::x"#)?, 1);
+
+    // Reloading discards the shared modules, including those importing the changed one
+    assert_eq!(resolver.reload_changed(&engine), vec![file_lib]);
+    assert_eq!(engine.eval::<INT>(r#"import "main" as m; m::x"#)?, 2);
+
+    fs::remove_dir_all(dir).unwrap();
+    Ok(())
+}
+
+#[cfg(feature = "sync")]
+#[test]
+fn test_file_resolver_watch() -> Result<(), Box<EvalAltResult>> {
+    let dir = make_scripts("watch", &[("a.rhai", "let x = 1; export x;")]);
+    let file_a = dir.join("a.rhai").canonicalize().unwrap();
+
+    let reloaded = Arc::new(Mutex::new(Vec::<PathBuf>::new()));
+    let log = reloaded.clone();
+
+    let mut resolver = FileModuleResolver::new_with_path(&dir);
+    resolver.on_reload(move |path| log.lock().unwrap().push(path.to_path_buf()));
+
+    let mut engine = engine_with(resolver.clone());
+    engine.set_share_modules(true);
+    let engine = Arc::new(engine);
+
+    assert_eq!(engine.eval::<INT>(r#"import "a" as a; a::x"#)?, 1);
+
+    let watcher = resolver.watch(engine.clone(), Duration::from_millis(10));
+
+    // The change is picked up without calling 'reload_changed'
+    thread::sleep(Duration::from_millis(10));
+    fs::write(&file_a, "let x = 2; export x;").unwrap();
+
+    for _ in 0..200 {
+        if !reloaded.lock().unwrap().is_empty() {
+            break;
+        }
+        thread::sleep(Duration::from_millis(10));
+    }
+
+    assert_eq!(*reloaded.lock().unwrap(), vec![file_a.clone()]);
+    assert_eq!(engine.eval::<INT>(r#"import "a" as a; a::x"#)?, 2);
+
+    // No more changes are picked up after the watcher is dropped
+    drop(watcher);
+    thread::sleep(Duration::from_millis(10));
+    fs::write(&file_a, "let x = 3; export x;").unwrap();
+    thread::sleep(Duration::from_millis(50));
+    assert_eq!(*reloaded.lock().unwrap(), vec![file_a]);
+
+    fs::remove_dir_all(dir).unwrap();
+    Ok(())
+}
@@ -0,0 +1,160 @@
+#![cfg(not(feature = "no_function"))]
+use rhai::{Engine, EvalAltResult, INT};
+
+#[cfg(not(feature = "no_object"))]
+#[test]
+fn test_fn_overload_types() -> Result<(), Box<EvalAltResult>> {
+    use rhai::RegisterFn;
+
+    #[derive(Debug, Clone)]
+    struct Square(INT);
+
+    #[derive(Debug, Clone)]
+    struct Circle(INT);
+
+    let mut engine = Engine::new();
+
+    engine
+        .register_type_with_name::<Square>("Square")
+        .register_fn("new_square", Square)
+        .register_fn("side", |s: &mut Square| s.0)
+        .register_type_with_name::<Circle>("Circle")
+        .register_fn("new_circle", Circle)
+        .register_fn("radius", |c: &mut Circle| c.0);
+
+    let script = r#"
+        fn area(s: Square) { s.side() * s.side() }
+        fn area(c: Circle) { 3 * c.radius() * c.radius() }
+        fn describe(x: i64) { "int" }
+        fn describe(x: string) { "string" }
+        fn describe(x) { "other" }
+    "#;
+
+    assert_eq!(
+        engine.eval::<INT>(&format!("{} area(new_square(6))", script))?,
+        36
+    );
+    assert_eq!(
+        engine.eval::<INT>(&format!("{} let c = new_circle(2); area(c)", script))?,
+        12
+    );
+    assert_eq!(
+        engine.eval::<String>(&format!(
+            r#"{} describe(42) + describe("x") + describe(true)"#,
+            script
+        ))?,
+        if cfg!(feature = "only_i32") {
+            "otherstringother"
+        } else {
+            "intstringother"
+        }
+    );
+
+    assert!(matches!(
+        *engine
+            .eval::<INT>(&format!("{} area(42)", script))
+            .expect_err("should error"),
+        EvalAltResult::ErrorFunctionNotFound(s, _)
+            if s.contains("matches none of") && s.contains("area(s: Square)") && s.contains("area(c: Circle)")
+    ));
+
+    // Function pointers
+    assert_eq!(
+        engine.eval::<INT>(&format!(
+            r#"{} let f = Fn("area"); f.call(new_square(3))"#,
+            script
+        ))?,
+        9
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_fn_overload_ambiguous() -> Result<(), Box<EvalAltResult>> {
+    let engine = Engine::new();
+
+    let script = r#"
+        fn f(a: bool, b) { "first" }
+        fn f(a, b: bool) { "second" }
+        fn f(a: bool, b: bool) { "both" }
+    "#;
+
+    assert_eq!(
+        engine.eval::<String>(&format!("{} f(true, 1)", script))?,
+        "first"
+    );
+    assert_eq!(
+        engine.eval::<String>(&format!("{} f(1, true)", script))?,
+        "second"
+    );
+    assert_eq!(
+        engine.eval::<String>(&format!("{} f(true, false)", script))?,
+        "both"
+    );
+
+    assert!(matches!(
+        *engine
+            .eval::<String>("fn f(a: bool, b) { 1 } fn f(a, b: bool) { 2 } f(true, false)")
+            .expect_err("should error"),
+        EvalAltResult::ErrorFunctionNotFound(s, _)
+            if s == "f (bool, bool) is ambiguous between f(a: bool, b), f(a, b: bool)"
+                || s == "f (bool, bool) is ambiguous between f(a, b: bool), f(a: bool, b)"
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_fn_overload_params() -> Result<(), Box<EvalAltResult>> {
+    let engine = Engine::new();
+
+    // Default values and named arguments
+    let script = r#"
+        fn pad(s: string, n = 2) { len(s) + n }
+        fn pad(x: bool, n = 2) { n }
+    "#;
+
+    assert_eq!(engine.eval::<INT>(&format!(r#"{} pad("abc")"#, script))?, 5);
+    assert_eq!(
+        engine.eval::<INT>(&format!(r#"{} pad(true, 42)"#, script))?,
+        42
+    );
+    assert_eq!(
+        engine.eval::<INT>(&format!(r#"{} pad(n: 40, x: false)"#, script))?,
+        40
+    );
+
+    // Method-call style
+    #[cfg(not(feature = "no_object"))]
+    assert_eq!(
+        engine.eval::<INT>(&format!(r#"{} let x = "abc"; x.pad("de")"#, script))?,
+        4
+    );
+
+    let ast = engine.compile(script)?;
+    let mut scope = rhai::Scope::new();
+    assert_eq!(
+        engine.call_fn::<_, INT>(&mut scope, &ast, "pad", (true, 42 as INT))?,
+        42
+    );
+
+    #[cfg(not(feature = "no_module"))]
+    {
+        let mut resolver = rhai::module_resolvers::StaticModuleResolver::new();
+        let module = rhai::Module::eval_ast_as_new(rhai::Scope::new(), &ast, &engine)?;
+        resolver.insert("m", module);
+
+        let mut engine = Engine::new();
+        engine.set_module_resolver(Some(resolver));
+
+        assert_eq!(
+            engine.eval::<INT>(r#"import "m" as m; m::pad("abc") + m::pad(true, 1)"#)?,
+            6
+        );
+    }
+
+    assert!(engine.compile("fn f(x: ) { x }").is_err());
+
+    Ok(())
+}
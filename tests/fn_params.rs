@@ -0,0 +1,175 @@
+use rhai::{Engine, EvalAltResult, RegisterFn, INT};
+
+#[cfg(not(feature = "no_function"))]
+#[test]
+fn test_fn_params_default() -> Result<(), Box<EvalAltResult>> {
+    let engine = Engine::new();
+
+    let script = r#"
+        fn f(a, b = 10, c = "x") { a + b + c }
+    "#;
+
+    assert_eq!(engine.eval::<String>(&format!("{} f(1)", script))?, "11x");
+    assert_eq!(engine.eval::<String>(&format!("{} f(1, 2)", script))?, "3x");
+    assert_eq!(
+        engine.eval::<String>(&format!("{} f(1, 2, \"y\")", script))?,
+        "3y"
+    );
+    assert!(matches!(
+        *engine
+            .eval::<String>(&format!("{} f()", script))
+            .expect_err("should error"),
+        EvalAltResult::ErrorFunctionNotFound(_, _)
+    ));
+
+    // Default values can refer to the parameters before them
+    assert_eq!(
+        engine.eval::<INT>("fn f(a, b = a * 2) { a + b } f(14)")?,
+        42
+    );
+    assert_eq!(
+        engine.eval::<INT>("fn f(a, b = a * 2, c = b + 1) { a + b + c } f(1) + f(1, 1)")?,
+        10
+    );
+
+    // An exact match takes precedence
+    assert_eq!(
+        engine.eval::<INT>("fn f(a, b = 1) { a + b } fn f(a) { 42 } f(1)")?,
+        42
+    );
+
+    // Function pointers
+    assert_eq!(
+        engine.eval::<INT>("fn f(a, b = 2) { a * b } let p = Fn(\"f\"); call(p, 21)")?,
+        42
+    );
+
+    let ast = engine.compile("fn f(a, b = 2) { a * b }")?;
+    let mut scope = rhai::Scope::new();
+    assert_eq!(
+        engine.call_fn::<_, INT>(&mut scope, &ast, "f", (21 as INT,))?,
+        42
+    );
+
+    #[cfg(not(feature = "no_module"))]
+    {
+        let mut resolver = rhai::module_resolvers::StaticModuleResolver::new();
+        let module = rhai::Module::eval_ast_as_new(rhai::Scope::new(), &ast, &engine)?;
+        resolver.insert("m", module);
+
+        let mut engine = Engine::new();
+        engine.set_module_resolver(Some(resolver));
+
+        assert_eq!(engine.eval::<INT>(r#"import "m" as m; m::f(21)"#)?, 42);
+        assert_eq!(engine.eval::<INT>(r#"import "m" as m; m::f(6, 7)"#)?, 42);
+    }
+
+    assert!(engine.compile("fn f(a = 1, b) { a + b }").is_err());
+
+    Ok(())
+}
+
+#[cfg(not(feature = "no_function"))]
+#[test]
+fn test_fn_params_default_merge() -> Result<(), Box<EvalAltResult>> {
+    let engine = Engine::new();
+
+    let ast1 = engine.compile("fn f(a, b = 1) { a + b }")?;
+    let ast2 = engine.compile("fn f(a, b) { a * b }")?;
+
+    let ast = ast1.merge(&ast2);
+    assert_eq!(
+        engine.eval_ast::<INT>(&ast.merge(&engine.compile("f(5, 2)")?))?,
+        10
+    );
+    assert!(matches!(
+        *engine
+            .eval_ast::<INT>(&ast.merge(&engine.compile("f(5)")?))
+            .expect_err("should error"),
+        EvalAltResult::ErrorFunctionNotFound(_, _)
+    ));
+    assert!(matches!(
+        *engine
+            .eval_ast::<INT>(&ast.merge(&engine.compile("f(a: 5)")?))
+            .expect_err("should error"),
+        EvalAltResult::ErrorFunctionNotFound(_, _)
+    ));
+
+    let ast = ast2.merge(&ast1);
+    assert_eq!(
+        engine.eval_ast::<INT>(&ast.merge(&engine.compile("f(5)")?))?,
+        6
+    );
+
+    Ok(())
+}
+
+#[cfg(not(feature = "no_function"))]
+#[test]
+fn test_fn_params_named() -> Result<(), Box<EvalAltResult>> {
+    let engine = Engine::new();
+
+    let script = r#"
+        fn f(a, b = 10, c = "x") { a + b + c }
+    "#;
+
+    assert_eq!(
+        engine.eval::<String>(&format!("{} f(1, c: \"y\")", script))?,
+        "11y"
+    );
+    assert_eq!(
+        engine.eval::<String>(&format!("{} f(c: \"y\", a: 1, b: 2)", script))?,
+        "3y"
+    );
+    assert_eq!(
+        engine.eval::<INT>("fn f(a, b = a + 1) { a * b } f(b: 3, a: 14)")?,
+        42
+    );
+    assert_eq!(
+        engine.eval::<INT>("fn f(a, b = a + 1) { a * b } let a = 0; f(a: 6)")?,
+        42
+    );
+
+    assert!(matches!(
+        *engine
+            .eval::<String>(&format!("{} f(1, d: 2)", script))
+            .expect_err("should error"),
+        EvalAltResult::ErrorFunctionNotFound(s, _) if s == "f (i64, d: i64)" || s == "f (i32, d: i32)"
+    ));
+    assert!(matches!(
+        *engine
+            .eval::<String>(&format!("{} f(1, a: 2)", script))
+            .expect_err("should error"),
+        EvalAltResult::ErrorFunctionNotFound(_, _)
+    ));
+    assert!(matches!(
+        *engine
+            .eval::<String>(&format!("{} f(b: 2)", script))
+            .expect_err("should error"),
+        EvalAltResult::ErrorFunctionNotFound(_, _)
+    ));
+
+    assert!(engine.compile("f(a: 1, a: 2)").is_err());
+    assert!(engine.compile("f(a: 1, 2)").is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_fn_params_named_native() -> Result<(), Box<EvalAltResult>> {
+    let mut engine = Engine::new();
+
+    engine.register_fn("sub", |x: INT, y: INT| x - y);
+    engine.register_fn_param_names("sub", &["x", "y"]);
+
+    assert_eq!(engine.eval::<INT>("sub(43, 1)")?, 42);
+    assert_eq!(engine.eval::<INT>("sub(43, y: 1)")?, 42);
+    assert_eq!(engine.eval::<INT>("sub(y: 1, x: 43)")?, 42);
+
+    assert!(matches!(
+        *engine.eval::<INT>("sub(x: 43)").expect_err("should error"),
+        EvalAltResult::ErrorFunctionNotFound(_, _)
+    ));
+
+    Ok(())
+}
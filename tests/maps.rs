@@ -185,6 +185,47 @@ fn test_map_json() -> Result<(), Box<EvalAltResult>> {
     Ok(())
 }
 
+#[test]
+#[cfg(feature = "ordered_map")]
+fn test_map_ordered() -> Result<(), Box<EvalAltResult>> {
+    let engine = Engine::new();
+
+    #[cfg(not(feature = "no_index"))]
+    assert_eq!(
+        engine.eval::<String>(
+            r#"
+                let map = #{ zeta: 1, alpha: 2, mu: 3 };
+                map.beta = 4;
+                map.remove("alpha");
+                map.mixin(#{ gamma: 5, zeta: 6 });
+
+                let s = "";
+                for k in map.keys() { s += k; s += ","; }
+                for v in map.values() { s += v; }
+                s
+            "#
+        )?,
+        "zeta,mu,beta,gamma,6345"
+    );
+
+    assert_eq!(
+        engine.eval::<String>(r#"#{ c: 1, a: 2, b: #{ y: 3, x: 4 } }.to_string()"#)?,
+        r#"#{"c": 1, "a": 2, "b": #{"y": 3, "x": 4}}"#
+    );
+
+    let map = engine.parse_json(r#"{"z": 1, "y": true, "x": null}"#, true)?;
+    assert_eq!(
+        map.keys().map(|k| k.as_str()).collect::<Vec<_>>(),
+        ["z", "y", "x"]
+    );
+    assert_eq!(
+        format!("{:?}", rhai::Dynamic::from(map)),
+        r#"#{"z": 1, "y": true, "x": ()}"#
+    );
+
+    Ok(())
+}
+
 #[test]
 #[cfg(not(feature = "no_function"))]
 fn test_map_oop() -> Result<(), Box<EvalAltResult>> {
@@ -268,3 +268,214 @@ fn test_module_export() -> Result<(), Box<EvalAltResult>> {
 
     Ok(())
 }
+
+#[test]
+fn test_module_export_const() -> Result<(), Box<EvalAltResult>> {
+    let engine = Engine::new();
+
+    let ast = engine.compile("export const answer = 42; export let x = 1; let y = 2;")?;
+    let module = Module::eval_ast_as_new(Scope::new(), &ast, &engine)?;
+
+    assert_eq!(module.get_var_value::<INT>("answer").unwrap(), 42);
+    assert_eq!(module.get_var_value::<INT>("x").unwrap(), 1);
+    assert!(!module.contains_var("y"));
+
+    let ast = engine.compile("let x = 1; let y = 2; export *;")?;
+    let module = Module::eval_ast_as_new(Scope::new(), &ast, &engine)?;
+
+    assert!(module.contains_var("x"));
+    assert!(module.contains_var("y"));
+
+    Ok(())
+}
+
+#[cfg(not(feature = "no_std"))]
+#[cfg(not(feature = "no_function"))]
+#[test]
+fn test_module_re_export() -> Result<(), Box<EvalAltResult>> {
+    use rhai::module_resolvers::ArchiveModuleResolver;
+
+    let mut resolver = ArchiveModuleResolver::new();
+    resolver.insert(
+        "inner.rhai",
+        r#"
+            export const value = 40;
+            export const hidden = 0;
+            fn add(x) { x + 2 }
+            fn add(x, y) { x + y }
+            private fn secret() { 0 }
+        "#,
+    );
+    resolver.insert(
+        "api.rhai",
+        r#"
+            import "inner" as inner;
+            export inner::value as answer, inner::add as plus;
+            export use "inner" as sub;
+        "#,
+    );
+    resolver.insert("all.rhai", r#"import "inner" as inner; export inner::*;"#);
+    resolver.insert("flat.rhai", r#"export use "inner";"#);
+    resolver.insert(
+        "bad.rhai",
+        r#"import "inner" as inner; export inner::missing;"#,
+    );
+
+    let mut engine = Engine::new();
+    engine.set_module_resolver(Some(resolver));
+
+    assert_eq!(
+        engine.eval::<INT>(r#"import "api" as api; api::answer"#)?,
+        40
+    );
+    assert_eq!(
+        engine.eval::<INT>(r#"import "api" as api; api::plus(api::answer)"#)?,
+        42
+    );
+    assert_eq!(
+        engine.eval::<INT>(r#"import "api" as api; api::plus(40, 2)"#)?,
+        42
+    );
+    assert_eq!(
+        engine.eval::<INT>(r#"import "api" as api; api::sub::value"#)?,
+        40
+    );
+    assert_eq!(
+        engine.eval::<INT>(r#"import "all" as m; m::add(m::value)"#)?,
+        42
+    );
+    assert_eq!(
+        engine.eval::<INT>(r#"import "flat" as m; m::add(m::value)"#)?,
+        42
+    );
+
+    assert!(matches!(
+        *engine
+            .eval::<INT>(r#"import "all" as m; m::secret()"#)
+            .expect_err("should error"),
+        EvalAltResult::ErrorFunctionNotFound(_, _)
+    ));
+
+    assert!(matches!(
+        *engine
+            .eval::<INT>(r#"import "api" as api; api::answer = 0; api::answer"#)
+            .expect_err("should error"),
+        EvalAltResult::ErrorAssignmentToConstant(_, _)
+    ));
+
+    assert!(matches!(
+        *engine
+            .eval::<()>(r#"import "bad" as m;"#)
+            .expect_err("should error"),
+        EvalAltResult::ErrorVariableNotFound(name, _) if name == "inner::missing"
+    ));
+
+    Ok(())
+}
+
+#[cfg(not(feature = "no_std"))]
+#[test]
+fn test_module_shared() -> Result<(), Box<EvalAltResult>> {
+    use rhai::{module_resolvers::ArchiveModuleResolver, RegisterFn};
+    use std::sync::{Arc, Mutex};
+
+    let mut resolver = ArchiveModuleResolver::new();
+    resolver.insert(
+        "a.rhai",
+        r#"log("a start"); import "b" as b; log("a end"); export let x = b::y + 1;"#,
+    );
+    resolver.insert("b.rhai", r#"log("b"); export const y = 41;"#);
+
+    let log = Arc::new(Mutex::new(Vec::<String>::new()));
+    let logger = log.clone();
+
+    let mut engine = Engine::new();
+    engine.set_module_resolver(Some(resolver));
+    engine.register_fn("log", move |s: &str| {
+        logger.lock().unwrap().push(s.to_string())
+    });
+
+    // Without sharing, the module is evaluated at every import
+    engine.eval::<()>(r#"import "a" as a; import "a" as a2;"#)?;
+    assert_eq!(log.lock().unwrap().len(), 6);
+    log.lock().unwrap().clear();
+
+    engine.set_share_modules(true);
+    assert!(engine.share_modules());
+
+    assert_eq!(
+        engine.eval::<INT>(r#"import "a" as a; import "a" as a2; a::x + a2::x"#)?,
+        84
+    );
+    assert_eq!(engine.eval::<INT>(r#"import "b" as b; b::y"#)?, 41);
+    assert_eq!(*log.lock().unwrap(), vec!["a start", "b", "a end"]);
+
+    // Shared modules still count towards the maximum number of modules
+    #[cfg(not(feature = "unchecked"))]
+    {
+        engine.set_max_modules(1);
+        assert!(matches!(
+            *engine
+                .eval::<()>(r#"import "a" as a; import "a" as a2;"#)
+                .expect_err("should error"),
+            EvalAltResult::ErrorTooManyModules(_)
+        ));
+        engine.set_max_modules(usize::MAX);
+    }
+
+    engine.clear_shared_modules();
+    log.lock().unwrap().clear();
+    engine.eval::<()>(r#"import "a" as a; import "a" as a2;"#)?;
+    assert_eq!(log.lock().unwrap().len(), 3);
+
+    Ok(())
+}
+
+#[test]
+fn test_module_shared_not_cloned() -> Result<(), Box<EvalAltResult>> {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    /// Counts how many times it is cloned, i.e. how many times the module holding it is cloned.
+    struct CloneCount(Arc<AtomicUsize>);
+
+    impl Clone for CloneCount {
+        fn clone(&self) -> Self {
+            self.0.fetch_add(1, Ordering::SeqCst);
+            Self(self.0.clone())
+        }
+    }
+
+    let count = Arc::new(AtomicUsize::new(0));
+
+    let mut module = Module::new();
+    module.set_var("x", 42 as INT);
+    module.set_var("counter", CloneCount(count.clone()));
+    module.set_fn_0("answer", || Ok(42 as INT));
+
+    let mut resolver = StaticModuleResolver::new();
+    resolver.insert("m", module);
+
+    let mut engine = Engine::new();
+    engine.set_module_resolver(Some(resolver));
+    engine.set_share_modules(true);
+
+    engine.consume(r#"import "m" as m;"#)?;
+    let imported = count.load(Ordering::SeqCst);
+
+    // Reading variables, calling methods on them and calling functions do not clone the module
+    assert_eq!(
+        engine.eval::<INT>(
+            r#"
+                import "m" as m;
+                let sum = 0;
+                for i in range(0, 10) { sum += m::x + m::x.abs() + m::answer(); }
+                sum
+            "#
+        )?,
+        1260
+    );
+    assert_eq!(count.load(Ordering::SeqCst), imported);
+
+    Ok(())
+}
@@ -0,0 +1,142 @@
+use rhai::{Dynamic, Engine, EvalAltResult, RegisterResultFn, INT};
+
+#[test]
+fn test_optional_coalesce() -> Result<(), Box<EvalAltResult>> {
+    let mut engine = Engine::new();
+
+    engine.register_result_fn("boom", || -> Result<Dynamic, Box<EvalAltResult>> {
+        Err("boom".into())
+    });
+
+    assert_eq!(engine.eval::<INT>("() ?? 42")?, 42);
+    assert_eq!(engine.eval::<INT>("let x = 42; x ?? 0")?, 42);
+    assert_eq!(engine.eval::<INT>("let x; x ?? 40 + 2")?, 42);
+    assert_eq!(engine.eval::<INT>("let x; let y; x ?? y ?? 42")?, 42);
+    assert!(!engine.eval::<bool>("let x = false; x ?? true")?);
+
+    // The right-hand side is only evaluated when needed
+    assert_eq!(engine.eval::<INT>("42 ?? boom()")?, 42);
+    assert!(engine.eval::<INT>("() ?? boom()").is_err());
+
+    Ok(())
+}
+
+#[cfg(not(feature = "no_object"))]
+#[test]
+fn test_optional_chaining() -> Result<(), Box<EvalAltResult>> {
+    let engine = Engine::new();
+
+    assert_eq!(
+        engine.eval::<INT>("let x = #{a: #{b: #{c: 42}}}; x?.a?.b?.c")?,
+        42
+    );
+    engine.eval::<()>("let x = #{a: #{b: ()}}; x?.a?.b?.c")?;
+    engine.eval::<()>("let x; x?.a.b.c")?;
+    assert_eq!(engine.eval::<INT>("let x; x?.a.b.c ?? 42")?, 42);
+    assert_eq!(engine.eval::<INT>("let x; x?.len() ?? 42")?, 42);
+    assert_eq!(engine.eval::<INT>(r#"let x = "hello"; x?.len() ?? 0"#)?, 5);
+    assert_eq!(engine.eval::<INT>("(())?.a ?? 42")?, 42);
+
+    // Plain property access on () is still an error
+    assert!(matches!(
+        *engine.eval::<()>("let x; x.a").expect_err("should error"),
+        EvalAltResult::ErrorDotExpr(_, _)
+    ));
+    assert!(matches!(
+        *engine
+            .eval::<()>("let x = #{a: ()}; x?.a.b")
+            .expect_err("should error"),
+        EvalAltResult::ErrorDotExpr(_, _)
+    ));
+
+    // Assignments through a short-circuited chain are skipped
+    assert_eq!(
+        engine.eval::<INT>("let x = #{a: #{b: 1}}; x?.a?.b = 42; x.a.b")?,
+        42
+    );
+    engine.eval::<()>("let x = #{a: ()}; x.a?.b = 42; if x.a != () { throw 1; }")?;
+
+    #[cfg(not(feature = "no_index"))]
+    {
+        assert_eq!(engine.eval::<INT>("let x = #{a: [1, 2, 3]}; x?.a?[1]")?, 2);
+        assert_eq!(engine.eval::<INT>("let x; x?[1] ?? 42")?, 42);
+        assert_eq!(engine.eval::<INT>("let x = [(), 2]; x[0]?[1] ?? 42")?, 42);
+        assert_eq!(engine.eval::<INT>("let x = [[1, 42]]; x[0]?[1]")?, 42);
+        assert_eq!(
+            engine.eval::<INT>("let x = [#{a: ()}]; x[0].a?[0].b ?? 42")?,
+            42
+        );
+
+        let json = r#"{"name": "Bob", "address": null, "phones": null}"#;
+        let mut scope = rhai::Scope::new();
+        scope.push("data", engine.parse_json(json, true)?);
+
+        assert_eq!(
+            engine.eval_with_scope::<String>(&mut scope, r#"data?.address?.city ?? "unknown""#)?,
+            "unknown"
+        );
+        assert_eq!(
+            engine.eval_with_scope::<String>(&mut scope, r#"data?.phones?[0] ?? "none""#)?,
+            "none"
+        );
+        assert_eq!(
+            engine.eval_with_scope::<String>(&mut scope, r#"data?.name ?? "nobody""#)?,
+            "Bob"
+        );
+    }
+
+    Ok(())
+}
+
+#[cfg(not(feature = "no_object"))]
+#[test]
+fn test_optional_chaining_get_set() -> Result<(), Box<EvalAltResult>> {
+    use rhai::RegisterFn;
+
+    #[derive(Clone)]
+    struct TestStruct {
+        x: INT,
+        array: Vec<INT>,
+    }
+
+    let mut engine = Engine::new();
+
+    engine.register_type::<TestStruct>();
+    engine.register_fn("new_ts", || TestStruct {
+        x: 1,
+        array: vec![1, 2, 3],
+    });
+    engine.register_get_set(
+        "x",
+        |t: &mut TestStruct| t.x,
+        |t: &mut TestStruct, x: INT| t.x = x,
+    );
+
+    assert_eq!(
+        engine.eval::<INT>("let a = #{t: new_ts()}; a?.t?.x = 42; a.t.x")?,
+        42
+    );
+    assert_eq!(
+        engine.eval::<INT>("let a = #{t: ()}; a?.t?.x = 42; a.t ?? 0")?,
+        0
+    );
+
+    #[cfg(not(feature = "no_index"))]
+    {
+        engine.register_indexer_get_set(
+            |t: &mut TestStruct, i: INT| t.array[i as usize],
+            |t: &mut TestStruct, i: INT, value: INT| t.array[i as usize] = value,
+        );
+
+        assert_eq!(
+            engine.eval::<INT>("let a = #{t: new_ts()}; a.t?[1] = 42; a.t?[1]")?,
+            42
+        );
+        assert_eq!(
+            engine.eval::<INT>("let a = #{}; a.t?[1] = 42; a.t?[1] ?? 0")?,
+            0
+        );
+    }
+
+    Ok(())
+}
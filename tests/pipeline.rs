@@ -0,0 +1,152 @@
+use rhai::{Engine, EvalAltResult, FnPtr, RegisterFn, Scope, INT};
+use std::convert::TryFrom;
+
+#[test]
+fn test_pipeline_functions() -> Result<(), Box<EvalAltResult>> {
+    let mut engine = Engine::new();
+
+    engine.register_fn("double", |x: INT| x * 2);
+    engine.register_fn("sub", |x: INT, y: INT| x - y);
+    engine.register_fn("add_to", |x: &mut INT, y: INT| {
+        *x += y;
+        *x
+    });
+
+    assert_eq!(engine.eval::<INT>("21 |> double")?, 42);
+    assert_eq!(engine.eval::<INT>("50 |> sub(8)")?, 42);
+    assert_eq!(engine.eval::<INT>("4 |> double |> sub(1) |> double")?, 14);
+
+    // Binds less tightly than arithmetic, but more tightly than comparisons
+    assert_eq!(engine.eval::<INT>("20 + 1 |> double")?, 42);
+    assert!(engine.eval::<bool>("20 + 1 |> double == 42")?);
+
+    // The left-hand side variable is passed by reference
+    assert_eq!(engine.eval::<INT>("let x = 40; x |> add_to(2); x")?, 42);
+    assert_eq!(
+        engine.eval::<INT>("let x = 40; (x + 0) |> add_to(2); x")?,
+        40
+    );
+
+    assert_eq!(
+        engine.eval::<String>(r#"let s = "  hello "; s |> trim(); s"#)?,
+        "hello"
+    );
+    assert_eq!(engine.eval::<String>(r#""x" |> type_of()"#)?, "string");
+
+    #[cfg(not(feature = "no_function"))]
+    assert_eq!(
+        engine.eval::<INT>(
+            r"
+                fn triple(x) { x * 3 }
+                fn mul(x, y) { x * y }
+                2 |> triple |> mul(7)
+            "
+        )?,
+        42
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_pipeline_fn_ptr() -> Result<(), Box<EvalAltResult>> {
+    let mut engine = Engine::new();
+
+    engine.register_fn("double", |x: INT| x * 2);
+    engine.register_fn("sub", |x: INT, y: INT| x - y);
+    engine.register_fn("add_to", |x: &mut INT, y: INT| *x += y);
+
+    assert_eq!(engine.eval::<INT>(r#"21 |> Fn("double")"#)?, 42);
+    assert_eq!(
+        engine.eval::<INT>(r#"let f = curry(Fn("sub"), 50); 8 |> f"#)?,
+        42
+    );
+    assert_eq!(engine.eval::<INT>(r#"8 |> curry(Fn("sub"), 50)"#)?, 42);
+    assert_eq!(engine.eval::<INT>(r#"50 |> call(Fn("sub"), 8)"#)?, 42);
+
+    #[cfg(not(feature = "no_object"))]
+    assert_eq!(
+        engine.eval::<INT>(r#"let f = Fn("sub").curry(50); 8 |> f"#)?,
+        42
+    );
+
+    // Function pointer in the left-hand side variable is passed by reference
+    assert_eq!(
+        engine.eval::<INT>(r#"let f = Fn("add_to"); let x = 40; x |> call(f, 2); x"#)?,
+        42
+    );
+
+    // Function pointers from the scope are called by name
+    let mut scope = Scope::new();
+    scope.push("g", FnPtr::try_from("sub")?);
+
+    assert_eq!(engine.eval_with_scope::<INT>(&mut scope, "50 |> g(8)")?, 42);
+
+    // ... but only via the pipeline operator
+    assert!(matches!(
+        *engine
+            .eval_with_scope::<INT>(&mut scope, "g(50, 8)")
+            .expect_err("should error"),
+        EvalAltResult::ErrorFunctionNotFound(f, _) if f.starts_with("g (")
+    ));
+    assert!(matches!(
+        *engine
+            .eval::<INT>(r#"let f = Fn("abs"); f(-3)"#)
+            .expect_err("should error"),
+        EvalAltResult::ErrorFunctionNotFound(f, _) if f.starts_with("f (")
+    ));
+
+    #[cfg(not(feature = "no_function"))]
+    {
+        assert_eq!(engine.eval::<INT>("21 |> |x| x * 2")?, 42);
+        assert_eq!(engine.eval::<INT>("let f = |x| x * 2; 21 |> f")?, 42);
+        assert_eq!(engine.eval::<INT>("let f = |x, y| x - y; 50 |> f(8)")?, 42);
+
+        #[cfg(not(feature = "no_closure"))]
+        #[cfg(not(feature = "no_object"))]
+        assert_eq!(
+            engine.eval::<INT>(
+                r"
+                    let y = 2;
+                    let f = |x| x * y;
+                    let g = |x| x |> sub(1) |> f;
+                    22 |> g
+                "
+            )?,
+            42
+        );
+        #[cfg(not(feature = "no_closure"))]
+        #[cfg(not(feature = "no_object"))]
+        assert_eq!(
+            engine.eval::<INT>(
+                r"
+                    let y = 2;
+                    let f = |x, z| x * y + z;
+                    let g = |x| x |> f(2);
+                    20 |> g
+                "
+            )?,
+            42
+        );
+
+        // A function of the same name is called instead of the function pointer
+        let script = r"
+            fn f(x) { x * 2 }
+            let f = |x| x + 1;
+        ";
+        assert_eq!(engine.eval::<INT>(&format!("{} 21 |> f", script))?, 42);
+        assert_eq!(engine.eval::<INT>(&format!("{} 21 |> f()", script))?, 42);
+    }
+
+    // Variables not holding function pointers are skipped
+    assert_eq!(engine.eval::<INT>("let double = 1; 21 |> double")?, 42);
+    assert_eq!(engine.eval::<INT>("let double = 1; 21 |> double()")?, 42);
+    assert!(matches!(
+        *engine
+            .eval::<INT>("let g = 1; 21 |> g")
+            .expect_err("should error"),
+        EvalAltResult::ErrorFunctionNotFound(f, _) if f.starts_with("g (")
+    ));
+
+    Ok(())
+}
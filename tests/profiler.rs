@@ -0,0 +1,109 @@
+#![cfg(not(feature = "no_function"))]
+use rhai::{Engine, EvalAltResult, Profiler, RegisterFn, Scope, INT};
+
+#[test]
+fn test_profiler() -> Result<(), Box<EvalAltResult>> {
+    let mut engine = Engine::new();
+    let profiler = Profiler::new();
+
+    engine.register_fn("add1", |x: INT| x + 1);
+    engine.set_profiler(profiler.clone());
+
+    let script = r"
+        fn inner(x) { add1(x) }
+        fn outer(x) { let y = inner(x); inner(y) * 2 }
+        outer(1) + outer(2)
+    ";
+
+    assert_eq!(engine.eval::<INT>(script)?, 14);
+
+    let entries = profiler.entries();
+    let names: Vec<_> = entries.iter().map(|e| e.name.as_str()).collect();
+    assert_eq!(names, vec!["outer(x)", "inner(x)", "add1"]);
+
+    let outer = &entries[0];
+    let inner = &entries[1];
+    let add1 = &entries[2];
+
+    assert!(!outer.is_native);
+    assert!(add1.is_native);
+    assert_eq!(outer.calls, 2);
+    assert_eq!(inner.calls, 4);
+    assert_eq!(add1.calls, 4);
+    assert_eq!(add1.operations, 4);
+    assert_eq!(inner.operations, inner.self_operations + add1.operations);
+    assert_eq!(outer.operations, outer.self_operations + inner.operations);
+
+    let folded = profiler.folded_stacks();
+    let stacks: Vec<_> = folded
+        .lines()
+        .map(|line| line.rsplitn(2, ' ').nth(1).unwrap())
+        .collect();
+    assert_eq!(
+        stacks,
+        vec!["outer(x)", "outer(x);inner(x)", "outer(x);inner(x);add1"]
+    );
+
+    let total: u64 = folded
+        .lines()
+        .map(|line| line.rsplitn(2, ' ').next().unwrap().parse::<u64>().unwrap())
+        .sum();
+    assert_eq!(total, outer.operations);
+
+    assert!(profiler.report().contains("add1 [native]"));
+
+    profiler.reset();
+    assert!(profiler.entries().is_empty());
+
+    engine.clear_profiler();
+    engine.eval::<INT>(script)?;
+    assert!(profiler.entries().is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn test_profiler_recursive() -> Result<(), Box<EvalAltResult>> {
+    let mut engine = Engine::new();
+    let profiler = Profiler::new();
+
+    engine.set_profiler(profiler.clone());
+
+    assert_eq!(
+        engine.eval::<INT>(
+            r"
+                fn fib(n) { if n < 2 { n } else { fib(n - 1) + fib(n - 2) } }
+                fib(10)
+            "
+        )?,
+        55
+    );
+
+    let entries = profiler.entries();
+    assert_eq!(entries.len(), 1);
+
+    let fib = &entries[0];
+    assert_eq!(fib.name, "fib(n)");
+    assert_eq!(fib.calls, 177);
+    assert_eq!(fib.operations, fib.self_operations);
+
+    Ok(())
+}
+
+#[test]
+fn test_profiler_call_fn() -> Result<(), Box<EvalAltResult>> {
+    let mut engine = Engine::new();
+    let profiler = Profiler::new();
+
+    engine.set_profiler(profiler.clone());
+
+    let ast = engine.compile("fn double(x) { x * 2 }")?;
+    let result: INT = engine.call_fn(&mut Scope::new(), &ast, "double", (21 as INT,))?;
+    assert_eq!(result, 42);
+
+    let entries = profiler.entries();
+    assert_eq!(entries[0].name, "double(x)");
+    assert_eq!(entries[0].calls, 1);
+
+    Ok(())
+}
@@ -0,0 +1,284 @@
+#![cfg(not(feature = "no_object"))]
+#![cfg(not(feature = "no_function"))]
+use rhai::{Dynamic, Engine, EvalAltResult, ParseErrorType, Record, Scope, INT};
+use std::sync::{Arc, RwLock};
+
+const POINT: &str = r#"
+    struct Point {
+        x,
+        y = 0,
+
+        fn sum() { this.x + this.y }
+        fn shift(dx, dy) { this.x += dx; this.y += dy; }
+
+        get diff() { this.x - this.y }
+        set diff(value) { this.x = this.y + value; }
+    }
+"#;
+
+#[test]
+fn test_records() -> Result<(), Box<EvalAltResult>> {
+    let engine = Engine::new();
+
+    assert_eq!(
+        engine.eval::<INT>(&format!("{} let p = Point(1, 2); p.x * 10 + p.y", POINT))?,
+        12
+    );
+    assert_eq!(
+        engine.eval::<INT>(&format!("{} let p = Point(7); p.y", POINT))?,
+        0
+    );
+    assert_eq!(
+        engine.eval::<INT>(&format!("{} let p = Point(y: 3, x: 4); p.sum()", POINT))?,
+        7
+    );
+    assert_eq!(
+        engine.eval::<String>(&format!("{} type_of(Point(1))", POINT))?,
+        "Point"
+    );
+    assert_eq!(
+        engine.eval::<String>(&format!("{} Point(1, 2).to_string()", POINT))?,
+        r#"Point #{"x": 1, "y": 2}"#
+    );
+
+    let point = engine.eval::<Record>(&format!("{} Point(1, 2)", POINT))?;
+    assert_eq!(point.type_name(), "Point");
+    assert_eq!(point.fields().len(), 2);
+
+    Ok(())
+}
+
+#[test]
+fn test_records_methods() -> Result<(), Box<EvalAltResult>> {
+    let engine = Engine::new();
+
+    assert_eq!(
+        engine.eval::<INT>(&format!(
+            "{} let p = Point(1, 2); p.shift(10, 20); p.x + p.y",
+            POINT
+        ))?,
+        33
+    );
+    assert_eq!(
+        engine.eval::<INT>(&format!(
+            r#"
+                {}
+                struct Line {{ from, to, fn len() {{ this.to.sum() - this.from.sum() }} }}
+                let line = Line(Point(1, 1), Point(5, 5));
+                line.to.shift(1, 1);
+                line.len()
+            "#,
+            POINT
+        ))?,
+        10
+    );
+
+    // Methods of different record types do not clash
+    assert_eq!(
+        engine.eval::<INT>(
+            r#"
+                struct A { v, fn get() { this.v } }
+                struct B { v, fn get() { this.v * 2 } }
+                A(1).get() + B(1).get()
+            "#
+        )?,
+        3
+    );
+
+    // Methods are not visible as normal functions
+    assert!(matches!(
+        *engine
+            .eval::<INT>(&format!("{} sum()", POINT))
+            .expect_err("should error"),
+        EvalAltResult::ErrorFunctionNotFound(_, _)
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_records_properties() -> Result<(), Box<EvalAltResult>> {
+    let engine = Engine::new();
+
+    assert_eq!(
+        engine.eval::<INT>(&format!("{} let p = Point(5, 2); p.diff", POINT))?,
+        3
+    );
+    assert_eq!(
+        engine.eval::<INT>(&format!("{} let p = Point(5, 2); p.diff = 10; p.x", POINT))?,
+        12
+    );
+    assert_eq!(
+        engine.eval::<INT>(&format!("{} let p = Point(5, 2); p.y = 42; p.y", POINT))?,
+        42
+    );
+
+    // Fields cannot be added to a record
+    assert!(matches!(
+        *engine
+            .eval::<INT>(&format!("{} let p = Point(1); p.z", POINT))
+            .expect_err("should error"),
+        EvalAltResult::ErrorDotExpr(msg, _) if msg.contains("'z' for Point")
+    ));
+    assert!(matches!(
+        *engine
+            .eval::<()>(&format!("{} let p = Point(1); p.z = 1;", POINT))
+            .expect_err("should error"),
+        EvalAltResult::ErrorDotExpr(msg, _) if msg.contains("'z' for Point")
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_records_other_script() -> Result<(), Box<EvalAltResult>> {
+    let engine = Engine::new();
+
+    let point = engine.eval::<Dynamic>(&format!("{} Point(5, 2)", POINT))?;
+
+    // Methods are carried by the record into a script not declaring its record type
+    let mut scope = Scope::new();
+    scope.push_dynamic("p", point);
+
+    assert_eq!(engine.eval_with_scope::<INT>(&mut scope, "p.sum()")?, 7);
+    assert_eq!(engine.eval_with_scope::<INT>(&mut scope, "p.diff")?, 3);
+    assert_eq!(
+        engine.eval_with_scope::<INT>(&mut scope, "p.shift(1, 1); p.diff = 10; p.x")?,
+        13
+    );
+    assert!(matches!(
+        *engine
+            .eval_with_scope::<INT>(&mut scope, "p.foo()")
+            .expect_err("should error"),
+        EvalAltResult::ErrorFunctionNotFound(_, _)
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_records_overload() -> Result<(), Box<EvalAltResult>> {
+    let engine = Engine::new();
+
+    assert_eq!(
+        engine.eval::<String>(
+            r#"
+                struct Cat { name }
+                struct Dog { name }
+                fn speak(c: Cat) { c.name + " meows" }
+                fn speak(d: Dog) { d.name + " barks" }
+                speak(Cat("Tom")) + ", " + speak(Dog("Rex"))
+            "#
+        )?,
+        "Tom meows, Rex barks"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_records_equality() -> Result<(), Box<EvalAltResult>> {
+    let engine = Engine::new();
+
+    assert!(engine.eval::<bool>(&format!("{} Point(1, 2) == Point(1, 2)", POINT))?);
+    assert!(!engine.eval::<bool>(&format!("{} Point(1, 2) == Point(1, 3)", POINT))?);
+    assert!(engine.eval::<bool>(&format!("{} Point(1, 2) != Point(2, 1)", POINT))?);
+    assert!(!engine.eval::<bool>(&format!("{} Point(1, 2) != Point(1, 2)", POINT))?);
+    assert!(engine.eval::<bool>(&format!(
+        "{} Point(Point(1), 2) == Point(Point(1), 2)",
+        POINT
+    ))?);
+    assert!(!engine.eval::<bool>(&format!(
+        "{} Point(Point(1), 2) == Point(Point(2), 2)",
+        POINT
+    ))?);
+    assert!(!engine.eval::<bool>(
+        r#"
+            struct Cat { name }
+            struct Dog { name }
+            Cat("Rex") == Dog("Rex")
+        "#
+    )?);
+    assert!(engine.eval::<bool>(&format!("{} Point(1) in [Point(0), Point(1)]", POINT))?);
+
+    Ok(())
+}
+
+#[test]
+fn test_records_strings() -> Result<(), Box<EvalAltResult>> {
+    let engine = Engine::new();
+
+    assert_eq!(
+        engine.eval::<String>(&format!(r#"{} "p = " + Point(1, 2)"#, POINT))?,
+        r#"p = Point #{"x": 1, "y": 2}"#
+    );
+    assert_eq!(
+        engine.eval::<String>(&format!(r#"{} Point(1, 2) + "!""#, POINT))?,
+        r#"Point #{"x": 1, "y": 2}!"#
+    );
+    assert_eq!(
+        engine.eval::<String>(&format!(r#"{} let s = "p: "; s += Point(3); s"#, POINT))?,
+        r#"p: Point #{"x": 3, "y": 0}"#
+    );
+
+    let output = Arc::new(RwLock::new(Vec::new()));
+    let log = output.clone();
+
+    let mut engine = Engine::new();
+    engine.on_print(move |s| log.write().unwrap().push(s.to_string()));
+    engine.eval::<()>(&format!("{} print(Point(1, 2))", POINT))?;
+
+    assert_eq!(*output.read().unwrap(), vec![r#"Point #{"x": 1, "y": 2}"#]);
+
+    Ok(())
+}
+
+#[test]
+fn test_records_errors() -> Result<(), Box<EvalAltResult>> {
+    let engine = Engine::new();
+
+    assert!(matches!(
+        *engine
+            .compile("struct Point { x, x }")
+            .expect_err("should error")
+            .0,
+        ParseErrorType::StructDuplicatedField(_, _)
+    ));
+    assert!(matches!(
+        *engine
+            .compile("struct Point { x } struct Point { x, y }")
+            .expect_err("should error")
+            .0,
+        ParseErrorType::DuplicatedStruct(s) if s == "Point"
+    ));
+    assert!(matches!(
+        *engine
+            .compile("fn foo() { struct Point { x } }")
+            .expect_err("should error")
+            .0,
+        ParseErrorType::WrongStructDefinition
+    ));
+    assert!(matches!(
+        *engine
+            .compile("struct Point { x = 0, y }")
+            .expect_err("should error")
+            .0,
+        ParseErrorType::MissingToken(_, _)
+    ));
+    assert!(matches!(
+        *engine
+            .compile("struct Point { x fn sum() { this.x } }")
+            .expect_err("should error")
+            .0,
+        ParseErrorType::MissingToken(_, _)
+    ));
+    assert!(matches!(
+        *engine
+            .compile("struct Point { x, get y(z) { z } }")
+            .expect_err("should error")
+            .0,
+        ParseErrorType::BadInput(_)
+    ));
+
+    Ok(())
+}
@@ -0,0 +1,215 @@
+use rhai::{Engine, EvalAltResult, RegisterFn, Resumable, Scope, Suspended, INT};
+use std::sync::{Arc, RwLock};
+
+fn expect_suspended(result: Resumable) -> Box<Suspended> {
+    match result {
+        Resumable::Suspended(s) => s,
+        Resumable::Completed(v) => panic!("script should be suspended, but completed with {}", v),
+    }
+}
+
+#[test]
+fn test_resumable_yield() -> Result<(), Box<EvalAltResult>> {
+    let engine = Engine::new();
+
+    let mut scope = Scope::new();
+    scope.push("x", 1 as INT);
+
+    let script = r#"
+        let a = yield "first";
+        let b = yield x + a;
+        x + a + b
+    "#;
+
+    let suspended = expect_suspended(engine.eval_resumable_with_scope(&scope, script)?);
+    assert_eq!(suspended.value().clone().cast::<String>(), "first");
+    assert_eq!(suspended.num_resumed(), 0);
+
+    let suspended = expect_suspended(engine.resume(suspended, 10 as INT)?);
+    assert_eq!(suspended.value().clone().cast::<INT>(), 11);
+    assert_eq!(suspended.num_resumed(), 1);
+
+    match engine.resume(suspended, 100 as INT)? {
+        Resumable::Completed(result) => assert_eq!(result.cast::<INT>(), 111),
+        Resumable::Suspended(_) => panic!("script should be completed"),
+    }
+
+    // The original scope is not modified
+    assert_eq!(scope.get_value::<INT>("x").unwrap(), 1);
+
+    Ok(())
+}
+
+#[test]
+fn test_resumable_no_yield() -> Result<(), Box<EvalAltResult>> {
+    let engine = Engine::new();
+
+    match engine.eval_resumable("let x = 40; x + 2")? {
+        Resumable::Completed(result) => assert_eq!(result.cast::<INT>(), 42),
+        Resumable::Suspended(_) => panic!("script should be completed"),
+    }
+
+    assert!(matches!(
+        *engine.eval::<INT>("yield 42").expect_err("should error"),
+        EvalAltResult::Yield(_, _)
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_resumable_replay_output() -> Result<(), Box<EvalAltResult>> {
+    let logbook = Arc::new(RwLock::new(Vec::<String>::new()));
+    let log = logbook.clone();
+
+    let mut engine = Engine::new();
+    engine.on_print(move |s| log.write().unwrap().push(s.into()));
+
+    let script = r#"
+        print("start");
+        let name = yield ();
+        print("hello, " + name);
+        yield ();
+        print("done");
+    "#;
+
+    let suspended = expect_suspended(engine.eval_resumable(script)?);
+    let suspended = expect_suspended(engine.resume(suspended, "world".to_string())?);
+    assert!(engine.resume(suspended, ())?.is_completed());
+
+    assert_eq!(
+        *logbook.read().unwrap(),
+        vec!["start", "hello, world", "done"]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_resumable_diverged() -> Result<(), Box<EvalAltResult>> {
+    let counter = Arc::new(RwLock::new(0 as INT));
+    let count = counter.clone();
+
+    let mut engine = Engine::new();
+    engine.register_fn("next_id", move || {
+        let mut count = count.write().unwrap();
+        *count += 1;
+        *count
+    });
+
+    // A different value is yielded when replayed
+    let suspended = expect_suspended(engine.eval_resumable("let x = yield next_id(); x")?);
+    assert!(matches!(
+        *engine.resume(suspended, ()).expect_err("should error"),
+        EvalAltResult::ErrorResumeDiverged(_)
+    ));
+
+    // A different path is taken when replayed
+    *counter.write().unwrap() = 0;
+    let script = "if next_id() == 1 { yield 1; yield 2; } else { yield 1; }";
+    let suspended = expect_suspended(engine.eval_resumable(script)?);
+    assert!(matches!(
+        *engine.resume(suspended, ()).expect_err("should error"),
+        EvalAltResult::ErrorResumeDiverged(_)
+    ));
+
+    // The script finishes before reaching the point it was suspended at
+    *counter.write().unwrap() = 0;
+    let script = "if next_id() == 1 { yield 1; } 42";
+    let suspended = expect_suspended(engine.eval_resumable(script)?);
+    assert!(matches!(
+        *engine.resume(suspended, ()).expect_err("should error"),
+        EvalAltResult::ErrorResumeDiverged(_)
+    ));
+
+    Ok(())
+}
+
+#[cfg(not(feature = "no_function"))]
+#[test]
+fn test_resumable_generator() -> Result<(), Box<EvalAltResult>> {
+    let logbook = Arc::new(RwLock::new(Vec::<String>::new()));
+    let log = logbook.clone();
+
+    let mut engine = Engine::new();
+    engine.on_print(move |s| log.write().unwrap().push(s.to_string()));
+
+    assert_eq!(
+        engine.eval::<INT>(
+            r"
+                fn evens(n) {
+                    let x = 0;
+                    while x < n {
+                        if x % 2 == 0 { yield x; }
+                        x += 1;
+                    }
+                }
+
+                let sum = 0;
+                for x in evens(10) { sum += x; }
+                sum
+            "
+        )?,
+        20
+    );
+
+    assert_eq!(
+        engine.eval::<INT>(
+            r"
+                fn gen() { yield 1; yield 2; return; yield 3; }
+                let sum = 0;
+                for x in gen() { sum += x; }
+                sum
+            "
+        )?,
+        3
+    );
+
+    // Values are produced lazily, so a generator may never finish
+    assert_eq!(
+        engine.eval::<INT>(
+            r#"
+                fn count(from, step = 1) {
+                    let x = from;
+                    loop {
+                        print("at " + x);
+                        yield x;
+                        x += step;
+                    }
+                }
+
+                let sum = 0;
+                for x in count(10, 2) {
+                    if x > 15 { break; }
+                    sum += x;
+                }
+                sum
+            "#
+        )?,
+        36
+    );
+
+    // Output is not repeated when the generator is run again for the next value
+    assert_eq!(
+        logbook.read().unwrap().clone(),
+        vec!["at 10", "at 12", "at 14", "at 16"]
+    );
+
+    assert_eq!(
+        engine.eval::<String>("fn gen() { yield 1; } type_of(gen())")?,
+        "generator"
+    );
+
+    // Generators do not suspend the script
+    match engine
+        .eval_resumable("fn gen() { yield 1; yield 2; } let n = 0; for x in gen() { n += 1; } n")?
+    {
+        Resumable::Completed(result) => assert_eq!(result.cast::<INT>(), 2),
+        Resumable::Suspended(_) => panic!("script should be completed"),
+    }
+
+    // Closures cannot be generators
+    assert!(engine.compile("let gen = || { yield 1; };").is_err());
+
+    Ok(())
+}
@@ -0,0 +1,185 @@
+use rhai::{Engine, EvalAltResult, ParseErrorType, RegisterFn, SandboxProfile, INT};
+
+#[test]
+fn test_sandbox_keywords() -> Result<(), Box<EvalAltResult>> {
+    let mut profile = SandboxProfile::new("no-eval");
+    profile.deny_keyword("eval").deny_keyword("while");
+
+    let mut engine = Engine::new();
+    engine.set_sandbox(profile);
+
+    assert_eq!(engine.sandbox().unwrap().name(), "no-eval");
+    assert_eq!(engine.eval::<INT>("let x = 40; x + 2")?, 42);
+
+    let err = engine
+        .compile("let x = 1;\nlet y = eval(\"x\");")
+        .expect_err("should error");
+    assert_eq!(
+        *err.0,
+        ParseErrorType::SandboxViolation("keyword 'eval'".into())
+    );
+    assert_eq!(err.1.line(), Some(2));
+    assert_eq!(err.1.position(), Some(9));
+
+    let err = engine
+        .compile("let x = 0;\nwhile x < 10 { x += 1; }")
+        .expect_err("should error");
+    assert_eq!(
+        *err.0,
+        ParseErrorType::SandboxViolation("keyword 'while'".into())
+    );
+    assert_eq!(err.1.line(), Some(2));
+
+    // Calls via function pointers are checked against the name they resolve to
+    for script in &[
+        r#"call(Fn("eval"), "40 + 2")"#,
+        r#"let e = "ev" + "al"; call(Fn(e), "1")"#,
+        r#"let f = Fn("eval"); "1" |> f"#,
+    ] {
+        assert!(
+            matches!(
+                *engine.eval::<INT>(script).expect_err("should error"),
+                EvalAltResult::ErrorSandboxViolation(ref s, _) if s == "keyword 'eval'"
+            ),
+            "{}",
+            script
+        );
+    }
+
+    engine.clear_sandbox();
+    assert_eq!(engine.eval::<INT>("eval(\"40 + 2\")")?, 42);
+
+    Ok(())
+}
+
+#[test]
+fn test_sandbox_functions() -> Result<(), Box<EvalAltResult>> {
+    let mut profile = SandboxProfile::new("safe");
+    profile.allow_function("safe").allow_function("len");
+
+    let mut engine = Engine::new();
+    engine.register_fn("safe", |x: INT| x + 1);
+    engine.register_fn("danger", |x: INT| x - 1);
+    engine.set_sandbox(profile);
+
+    assert_eq!(engine.eval::<INT>("safe(41)")?, 42);
+    #[cfg(not(feature = "no_object"))]
+    assert_eq!(engine.eval::<INT>(r#""hello".len"#)?, 5);
+
+    // Operators are always allowed
+    assert_eq!(engine.eval::<INT>("40 + 2")?, 42);
+
+    // Script-defined functions are always allowed
+    #[cfg(not(feature = "no_function"))]
+    assert_eq!(
+        engine.eval::<INT>("fn helper(x) { safe(x) + 1 } helper(40)")?,
+        42
+    );
+
+    let err = engine.compile("danger(43)").expect_err("should error");
+    assert_eq!(
+        *err.0,
+        ParseErrorType::SandboxViolation("function 'danger'".into())
+    );
+
+    #[cfg(not(feature = "no_function"))]
+    assert!(matches!(
+        *engine
+            .compile("fn helper(x) { danger(x) }")
+            .expect_err("should error")
+            .0,
+        ParseErrorType::SandboxViolation(_)
+    ));
+
+    // Function pointers are checked when called
+    let err = engine
+        .eval::<INT>(r#"let f = Fn("danger"); call(f, 43)"#)
+        .expect_err("should error");
+    assert!(matches!(*err, EvalAltResult::ErrorSandboxViolation(s, _) if s == "function 'danger'"));
+
+    Ok(())
+}
+
+#[cfg(not(feature = "no_module"))]
+#[test]
+fn test_sandbox_modules() -> Result<(), Box<EvalAltResult>> {
+    use rhai::{module_resolvers::StaticModuleResolver, Module};
+
+    let mut resolver = StaticModuleResolver::new();
+
+    let mut module = Module::new();
+    module.set_fn_1("open", |x: INT| Ok(x + 1));
+    module.set_fn_1("secret", |x: INT| Ok(x - 1));
+    resolver.insert("crypto", module);
+    resolver.insert("net", Module::new());
+
+    let mut profile = SandboxProfile::new("offline");
+    profile.deny_module("net").deny_function("crypto::secret");
+
+    let mut engine = Engine::new();
+    engine.set_module_resolver(Some(resolver));
+    engine.set_sandbox(profile);
+
+    assert_eq!(
+        engine.eval::<INT>(r#"import "crypto" as c; c::open(41)"#)?,
+        42
+    );
+
+    let err = engine
+        .compile(r#"import "net" as n;"#)
+        .expect_err("should error");
+    assert_eq!(
+        *err.0,
+        ParseErrorType::SandboxViolation("module 'net'".into())
+    );
+    assert_eq!(err.1.position(), Some(8));
+
+    // Functions are matched by the module path, not the alias
+    let err = engine
+        .compile(r#"import "crypto" as c; c::secret(43)"#)
+        .expect_err("should error");
+    assert_eq!(
+        *err.0,
+        ParseErrorType::SandboxViolation("function 'crypto::secret'".into())
+    );
+
+    // Non-constant module paths are checked when imported
+    let err = engine
+        .eval::<()>(r#"let p = "n" + "et"; import p as n;"#)
+        .expect_err("should error");
+    assert!(matches!(*err, EvalAltResult::ErrorSandboxViolation(s, _) if s == "module 'net'"));
+
+    let err = engine
+        .eval::<INT>(r#"let p = "cry" + "pto"; import p as c; c::secret(43)"#)
+        .expect_err("should error");
+    assert!(
+        matches!(*err, EvalAltResult::ErrorSandboxViolation(s, _) if s == "function 'crypto::secret'")
+    );
+
+    Ok(())
+}
+
+#[cfg(not(feature = "unchecked"))]
+#[test]
+fn test_sandbox_limits() -> Result<(), Box<EvalAltResult>> {
+    let mut profile = SandboxProfile::new("limited");
+    profile.set_max_operations(500).set_max_string_size(10);
+
+    let mut engine = Engine::new();
+    engine.set_max_string_size(100);
+    engine.set_max_call_levels(5);
+    engine.set_sandbox(profile);
+
+    assert_eq!(engine.max_operations(), 500);
+    assert_eq!(engine.max_string_size(), 10);
+    assert_eq!(engine.max_call_levels(), 5);
+
+    assert!(matches!(
+        *engine
+            .eval::<()>("let x = 0; loop { x += 1; }")
+            .expect_err("should error"),
+        EvalAltResult::ErrorTooManyOperations(_)
+    ));
+
+    Ok(())
+}
@@ -1,17 +1,20 @@
 #![cfg(feature = "serde")]
 
-use rhai::{de::from_dynamic, ser::to_dynamic, Dynamic, Engine, EvalAltResult, INT};
+use rhai::{de::from_dynamic, ser::to_dynamic, Dynamic, Engine, EvalAltResult, Scope, INT};
 use serde::{Deserialize, Serialize};
 
 #[cfg(not(feature = "no_index"))]
 use rhai::Array;
 #[cfg(not(feature = "no_object"))]
-use rhai::Map;
+use rhai::{Map, Record};
 
 #[test]
 fn test_serde_ser_primary_types() -> Result<(), Box<EvalAltResult>> {
     assert!(to_dynamic(42_u64)?.is::<INT>());
+    #[cfg(not(feature = "bigint"))]
     assert!(to_dynamic(u64::MAX)?.is::<u64>());
+    #[cfg(feature = "bigint")]
+    assert!(to_dynamic(u64::MAX)?.is::<rhai::BigInt>());
     assert!(to_dynamic(42 as INT)?.is::<INT>());
     assert!(to_dynamic(true)?.is::<bool>());
     assert!(to_dynamic(())?.is::<()>());
@@ -425,6 +428,86 @@ fn test_serde_de_script() -> Result<(), Box<EvalAltResult>> {
     Ok(())
 }
 
+#[test]
+#[cfg(not(feature = "no_object"))]
+#[cfg(not(feature = "no_function"))]
+fn test_serde_de_record() -> Result<(), Box<EvalAltResult>> {
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct Point {
+        x: INT,
+        y: INT,
+    }
+
+    let engine = Engine::new();
+
+    let result: Dynamic = engine.eval(
+        r#"
+            struct Point { x, y = 0 }
+            Point(42)
+        "#,
+    )?;
+
+    // The fields of a record are deserialized like an object map
+    let point: Point = from_dynamic(&result)?;
+    assert_eq!(point, Point { x: 42, y: 0 });
+
+    Ok(())
+}
+
+#[test]
+#[cfg(not(feature = "no_index"))]
+#[cfg(not(feature = "no_object"))]
+#[cfg(not(feature = "no_function"))]
+fn test_serde_ser_record() -> Result<(), Box<EvalAltResult>> {
+    let engine = Engine::new();
+
+    let value: Dynamic = engine.eval(
+        r#"
+            struct Point { x, y = 0 }
+            [Point(42), #{ p: Point(1, 2) }, Fn("foo")]
+        "#,
+    )?;
+
+    // Records serialized into a `Dynamic` come back as records
+    let array = to_dynamic(&value)?.cast::<Array>();
+    assert_eq!(array.len(), 3);
+
+    let point = array[0].clone().cast::<Record>();
+    assert_eq!(point.type_name(), "Point");
+    assert_eq!(point.get("x").unwrap().as_int().unwrap(), 42);
+    assert_eq!(point.get("y").unwrap().as_int().unwrap(), 0);
+
+    let map = array[1].clone().cast::<Map>();
+    let point = map["p"].clone().cast::<Record>();
+    assert_eq!(point.type_name(), "Point");
+    assert_eq!(point.get("y").unwrap().as_int().unwrap(), 2);
+
+    assert_eq!(array[2].clone().cast::<String>(), "foo");
+
+    // A record round-trips through `to_dynamic` and compares equal to the original
+    let mut scope = Scope::new();
+    scope.push("p", to_dynamic(array[0].clone().cast::<Record>())?);
+    assert!(engine.eval_with_scope::<bool>(
+        &mut scope,
+        r#"
+            struct Point { x, y = 0 }
+            p == Point(42) && p.type_of() == "Point"
+        "#
+    )?);
+
+    // The fields survive the round trip into a Rust type
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct Point {
+        x: INT,
+        y: INT,
+    }
+
+    let point: Point = from_dynamic(&to_dynamic(&array[0])?)?;
+    assert_eq!(point, Point { x: 42, y: 0 });
+
+    Ok(())
+}
+
 #[test]
 fn test_serde_de_unit_enum() -> Result<(), Box<EvalAltResult>> {
     #[derive(Debug, PartialEq, Deserialize)]
@@ -682,3 +765,106 @@ fn test_serde_de_untagged_enum() -> Result<(), Box<EvalAltResult>> {
 
     Ok(())
 }
+
+#[test]
+#[cfg(not(feature = "no_object"))]
+#[cfg(feature = "ordered_map")]
+fn test_serde_ordered_map() -> Result<(), Box<EvalAltResult>> {
+    use serde::de::{Deserializer, MapAccess, Visitor};
+    use std::fmt;
+
+    #[derive(Debug, Serialize)]
+    struct Test {
+        zeta: INT,
+        alpha: INT,
+        mu: INT,
+    }
+
+    /// Property names of a map, in order.
+    struct Keys(Vec<String>);
+
+    impl<'de> Deserialize<'de> for Keys {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            struct KeysVisitor;
+
+            impl<'de> Visitor<'de> for KeysVisitor {
+                type Value = Keys;
+
+                fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                    f.write_str("a map")
+                }
+                fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Keys, A::Error> {
+                    let mut keys = Vec::new();
+                    while let Some((key, _)) = map.next_entry::<String, INT>()? {
+                        keys.push(key);
+                    }
+                    Ok(Keys(keys))
+                }
+            }
+
+            deserializer.deserialize_map(KeysVisitor)
+        }
+    }
+
+    let map = to_dynamic(Test {
+        zeta: 1,
+        alpha: 2,
+        mu: 3,
+    })?
+    .cast::<Map>();
+
+    assert_eq!(
+        map.keys().map(|k| k.as_str()).collect::<Vec<_>>(),
+        ["zeta", "alpha", "mu"]
+    );
+
+    let engine = Engine::new();
+    let value = engine.eval::<Dynamic>("#{ c: 1, a: 2, b: 3 }")?;
+
+    assert_eq!(from_dynamic::<Keys>(&value)?.0, ["c", "a", "b"]);
+
+    Ok(())
+}
+
+#[test]
+#[cfg(not(feature = "no_index"))]
+#[cfg(not(feature = "no_object"))]
+fn test_serde_scope() -> Result<(), Box<EvalAltResult>> {
+    #[derive(Clone)]
+    struct Custom;
+
+    let engine = Engine::new();
+    let mut scope = Scope::new();
+
+    scope.push("x", 42 as INT);
+    scope.push_constant("name", "hello".to_string());
+    scope.push("c", 'x');
+
+    engine.consume_with_scope(&mut scope, "let x = [x, name]; let f = Fn(\"foo\");")?;
+
+    let mut scope: Scope = from_dynamic(&to_dynamic(&scope)?)?;
+
+    assert_eq!(scope.len(), 5);
+    assert_eq!(scope.get_value::<char>("c").unwrap(), 'x');
+    assert_eq!(
+        engine.eval_with_scope::<String>(&mut scope, "x[1] + x[0] + c + f.name")?,
+        "hello42xfoo"
+    );
+
+    // Shadowed variables are kept
+    scope.remove("x");
+    assert_eq!(scope.get_value::<INT>("x").unwrap(), 42);
+
+    // Constants are kept constant
+    assert!(matches!(
+        *engine
+            .consume_with_scope(&mut scope, "name = 1")
+            .expect_err("should error"),
+        EvalAltResult::ErrorAssignmentToConstant(_, _)
+    ));
+
+    scope.push("custom", Custom);
+    assert!(to_dynamic(&scope).is_err());
+
+    Ok(())
+}
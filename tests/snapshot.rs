@@ -0,0 +1,196 @@
+#![cfg(feature = "serde")]
+
+#[cfg(not(feature = "no_index"))]
+#[cfg(not(feature = "no_object"))]
+use rhai::{de::from_dynamic, ser::to_dynamic};
+use rhai::{Engine, EvalAltResult, Resumable, Scope, Snapshot, Suspended, INT};
+
+#[cfg(not(feature = "no_object"))]
+use rhai::RegisterFn;
+#[cfg(not(feature = "no_object"))]
+use serde::{Deserialize, Serialize};
+
+fn expect_suspended(result: Resumable) -> Box<Suspended> {
+    match result {
+        Resumable::Suspended(s) => s,
+        Resumable::Completed(v) => panic!("script should be suspended, but completed with {}", v),
+    }
+}
+
+fn round_trip(snapshot: Snapshot) -> Result<Snapshot, Box<EvalAltResult>> {
+    // A serialized snapshot is held in arrays and object maps
+    #[cfg(not(feature = "no_index"))]
+    #[cfg(not(feature = "no_object"))]
+    return from_dynamic(&to_dynamic(snapshot)?);
+    #[cfg(any(feature = "no_index", feature = "no_object"))]
+    return Ok(snapshot);
+}
+
+#[test]
+fn test_snapshot_restore() -> Result<(), Box<EvalAltResult>> {
+    let engine = Engine::new();
+
+    let mut scope = Scope::new();
+    scope.push("x", 1 as INT);
+    scope.push_constant("name", "hello".to_string());
+
+    let ast = engine.compile_with_scope(
+        &scope,
+        r#"
+            let a = yield name;
+            let b = yield x + a;
+            name + ":" + (x + a + b)
+        "#,
+    )?;
+
+    let suspended = expect_suspended(engine.eval_ast_with_scope_resumable(&scope, &ast)?);
+    let suspended = expect_suspended(engine.resume(suspended, 10 as INT)?);
+
+    let snapshot = round_trip(engine.snapshot(&suspended)?)?;
+    let restored = engine.restore(&ast, &snapshot)?;
+
+    assert_eq!(restored.value().clone().cast::<INT>(), 11);
+    assert_eq!(restored.position(), suspended.position());
+    assert_eq!(restored.num_resumed(), 1);
+
+    match engine.resume(restored, 100 as INT)? {
+        Resumable::Completed(result) => assert_eq!(result.cast::<String>(), "hello:111"),
+        Resumable::Suspended(_) => panic!("script should be completed"),
+    }
+
+    // Restoring against a different script fails
+    let ast2 = engine.compile("yield 42")?;
+    assert!(engine.restore(&ast2, &snapshot).is_err());
+
+    // Scripts differing only in the functions called are different
+    let ast3 = engine.compile_with_scope(
+        &scope,
+        r#"
+            let a = yield name;
+            let b = yield x - a;
+            name + ":" + (x + a + b)
+        "#,
+    )?;
+    assert!(engine.restore(&ast3, &snapshot).is_err());
+
+    // Positions out of range are rejected
+    #[cfg(not(feature = "no_index"))]
+    #[cfg(not(feature = "no_object"))]
+    {
+        use rhai::Dynamic;
+
+        let mut map = to_dynamic(snapshot)?.cast::<rhai::Map>();
+        let pos = vec![Dynamic::from(100_000 as INT), Dynamic::from(1 as INT)];
+        map.insert("pos".into(), pos.into());
+        let snapshot: Snapshot = from_dynamic(&map.into())?;
+        assert!(engine.restore(&ast, &snapshot).is_err());
+    }
+
+    Ok(())
+}
+
+#[cfg(not(feature = "no_index"))]
+#[cfg(not(feature = "no_object"))]
+#[cfg(not(feature = "no_function"))]
+#[test]
+fn test_snapshot_values() -> Result<(), Box<EvalAltResult>> {
+    let engine = Engine::new();
+
+    let ast = engine.compile(
+        r#"
+            fn add(x, y) { x + y }
+            let r = yield [1, 'x', #{ a: "hello", b: Fn("add").curry(40) }];
+            r.b.call(2)
+        "#,
+    )?;
+
+    let suspended = expect_suspended(engine.eval_ast_resumable(&ast)?);
+    let restored = engine.restore(&ast, &round_trip(engine.snapshot(&suspended)?)?)?;
+
+    let value = restored.value().clone();
+    let value = value.cast::<rhai::Array>();
+    assert_eq!(value[0].clone().cast::<INT>(), 1);
+    assert_eq!(value[1].clone().cast::<char>(), 'x');
+
+    let map = value[2].clone().cast::<rhai::Map>();
+
+    match engine.resume(restored, map)? {
+        Resumable::Completed(result) => assert_eq!(result.cast::<INT>(), 42),
+        Resumable::Suspended(_) => panic!("script should be completed"),
+    }
+
+    Ok(())
+}
+
+#[cfg(not(feature = "no_object"))]
+#[cfg(not(feature = "no_function"))]
+#[test]
+fn test_snapshot_record() -> Result<(), Box<EvalAltResult>> {
+    let engine = Engine::new();
+
+    let ast = engine.compile(
+        r#"
+            struct Point { x, y, fn sum() { this.x + this.y } }
+            let p = yield Point(40, 1);
+            p.x += 1;
+            p.sum()
+        "#,
+    )?;
+
+    let suspended = expect_suspended(engine.eval_ast_resumable(&ast)?);
+    let snapshot = round_trip(engine.snapshot(&suspended)?)?;
+
+    // Restoring against the same script compiled again works
+    let ast = engine.compile(
+        r#"
+            struct Point { x, y, fn sum() { this.x + this.y } }
+            let p = yield Point(40, 1);
+            p.x += 1;
+            p.sum()
+        "#,
+    )?;
+    let restored = engine.restore(&ast, &snapshot)?;
+
+    let value = restored.value().clone();
+    assert_eq!(value.to_string(), r#"Point #{"x": 40, "y": 1}"#);
+
+    match engine.resume(restored, value)? {
+        Resumable::Completed(result) => assert_eq!(result.cast::<INT>(), 42),
+        Resumable::Suspended(_) => panic!("script should be completed"),
+    }
+
+    Ok(())
+}
+
+#[cfg(not(feature = "no_object"))]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct Point {
+    x: INT,
+    y: INT,
+}
+
+#[cfg(not(feature = "no_object"))]
+#[test]
+fn test_snapshot_custom_type() -> Result<(), Box<EvalAltResult>> {
+    let mut engine = Engine::new();
+    engine.register_fn("point", |x: INT, y: INT| Point { x, y });
+
+    let ast = engine.compile("let p = point(1, 2); yield p")?;
+
+    let suspended = expect_suspended(engine.eval_ast_resumable(&ast)?);
+
+    // Custom types must be registered
+    assert!(engine.snapshot(&suspended).is_err());
+
+    engine.register_serializable::<Point>("Point");
+
+    let snapshot = round_trip(engine.snapshot(&suspended)?)?;
+    let restored = engine.restore(&ast, &snapshot)?;
+
+    assert_eq!(
+        restored.value().clone().cast::<Point>(),
+        Point { x: 1, y: 2 }
+    );
+
+    Ok(())
+}
@@ -1,4 +1,6 @@
-use rhai::{Engine, EvalAltResult, ParseErrorType, RegisterFn, INT};
+use rhai::{
+    Associativity, Engine, EvalAltResult, ImmutableString, ParseErrorType, RegisterFn, INT,
+};
 
 #[test]
 fn test_tokens_disabled() {
@@ -52,6 +54,142 @@ fn test_tokens_custom_operator() -> Result<(), Box<EvalAltResult>> {
     Ok(())
 }
 
+#[test]
+fn test_tokens_custom_symbol_operator() -> Result<(), Box<EvalAltResult>> {
+    let mut engine = Engine::new();
+
+    engine
+        .register_custom_operator("<$>", 100)
+        .unwrap()
+        .register_custom_operator("=$", 100)
+        .unwrap()
+        .register_custom_binary_operator("$$", 20, Associativity::Right)
+        .unwrap()
+        .register_custom_binary_operator("^@", 200, Associativity::Right)
+        .unwrap();
+
+    engine.register_fn("<$>", |x: INT, y: INT| (x - y).signum());
+    engine.register_fn("=$", |s: ImmutableString, p: ImmutableString| {
+        s.contains(p.as_str())
+    });
+    engine.register_fn("$$", |x: INT, y: INT| if x < 0 { y } else { x });
+    engine.register_fn("^@", |x: INT, y: INT| x.pow(y as u32));
+
+    assert_eq!(engine.eval_expression::<INT>("1 + 2<$>3 * 4")?, -1);
+    assert_eq!(engine.eval_expression::<INT>("5 <$> -1")?, 1);
+    assert_eq!(engine.eval_expression::<bool>(r#""hello" =$ "ell""#)?, true);
+    assert_eq!(engine.eval_expression::<INT>("2 ^@ 3 ^@ 2")?, 512);
+    assert_eq!(engine.eval_expression::<INT>("-1 $$ -2 $$ 42")?, 42);
+
+    // Built-in operators sharing a prefix with a custom symbol still work
+    assert_eq!(engine.eval_expression::<bool>("1 < 2")?, true);
+    assert_eq!(engine.eval::<INT>("let x =-1; x")?, -1);
+    assert_eq!(engine.eval::<INT>("let x = 2 ^ 3; x")?, 1);
+
+    engine.register_custom_operator("$$$", 20).unwrap();
+    assert_eq!(engine.eval_expression::<INT>("-1 $$-2")?, -2);
+
+    // Characters partially matching a custom symbol are put back
+    engine.register_custom_operator("--$", 20).unwrap();
+    assert_eq!(engine.eval::<INT>("let x = 1; x --1")?, 2);
+
+    Ok(())
+}
+
+#[test]
+fn test_tokens_custom_prefix_operator() -> Result<(), Box<EvalAltResult>> {
+    let mut engine = Engine::new();
+
+    engine
+        .register_custom_prefix_operator("sqr")
+        .unwrap()
+        .register_custom_prefix_operator("!$")
+        .unwrap()
+        .register_custom_operator("sqr", 160)
+        .unwrap();
+
+    engine.register_fn("sqr", |x: INT| x * x);
+    engine.register_fn("sqr", |x: INT, y: INT| x * x + y * y);
+    engine.register_fn("!$", |x: INT| x != 0);
+
+    assert_eq!(engine.eval_expression::<INT>("1 + sqr 3 * 2")?, 19);
+    assert_eq!(engine.eval_expression::<INT>("sqr -sqr 2")?, 16);
+    assert_eq!(engine.eval_expression::<INT>("3 sqr 4")?, 25);
+    assert_eq!(engine.eval_expression::<bool>("!$42")?, true);
+
+    #[cfg(not(feature = "no_function"))]
+    assert_eq!(
+        engine.eval::<INT>(
+            r"
+                fn sqr(x) { x + x }
+                sqr 21
+            "
+        )?,
+        42
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_tokens_custom_operator_conflicts() {
+    let mut engine = Engine::new();
+
+    assert_eq!(
+        engine.register_custom_operator("+", 100).err().unwrap(),
+        "'+' is a built-in operator"
+    );
+    assert_eq!(
+        engine.register_custom_operator("??", 100).err().unwrap(),
+        "'??' is a built-in operator"
+    );
+    assert_eq!(
+        engine.register_custom_operator("if", 100).err().unwrap(),
+        "'if' is a reserved keyword"
+    );
+    assert_eq!(
+        engine.register_custom_operator("//", 100).err().unwrap(),
+        "'//' conflicts with comments"
+    );
+    assert_eq!(
+        engine.register_custom_operator("<(", 100).err().unwrap(),
+        "not a valid identifier or symbol: '<(' (invalid character '(')"
+    );
+
+    // Symbols must not change how built-in symbols are tokenized
+    assert_eq!(
+        engine.register_custom_operator("?", 100).err().unwrap(),
+        "'?' conflicts with built-in operator '?.'"
+    );
+    assert_eq!(
+        engine.register_custom_operator("<=>", 100).err().unwrap(),
+        "'<=>' conflicts with built-in operator '<='"
+    );
+    assert_eq!(
+        engine.register_custom_operator("+-", 100).err().unwrap(),
+        "'+-' conflicts with built-in operators '+' followed by '-'"
+    );
+    assert_eq!(
+        engine.register_custom_operator("..", 100).err().unwrap(),
+        "'..' conflicts with built-in operators '.' followed by '.'"
+    );
+    assert_eq!(
+        engine.register_custom_prefix_operator("!!").err().unwrap(),
+        "'!!' conflicts with built-in operators '!' followed by '!'"
+    );
+    assert_eq!(
+        engine.register_custom_operator("=>", 100).err().unwrap(),
+        "'=>' conflicts with built-in operators '=' followed by '>'"
+    );
+
+    // A precedence of zero only reserves the keyword
+    assert!(engine.register_custom_operator("foo", 0).is_ok());
+    assert!(engine.compile("let foo = 1;").is_err());
+
+    engine.disable_symbol("+");
+    assert!(engine.register_custom_operator("+", 100).is_ok());
+}
+
 #[test]
 fn test_tokens_unicode_xid_ident() -> Result<(), Box<EvalAltResult>> {
     let engine = Engine::new();
@@ -1,4 +1,4 @@
-use rhai::{Engine, EvalAltResult, Scope, INT};
+use rhai::{Engine, EvalAltResult, Position, Scope, INT};
 
 #[test]
 fn test_var_scope() -> Result<(), Box<EvalAltResult>> {
@@ -52,3 +52,157 @@ fn test_scope_eval() -> Result<(), Box<EvalAltResult>> {
 
     Ok(())
 }
+
+#[test]
+fn test_var_resolver() -> Result<(), Box<EvalAltResult>> {
+    let mut engine = Engine::new();
+    let mut scope = Scope::new();
+
+    scope.push("x", 1 as INT);
+
+    engine.on_var(|name, _, context| match name {
+        "lazy" => Ok(Some(((40 as INT).into(), true))),
+        "temp" => Ok(Some(((10 as INT).into(), false))),
+        "level" => Ok(Some(((context.call_level() as INT).into(), true))),
+        "oops" => {
+            Err(EvalAltResult::ErrorRuntime("cannot resolve".into(), Position::none()).into())
+        }
+        _ => Ok(None),
+    });
+
+    assert_eq!(
+        engine.eval_with_scope::<INT>(&mut scope, "x + lazy + 1")?,
+        42
+    );
+
+    // Variables declared by the script are still found by their offsets
+    assert_eq!(
+        engine.eval_with_scope::<INT>(
+            &mut scope,
+            "let a = 1; let b = lazy; { let c = a + lazy; let d = b; a + c + d + x }"
+        )?,
+        83
+    );
+
+    // The scope takes precedence
+    assert_eq!(
+        engine.eval_with_scope::<INT>(&mut scope, "let lazy = 2; lazy")?,
+        2
+    );
+    scope.rewind(1);
+
+    // Changing a non-constant variable adds it to the scope
+    assert_eq!(
+        engine.eval_with_scope::<INT>(&mut scope, "temp += 1; temp")?,
+        11
+    );
+    assert_eq!(scope.len(), 2);
+    assert_eq!(scope.get_value::<INT>("temp"), Some(11));
+    scope.rewind(1);
+
+    assert_eq!(
+        engine
+            .eval_with_scope::<INT>(&mut scope, "let a = 1; temp = 5; let b = 2; a + temp + b")?,
+        8
+    );
+    scope.rewind(1);
+
+    // ... until the end of the block
+    assert_eq!(
+        engine.eval_with_scope::<INT>(&mut scope, "let a = { temp *= 2; temp }; a + temp")?,
+        30
+    );
+    scope.rewind(1);
+
+    assert!(matches!(
+        *engine.eval_with_scope::<INT>(&mut scope, "lazy = 1; lazy").expect_err("should error"),
+        EvalAltResult::ErrorAssignmentToConstant(v, _) if v == "lazy"
+    ));
+    assert!(matches!(
+        *engine.eval_with_scope::<INT>(&mut scope, "unknown").expect_err("should error"),
+        EvalAltResult::ErrorVariableNotFound(v, _) if v == "unknown"
+    ));
+    assert!(matches!(
+        *engine.eval_with_scope::<INT>(&mut scope, "oops").expect_err("should error"),
+        EvalAltResult::ErrorRuntime(_, pos) if pos.line() == Some(1)
+    ));
+
+    #[cfg(not(feature = "no_function"))]
+    assert_eq!(
+        engine.eval_with_scope::<INT>(&mut scope, "fn f() { level + lazy } f() + level")?,
+        41
+    );
+
+    #[cfg(not(feature = "no_object"))]
+    {
+        engine.on_var(|name, _, _| {
+            let mut map = rhai::Map::new();
+            map.insert("name".into(), "Bob".into());
+
+            match name {
+                "user" => Ok(Some((map.into(), true))),
+                "guest" => Ok(Some((map.into(), false))),
+                _ => Ok(None),
+            }
+        });
+
+        assert_eq!(engine.eval::<String>("user.name")?, "Bob");
+        assert_eq!(engine.eval::<INT>("len(user.name)")?, 3);
+        assert!(matches!(
+            *engine.consume("user.name = 1").expect_err("should error"),
+            EvalAltResult::ErrorAssignmentToConstant(_, _)
+        ));
+        assert_eq!(
+            engine.eval::<String>(r#"guest.name = "Alice"; guest.name"#)?,
+            "Alice"
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_scope_remove_and_restore() -> Result<(), Box<EvalAltResult>> {
+    let engine = Engine::new();
+    let mut scope = Scope::new();
+
+    scope.push("x", 1 as INT);
+    scope.push_constant("y", 2 as INT);
+
+    let snapshot = scope.snapshot();
+
+    engine.consume_with_scope(&mut scope, "let z = x + y; x = 40;")?;
+
+    *scope.get_mut("z").unwrap() = (2 as INT).into();
+    assert!(scope.get_mut("y").is_none());
+    assert_eq!(engine.eval_with_scope::<INT>(&mut scope, "x + z")?, 42);
+
+    assert_eq!(scope.remove("y").unwrap().cast::<INT>(), 2);
+    assert!(scope.remove("y").is_none());
+    assert_eq!(scope.len(), 2);
+
+    // Offsets of variables are still valid after removal
+    assert_eq!(
+        engine.eval_with_scope::<INT>(&mut scope, "let a = 1; x + z + a")?,
+        43
+    );
+
+    scope.restore(&snapshot);
+    assert_eq!(scope.len(), 2);
+    assert_eq!(engine.eval_with_scope::<INT>(&mut scope, "x + y")?, 3);
+
+    #[cfg(not(feature = "no_closure"))]
+    #[cfg(not(feature = "no_function"))]
+    #[cfg(not(feature = "no_object"))]
+    {
+        // Values of shared variables are copied into the snapshot
+        engine.consume_with_scope(&mut scope, "let f = |v| x += v;")?;
+        let snapshot = scope.snapshot();
+        engine.consume_with_scope(&mut scope, "x += 10")?;
+
+        assert_eq!(scope.get_value::<INT>("x").unwrap(), 11);
+        assert_eq!(snapshot.get_value::<INT>("x").unwrap(), 1);
+    }
+
+    Ok(())
+}
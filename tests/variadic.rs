@@ -0,0 +1,207 @@
+use rhai::{Engine, EvalAltResult, RegisterFn, INT};
+use std::any::TypeId;
+
+#[cfg(not(feature = "no_index"))]
+#[cfg(not(feature = "no_function"))]
+#[test]
+fn test_variadic_script() -> Result<(), Box<EvalAltResult>> {
+    let engine = Engine::new();
+
+    let script = r"
+        fn sum(...xs) {
+            let total = 0;
+            for x in xs { total += x; }
+            total
+        }
+    ";
+
+    assert_eq!(engine.eval::<INT>(&format!("{} sum()", script))?, 0);
+    assert_eq!(engine.eval::<INT>(&format!("{} sum(42)", script))?, 42);
+    assert_eq!(
+        engine.eval::<INT>(&format!("{} sum(1, 2, 3, 36)", script))?,
+        42
+    );
+
+    assert_eq!(
+        engine.eval::<INT>("fn f(a, ...rest) { a * 10 + len(rest) } f(4, true, 'x')")?,
+        42
+    );
+    assert_eq!(
+        engine.eval::<INT>("fn f(a, ...rest) { a * 10 + len(rest) } f(4)")?,
+        40
+    );
+    assert!(matches!(
+        *engine
+            .eval::<INT>("fn f(a, ...rest) { a } f()")
+            .expect_err("should error"),
+        EvalAltResult::ErrorFunctionNotFound(_, _)
+    ));
+
+    // Default values before the rest parameter
+    assert_eq!(
+        engine.eval::<INT>("fn f(a, b = 2, ...rest) { a * b + len(rest) } f(20) + f(1, 1, 1)")?,
+        42
+    );
+
+    // An exact match takes precedence
+    assert_eq!(
+        engine.eval::<INT>("fn f(...xs) { len(xs) } fn f(a) { 42 } f(1) + f(1, 2)")?,
+        44
+    );
+
+    // The one with the most fixed parameters is the best match
+    let overloads = r#"
+        fn f(...xs) { "any" }
+        fn f(a, ...xs) { "one" }
+        fn f(a, b, ...xs) { "two" }
+    "#;
+    assert_eq!(
+        engine.eval::<String>(&format!("{} f() + f(1) + f(1, 2, 3)", overloads))?,
+        "anyonetwo"
+    );
+
+    // Overloads with typed parameters are told apart by the parameter types
+    #[cfg(not(feature = "only_i32"))]
+    let typed = r#"
+        fn f(a: i64, ...r) { a + len(r) }
+        fn f(a: string, ...r) { len(a) }
+    "#;
+    #[cfg(not(feature = "only_i32"))]
+    assert_eq!(
+        engine.eval::<INT>(&format!(r#"{} f(1, 2) + f("hello", 2) + f(35)"#, typed))?,
+        42
+    );
+
+    // Method-call style and function pointers
+    #[cfg(not(feature = "no_object"))]
+    assert_eq!(
+        engine.eval::<INT>(&format!("{} let x = 40; x.sum(1, 1)", script))?,
+        2
+    );
+    assert_eq!(
+        engine.eval::<INT>(&format!(r#"{} let p = Fn("sum"); call(p, 40, 2)"#, script))?,
+        42
+    );
+
+    #[cfg(not(feature = "no_module"))]
+    {
+        let ast = engine.compile(script)?;
+        let mut resolver = rhai::module_resolvers::StaticModuleResolver::new();
+        let module = rhai::Module::eval_ast_as_new(rhai::Scope::new(), &ast, &engine)?;
+        resolver.insert("m", module);
+
+        let mut engine = Engine::new();
+        engine.set_module_resolver(Some(resolver));
+
+        assert_eq!(
+            engine.eval::<INT>(r#"import "m" as m; m::sum(40, 1, 1)"#)?,
+            42
+        );
+
+        let ast = engine.compile(overloads)?;
+        let mut resolver = rhai::module_resolvers::StaticModuleResolver::new();
+        let module = rhai::Module::eval_ast_as_new(rhai::Scope::new(), &ast, &engine)?;
+        resolver.insert("m", module);
+        engine.set_module_resolver(Some(resolver));
+
+        assert_eq!(
+            engine.eval::<String>(r#"import "m" as m; m::f() + m::f(1) + m::f(1, 2)"#)?,
+            "anyonetwo"
+        );
+
+        #[cfg(not(feature = "only_i32"))]
+        {
+            let ast = engine.compile(typed)?;
+            let mut resolver = rhai::module_resolvers::StaticModuleResolver::new();
+            let module = rhai::Module::eval_ast_as_new(rhai::Scope::new(), &ast, &engine)?;
+            resolver.insert("m", module);
+            engine.set_module_resolver(Some(resolver));
+
+            assert_eq!(
+                engine.eval::<INT>(r#"import "m" as m; m::f(1, 2) + m::f("hello", 2)"#)?,
+                7
+            );
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(not(feature = "no_index"))]
+#[cfg(not(feature = "no_function"))]
+#[test]
+fn test_variadic_script_parse() {
+    let engine = Engine::new();
+
+    assert!(matches!(
+        *engine
+            .compile("fn f(...xs, y) { y }")
+            .expect_err("should error")
+            .0,
+        rhai::ParseErrorType::MissingToken(_, _)
+    ));
+    assert!(engine.compile("fn f(...) { 0 }").is_err());
+    assert!(engine.compile("fn f(x, ...x) { x }").is_err());
+}
+
+#[test]
+fn test_variadic_native() -> Result<(), Box<EvalAltResult>> {
+    let mut engine = Engine::new();
+
+    engine.register_variadic_fn("count", &[], |_, args| Ok(args.len() as INT));
+
+    assert_eq!(engine.eval::<INT>("count()")?, 0);
+    assert_eq!(engine.eval::<INT>(r#"count(1, true, "x")"#)?, 3);
+
+    engine.register_variadic_fn("max_of", &[TypeId::of::<INT>()], |_, args| {
+        let first = args[0].clone().cast::<INT>();
+
+        args.iter().skip(1).try_fold(first, |max, v| {
+            v.as_int()
+                .map(|v| if v > max { v } else { max })
+                .map_err(|typ| {
+                    EvalAltResult::ErrorMismatchOutputType(
+                        "integer".into(),
+                        typ.into(),
+                        rhai::Position::none(),
+                    )
+                    .into()
+                })
+        })
+    });
+
+    assert_eq!(engine.eval::<INT>("max_of(1, 42, 7)")?, 42);
+    assert_eq!(engine.eval::<INT>("max_of(42)")?, 42);
+    assert!(matches!(
+        *engine.eval::<INT>("max_of()").expect_err("should error"),
+        EvalAltResult::ErrorFunctionNotFound(_, _)
+    ));
+    assert!(matches!(
+        *engine
+            .eval::<INT>(r#"max_of("x", 1)"#)
+            .expect_err("should error"),
+        EvalAltResult::ErrorFunctionNotFound(_, _)
+    ));
+
+    // The one with the most matching fixed parameters is the best match
+    engine.register_variadic_fn("count", &[TypeId::of::<bool>()], |_, args| {
+        Ok(-(args.len() as INT))
+    });
+    assert_eq!(engine.eval::<INT>("count(true, 1)")?, -2);
+    assert_eq!(engine.eval::<INT>("count(1, true)")?, 2);
+
+    // The first argument is passed by value
+    engine.register_variadic_fn("reset", &[TypeId::of::<INT>()], |_, args| {
+        *args[0] = (0 as INT).into();
+        Ok(())
+    });
+    #[cfg(not(feature = "no_object"))]
+    assert_eq!(engine.eval::<INT>("let x = 42; x.reset(1); x")?, 42);
+
+    // An exact match takes precedence
+    engine.register_fn("count", |x: INT| x * 2);
+    assert_eq!(engine.eval::<INT>("count(21)")?, 42);
+    assert_eq!(engine.eval::<INT>("count(21, 1)")?, 2);
+
+    Ok(())
+}